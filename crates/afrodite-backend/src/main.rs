@@ -121,7 +121,7 @@ fn handle_image_process_mode(
         Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
             eprintln!("{:?}", e);
-            ExitCode::FAILURE
+            ExitCode::from(e.current_context().exit_code())
         }
     }
 }
@@ -114,6 +114,16 @@ pub enum PostSetPermissionsError {
     UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`post_start_admin_data_export`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PostStartAdminDataExportError {
+    Status401(),
+    Status406(),
+    Status500(),
+    UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`post_update_news_translation`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -519,6 +529,42 @@ pub async fn post_set_permissions(configuration: &configuration::Configuration,
     }
 }
 
+/// Export progress and download token are sent to the admin account like when exporting own account data. The archive can be downloaded using the same API as own account data export archive.  The admin account has only one export archive, so a new export can not be started while the archive of another account is available for download.  # Access  Permission [model_account::Permissions::admin_export_data] is required.
+pub async fn post_start_admin_data_export(configuration: &configuration::Configuration, aid: &str) -> Result<(), Error<PostStartAdminDataExportError>> {
+    let local_var_configuration = configuration;
+
+    let local_var_client = &local_var_configuration.client;
+
+    let local_var_uri_str = format!("{}/account_api/start_admin_data_export/{aid}", local_var_configuration.base_path, aid=crate::apis::urlencode(aid));
+    let mut local_var_req_builder = local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
+
+    if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+        local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+    }
+    if let Some(ref local_var_apikey) = local_var_configuration.api_key {
+        let local_var_key = local_var_apikey.key.clone();
+        let local_var_value = match local_var_apikey.prefix {
+            Some(ref local_var_prefix) => format!("{} {}", local_var_prefix, local_var_key),
+            None => local_var_key,
+        };
+        local_var_req_builder = local_var_req_builder.header("x-access-token", local_var_value);
+    };
+
+    let local_var_req = local_var_req_builder.build()?;
+    let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+    let local_var_status = local_var_resp.status();
+    let local_var_content = local_var_resp.text().await?;
+
+    if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+        Ok(())
+    } else {
+        let local_var_entity: Option<PostStartAdminDataExportError> = serde_json::from_str(&local_var_content).ok();
+        let local_var_error = ResponseContent { status: local_var_status, content: local_var_content, entity: local_var_entity };
+        Err(Error::ResponseError(local_var_error))
+    }
+}
+
 pub async fn post_update_news_translation(configuration: &configuration::Configuration, nid: i64, locale: &str, update_news_translation: models::UpdateNewsTranslation) -> Result<models::UpdateNewsTranslationResult, Error<PostUpdateNewsTranslationError>> {
     let local_var_configuration = configuration;
 
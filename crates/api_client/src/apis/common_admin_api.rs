@@ -339,8 +339,8 @@ pub async fn get_manager_instance_names(configuration: &configuration::Configura
     }
 }
 
-/// # Permissions Requires admin_server_maintenance_view_info.
-pub async fn get_perf_data(configuration: &configuration::Configuration, start_time: Option<models::UnixTime>, end_time: Option<models::GetPerfDataEndTimeParameter>) -> Result<models::PerfMetricQueryResult, Error<GetPerfDataError>> {
+/// Minute values are available for the last 24 hours. Older values and values with hour or day granularity are read from the history database.  # Permissions Requires admin_server_maintenance_view_info.
+pub async fn get_perf_data(configuration: &configuration::Configuration, start_time: Option<models::UnixTime>, end_time: Option<models::GetPerfDataEndTimeParameter>, metrics: Option<&str>, time_granularity: Option<models::TimeGranularity>) -> Result<models::PerfMetricQueryResult, Error<GetPerfDataError>> {
     let local_var_configuration = configuration;
 
     let local_var_client = &local_var_configuration.client;
@@ -354,6 +354,12 @@ pub async fn get_perf_data(configuration: &configuration::Configuration, start_t
     if let Some(ref local_var_str) = end_time {
         local_var_req_builder = local_var_req_builder.query(&[("end_time", &local_var_str.to_string())]);
     }
+    if let Some(ref local_var_str) = metrics {
+        local_var_req_builder = local_var_req_builder.query(&[("metrics", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = time_granularity {
+        local_var_req_builder = local_var_req_builder.query(&[("time_granularity", &local_var_str.to_string())]);
+    }
     if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
         local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
     }
@@ -24,6 +24,15 @@ pub enum GetProfileContentPendingModerationListError {
     UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`get_similar_content`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetSimilarContentError {
+    Status401(),
+    Status500(),
+    UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`post_moderate_profile_content`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -72,6 +81,42 @@ pub async fn get_profile_content_pending_moderation_list(configuration: &configu
     }
 }
 
+/// Near-duplicates are detected using perceptual hashes. The blocklist contains images which human moderators have rejected and images uploaded by banned accounts.  # Access  Permission [model_media::Permissions::admin_moderate_media_content] is required.
+pub async fn get_similar_content(configuration: &configuration::Configuration, aid: &str, cid: &str) -> Result<models::SimilarContentList, Error<GetSimilarContentError>> {
+    let local_var_configuration = configuration;
+
+    let local_var_client = &local_var_configuration.client;
+
+    let local_var_uri_str = format!("{}/media_api/admin/similar_content/{aid}/{cid}", local_var_configuration.base_path, aid=crate::apis::urlencode(aid), cid=crate::apis::urlencode(cid));
+    let mut local_var_req_builder = local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+    if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+        local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+    }
+    if let Some(ref local_var_apikey) = local_var_configuration.api_key {
+        let local_var_key = local_var_apikey.key.clone();
+        let local_var_value = match local_var_apikey.prefix {
+            Some(ref local_var_prefix) => format!("{} {}", local_var_prefix, local_var_key),
+            None => local_var_key,
+        };
+        local_var_req_builder = local_var_req_builder.header("x-access-token", local_var_value);
+    };
+
+    let local_var_req = local_var_req_builder.build()?;
+    let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+    let local_var_status = local_var_resp.status();
+    let local_var_content = local_var_resp.text().await?;
+
+    if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+        serde_json::from_str(&local_var_content).map_err(Error::from)
+    } else {
+        let local_var_entity: Option<GetSimilarContentError> = serde_json::from_str(&local_var_content).ok();
+        let local_var_error = ResponseContent { status: local_var_status, content: local_var_content, entity: local_var_entity };
+        Err(Error::ResponseError(local_var_error))
+    }
+}
+
 /// This route will fail if the content is in slot.  Also profile visibility moves from pending to normal when all profile content is moderated as accepted.
 pub async fn post_moderate_profile_content(configuration: &configuration::Configuration, post_moderate_profile_content: models::PostModerateProfileContent) -> Result<(), Error<PostModerateProfileContentError>> {
     let local_var_configuration = configuration;
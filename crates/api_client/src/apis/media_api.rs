@@ -38,6 +38,7 @@ pub enum GetAllAccountMediaContentError {
 #[serde(untagged)]
 pub enum GetContentError {
     Status401(),
+    Status416(),
     Status500(),
     UnknownValue(serde_json::Value),
 }
@@ -57,6 +58,7 @@ pub enum GetContentSlotStateError {
 #[serde(untagged)]
 pub enum GetMapTileError {
     Status401(),
+    Status416(),
     Status500(),
     UnknownValue(serde_json::Value),
 }
@@ -207,8 +209,8 @@ pub async fn get_all_account_media_content(configuration: &configuration::Config
     }
 }
 
-/// # Access  ## Own content Unrestricted access.  ## Public other content Normal account state required.  ## Private other content If owner of the requested content is a match and the requested content is in current profile content, then the requested content can be accessed if query parameter `is_match` is set to `true`.  If the previous is not true, then permission `admin_view_all_profiles` or `admin_moderate_media_content` is required.  
-pub async fn get_content(configuration: &configuration::Configuration, aid: &str, cid: &str, is_match: Option<bool>) -> Result<std::path::PathBuf, Error<GetContentError>> {
+/// # Access  ## Own content Unrestricted access.  ## Public other content Normal account state required.  ## Private other content If owner of the requested content is a match and the requested content is in current profile content, then the requested content can be accessed if query parameter `is_match` is set to `true`.  If the previous is not true, then permission `admin_view_all_profiles` or `admin_moderate_media_content` is required.  # Caching  The response has a strong `ETag` header. Requests with `If-None-Match` and single range `Range` headers are supported.  Content does not change after it is stored, so `Cache-Control` header marks the response immutable. [model::ContentSize::Small] is an exception as it changes when profile grid crop info changes, so it must be revalidated using the `ETag`.  
+pub async fn get_content(configuration: &configuration::Configuration, aid: &str, cid: &str, is_match: Option<bool>, size: Option<models::ContentSize>) -> Result<std::path::PathBuf, Error<GetContentError>> {
     let local_var_configuration = configuration;
 
     let local_var_client = &local_var_configuration.client;
@@ -219,6 +221,9 @@ pub async fn get_content(configuration: &configuration::Configuration, aid: &str
     if let Some(ref local_var_str) = is_match {
         local_var_req_builder = local_var_req_builder.query(&[("is_match", &local_var_str.to_string())]);
     }
+    if let Some(ref local_var_str) = size {
+        local_var_req_builder = local_var_req_builder.query(&[("size", &local_var_str.to_string())]);
+    }
     if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
         local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
     }
@@ -282,7 +287,7 @@ pub async fn get_content_slot_state(configuration: &configuration::Configuration
     }
 }
 
-/// Returns a .png even if the URL does not have it.
+/// Returns a .png even if the URL does not have it.  The response has a strong `ETag` and immutable `Cache-Control` header. Requests with `If-None-Match` and single range `Range` headers are supported.
 pub async fn get_map_tile(configuration: &configuration::Configuration, z: i32, x: i32, y: &str) -> Result<std::path::PathBuf, Error<GetMapTileError>> {
     let local_var_configuration = configuration;
 
@@ -24,6 +24,15 @@ pub enum GetAdminProfileIteratorPageError {
     UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`get_admin_profile_search_page`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetAdminProfileSearchPageError {
+    Status401(),
+    Status500(),
+    UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`get_latest_created_account_id_db`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -42,6 +51,15 @@ pub enum GetProfileAgeAndNameError {
     UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`get_profile_moderation_history`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetProfileModerationHistoryError {
+    Status401(),
+    Status500(),
+    UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`get_profile_name_pending_moderation_list`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -144,6 +162,67 @@ pub async fn get_admin_profile_iterator_page(configuration: &configuration::Conf
     }
 }
 
+/// Returns both public and private profiles. Results are ordered by search relevance if search text is used. Otherwise the most recently created accounts are returned first.  # Access - Permission [model::Permissions::admin_search_profiles]
+pub async fn get_admin_profile_search_page(configuration: &configuration::Configuration, page: i64, text: Option<&str>, fuzzy: Option<bool>, initial_setup_completed: Option<bool>, banned: Option<bool>, pending_deletion: Option<bool>, min_report_count: Option<i64>, created_after_unix_time: Option<i64>, created_before_unix_time: Option<i64>) -> Result<models::ProfileSearchResultPage, Error<GetAdminProfileSearchPageError>> {
+    let local_var_configuration = configuration;
+
+    let local_var_client = &local_var_configuration.client;
+
+    let local_var_uri_str = format!("{}/profile_api/get_admin_profile_search_page", local_var_configuration.base_path);
+    let mut local_var_req_builder = local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+    if let Some(ref local_var_str) = text {
+        local_var_req_builder = local_var_req_builder.query(&[("text", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = fuzzy {
+        local_var_req_builder = local_var_req_builder.query(&[("fuzzy", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = initial_setup_completed {
+        local_var_req_builder = local_var_req_builder.query(&[("initial_setup_completed", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = banned {
+        local_var_req_builder = local_var_req_builder.query(&[("banned", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = pending_deletion {
+        local_var_req_builder = local_var_req_builder.query(&[("pending_deletion", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = min_report_count {
+        local_var_req_builder = local_var_req_builder.query(&[("min_report_count", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = created_after_unix_time {
+        local_var_req_builder = local_var_req_builder.query(&[("created_after_unix_time", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = created_before_unix_time {
+        local_var_req_builder = local_var_req_builder.query(&[("created_before_unix_time", &local_var_str.to_string())]);
+    }
+    local_var_req_builder = local_var_req_builder.query(&[("page", &page.to_string())]);
+    if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+        local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+    }
+    if let Some(ref local_var_apikey) = local_var_configuration.api_key {
+        let local_var_key = local_var_apikey.key.clone();
+        let local_var_value = match local_var_apikey.prefix {
+            Some(ref local_var_prefix) => format!("{} {}", local_var_prefix, local_var_key),
+            None => local_var_key,
+        };
+        local_var_req_builder = local_var_req_builder.header("x-access-token", local_var_value);
+    };
+
+    let local_var_req = local_var_req_builder.build()?;
+    let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+    let local_var_status = local_var_resp.status();
+    let local_var_content = local_var_resp.text().await?;
+
+    if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+        serde_json::from_str(&local_var_content).map_err(Error::from)
+    } else {
+        let local_var_entity: Option<GetAdminProfileSearchPageError> = serde_json::from_str(&local_var_content).ok();
+        let local_var_error = ResponseContent { status: local_var_status, content: local_var_content, entity: local_var_entity };
+        Err(Error::ResponseError(local_var_error))
+    }
+}
+
 /// # Access - Permission [model::Permissions::admin_view_all_profiles]
 pub async fn get_latest_created_account_id_db(configuration: &configuration::Configuration, ) -> Result<models::AccountIdDbValue, Error<GetLatestCreatedAccountIdDbError>> {
     let local_var_configuration = configuration;
@@ -216,6 +295,42 @@ pub async fn get_profile_age_and_name(configuration: &configuration::Configurati
     }
 }
 
+/// moderation results.  # Access - Permission [model::Permissions::admin_view_profile_history]
+pub async fn get_profile_moderation_history(configuration: &configuration::Configuration, aid: &str) -> Result<models::GetProfileModerationHistory, Error<GetProfileModerationHistoryError>> {
+    let local_var_configuration = configuration;
+
+    let local_var_client = &local_var_configuration.client;
+
+    let local_var_uri_str = format!("{}/profile_api/get_profile_moderation_history/{aid}", local_var_configuration.base_path, aid=crate::apis::urlencode(aid));
+    let mut local_var_req_builder = local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+    if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+        local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+    }
+    if let Some(ref local_var_apikey) = local_var_configuration.api_key {
+        let local_var_key = local_var_apikey.key.clone();
+        let local_var_value = match local_var_apikey.prefix {
+            Some(ref local_var_prefix) => format!("{} {}", local_var_prefix, local_var_key),
+            None => local_var_key,
+        };
+        local_var_req_builder = local_var_req_builder.header("x-access-token", local_var_value);
+    };
+
+    let local_var_req = local_var_req_builder.build()?;
+    let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+    let local_var_status = local_var_resp.status();
+    let local_var_content = local_var_resp.text().await?;
+
+    if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+        serde_json::from_str(&local_var_content).map_err(Error::from)
+    } else {
+        let local_var_entity: Option<GetProfileModerationHistoryError> = serde_json::from_str(&local_var_content).ok();
+        let local_var_error = ResponseContent { status: local_var_status, content: local_var_content, entity: local_var_entity };
+        Err(Error::ResponseError(local_var_error))
+    }
+}
+
 pub async fn get_profile_name_pending_moderation_list(configuration: &configuration::Configuration, ) -> Result<models::GetProfileNamePendingModerationList, Error<GetProfileNamePendingModerationListError>> {
     let local_var_configuration = configuration;
 
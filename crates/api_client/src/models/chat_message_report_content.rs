@@ -0,0 +1,32 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChatMessageReportContent {
+    /// Message text from the report creator. The server has not verified this.
+    #[serde(rename = "message")]
+    pub message: String,
+    /// Reported message with valid server signature.
+    #[serde(rename = "signed_message")]
+    pub signed_message: Box<models::SignedMessageInfo>,
+}
+
+impl ChatMessageReportContent {
+    pub fn new(message: String, signed_message: models::SignedMessageInfo) -> ChatMessageReportContent {
+        ChatMessageReportContent {
+            message,
+            signed_message: Box::new(signed_message),
+        }
+    }
+}
+
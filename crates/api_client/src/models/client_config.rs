@@ -16,6 +16,9 @@ pub struct ClientConfig {
     /// Account component specific config. It is also possible that custom reports are not configured.
     #[serde(rename = "custom_reports", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
     pub custom_reports: Option<Option<Box<models::CustomReportsFileHash>>>,
+    /// Chat component specific config. Public key for verifying server signatures of chat messages and public keys.
+    #[serde(rename = "message_signing_public_key", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub message_signing_public_key: Option<Option<Box<models::MessageSigningPublicKey>>>,
     /// Profile component specific config. It is also possible that attributes are not configured.
     #[serde(rename = "profile_attributes", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
     pub profile_attributes: Option<Option<Box<models::ProfileAttributeInfo>>>,
@@ -27,6 +30,7 @@ impl ClientConfig {
     pub fn new(sync_version: models::ClientConfigSyncVersion) -> ClientConfig {
         ClientConfig {
             custom_reports: None,
+            message_signing_public_key: None,
             profile_attributes: None,
             sync_version: Box::new(sync_version),
         }
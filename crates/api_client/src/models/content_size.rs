@@ -0,0 +1,42 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// ContentSize : Image size variant of media content.
+/// Image size variant of media content.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum ContentSize {
+    #[serde(rename = "Original")]
+    Original,
+    #[serde(rename = "Medium")]
+    Medium,
+    #[serde(rename = "Small")]
+    Small,
+
+}
+
+impl std::fmt::Display for ContentSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Original => write!(f, "Original"),
+            Self::Medium => write!(f, "Medium"),
+            Self::Small => write!(f, "Small"),
+        }
+    }
+}
+
+impl Default for ContentSize {
+    fn default() -> ContentSize {
+        Self::Original
+    }
+}
+
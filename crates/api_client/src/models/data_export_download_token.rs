@@ -0,0 +1,29 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// DataExportDownloadToken : Token which is required for downloading data export archive.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DataExportDownloadToken {
+    #[serde(rename = "token")]
+    pub token: String,
+}
+
+impl DataExportDownloadToken {
+    /// Token which is required for downloading data export archive.
+    pub fn new(token: String) -> DataExportDownloadToken {
+        DataExportDownloadToken {
+            token,
+        }
+    }
+}
+
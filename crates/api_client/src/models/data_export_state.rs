@@ -0,0 +1,43 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DataExportState {
+    /// Time when export archive is removed. Available when state is [DataExportStateType::Done].
+    #[serde(rename = "download_expires", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub download_expires: Option<Option<Box<models::UnixTime>>>,
+    /// Available when state is [DataExportStateType::Done].
+    #[serde(rename = "download_token", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub download_token: Option<Option<Box<models::DataExportDownloadToken>>>,
+    /// Export progress in percentages. Available when state is [DataExportStateType::InProgress].
+    #[serde(rename = "progress", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub progress: Option<Option<i32>>,
+    #[serde(rename = "state")]
+    pub state: models::DataExportStateType,
+    /// Account which data is exported.
+    #[serde(rename = "target", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub target: Option<Option<Box<models::AccountId>>>,
+}
+
+impl DataExportState {
+    pub fn new(state: models::DataExportStateType) -> DataExportState {
+        DataExportState {
+            download_expires: None,
+            download_token: None,
+            progress: None,
+            state,
+            target: None,
+        }
+    }
+}
+
@@ -0,0 +1,44 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum DataExportStateType {
+    #[serde(rename = "Empty")]
+    Empty,
+    #[serde(rename = "InProgress")]
+    InProgress,
+    #[serde(rename = "Done")]
+    Done,
+    #[serde(rename = "Error")]
+    Error,
+
+}
+
+impl std::fmt::Display for DataExportStateType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Empty"),
+            Self::InProgress => write!(f, "InProgress"),
+            Self::Done => write!(f, "Done"),
+            Self::Error => write!(f, "Error"),
+        }
+    }
+}
+
+impl Default for DataExportStateType {
+    fn default() -> DataExportStateType {
+        Self::Empty
+    }
+}
+
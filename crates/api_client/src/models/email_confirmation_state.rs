@@ -0,0 +1,32 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EmailConfirmationState {
+    /// Time when the latest email confirmation email was requested.
+    #[serde(rename = "email_confirmation_sent_unix_time", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub email_confirmation_sent_unix_time: Option<Option<Box<models::UnixTime>>>,
+    /// True when the current email address is confirmed. Changing the email address resets this to false.
+    #[serde(rename = "email_verified")]
+    pub email_verified: bool,
+}
+
+impl EmailConfirmationState {
+    pub fn new(email_verified: bool) -> EmailConfirmationState {
+        EmailConfirmationState {
+            email_confirmation_sent_unix_time: None,
+            email_verified,
+        }
+    }
+}
+
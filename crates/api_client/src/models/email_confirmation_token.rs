@@ -0,0 +1,29 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// EmailConfirmationToken : Token from email confirmation email.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EmailConfirmationToken {
+    #[serde(rename = "token")]
+    pub token: String,
+}
+
+impl EmailConfirmationToken {
+    /// Token from email confirmation email.
+    pub fn new(token: String) -> EmailConfirmationToken {
+        EmailConfirmationToken {
+            token,
+        }
+    }
+}
+
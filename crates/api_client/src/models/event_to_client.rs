@@ -17,6 +17,9 @@ pub struct EventToClient {
     /// Data for event ContentProcessingStateChanged
     #[serde(rename = "content_processing_state_changed", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
     pub content_processing_state_changed: Option<Option<Box<models::ContentProcessingStateChanged>>>,
+    /// Data for event DataExportStateChanged
+    #[serde(rename = "data_export_state_changed", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub data_export_state_changed: Option<Option<Box<models::DataExportState>>>,
     #[serde(rename = "event")]
     pub event: models::EventType,
     /// Data for event LatestViewedMessageChanged
@@ -32,6 +35,7 @@ impl EventToClient {
     pub fn new(event: models::EventType) -> EventToClient {
         EventToClient {
             content_processing_state_changed: None,
+            data_export_state_changed: None,
             event,
             latest_viewed_message_changed: None,
             scheduled_maintenance_status: None,
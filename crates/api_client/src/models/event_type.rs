@@ -45,6 +45,8 @@ pub enum EventType {
     MediaContentChanged,
     #[serde(rename = "ScheduledMaintenanceStatus")]
     ScheduledMaintenanceStatus,
+    #[serde(rename = "DataExportStateChanged")]
+    DataExportStateChanged,
 
 }
 
@@ -66,6 +68,7 @@ impl std::fmt::Display for EventType {
             Self::InitialContentModerationCompleted => write!(f, "InitialContentModerationCompleted"),
             Self::MediaContentChanged => write!(f, "MediaContentChanged"),
             Self::ScheduledMaintenanceStatus => write!(f, "ScheduledMaintenanceStatus"),
+            Self::DataExportStateChanged => write!(f, "DataExportStateChanged"),
         }
     }
 }
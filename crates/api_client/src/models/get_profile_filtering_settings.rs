@@ -15,16 +15,22 @@ use serde::{Deserialize, Serialize};
 pub struct GetProfileFilteringSettings {
     #[serde(rename = "filters")]
     pub filters: Vec<models::ProfileAttributeFilterValue>,
+    /// Hide profiles which current account has liked, matched or blocked. The setting is applied starting from the next profile iterator reset.
+    #[serde(rename = "hide_interacted_profiles", skip_serializing_if = "Option::is_none")]
+    pub hide_interacted_profiles: Option<bool>,
     #[serde(rename = "last_seen_time_filter", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
     pub last_seen_time_filter: Option<Option<Box<models::LastSeenTimeFilter>>>,
     /// Show profiles until this far from current location. The value is in kilometers.  The value must be `None`, 1 or greater number.
     #[serde(rename = "max_distance_km_filter", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
     pub max_distance_km_filter: Option<Option<Box<models::MaxDistanceKm>>>,
+    /// Show current profile only to accounts which profile attributes match with current account's attribute filters. Accounts which do not match can not send likes to current account.
+    #[serde(rename = "mutual_attribute_filters", skip_serializing_if = "Option::is_none")]
+    pub mutual_attribute_filters: Option<bool>,
     #[serde(rename = "profile_created_filter", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
     pub profile_created_filter: Option<Option<Box<models::ProfileCreatedTimeFilter>>>,
     #[serde(rename = "profile_edited_filter", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
     pub profile_edited_filter: Option<Option<Box<models::ProfileEditedTimeFilter>>>,
-    /// Randomize iterator starting position within the profile index area which current position and [Self::max_distance_km] defines.
+    /// Randomize iterator starting position within the profile index area which current position and [Self::max_distance_km_filter] defines.
     #[serde(rename = "random_profile_order", skip_serializing_if = "Option::is_none")]
     pub random_profile_order: Option<bool>,
     #[serde(rename = "unlimited_likes_filter", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
@@ -35,8 +41,10 @@ impl GetProfileFilteringSettings {
     pub fn new(filters: Vec<models::ProfileAttributeFilterValue>) -> GetProfileFilteringSettings {
         GetProfileFilteringSettings {
             filters,
+            hide_interacted_profiles: None,
             last_seen_time_filter: None,
             max_distance_km_filter: None,
+            mutual_attribute_filters: None,
             profile_created_filter: None,
             profile_edited_filter: None,
             random_profile_order: None,
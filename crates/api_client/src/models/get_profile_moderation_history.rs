@@ -0,0 +1,32 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// GetProfileModerationHistory : Submitted profile names and texts of one account. The latest submission is the first item.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetProfileModerationHistory {
+    #[serde(rename = "names")]
+    pub names: Vec<models::ProfileNameModerationHistoryEntry>,
+    #[serde(rename = "texts")]
+    pub texts: Vec<models::ProfileTextModerationHistoryEntry>,
+}
+
+impl GetProfileModerationHistory {
+    /// Submitted profile names and texts of one account. The latest submission is the first item.
+    pub fn new(names: Vec<models::ProfileNameModerationHistoryEntry>, texts: Vec<models::ProfileTextModerationHistoryEntry>) -> GetProfileModerationHistory {
+        GetProfileModerationHistory {
+            names,
+            texts,
+        }
+    }
+}
+
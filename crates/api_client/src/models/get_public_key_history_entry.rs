@@ -0,0 +1,27 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetPublicKeyHistoryEntry {
+    #[serde(rename = "key", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub key: Option<Option<Box<models::PublicKeyHistoryEntry>>>,
+}
+
+impl GetPublicKeyHistoryEntry {
+    pub fn new() -> GetPublicKeyHistoryEntry {
+        GetPublicKeyHistoryEntry {
+            key: None,
+        }
+    }
+}
+
@@ -0,0 +1,29 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// LoginSessionId : Login session ID.  One account can have multiple login sessions, for example one session per device. Each session has its own access and refresh tokens.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoginSessionId {
+    #[serde(rename = "sid")]
+    pub sid: String,
+}
+
+impl LoginSessionId {
+    /// Login session ID.  One account can have multiple login sessions, for example one session per device. Each session has its own access and refresh tokens.
+    pub fn new(sid: String) -> LoginSessionId {
+        LoginSessionId {
+            sid,
+        }
+    }
+}
+
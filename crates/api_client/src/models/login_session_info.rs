@@ -0,0 +1,42 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoginSessionInfo {
+    #[serde(rename = "created_unix_time")]
+    pub created_unix_time: Box<models::UnixTime>,
+    /// True if the session is the session which made the request.
+    #[serde(rename = "current")]
+    pub current: bool,
+    #[serde(rename = "id")]
+    pub id: Box<models::LoginSessionId>,
+    /// Time when tokens were refreshed last time.
+    #[serde(rename = "last_used_unix_time")]
+    pub last_used_unix_time: Box<models::UnixTime>,
+    /// True if FCM device token is set for the session.
+    #[serde(rename = "push_notifications_enabled")]
+    pub push_notifications_enabled: bool,
+}
+
+impl LoginSessionInfo {
+    pub fn new(created_unix_time: models::UnixTime, current: bool, id: models::LoginSessionId, last_used_unix_time: models::UnixTime, push_notifications_enabled: bool) -> LoginSessionInfo {
+        LoginSessionInfo {
+            created_unix_time: Box::new(created_unix_time),
+            current,
+            id: Box::new(id),
+            last_used_unix_time: Box::new(last_used_unix_time),
+            push_notifications_enabled,
+        }
+    }
+}
+
@@ -0,0 +1,27 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoginSessionList {
+    #[serde(rename = "sessions")]
+    pub sessions: Vec<models::LoginSessionInfo>,
+}
+
+impl LoginSessionList {
+    pub fn new(sessions: Vec<models::LoginSessionInfo>) -> LoginSessionList {
+        LoginSessionList {
+            sessions,
+        }
+    }
+}
+
@@ -0,0 +1,29 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// MessageSignature : Server Ed25519 signature for chat message metadata.  Base64 standard encoding is used.  The signed data is - Format version (u8, current value is 1) - Sender account ID (16 UUID bytes) - Receiver account ID (16 UUID bytes) - Message number (i64, little endian) - Unix time when server received the message (i64, little endian) - SHA-256 hash of the message bytes  The signature can be verified using [MessageSigningPublicKey] from client config.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MessageSignature {
+    #[serde(rename = "s")]
+    pub s: String,
+}
+
+impl MessageSignature {
+    /// Server Ed25519 signature for chat message metadata.  Base64 standard encoding is used.  The signed data is - Format version (u8, current value is 1) - Sender account ID (16 UUID bytes) - Receiver account ID (16 UUID bytes) - Message number (i64, little endian) - Unix time when server received the message (i64, little endian) - SHA-256 hash of the message bytes  The signature can be verified using [MessageSigningPublicKey] from client config.
+    pub fn new(s: String) -> MessageSignature {
+        MessageSignature {
+            s,
+        }
+    }
+}
+
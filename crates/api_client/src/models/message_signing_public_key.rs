@@ -0,0 +1,29 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// MessageSigningPublicKey : Server Ed25519 public key for verifying [MessageSignature] and [PublicKeySignature].  Base64 standard encoding is used.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MessageSigningPublicKey {
+    #[serde(rename = "key")]
+    pub key: String,
+}
+
+impl MessageSigningPublicKey {
+    /// Server Ed25519 public key for verifying [MessageSignature] and [PublicKeySignature].  Base64 standard encoding is used.
+    pub fn new(key: String) -> MessageSigningPublicKey {
+        MessageSigningPublicKey {
+            key,
+        }
+    }
+}
+
@@ -50,6 +50,8 @@ pub mod boolean_setting;
 pub use self::boolean_setting::BooleanSetting;
 pub mod bot_config;
 pub use self::bot_config::BotConfig;
+pub mod chat_message_report_content;
+pub use self::chat_message_report_content::ChatMessageReportContent;
 pub mod client_config;
 pub use self::client_config::ClientConfig;
 pub mod client_config_sync_version;
@@ -84,6 +86,8 @@ pub mod content_processing_state_changed;
 pub use self::content_processing_state_changed::ContentProcessingStateChanged;
 pub mod content_processing_state_type;
 pub use self::content_processing_state_type::ContentProcessingStateType;
+pub mod content_size;
+pub use self::content_size::ContentSize;
 pub mod content_slot;
 pub use self::content_slot::ContentSlot;
 pub mod current_account_interaction_state;
@@ -104,6 +108,12 @@ pub mod custom_reports_file_hash;
 pub use self::custom_reports_file_hash::CustomReportsFileHash;
 pub mod custom_reports_order_mode;
 pub use self::custom_reports_order_mode::CustomReportsOrderMode;
+pub mod data_export_download_token;
+pub use self::data_export_download_token::DataExportDownloadToken;
+pub mod data_export_state;
+pub use self::data_export_state::DataExportState;
+pub mod data_export_state_type;
+pub use self::data_export_state_type::DataExportStateType;
 pub mod delete_like_result;
 pub use self::delete_like_result::DeleteLikeResult;
 pub mod demo_mode_confirm_login;
@@ -120,6 +130,10 @@ pub mod demo_mode_password;
 pub use self::demo_mode_password::DemoModePassword;
 pub mod demo_mode_token;
 pub use self::demo_mode_token::DemoModeToken;
+pub mod email_confirmation_state;
+pub use self::email_confirmation_state::EmailConfirmationState;
+pub mod email_confirmation_token;
+pub use self::email_confirmation_token::EmailConfirmationToken;
 pub mod event_to_client;
 pub use self::event_to_client::EventToClient;
 pub mod event_type;
@@ -156,6 +170,8 @@ pub mod get_profile_content_result;
 pub use self::get_profile_content_result::GetProfileContentResult;
 pub mod get_profile_filtering_settings;
 pub use self::get_profile_filtering_settings::GetProfileFilteringSettings;
+pub mod get_profile_moderation_history;
+pub use self::get_profile_moderation_history::GetProfileModerationHistory;
 pub mod get_profile_name_pending_moderation_list;
 pub use self::get_profile_name_pending_moderation_list::GetProfileNamePendingModerationList;
 pub mod get_profile_name_state;
@@ -172,6 +188,8 @@ pub mod get_profile_text_state;
 pub use self::get_profile_text_state::GetProfileTextState;
 pub mod get_public_key;
 pub use self::get_public_key::GetPublicKey;
+pub mod get_public_key_history_entry;
+pub use self::get_public_key_history_entry::GetPublicKeyHistoryEntry;
 pub mod get_report_list;
 pub use self::get_report_list::GetReportList;
 pub mod group_values;
@@ -192,6 +210,12 @@ pub mod location;
 pub use self::location::Location;
 pub mod login_result;
 pub use self::login_result::LoginResult;
+pub mod login_session_id;
+pub use self::login_session_id::LoginSessionId;
+pub mod login_session_info;
+pub use self::login_session_info::LoginSessionInfo;
+pub mod login_session_list;
+pub use self::login_session_list::LoginSessionList;
 pub mod maintenance_task;
 pub use self::maintenance_task::MaintenanceTask;
 pub mod manager_instance_name_list;
@@ -210,6 +234,10 @@ pub mod media_content_type;
 pub use self::media_content_type::MediaContentType;
 pub mod message_number;
 pub use self::message_number::MessageNumber;
+pub mod message_signature;
+pub use self::message_signature::MessageSignature;
+pub mod message_signing_public_key;
+pub use self::message_signing_public_key::MessageSigningPublicKey;
 pub mod moderation_queue_type;
 pub use self::moderation_queue_type::ModerationQueueType;
 pub mod my_profile_content;
@@ -246,6 +274,8 @@ pub mod pending_notification_token;
 pub use self::pending_notification_token::PendingNotificationToken;
 pub mod pending_notification_with_data;
 pub use self::pending_notification_with_data::PendingNotificationWithData;
+pub mod perceptual_hash_blocklist_reason;
+pub use self::perceptual_hash_blocklist_reason::PerceptualHashBlocklistReason;
 pub mod perf_metric_query_result;
 pub use self::perf_metric_query_result::PerfMetricQueryResult;
 pub mod perf_metric_value_area;
@@ -296,6 +326,8 @@ pub mod profile_content_version;
 pub use self::profile_content_version::ProfileContentVersion;
 pub mod profile_created_time_filter;
 pub use self::profile_created_time_filter::ProfileCreatedTimeFilter;
+pub mod profile_distance_bucket;
+pub use self::profile_distance_bucket::ProfileDistanceBucket;
 pub mod profile_edited_time_filter;
 pub use self::profile_edited_time_filter::ProfileEditedTimeFilter;
 pub mod profile_filtering_settings_update;
@@ -308,6 +340,8 @@ pub mod profile_iterator_session_id;
 pub use self::profile_iterator_session_id::ProfileIteratorSessionId;
 pub mod profile_link;
 pub use self::profile_link::ProfileLink;
+pub mod profile_name_moderation_history_entry;
+pub use self::profile_name_moderation_history_entry::ProfileNameModerationHistoryEntry;
 pub mod profile_name_moderation_state;
 pub use self::profile_name_moderation_state::ProfileNameModerationState;
 pub mod profile_name_pending_moderation;
@@ -316,12 +350,18 @@ pub mod profile_page;
 pub use self::profile_page::ProfilePage;
 pub mod profile_search_age_range;
 pub use self::profile_search_age_range::ProfileSearchAgeRange;
+pub mod profile_search_result_page;
+pub use self::profile_search_result_page::ProfileSearchResultPage;
+pub mod profile_search_result_value;
+pub use self::profile_search_result_value::ProfileSearchResultValue;
 pub mod profile_statistics_history_value;
 pub use self::profile_statistics_history_value::ProfileStatisticsHistoryValue;
 pub mod profile_statistics_history_value_type;
 pub use self::profile_statistics_history_value_type::ProfileStatisticsHistoryValueType;
 pub mod profile_sync_version;
 pub use self::profile_sync_version::ProfileSyncVersion;
+pub mod profile_text_moderation_history_entry;
+pub use self::profile_text_moderation_history_entry::ProfileTextModerationHistoryEntry;
 pub mod profile_text_moderation_info;
 pub use self::profile_text_moderation_info::ProfileTextModerationInfo;
 pub mod profile_text_moderation_rejected_reason_category;
@@ -342,10 +382,16 @@ pub mod public_key;
 pub use self::public_key::PublicKey;
 pub mod public_key_data;
 pub use self::public_key_data::PublicKeyData;
+pub mod public_key_history;
+pub use self::public_key_history::PublicKeyHistory;
+pub mod public_key_history_entry;
+pub use self::public_key_history_entry::PublicKeyHistoryEntry;
 pub mod public_key_id;
 pub use self::public_key_id::PublicKeyId;
 pub mod public_key_id_and_version;
 pub use self::public_key_id_and_version::PublicKeyIdAndVersion;
+pub mod public_key_signature;
+pub use self::public_key_signature::PublicKeySignature;
 pub mod public_key_version;
 pub use self::public_key_version::PublicKeyVersion;
 pub mod received_blocks_page;
@@ -400,6 +446,8 @@ pub mod search_groups;
 pub use self::search_groups::SearchGroups;
 pub mod security_content;
 pub use self::security_content::SecurityContent;
+pub mod send_email_confirmation_result;
+pub use self::send_email_confirmation_result::SendEmailConfirmationResult;
 pub mod send_like_result;
 pub use self::send_like_result::SendLikeResult;
 pub mod send_message_result;
@@ -424,8 +472,18 @@ pub mod set_profile_content;
 pub use self::set_profile_content::SetProfileContent;
 pub mod set_public_key;
 pub use self::set_public_key::SetPublicKey;
+pub mod sign_in_with_apple_info;
+pub use self::sign_in_with_apple_info::SignInWithAppleInfo;
 pub mod sign_in_with_login_info;
 pub use self::sign_in_with_login_info::SignInWithLoginInfo;
+pub mod signed_message_info;
+pub use self::signed_message_info::SignedMessageInfo;
+pub mod similar_blocklisted_content;
+pub use self::similar_blocklisted_content::SimilarBlocklistedContent;
+pub mod similar_content;
+pub use self::similar_content::SimilarContent;
+pub mod similar_content_list;
+pub use self::similar_content_list::SimilarContentList;
 pub mod software_info;
 pub use self::software_info::SoftwareInfo;
 pub mod software_update_state;
@@ -16,6 +16,9 @@ use serde::{Deserialize, Serialize};
 pub struct PendingMessage {
     #[serde(rename = "id")]
     pub id: Box<models::PendingMessageId>,
+    /// Server signature for the message metadata.
+    #[serde(rename = "signature", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Option<Box<models::MessageSignature>>>,
     /// Unix time when server received the message.
     #[serde(rename = "unix_time")]
     pub unix_time: Box<models::UnixTime>,
@@ -26,6 +29,7 @@ impl PendingMessage {
     pub fn new(id: models::PendingMessageId, unix_time: models::UnixTime) -> PendingMessage {
         PendingMessage {
             id: Box::new(id),
+            signature: None,
             unix_time: Box::new(unix_time),
         }
     }
@@ -0,0 +1,38 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum PerceptualHashBlocklistReason {
+    #[serde(rename = "RejectedContent")]
+    RejectedContent,
+    #[serde(rename = "BannedAccount")]
+    BannedAccount,
+
+}
+
+impl std::fmt::Display for PerceptualHashBlocklistReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::RejectedContent => write!(f, "RejectedContent"),
+            Self::BannedAccount => write!(f, "BannedAccount"),
+        }
+    }
+}
+
+impl Default for PerceptualHashBlocklistReason {
+    fn default() -> PerceptualHashBlocklistReason {
+        Self::RejectedContent
+    }
+}
+
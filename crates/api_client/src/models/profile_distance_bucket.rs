@@ -0,0 +1,32 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// ProfileDistanceBucket : Approximate distance between the profile owner and the profile viewer.  The exact distance is equal or greater than [Self::min_km] and less than [Self::max_km]. If [Self::max_km] is `None`, then there is no upper limit.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProfileDistanceBucket {
+    #[serde(rename = "max_km", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub max_km: Option<Option<i32>>,
+    #[serde(rename = "min_km")]
+    pub min_km: i32,
+}
+
+impl ProfileDistanceBucket {
+    /// Approximate distance between the profile owner and the profile viewer.  The exact distance is equal or greater than [Self::min_km] and less than [Self::max_km]. If [Self::max_km] is `None`, then there is no upper limit.
+    pub fn new(min_km: i32) -> ProfileDistanceBucket {
+        ProfileDistanceBucket {
+            max_km: None,
+            min_km,
+        }
+    }
+}
+
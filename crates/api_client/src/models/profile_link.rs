@@ -16,6 +16,9 @@ pub struct ProfileLink {
     /// This is optional because media component owns it.
     #[serde(rename = "content_version", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
     pub content_version: Option<Option<Box<models::ProfileContentVersion>>>,
+    /// Approximate distance from the profile viewer. This is only available when the profile is from the profile iterator.
+    #[serde(rename = "distance", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub distance: Option<Option<Box<models::ProfileDistanceBucket>>>,
     #[serde(rename = "id")]
     pub id: Box<models::AccountId>,
     /// Account's most recent disconnect time.  If the last seen time is not None, then it is Unix timestamp or -1 if the profile is currently online.
@@ -29,6 +32,7 @@ impl ProfileLink {
     pub fn new(id: models::AccountId, version: models::ProfileVersion) -> ProfileLink {
         ProfileLink {
             content_version: None,
+            distance: None,
             id: Box::new(id),
             last_seen_time: None,
             version: Box::new(version),
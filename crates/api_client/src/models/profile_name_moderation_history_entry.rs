@@ -0,0 +1,40 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProfileNameModerationHistoryEntry {
+    #[serde(rename = "moderated", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub moderated: Option<Option<Box<models::UnixTime>>>,
+    /// Human or bot moderator account which made the latest moderation decision.
+    #[serde(rename = "moderator_id", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub moderator_id: Option<Option<Box<models::AccountId>>>,
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "state")]
+    pub state: models::ProfileNameModerationState,
+    #[serde(rename = "submitted")]
+    pub submitted: Box<models::UnixTime>,
+}
+
+impl ProfileNameModerationHistoryEntry {
+    pub fn new(name: String, state: models::ProfileNameModerationState, submitted: models::UnixTime) -> ProfileNameModerationHistoryEntry {
+        ProfileNameModerationHistoryEntry {
+            moderated: None,
+            moderator_id: None,
+            name,
+            state,
+            submitted: Box::new(submitted),
+        }
+    }
+}
+
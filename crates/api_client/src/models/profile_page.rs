@@ -15,6 +15,7 @@ use serde::{Deserialize, Serialize};
 pub struct ProfilePage {
     #[serde(rename = "error_invalid_iterator_session_id", skip_serializing_if = "Option::is_none")]
     pub error_invalid_iterator_session_id: Option<bool>,
+    /// Profiles sorted by distance from the current account.
     #[serde(rename = "profiles")]
     pub profiles: Vec<models::ProfileLink>,
 }
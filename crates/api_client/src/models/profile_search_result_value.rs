@@ -0,0 +1,52 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProfileSearchResultValue {
+    #[serde(rename = "account_created")]
+    pub account_created: Box<models::UnixTime>,
+    #[serde(rename = "account_id")]
+    pub account_id: Box<models::AccountId>,
+    #[serde(rename = "age")]
+    pub age: i64,
+    #[serde(rename = "banned")]
+    pub banned: bool,
+    #[serde(rename = "initial_setup_completed")]
+    pub initial_setup_completed: bool,
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "pending_deletion")]
+    pub pending_deletion: bool,
+    #[serde(rename = "ptext")]
+    pub ptext: String,
+    /// Count of reports which other accounts have made about the account.
+    #[serde(rename = "report_count")]
+    pub report_count: i64,
+}
+
+impl ProfileSearchResultValue {
+    pub fn new(account_created: models::UnixTime, account_id: models::AccountId, age: i64, banned: bool, initial_setup_completed: bool, name: String, pending_deletion: bool, ptext: String, report_count: i64) -> ProfileSearchResultValue {
+        ProfileSearchResultValue {
+            account_created: Box::new(account_created),
+            account_id: Box::new(account_id),
+            age,
+            banned,
+            initial_setup_completed,
+            name,
+            pending_deletion,
+            ptext,
+            report_count,
+        }
+    }
+}
+
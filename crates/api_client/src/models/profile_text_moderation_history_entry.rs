@@ -0,0 +1,46 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProfileTextModerationHistoryEntry {
+    #[serde(rename = "moderated", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub moderated: Option<Option<Box<models::UnixTime>>>,
+    /// Human or bot moderator account which made the latest moderation decision.
+    #[serde(rename = "moderator_id", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub moderator_id: Option<Option<Box<models::AccountId>>>,
+    #[serde(rename = "rejected_reason_category", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub rejected_reason_category: Option<Option<Box<models::ProfileTextModerationRejectedReasonCategory>>>,
+    #[serde(rename = "rejected_reason_details", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub rejected_reason_details: Option<Option<Box<models::ProfileTextModerationRejectedReasonDetails>>>,
+    #[serde(rename = "state")]
+    pub state: models::ProfileTextModerationState,
+    #[serde(rename = "submitted")]
+    pub submitted: Box<models::UnixTime>,
+    #[serde(rename = "text")]
+    pub text: String,
+}
+
+impl ProfileTextModerationHistoryEntry {
+    pub fn new(state: models::ProfileTextModerationState, submitted: models::UnixTime, text: String) -> ProfileTextModerationHistoryEntry {
+        ProfileTextModerationHistoryEntry {
+            moderated: None,
+            moderator_id: None,
+            rejected_reason_category: None,
+            rejected_reason_details: None,
+            state,
+            submitted: Box::new(submitted),
+            text,
+        }
+    }
+}
+
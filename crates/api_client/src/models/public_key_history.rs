@@ -0,0 +1,29 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// PublicKeyHistory : All public keys which account has uploaded.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PublicKeyHistory {
+    #[serde(rename = "keys")]
+    pub keys: Vec<models::PublicKeyHistoryEntry>,
+}
+
+impl PublicKeyHistory {
+    /// All public keys which account has uploaded.
+    pub fn new(keys: Vec<models::PublicKeyHistoryEntry>) -> PublicKeyHistory {
+        PublicKeyHistory {
+            keys,
+        }
+    }
+}
+
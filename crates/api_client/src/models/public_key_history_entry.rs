@@ -0,0 +1,41 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// PublicKeyHistoryEntry : Public key from account's public key history.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PublicKeyHistoryEntry {
+    #[serde(rename = "data")]
+    pub data: Box<models::PublicKeyData>,
+    #[serde(rename = "id")]
+    pub id: Box<models::PublicKeyId>,
+    #[serde(rename = "signature")]
+    pub signature: Box<models::PublicKeySignature>,
+    #[serde(rename = "ut")]
+    pub ut: Box<models::UnixTime>,
+    #[serde(rename = "version")]
+    pub version: Box<models::PublicKeyVersion>,
+}
+
+impl PublicKeyHistoryEntry {
+    /// Public key from account's public key history.
+    pub fn new(data: models::PublicKeyData, id: models::PublicKeyId, signature: models::PublicKeySignature, ut: models::UnixTime, version: models::PublicKeyVersion) -> PublicKeyHistoryEntry {
+        PublicKeyHistoryEntry {
+            data: Box::new(data),
+            id: Box::new(id),
+            signature: Box::new(signature),
+            ut: Box::new(ut),
+            version: Box::new(version),
+        }
+    }
+}
+
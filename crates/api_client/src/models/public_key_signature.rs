@@ -0,0 +1,29 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// PublicKeySignature : Server Ed25519 signature for public key which account has uploaded.  Base64 standard encoding is used.  The signed data is - Data type (u8, current value is 2). Value 1 is used for [MessageSignature] data. - Public key owner account ID (16 UUID bytes) - Public key version (i64, little endian) - Public key ID (i64, little endian) - Unix time when server received the public key (i64, little endian) - SHA-256 hash of the public key data UTF-8 bytes  The signature can be verified using [MessageSigningPublicKey] from client config.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PublicKeySignature {
+    #[serde(rename = "s")]
+    pub s: String,
+}
+
+impl PublicKeySignature {
+    /// Server Ed25519 signature for public key which account has uploaded.  Base64 standard encoding is used.  The signed data is - Data type (u8, current value is 2). Value 1 is used for [MessageSignature] data. - Public key owner account ID (16 UUID bytes) - Public key version (i64, little endian) - Public key ID (i64, little endian) - Unix time when server received the public key (i64, little endian) - SHA-256 hash of the public key data UTF-8 bytes  The signature can be verified using [MessageSigningPublicKey] from client config.
+    pub fn new(s: String) -> PublicKeySignature {
+        PublicKeySignature {
+            s,
+        }
+    }
+}
+
@@ -0,0 +1,34 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SendEmailConfirmationResult {
+    #[serde(rename = "error_email_already_verified")]
+    pub error_email_already_verified: bool,
+    #[serde(rename = "error_email_not_set")]
+    pub error_email_not_set: bool,
+    /// New email confirmation email can be requested after this time.
+    #[serde(rename = "error_try_again_later_unix_time", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub error_try_again_later_unix_time: Option<Option<Box<models::UnixTime>>>,
+}
+
+impl SendEmailConfirmationResult {
+    pub fn new(error_email_already_verified: bool, error_email_not_set: bool) -> SendEmailConfirmationResult {
+        SendEmailConfirmationResult {
+            error_email_already_verified,
+            error_email_not_set,
+            error_try_again_later_unix_time: None,
+        }
+    }
+}
+
@@ -15,6 +15,9 @@ use serde::{Deserialize, Serialize};
 pub struct SendLikeResult {
     #[serde(rename = "error_account_interaction_state_mismatch", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
     pub error_account_interaction_state_mismatch: Option<Option<models::CurrentAccountInteractionState>>,
+    /// The like receiver has enabled mutual attribute filters and current account's profile attributes do not match with those.
+    #[serde(rename = "error_attribute_filters_mismatch", skip_serializing_if = "Option::is_none")]
+    pub error_attribute_filters_mismatch: Option<bool>,
     #[serde(rename = "status", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
     pub status: Option<Option<models::LimitedActionStatus>>,
 }
@@ -23,6 +26,7 @@ impl SendLikeResult {
     pub fn new() -> SendLikeResult {
         SendLikeResult {
             error_account_interaction_state_mismatch: None,
+            error_attribute_filters_mismatch: None,
             status: None,
         }
     }
@@ -13,8 +13,14 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SendMessageResult {
+    /// Message is larger than the server message size limit.
+    #[serde(rename = "error_message_too_large", skip_serializing_if = "Option::is_none")]
+    pub error_message_too_large: Option<bool>,
     #[serde(rename = "error_receiver_blocked_sender_or_receiver_not_found", skip_serializing_if = "Option::is_none")]
     pub error_receiver_blocked_sender_or_receiver_not_found: Option<bool>,
+    /// Receiver has too many not acknowledged messages from all conversations.
+    #[serde(rename = "error_receiver_inbox_full", skip_serializing_if = "Option::is_none")]
+    pub error_receiver_inbox_full: Option<bool>,
     #[serde(rename = "error_receiver_public_key_outdated", skip_serializing_if = "Option::is_none")]
     pub error_receiver_public_key_outdated: Option<bool>,
     #[serde(rename = "error_too_many_receiver_acknowledgements_missing", skip_serializing_if = "Option::is_none")]
@@ -24,6 +30,9 @@ pub struct SendMessageResult {
     /// None if error happened
     #[serde(rename = "mn", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
     pub mn: Option<Option<Box<models::MessageNumber>>>,
+    /// Server signature for the message metadata. None if error happened
+    #[serde(rename = "signature", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Option<Box<models::MessageSignature>>>,
     /// None if error happened
     #[serde(rename = "ut", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
     pub ut: Option<Option<Box<models::UnixTime>>>,
@@ -32,11 +41,14 @@ pub struct SendMessageResult {
 impl SendMessageResult {
     pub fn new() -> SendMessageResult {
         SendMessageResult {
+            error_message_too_large: None,
             error_receiver_blocked_sender_or_receiver_not_found: None,
+            error_receiver_inbox_full: None,
             error_receiver_public_key_outdated: None,
             error_too_many_receiver_acknowledgements_missing: None,
             error_too_many_sender_acknowledgements_missing: None,
             mn: None,
+            signature: None,
             ut: None,
         }
     }
@@ -0,0 +1,31 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignInWithAppleInfo {
+    /// Nonce which client generated. SHA-256 hash of the nonce in lowercase hex format must be in the token.
+    #[serde(rename = "nonce")]
+    pub nonce: String,
+    #[serde(rename = "token")]
+    pub token: String,
+}
+
+impl SignInWithAppleInfo {
+    pub fn new(nonce: String, token: String) -> SignInWithAppleInfo {
+        SignInWithAppleInfo {
+            nonce,
+            token,
+        }
+    }
+}
+
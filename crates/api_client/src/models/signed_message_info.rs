@@ -0,0 +1,46 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// SignedMessageInfo : Message metadata and [MessageSignature] which client received from server.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignedMessageInfo {
+    /// Base64 standard encoded message bytes.
+    #[serde(rename = "message")]
+    pub message: String,
+    #[serde(rename = "mn")]
+    pub mn: Box<models::MessageNumber>,
+    #[serde(rename = "receiver")]
+    pub receiver: Box<models::AccountId>,
+    #[serde(rename = "sender")]
+    pub sender: Box<models::AccountId>,
+    #[serde(rename = "signature")]
+    pub signature: Box<models::MessageSignature>,
+    /// Unix time when server received the message.
+    #[serde(rename = "ut")]
+    pub ut: Box<models::UnixTime>,
+}
+
+impl SignedMessageInfo {
+    /// Message metadata and [MessageSignature] which client received from server.
+    pub fn new(message: String, mn: models::MessageNumber, receiver: models::AccountId, sender: models::AccountId, signature: models::MessageSignature, ut: models::UnixTime) -> SignedMessageInfo {
+        SignedMessageInfo {
+            message,
+            mn: Box::new(mn),
+            receiver: Box::new(receiver),
+            sender: Box::new(sender),
+            signature: Box::new(signature),
+            ut: Box::new(ut),
+        }
+    }
+}
+
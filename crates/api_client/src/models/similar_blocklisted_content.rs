@@ -0,0 +1,40 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// SimilarBlocklistedContent : Perceptual hash blocklist entry which is similar to some content.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimilarBlocklistedContent {
+    /// Account which uploaded the blocklisted image. Empty if the account is deleted.
+    #[serde(rename = "account_id", default, with = "::serde_with::rust::double_option", skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<Option<Box<models::AccountId>>>,
+    #[serde(rename = "added_unix_time")]
+    pub added_unix_time: Box<models::UnixTime>,
+    /// Hamming distance between the perceptual hashes.
+    #[serde(rename = "distance")]
+    pub distance: i32,
+    #[serde(rename = "reason")]
+    pub reason: models::PerceptualHashBlocklistReason,
+}
+
+impl SimilarBlocklistedContent {
+    /// Perceptual hash blocklist entry which is similar to some content.
+    pub fn new(added_unix_time: models::UnixTime, distance: i32, reason: models::PerceptualHashBlocklistReason) -> SimilarBlocklistedContent {
+        SimilarBlocklistedContent {
+            account_id: None,
+            added_unix_time: Box::new(added_unix_time),
+            distance,
+            reason,
+        }
+    }
+}
+
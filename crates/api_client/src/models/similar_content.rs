@@ -0,0 +1,39 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// SimilarContent : Content of other account which is a near-duplicate of some content.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimilarContent {
+    #[serde(rename = "account_id")]
+    pub account_id: Box<models::AccountId>,
+    #[serde(rename = "content_id")]
+    pub content_id: Box<models::ContentId>,
+    /// Hamming distance between the perceptual hashes.
+    #[serde(rename = "distance")]
+    pub distance: i32,
+    #[serde(rename = "state")]
+    pub state: models::ContentModerationState,
+}
+
+impl SimilarContent {
+    /// Content of other account which is a near-duplicate of some content.
+    pub fn new(account_id: models::AccountId, content_id: models::ContentId, distance: i32, state: models::ContentModerationState) -> SimilarContent {
+        SimilarContent {
+            account_id: Box::new(account_id),
+            content_id: Box::new(content_id),
+            distance,
+            state,
+        }
+    }
+}
+
@@ -0,0 +1,37 @@
+/*
+ * afrodite-backend
+ *
+ * Dating app backend API
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// SimilarContentList : Content which has a similar perceptual hash.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimilarContentList {
+    #[serde(rename = "blocklist")]
+    pub blocklist: Vec<models::SimilarBlocklistedContent>,
+    /// Content of other accounts.
+    #[serde(rename = "content")]
+    pub content: Vec<models::SimilarContent>,
+    /// Similar content or blocklisted hash existed when the content was uploaded, so the content is moderated by humans.
+    #[serde(rename = "perceptual_hash_match")]
+    pub perceptual_hash_match: bool,
+}
+
+impl SimilarContentList {
+    /// Content which has a similar perceptual hash.
+    pub fn new(blocklist: Vec<models::SimilarBlocklistedContent>, content: Vec<models::SimilarContent>, perceptual_hash_match: bool) -> SimilarContentList {
+        SimilarContentList {
+            blocklist,
+            content,
+            perceptual_hash_match,
+        }
+    }
+}
+
@@ -18,6 +18,8 @@ pub enum TimeGranularity {
     Minutes,
     #[serde(rename = "Hours")]
     Hours,
+    #[serde(rename = "Days")]
+    Days,
 
 }
 
@@ -26,6 +28,7 @@ impl std::fmt::Display for TimeGranularity {
         match self {
             Self::Minutes => write!(f, "Minutes"),
             Self::Hours => write!(f, "Hours"),
+            Self::Days => write!(f, "Days"),
         }
     }
 }
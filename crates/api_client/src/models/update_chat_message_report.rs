@@ -13,16 +13,21 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct UpdateChatMessageReport {
+    /// Decrypted message text for moderators. The server can not verify this as the message bytes are end-to-end encrypted.
     #[serde(rename = "message")]
     pub message: String,
+    /// Server signed reported message. The report target must be the message sender and the report creator must be the message receiver.
+    #[serde(rename = "signed_message")]
+    pub signed_message: Box<models::SignedMessageInfo>,
     #[serde(rename = "target")]
     pub target: Box<models::AccountId>,
 }
 
 impl UpdateChatMessageReport {
-    pub fn new(message: String, target: models::AccountId) -> UpdateChatMessageReport {
+    pub fn new(message: String, signed_message: models::SignedMessageInfo, target: models::AccountId) -> UpdateChatMessageReport {
         UpdateChatMessageReport {
             message,
+            signed_message: Box::new(signed_message),
             target: Box::new(target),
         }
     }
@@ -13,6 +13,9 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct UpdateReportResult {
+    /// Chat message report specific error. The server signature for the reported message is invalid.
+    #[serde(rename = "error_invalid_message_signature", skip_serializing_if = "Option::is_none")]
+    pub error_invalid_message_signature: Option<bool>,
     #[serde(rename = "error_outdated_report_content", skip_serializing_if = "Option::is_none")]
     pub error_outdated_report_content: Option<bool>,
     #[serde(rename = "error_too_many_reports", skip_serializing_if = "Option::is_none")]
@@ -22,6 +25,7 @@ pub struct UpdateReportResult {
 impl UpdateReportResult {
     pub fn new() -> UpdateReportResult {
         UpdateReportResult {
+            error_invalid_message_signature: None,
             error_outdated_report_content: None,
             error_too_many_reports: None,
         }
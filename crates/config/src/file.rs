@@ -50,9 +50,20 @@ pub const DEFAULT_CONFIG_FILE_TEXT: &str = r#"
 
 # [limits.account]
 # account_deletion_wait_duration = "90d"
+# access_token_max_age = "1d"
+# max_login_sessions_per_account = 10
+# login_session_idle_expiry = "90d"
+# data_export_download_duration = "1d"
+# email_confirmation_token_max_age = "1d"
+# email_confirmation_resend_wait_duration = "5m"
+# email_confirmation_required_for_initial_setup = false
+# chat_email_notification_delay = "1h"
 
 # [limits.chat]
 # like_limit_reset_time_utc_offset_hours = 0
+# max_pending_messages_per_conversation = 50
+# max_pending_messages_per_account = 1000
+# max_message_size_bytes = 65535
 
 # [limits.media]
 # concurrent_content_uploads = 10
@@ -265,20 +276,128 @@ impl Default for ProcessedReportDeletionConfig {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct AccountLimitsConfig {
     pub account_deletion_wait_duration: DurationValue,
+    /// Access token is valid this long after it is created.
+    /// Refresh token can be used to get a new access token.
+    #[serde(default = "default_access_token_max_age")]
+    pub access_token_max_age: DurationValue,
+    /// If new login session would exceed this, the least recently used
+    /// login sessions are revoked.
+    #[serde(default = "default_max_login_sessions_per_account")]
+    pub max_login_sessions_per_account: u16,
+    /// Login sessions which are not used during this time are revoked.
+    /// Using refresh token or connecting WebSocket updates the last
+    /// used time.
+    #[serde(default = "default_login_session_idle_expiry")]
+    pub login_session_idle_expiry: DurationValue,
+    /// Data export archive is available for downloading this long
+    /// after the export is completed.
+    #[serde(default = "default_data_export_download_duration")]
+    pub data_export_download_duration: DurationValue,
+    /// Email confirmation token in email confirmation email is valid
+    /// this long after it is created.
+    #[serde(default = "default_email_confirmation_token_max_age")]
+    pub email_confirmation_token_max_age: DurationValue,
+    /// Min wait duration between email confirmation email requests.
+    #[serde(default = "default_email_confirmation_resend_wait_duration")]
+    pub email_confirmation_resend_wait_duration: DurationValue,
+    /// Prevent completing account initial setup until
+    /// email address is confirmed.
+    #[serde(default)]
+    pub email_confirmation_required_for_initial_setup: bool,
+    /// Send chat notification digest email if new chat requests or
+    /// messages are not received by the client before this delay
+    /// elapses. Accounts with push notifications enabled do not receive
+    /// the email.
+    #[serde(default = "default_chat_email_notification_delay")]
+    pub chat_email_notification_delay: DurationValue,
+}
+
+fn default_access_token_max_age() -> DurationValue {
+    DurationValue::from_days(1)
+}
+
+fn default_max_login_sessions_per_account() -> u16 {
+    10
+}
+
+fn default_login_session_idle_expiry() -> DurationValue {
+    DurationValue::from_days(90)
+}
+
+fn default_data_export_download_duration() -> DurationValue {
+    DurationValue::from_days(1)
+}
+
+fn default_email_confirmation_token_max_age() -> DurationValue {
+    DurationValue::from_days(1)
+}
+
+fn default_email_confirmation_resend_wait_duration() -> DurationValue {
+    DurationValue::from_minutes(5)
+}
+
+fn default_chat_email_notification_delay() -> DurationValue {
+    DurationValue::from_minutes(60)
 }
 
 impl Default for AccountLimitsConfig {
     fn default() -> Self {
         Self {
             account_deletion_wait_duration: DurationValue::from_days(90),
+            access_token_max_age: default_access_token_max_age(),
+            max_login_sessions_per_account: default_max_login_sessions_per_account(),
+            login_session_idle_expiry: default_login_session_idle_expiry(),
+            data_export_download_duration: default_data_export_download_duration(),
+            email_confirmation_token_max_age: default_email_confirmation_token_max_age(),
+            email_confirmation_resend_wait_duration:
+                default_email_confirmation_resend_wait_duration(),
+            email_confirmation_required_for_initial_setup: false,
+            chat_email_notification_delay: default_chat_email_notification_delay(),
         }
     }
 }
 
 /// Chat releated limits config
-#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ChatLimitsConfig {
+    #[serde(default)]
     pub like_limit_reset_time_utc_offset_hours: i8,
+    /// Max count of messages which receiver has not yet acknowledged
+    /// in one conversation. The same limit is also used for
+    /// messages which sender has not yet acknowledged.
+    #[serde(default = "default_max_pending_messages_per_conversation")]
+    pub max_pending_messages_per_conversation: u16,
+    /// Max count of messages which receiver has not yet acknowledged
+    /// in all conversations.
+    #[serde(default = "default_max_pending_messages_per_account")]
+    pub max_pending_messages_per_account: u32,
+    /// Max message size. Values larger than [u16::MAX] are not
+    /// allowed.
+    #[serde(default = "default_max_message_size_bytes")]
+    pub max_message_size_bytes: u16,
+}
+
+fn default_max_pending_messages_per_conversation() -> u16 {
+    50
+}
+
+fn default_max_pending_messages_per_account() -> u32 {
+    1000
+}
+
+fn default_max_message_size_bytes() -> u16 {
+    u16::MAX
+}
+
+impl Default for ChatLimitsConfig {
+    fn default() -> Self {
+        Self {
+            like_limit_reset_time_utc_offset_hours: 0,
+            max_pending_messages_per_conversation: default_max_pending_messages_per_conversation(),
+            max_pending_messages_per_account: default_max_pending_messages_per_account(),
+            max_message_size_bytes: default_max_message_size_bytes(),
+        }
+    }
 }
 
 /// Media related limits config
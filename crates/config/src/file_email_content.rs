@@ -52,5 +52,13 @@ impl EmailContentFile {
 pub struct EmailContent {
     pub message_type: EmailMessages,
     pub subject: String,
+    /// Email body text. For [EmailMessages::EmailConfirmation] the
+    /// [EmailMessages::EMAIL_CONFIRMATION_TOKEN_PLACEHOLDER] is replaced
+    /// with the email confirmation token.
+    ///
+    /// For [EmailMessages::ChatNotificationDigest] the
+    /// [EmailMessages::NEW_CHAT_REQUESTS_COUNT_PLACEHOLDER] and
+    /// [EmailMessages::NEW_MESSAGES_COUNT_PLACEHOLDER] are replaced
+    /// with the related counts. Message content is never included.
     pub body: String,
 }
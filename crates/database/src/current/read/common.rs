@@ -9,7 +9,7 @@ use crate::{
 
 mod queue_number;
 mod state;
-mod token;
+mod login_session;
 mod report;
 mod client_config;
 
@@ -24,8 +24,8 @@ impl<'a> CurrentReadCommon<'a> {
         queue_number::CurrentReadCommonQueueNumber::new(self.cmds)
     }
 
-    pub fn token(self) -> token::CurrentReadAccountToken<'a> {
-        token::CurrentReadAccountToken::new(self.cmds)
+    pub fn login_session(self) -> login_session::CurrentReadCommonLoginSession<'a> {
+        login_session::CurrentReadCommonLoginSession::new(self.cmds)
     }
 
     pub fn report(self) -> report::CurrentReadCommonReport<'a> {
@@ -0,0 +1,139 @@
+use diesel::prelude::*;
+use error_stack::{Result, ResultExt};
+use model::{
+    AccessToken, AccountIdInternal, LoginSessionAccessToken, LoginSessionId, LoginSessionRaw,
+    RefreshToken, UnixTime,
+};
+
+use crate::{define_current_read_commands, DieselDatabaseError, IntoDatabaseError};
+
+define_current_read_commands!(CurrentReadCommonLoginSession);
+
+impl CurrentReadCommonLoginSession<'_> {
+    pub fn refresh_token(
+        &mut self,
+        session: LoginSessionId,
+    ) -> Result<Option<RefreshToken>, DieselDatabaseError> {
+        use crate::schema::login_session::dsl::*;
+
+        let data: Option<Vec<u8>> = login_session
+            .filter(session_id.eq(session))
+            .select(refresh_token)
+            .first(self.conn())
+            .optional()
+            .into_db_error(session)?;
+
+        Ok(data.map(|data| RefreshToken::from_bytes(&data)))
+    }
+
+    pub fn access_tokens(
+        &mut self,
+        id: AccountIdInternal,
+    ) -> Result<Vec<LoginSessionAccessToken>, DieselDatabaseError> {
+        use crate::schema::login_session::dsl::*;
+
+        let data: Vec<(LoginSessionId, String, UnixTime)> = login_session
+            .filter(account_id.eq(id.as_db_id()))
+            .select((session_id, access_token, access_token_unix_time))
+            .load(self.conn())
+            .into_db_error(id)?;
+
+        Ok(data
+            .into_iter()
+            .map(|(session, token, time)| LoginSessionAccessToken {
+                session_id: session,
+                access_token: AccessToken::new(token),
+                access_token_unix_time: time,
+            })
+            .collect())
+    }
+
+    /// Find login session using current refresh token of the session.
+    pub fn login_session_with_refresh_token(
+        &mut self,
+        token: RefreshToken,
+    ) -> Result<Option<(AccountIdInternal, LoginSessionId)>, DieselDatabaseError> {
+        use crate::schema::{account_id, login_session};
+
+        let token = token
+            .bytes()
+            .change_context(DieselDatabaseError::DataFormatConversion)?;
+
+        login_session::table
+            .inner_join(account_id::table)
+            .filter(login_session::refresh_token.eq(token))
+            .select((AccountIdInternal::as_select(), login_session::session_id))
+            .first(self.conn())
+            .optional()
+            .into_db_error(())
+    }
+
+    /// Find login session using already rotated refresh token.
+    pub fn login_session_with_used_refresh_token(
+        &mut self,
+        token: RefreshToken,
+    ) -> Result<Option<(AccountIdInternal, LoginSessionId)>, DieselDatabaseError> {
+        use crate::schema::{account_id, login_session, used_refresh_token};
+
+        let token = token
+            .bytes()
+            .change_context(DieselDatabaseError::DataFormatConversion)?;
+
+        used_refresh_token::table
+            .inner_join(login_session::table.inner_join(account_id::table))
+            .filter(used_refresh_token::refresh_token.eq(token))
+            .select((AccountIdInternal::as_select(), login_session::session_id))
+            .first(self.conn())
+            .optional()
+            .into_db_error(())
+    }
+
+    pub fn access_token(
+        &mut self,
+        session: LoginSessionId,
+    ) -> Result<Option<AccessToken>, DieselDatabaseError> {
+        use crate::schema::login_session::dsl::*;
+
+        let data: Option<String> = login_session
+            .filter(session_id.eq(session))
+            .select(access_token)
+            .first(self.conn())
+            .optional()
+            .into_db_error(session)?;
+
+        Ok(data.map(AccessToken::new))
+    }
+
+    pub fn login_sessions(
+        &mut self,
+        id: AccountIdInternal,
+    ) -> Result<Vec<LoginSessionRaw>, DieselDatabaseError> {
+        use crate::schema::login_session::dsl::*;
+
+        login_session
+            .filter(account_id.eq(id.as_db_id()))
+            .select(LoginSessionRaw::as_select())
+            .order(created_unix_time.asc())
+            .load(self.conn())
+            .into_db_error(id)
+    }
+
+    /// Check that the login session is owned by the account.
+    pub fn login_session_exists(
+        &mut self,
+        id: AccountIdInternal,
+        session: LoginSessionId,
+    ) -> Result<bool, DieselDatabaseError> {
+        use crate::schema::login_session::dsl::*;
+
+        let data: Option<LoginSessionId> = login_session
+            .filter(account_id.eq(id.as_db_id()))
+            .filter(session_id.eq(session))
+            .select(session_id)
+            .first(self.conn())
+            .optional()
+            .into_db_error((id, session))?;
+
+        Ok(data.is_some())
+    }
+}
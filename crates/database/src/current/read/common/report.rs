@@ -1,12 +1,14 @@
 use config::file::Components;
 use diesel::{alias, prelude::*};
 use error_stack::Result;
-use model::{AccountId, AccountIdDb, AccountIdInternal, AccountInteractionInternal, ContentId, CustomReportContent, ReportAccountInfo, ReportChatInfo, ReportChatInfoInteractionState, ReportContent, ReportDetailed, ReportDetailedInfo, ReportDetailedInfoInternal, ReportDetailedWithId, ReportIdDb, ReportInternal, ReportProcessingState, ReportTypeNumberInternal};
+use model::{AccountId, AccountIdDb, AccountIdInternal, AccountInteractionInternal, ContentId, ChatMessageReportContent, CustomReportContent, MessageNumber, ReportAccountInfo, ReportChatInfo, ReportChatInfoInteractionState, ReportContent, ReportDetailed, ReportDetailedInfo, ReportDetailedInfoInternal, ReportDetailedWithId, ReportIdDb, ReportInternal, ReportProcessingState, ReportTypeNumberInternal, SignedMessageInternal, UnixTime};
 
 use crate::{define_current_read_commands, DieselDatabaseError, IntoDatabaseError};
 
 define_current_read_commands!(CurrentReadCommonReport);
 
+type ChatMessageReportRow = (String, AccountId, AccountId, MessageNumber, UnixTime, Vec<u8>, Vec<u8>);
+
 impl CurrentReadCommonReport<'_> {
     fn get_all_internal_reports(
         &mut self,
@@ -74,6 +76,54 @@ impl CurrentReadCommonReport<'_> {
         Ok(reports)
     }
 
+    /// All reports which the account has created.
+    pub fn get_all_detailed_reports_created_by(
+        &mut self,
+        creator: AccountIdInternal,
+        components: Components,
+    ) -> Result<Vec<ReportDetailed>, DieselDatabaseError> {
+        use crate::schema::{account_id, common_report::dsl::*};
+
+        let (creator_aid, target_aid) =
+            alias!(account_id as creator_aid, account_id as target_aid);
+
+        let values: Vec<(AccountId, AccountIdDb, AccountId, AccountIdDb, ReportIdDb, ReportProcessingState, ReportTypeNumberInternal)> = common_report
+            .inner_join(creator_aid.on(creator_account_id.eq(creator_aid.field(account_id::id))))
+            .inner_join(target_aid.on(target_account_id.eq(target_aid.field(account_id::id))))
+            .filter(creator_account_id.eq(creator.as_db_id()))
+            .select((
+                creator_aid.field(account_id::uuid),
+                creator_account_id,
+                target_aid.field(account_id::uuid),
+                target_account_id,
+                id,
+                processing_state,
+                report_type_number,
+            ))
+            .order(creation_unix_time.asc())
+            .load(self.conn())
+            .into_db_error(())?;
+
+        let mut reports = vec![];
+        for (creator, creator_db_id, target, target_db_id, report_id, state, report_type) in values {
+            let internal = ReportInternal {
+                info: ReportDetailedInfoInternal {
+                    creator,
+                    target,
+                    processing_state: state,
+                    report_type,
+                },
+                id: report_id,
+                creator_db_id,
+                target_db_id,
+            };
+            let detailed = self.convert_to_detailed_report(internal, components)?;
+            reports.push(detailed.report);
+        }
+
+        Ok(reports)
+    }
+
     pub fn convert_to_detailed_report(
         &mut self,
         report: ReportInternal,
@@ -177,15 +227,36 @@ impl CurrentReadCommonReport<'_> {
     fn chat_message_report(
         &mut self,
         id: ReportIdDb
-    ) -> Result<Option<String>, DieselDatabaseError> {
+    ) -> Result<Option<ChatMessageReportContent>, DieselDatabaseError> {
         use crate::schema::chat_report_chat_message::dsl::*;
 
-        chat_report_chat_message.find(id)
-            .select(chat_message)
+        let message: Option<ChatMessageReportRow> = chat_report_chat_message.find(id)
+            .select((
+                chat_message,
+                message_sender_account_id_uuid,
+                message_receiver_account_id_uuid,
+                message_number,
+                message_unix_time,
+                message_bytes,
+                message_signature,
+            ))
             .first(self.conn())
             .optional()
-            .into_db_error(())
-            .map(|v| v.flatten())
+            .into_db_error(())?;
+
+        Ok(message.map(|(text, sender, receiver, mn, ut, message, signature)| {
+            ChatMessageReportContent {
+                message: text,
+                signed_message: SignedMessageInternal {
+                    sender,
+                    receiver,
+                    mn,
+                    ut,
+                    message,
+                    signature,
+                }.to_signed_message_info(),
+            }
+        }))
     }
 
     fn custom_report(
@@ -7,7 +7,7 @@ use crate::{define_current_write_commands, IntoDatabaseError};
 
 mod queue_number;
 mod state;
-mod token;
+mod login_session;
 mod report;
 mod client_config;
 
@@ -22,8 +22,8 @@ impl<'a> CurrentWriteCommon<'a> {
         state::CurrentWriteCommonState::new(self.cmds)
     }
 
-    pub fn token(self) -> token::CurrentWriteAccountToken<'a> {
-        token::CurrentWriteAccountToken::new(self.cmds)
+    pub fn login_session(self) -> login_session::CurrentWriteCommonLoginSession<'a> {
+        login_session::CurrentWriteCommonLoginSession::new(self.cmds)
     }
 
     pub fn report(self) -> report::CurrentWriteCommonReport<'a> {
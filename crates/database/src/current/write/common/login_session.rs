@@ -0,0 +1,128 @@
+use diesel::{delete, insert_into, prelude::*, update};
+use error_stack::{Result, ResultExt};
+use model::{AccessToken, AccountIdInternal, LoginSessionId, RefreshToken, UnixTime};
+
+use crate::{define_current_write_commands, DieselDatabaseError, IntoDatabaseError};
+
+define_current_write_commands!(CurrentWriteCommonLoginSession);
+
+/// Max count of used refresh tokens which are stored per login session
+/// for refresh token reuse detection.
+const MAX_USED_REFRESH_TOKENS_PER_SESSION: i64 = 10;
+
+impl CurrentWriteCommonLoginSession<'_> {
+    /// Returns the access token creation time.
+    pub fn insert_login_session(
+        &mut self,
+        id: AccountIdInternal,
+        session: LoginSessionId,
+        access: AccessToken,
+        refresh: RefreshToken,
+    ) -> Result<UnixTime, DieselDatabaseError> {
+        use model::schema::login_session::dsl::*;
+
+        let refresh = refresh
+            .bytes()
+            .change_context(DieselDatabaseError::DataFormatConversion)?;
+        let current_time = UnixTime::current_time();
+
+        insert_into(login_session)
+            .values((
+                session_id.eq(session),
+                account_id.eq(id.as_db_id()),
+                access_token.eq(access.as_str()),
+                access_token_unix_time.eq(current_time),
+                refresh_token.eq(refresh),
+                created_unix_time.eq(current_time),
+                last_used_unix_time.eq(current_time),
+            ))
+            .execute(self.conn())
+            .into_db_error((id, session))?;
+
+        Ok(current_time)
+    }
+
+    /// Update tokens and last used time. The current refresh token
+    /// is saved to used refresh tokens, so reusing it can be detected.
+    /// Only latest [MAX_USED_REFRESH_TOKENS_PER_SESSION] used refresh
+    /// tokens are kept.
+    ///
+    /// Returns the new access token creation time.
+    pub fn update_tokens(
+        &mut self,
+        session: LoginSessionId,
+        access: AccessToken,
+        refresh: RefreshToken,
+    ) -> Result<UnixTime, DieselDatabaseError> {
+        use model::schema::{login_session, used_refresh_token};
+
+        let refresh = refresh
+            .bytes()
+            .change_context(DieselDatabaseError::DataFormatConversion)?;
+        let current_time = UnixTime::current_time();
+
+        let current_refresh_token: Vec<u8> = login_session::table
+            .find(session)
+            .select(login_session::refresh_token)
+            .first(self.conn())
+            .into_db_error(session)?;
+
+        insert_into(used_refresh_token::table)
+            .values((
+                used_refresh_token::refresh_token.eq(current_refresh_token),
+                used_refresh_token::session_id.eq(session),
+            ))
+            .execute(self.conn())
+            .into_db_error(session)?;
+
+        let old_used_tokens: Vec<i64> = used_refresh_token::table
+            .filter(used_refresh_token::session_id.eq(session))
+            .select(used_refresh_token::id)
+            .order(used_refresh_token::id.desc())
+            .offset(MAX_USED_REFRESH_TOKENS_PER_SESSION)
+            .load(self.conn())
+            .into_db_error(session)?;
+
+        delete(used_refresh_token::table.filter(used_refresh_token::id.eq_any(old_used_tokens)))
+            .execute(self.conn())
+            .into_db_error(session)?;
+
+        update(login_session::table.find(session))
+            .set((
+                login_session::access_token.eq(access.as_str()),
+                login_session::access_token_unix_time.eq(current_time),
+                login_session::refresh_token.eq(refresh),
+                login_session::last_used_unix_time.eq(current_time),
+            ))
+            .execute(self.conn())
+            .into_db_error(session)?;
+
+        Ok(current_time)
+    }
+
+    pub fn delete_login_session(
+        &mut self,
+        session: LoginSessionId,
+    ) -> Result<(), DieselDatabaseError> {
+        use model::schema::login_session::dsl::*;
+
+        delete(login_session.find(session))
+            .execute(self.conn())
+            .into_db_error(session)?;
+
+        Ok(())
+    }
+
+    pub fn delete_all_login_sessions(
+        &mut self,
+        id: AccountIdInternal,
+    ) -> Result<(), DieselDatabaseError> {
+        use model::schema::login_session::dsl::*;
+
+        delete(login_session.filter(account_id.eq(id.as_db_id())))
+            .execute(self.conn())
+            .into_db_error(id)?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
-
-use diesel::{sql_query, sql_types::Text, RunQueryDsl};
+use diesel::{prelude::*, sql_query, sql_types::Text, RunQueryDsl};
+use model::UnixTime;
 use simple_backend_database::diesel_db::DieselDatabaseError;
+use simple_backend_model::{MetricName, PerfHistoryValue};
 use error_stack::Result;
 
 use crate::{define_current_read_commands, IntoDatabaseError};
@@ -15,4 +16,54 @@ impl HistoryReadCommon<'_> {
             .into_db_error(())?;
         Ok(())
     }
+
+    /// Performance metric save times in the time range.
+    pub fn perf_data_save_times(
+        &mut self,
+        start_time: UnixTime,
+        end_time: UnixTime,
+    ) -> Result<Vec<UnixTime>, DieselDatabaseError> {
+        use crate::schema::history_performance_statistics_save_time::dsl::*;
+
+        history_performance_statistics_save_time
+            .filter(unix_time.ge(start_time))
+            .filter(unix_time.le(end_time))
+            .select(unix_time)
+            .order(unix_time.asc())
+            .load(self.conn())
+            .into_db_error(())
+    }
+
+    /// Performance metric values in the time range. If `names` is `None`,
+    /// values for all metrics are returned.
+    pub fn perf_data_values(
+        &mut self,
+        start_time: UnixTime,
+        end_time: UnixTime,
+        names: Option<Vec<MetricName>>,
+    ) -> Result<Vec<PerfHistoryValue>, DieselDatabaseError> {
+        use crate::schema::{
+            history_performance_statistics_metric_name as metric_name,
+            history_performance_statistics_metric_value as metric_value,
+            history_performance_statistics_save_time as save_time,
+        };
+
+        let mut query = metric_value::table
+            .inner_join(save_time::table)
+            .inner_join(metric_name::table)
+            .filter(save_time::unix_time.ge(start_time))
+            .filter(save_time::unix_time.le(end_time))
+            .select((
+                save_time::unix_time,
+                metric_name::metric_name,
+                metric_value::metric_value,
+            ))
+            .into_boxed();
+
+        if let Some(names) = names {
+            query = query.filter(metric_name::metric_name.eq_any(names));
+        }
+
+        query.load(self.conn()).into_db_error(())
+    }
 }
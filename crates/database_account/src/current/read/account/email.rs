@@ -2,7 +2,7 @@ use database::{define_current_read_commands, DieselDatabaseError};
 use diesel::prelude::*;
 use error_stack::Result;
 use model::AccountIdInternal;
-use model_account::AccountEmailSendingStateRaw;
+use model_account::{AccountEmailSendingStateRaw, AccountGlobalState, EmailConfirmationState};
 
 use crate::IntoDatabaseError;
 
@@ -23,4 +23,44 @@ impl CurrentReadAccountEmail<'_> {
             .into_db_error(id)
             .map(|data| data.unwrap_or_default())
     }
+
+    pub fn email_confirmation_state(
+        &mut self,
+        id: AccountIdInternal,
+    ) -> Result<EmailConfirmationState, DieselDatabaseError> {
+        use crate::schema::account::dsl::*;
+
+        account
+            .filter(account_id.eq(id.as_db_id()))
+            .select(EmailConfirmationState::as_select())
+            .first(self.conn())
+            .into_db_error(id)
+    }
+
+    pub fn email_notifications_enabled(
+        &mut self,
+        id: AccountIdInternal,
+    ) -> Result<bool, DieselDatabaseError> {
+        use crate::schema::account::dsl::*;
+
+        account
+            .filter(account_id.eq(id.as_db_id()))
+            .select(email_notifications_enabled)
+            .first(self.conn())
+            .into_db_error(id)
+    }
+
+    pub fn email_confirmation_token_key(
+        &mut self,
+    ) -> Result<Option<Vec<u8>>, DieselDatabaseError> {
+        use crate::schema::account_global_state::dsl::*;
+
+        account_global_state
+            .filter(row_type.eq(AccountGlobalState::ACCOUNT_GLOBAL_STATE_ROW_TYPE))
+            .select(email_confirmation_token_key)
+            .first(self.conn())
+            .optional()
+            .into_db_error(())
+            .map(|v| v.flatten())
+    }
 }
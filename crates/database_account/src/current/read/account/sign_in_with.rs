@@ -2,7 +2,7 @@ use database::{define_current_read_commands, DieselDatabaseError};
 use diesel::prelude::*;
 use error_stack::Result;
 use model::AccountIdInternal;
-use model_account::{GoogleAccountId, SignInWithInfoRaw};
+use model_account::{AppleAccountId, GoogleAccountId, SignInWithInfoRaw};
 
 use crate::IntoDatabaseError;
 
@@ -24,6 +24,21 @@ impl CurrentReadAccountSignInWith<'_> {
             .into_db_error(google_id)
     }
 
+    pub fn apple_account_id_to_account_id(
+        &mut self,
+        apple_id: AppleAccountId,
+    ) -> Result<Option<AccountIdInternal>, DieselDatabaseError> {
+        use crate::schema::{account_id, sign_in_with_info};
+
+        sign_in_with_info::table
+            .inner_join(account_id::table)
+            .filter(sign_in_with_info::apple_account_id.eq(apple_id.as_str()))
+            .select(AccountIdInternal::as_select())
+            .first(self.conn())
+            .optional()
+            .into_db_error(apple_id)
+    }
+
     pub fn sign_in_with_info_raw(
         &mut self,
         id: AccountIdInternal,
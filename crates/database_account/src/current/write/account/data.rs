@@ -31,6 +31,8 @@ impl CurrentWriteAccountData<'_> {
         id: AccountIdInternal,
         account_data: &AccountInternal,
     ) -> Result<(), DieselDatabaseError> {
+        self.reset_email_verified_if_email_changes(id, account_data.email.as_ref())?;
+
         use model::schema::account::dsl::*;
 
         update(account.find(id.as_db_id()))
@@ -117,6 +119,8 @@ impl CurrentWriteAccountData<'_> {
         id: AccountIdInternal,
         email_address: &EmailAddress,
     ) -> Result<(), DieselDatabaseError> {
+        self.reset_email_verified_if_email_changes(id, Some(email_address))?;
+
         use model::schema::account::dsl::*;
 
         update(account.find(id.as_db_id()))
@@ -127,6 +131,29 @@ impl CurrentWriteAccountData<'_> {
         Ok(())
     }
 
+    fn reset_email_verified_if_email_changes(
+        &mut self,
+        id: AccountIdInternal,
+        new_email: Option<&EmailAddress>,
+    ) -> Result<(), DieselDatabaseError> {
+        use model::schema::account::dsl::*;
+
+        let current_email: Option<EmailAddress> = account
+            .find(id.as_db_id())
+            .select(email)
+            .first(self.conn())
+            .into_db_error(id)?;
+
+        if current_email.as_ref() != new_email {
+            update(account.find(id.as_db_id()))
+                .set(email_verified.eq(false))
+                .execute(self.conn())
+                .into_db_error(id)?;
+        }
+
+        Ok(())
+    }
+
     pub fn get_next_client_id(
         mut self,
         id: AccountIdInternal,
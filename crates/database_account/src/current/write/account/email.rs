@@ -1,8 +1,8 @@
 use database::{define_current_write_commands, DieselDatabaseError};
-use diesel::{insert_into, prelude::*};
+use diesel::{insert_into, prelude::*, update};
 use error_stack::Result;
-use model::AccountIdInternal;
-use model_account::AccountEmailSendingStateRaw;
+use model::{AccountIdInternal, UnixTime};
+use model_account::{AccountEmailSendingStateRaw, AccountGlobalState};
 
 use crate::{current::read::GetDbReadCommandsAccount, IntoDatabaseError};
 
@@ -30,4 +30,69 @@ impl CurrentWriteAccountEmail<'_> {
 
         Ok(())
     }
+
+    pub fn set_email_verified(
+        &mut self,
+        id: AccountIdInternal,
+        value: bool,
+    ) -> Result<(), DieselDatabaseError> {
+        use model::schema::account::dsl::*;
+
+        update(account.find(id.as_db_id()))
+            .set(email_verified.eq(value))
+            .execute(self.conn())
+            .into_db_error(id)?;
+
+        Ok(())
+    }
+
+    pub fn set_email_notifications_enabled(
+        &mut self,
+        id: AccountIdInternal,
+        value: bool,
+    ) -> Result<(), DieselDatabaseError> {
+        use model::schema::account::dsl::*;
+
+        update(account.find(id.as_db_id()))
+            .set(email_notifications_enabled.eq(value))
+            .execute(self.conn())
+            .into_db_error(id)?;
+
+        Ok(())
+    }
+
+    pub fn update_email_confirmation_sent_time(
+        &mut self,
+        id: AccountIdInternal,
+        time: UnixTime,
+    ) -> Result<(), DieselDatabaseError> {
+        use model::schema::account::dsl::*;
+
+        update(account.find(id.as_db_id()))
+            .set(email_confirmation_sent_unix_time.eq(Some(time)))
+            .execute(self.conn())
+            .into_db_error(id)?;
+
+        Ok(())
+    }
+
+    pub fn upsert_email_confirmation_token_key(
+        &mut self,
+        key: Vec<u8>,
+    ) -> Result<(), DieselDatabaseError> {
+        use model::schema::account_global_state::dsl::*;
+
+        insert_into(account_global_state)
+            .values((
+                row_type.eq(AccountGlobalState::ACCOUNT_GLOBAL_STATE_ROW_TYPE),
+                email_confirmation_token_key.eq(&key),
+            ))
+            .on_conflict(row_type)
+            .do_update()
+            .set(email_confirmation_token_key.eq(&key))
+            .execute(self.conn())
+            .into_db_error(())?;
+
+        Ok(())
+    }
 }
@@ -20,6 +20,7 @@ impl CurrentWriteAccountSignInWith<'_> {
             .values((
                 account_id.eq(id.as_db_id()),
                 google_account_id.eq(&data.google_account_id),
+                apple_account_id.eq(&data.apple_account_id),
             ))
             .execute(self.conn())
             .into_db_error(id)?;
@@ -35,7 +36,10 @@ impl CurrentWriteAccountSignInWith<'_> {
         use model::schema::sign_in_with_info::dsl::*;
 
         update(sign_in_with_info.find(id.as_db_id()))
-            .set((google_account_id.eq(&data.google_account_id),))
+            .set((
+                google_account_id.eq(&data.google_account_id),
+                apple_account_id.eq(&data.apple_account_id),
+            ))
             .execute(self.conn())
             .into_db_error(id)?;
 
@@ -2,8 +2,9 @@ use database::{define_current_read_commands, DieselDatabaseError};
 use diesel::{prelude::*, SelectableHelper};
 use error_stack::Result;
 use model_chat::{
-    AccountIdInternal, ChatGlobalState, ChatStateRaw, PublicKey, PublicKeyData, PublicKeyId,
-    PublicKeyVersion, CHAT_GLOBAL_STATE_ROW_TYPE,
+    AccountIdInternal, ChatGlobalState, ChatStateRaw, PublicKey, PublicKeyData,
+    PublicKeyHistoryEntry, PublicKeyId, PublicKeySignature, PublicKeyVersion, UnixTime,
+    CHAT_GLOBAL_STATE_ROW_TYPE,
 };
 
 use crate::IntoDatabaseError;
@@ -59,6 +60,71 @@ impl CurrentReadChat<'_> {
         }
     }
 
+    /// Public key history ordered by public key version and ID.
+    pub fn public_key_history(
+        &mut self,
+        account_id_value: AccountIdInternal,
+    ) -> Result<Vec<PublicKeyHistoryEntry>, DieselDatabaseError> {
+        use crate::schema::public_key_history::dsl::*;
+
+        let query_result: Vec<PublicKeyHistoryRow> = public_key_history
+            .filter(account_id.eq(account_id_value.as_db_id()))
+            .select((
+                public_key_id,
+                public_key_version,
+                public_key_data,
+                upload_unix_time,
+                server_signature,
+            ))
+            .order((public_key_version.asc(), public_key_id.asc()))
+            .load(self.conn())
+            .into_db_error(())?;
+
+        Ok(query_result
+            .into_iter()
+            .map(public_key_history_row_to_entry)
+            .collect())
+    }
+
+    pub fn public_key_history_entry(
+        &mut self,
+        account_id_value: AccountIdInternal,
+        version: PublicKeyVersion,
+        key_id: PublicKeyId,
+    ) -> Result<Option<PublicKeyHistoryEntry>, DieselDatabaseError> {
+        use crate::schema::public_key_history::dsl::*;
+
+        let query_result: Option<PublicKeyHistoryRow> = public_key_history
+            .filter(account_id.eq(account_id_value.as_db_id()))
+            .filter(public_key_version.eq(version))
+            .filter(public_key_id.eq(key_id))
+            .select((
+                public_key_id,
+                public_key_version,
+                public_key_data,
+                upload_unix_time,
+                server_signature,
+            ))
+            .first(self.conn())
+            .optional()
+            .into_db_error(())?;
+
+        Ok(query_result.map(public_key_history_row_to_entry))
+    }
+
+    pub fn public_key_history_count(
+        &mut self,
+        account_id_value: AccountIdInternal,
+    ) -> Result<i64, DieselDatabaseError> {
+        use crate::schema::public_key_history::dsl::*;
+
+        public_key_history
+            .filter(account_id.eq(account_id_value.as_db_id()))
+            .count()
+            .get_result(self.conn())
+            .into_db_error(())
+    }
+
     pub fn global_state(&mut self) -> Result<ChatGlobalState, DieselDatabaseError> {
         use model::schema::chat_global_state::dsl::*;
 
@@ -71,3 +137,23 @@ impl CurrentReadChat<'_> {
             .into_db_error(())
     }
 }
+
+type PublicKeyHistoryRow = (
+    PublicKeyId,
+    PublicKeyVersion,
+    PublicKeyData,
+    UnixTime,
+    Vec<u8>,
+);
+
+fn public_key_history_row_to_entry(
+    (id, version, data, ut, signature): PublicKeyHistoryRow,
+) -> PublicKeyHistoryEntry {
+    PublicKeyHistoryEntry {
+        id,
+        version,
+        data,
+        ut,
+        signature: PublicKeySignature::from_bytes(&signature),
+    }
+}
@@ -2,8 +2,8 @@ use database::{define_current_read_commands, DieselDatabaseError};
 use diesel::prelude::*;
 use error_stack::Result;
 use model_chat::{
-    AccountId, AccountIdInternal, PendingMessage, PendingMessageAndMessageData, PendingMessageId,
-    PendingMessageInternal, SentMessageId,
+    AccountId, AccountIdInternal, MessageSignature, PendingMessage, PendingMessageAndMessageData,
+    PendingMessageId, PendingMessageInternal, SentMessageId,
 };
 
 use crate::IntoDatabaseError;
@@ -36,6 +36,10 @@ impl CurrentReadChatMessage<'_> {
                         mn: msg.message_number,
                     },
                     unix_time: msg.unix_time,
+                    signature: msg
+                        .server_signature
+                        .as_deref()
+                        .map(MessageSignature::from_bytes),
                 },
                 message: msg.message_bytes,
             })
@@ -90,6 +94,20 @@ impl CurrentReadChatMessage<'_> {
         Ok(messages)
     }
 
+    pub fn receiver_acknowledgements_missing_count(
+        &mut self,
+        id_message_receiver: AccountIdInternal,
+    ) -> Result<i64, DieselDatabaseError> {
+        use crate::schema::pending_messages::dsl::*;
+
+        pending_messages
+            .filter(account_id_receiver.eq(id_message_receiver.as_db_id()))
+            .filter(receiver_acknowledgement.eq(false))
+            .count()
+            .get_result(self.conn())
+            .into_db_error(())
+    }
+
     pub fn receiver_acknowledgements_missing_count_for_one_conversation(
         &mut self,
         id_message_sender: AccountIdInternal,
@@ -4,7 +4,7 @@ use error_stack::Result;
 use model_chat::{
     AccountIdInternal, ChatStateRaw, MatchId, MatchesSyncVersion, NewReceivedLikesCount,
     PublicKeyId, ReceivedBlocksSyncVersion, ReceivedLikesSyncVersion, SentBlocksSyncVersion,
-    SentLikesSyncVersion, SetPublicKey, SyncVersionUtils, CHAT_GLOBAL_STATE_ROW_TYPE,
+    SentLikesSyncVersion, SetPublicKey, SyncVersionUtils, UnixTime, CHAT_GLOBAL_STATE_ROW_TYPE,
 };
 use simple_backend_utils::ContextExt;
 
@@ -126,6 +126,31 @@ impl CurrentWriteChat<'_> {
         Ok(new_id)
     }
 
+    pub fn insert_public_key_history_entry(
+        &mut self,
+        id: AccountIdInternal,
+        key: SetPublicKey,
+        key_id: PublicKeyId,
+        upload_time: UnixTime,
+        signature: Vec<u8>,
+    ) -> Result<(), DieselDatabaseError> {
+        use model::schema::public_key_history::dsl::*;
+
+        insert_into(public_key_history)
+            .values((
+                account_id.eq(id.as_db_id()),
+                public_key_version.eq(key.version),
+                public_key_id.eq(key_id),
+                public_key_data.eq(key.data),
+                upload_unix_time.eq(upload_time),
+                server_signature.eq(signature),
+            ))
+            .execute(self.conn())
+            .into_db_error(id)?;
+
+        Ok(())
+    }
+
     /// Return unused MatchId
     pub fn upsert_next_match_id(&mut self) -> Result<MatchId, DieselDatabaseError> {
         use model::schema::chat_global_state::dsl::*;
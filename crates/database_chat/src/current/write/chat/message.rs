@@ -2,7 +2,7 @@ use database::{define_current_write_commands, DieselDatabaseError};
 use diesel::{delete, insert_into, prelude::*, update};
 use error_stack::Result;
 use model_chat::{
-    AccountIdInternal, AccountInteractionState, ClientId, ClientLocalId,
+    AccountIdInternal, AccountInteractionState, ClientId, ClientLocalId, MessageNumber,
     NewPendingMessageValues, PendingMessageIdInternal, SentMessageId, UnixTime,
 };
 
@@ -131,4 +131,24 @@ impl CurrentWriteChatMessage<'_> {
             message_number: new_message_number,
         }))
     }
+
+    pub fn set_pending_message_server_signature(
+        &mut self,
+        sender: AccountIdInternal,
+        receiver: AccountIdInternal,
+        mn: MessageNumber,
+        signature: Vec<u8>,
+    ) -> Result<(), DieselDatabaseError> {
+        use model::schema::pending_messages::dsl::*;
+
+        update(pending_messages)
+            .filter(account_id_sender.eq(sender.as_db_id()))
+            .filter(account_id_receiver.eq(receiver.as_db_id()))
+            .filter(message_number.eq(mn))
+            .set(server_signature.eq(signature))
+            .execute(self.conn())
+            .into_db_error((sender, receiver, mn))?;
+
+        Ok(())
+    }
 }
@@ -2,8 +2,8 @@ use database::{define_current_write_commands, DieselDatabaseError};
 use diesel::{prelude::*, update};
 use error_stack::Result;
 use model::{
-    AccountIdInternal, FcmDeviceToken, PendingNotification, PendingNotificationToken,
-    PushNotificationStateInfo,
+    AccountIdInternal, FcmDeviceToken, LoginSessionId, PendingNotification,
+    PendingNotificationToken, PushNotificationDeviceInfo, PushNotificationStateInfo,
 };
 
 use crate::IntoDatabaseError;
@@ -13,74 +13,68 @@ define_current_write_commands!(CurrentWriteChatPushNotifications);
 impl CurrentWriteChatPushNotifications<'_> {
     pub fn remove_fcm_device_token(
         &mut self,
-        id: AccountIdInternal,
+        session: LoginSessionId,
     ) -> Result<(), DieselDatabaseError> {
-        use model::schema::chat_state::dsl::*;
+        use model::schema::login_session::dsl::*;
 
-        update(chat_state.find(id.as_db_id()))
+        update(login_session.find(session))
             .set((
                 fcm_device_token.eq(None::<FcmDeviceToken>),
                 fcm_notification_sent.eq(false),
+                pending_notification_token.eq(None::<PendingNotificationToken>),
             ))
             .execute(self.conn())
-            .into_db_error(id)?;
+            .into_db_error(session)?;
 
         Ok(())
     }
 
     pub fn update_fcm_device_token_and_generate_new_notification_token(
         &mut self,
-        id: AccountIdInternal,
+        session: LoginSessionId,
         token: FcmDeviceToken,
     ) -> Result<PendingNotificationToken, DieselDatabaseError> {
-        use model::schema::chat_state::dsl::*;
+        use model::schema::login_session::dsl::*;
 
-        // Remove the token from other accounts. It is possible that
+        // Remove the token from other login sessions. It is possible that
         // same device is used for multiple accounts.
-        update(chat_state.filter(fcm_device_token.eq(token.clone())))
-            .set(fcm_device_token.eq(None::<FcmDeviceToken>))
+        update(login_session.filter(fcm_device_token.eq(token.clone())))
+            .set((
+                fcm_device_token.eq(None::<FcmDeviceToken>),
+                pending_notification_token.eq(None::<PendingNotificationToken>),
+            ))
             .execute(self.conn())
             .into_db_error(())?;
 
         let notification_token = PendingNotificationToken::generate_new();
 
-        update(chat_state.find(id.as_db_id()))
+        update(login_session.find(session))
             .set((
                 fcm_device_token.eq(token),
                 fcm_notification_sent.eq(false),
                 pending_notification_token.eq(notification_token.clone()),
             ))
             .execute(self.conn())
-            .into_db_error(id)?;
+            .into_db_error(session)?;
 
         Ok(notification_token)
     }
 
-    pub fn update_fcm_notification_sent_value(
+    pub fn reset_pending_notification(
         &mut self,
         id: AccountIdInternal,
-        fcm_notification_sent_value: bool,
     ) -> Result<(), DieselDatabaseError> {
-        use model::schema::chat_state::dsl::*;
+        use model::schema::{chat_state, login_session};
 
-        update(chat_state.find(id.as_db_id()))
-            .set(fcm_notification_sent.eq(fcm_notification_sent_value))
+        update(chat_state::table.find(id.as_db_id()))
+            .set(chat_state::pending_notification.eq(0))
             .execute(self.conn())
             .into_db_error(id)?;
 
-        Ok(())
-    }
-
-    pub fn reset_pending_notification(
-        &mut self,
-        id: AccountIdInternal,
-    ) -> Result<(), DieselDatabaseError> {
-        use model::schema::chat_state::dsl::*;
-
-        update(chat_state.find(id.as_db_id()))
-            .set((pending_notification.eq(0), fcm_notification_sent.eq(false)))
+        update(login_session::table.filter(login_session::account_id.eq(id.as_db_id())))
+            .set(login_session::fcm_notification_sent.eq(false))
             .execute(self.conn())
-            .into_db_error(())?;
+            .into_db_error(id)?;
 
         Ok(())
     }
@@ -89,12 +83,12 @@ impl CurrentWriteChatPushNotifications<'_> {
         &mut self,
         token: PendingNotificationToken,
     ) -> Result<(AccountIdInternal, PendingNotification), DieselDatabaseError> {
-        use model::schema::{account_id, chat_state};
+        use model::schema::{account_id, chat_state, login_session};
 
-        let token_clone = token.clone();
-        let (id, notification) = chat_state::table
+        let (id, notification) = login_session::table
             .inner_join(account_id::table)
-            .filter(chat_state::pending_notification_token.eq(token_clone))
+            .inner_join(chat_state::table.on(chat_state::account_id.eq(account_id::id)))
+            .filter(login_session::pending_notification_token.eq(token))
             .select((
                 AccountIdInternal::as_select(),
                 chat_state::pending_notification,
@@ -102,27 +96,21 @@ impl CurrentWriteChatPushNotifications<'_> {
             .first(self.conn())
             .into_db_error(())?;
 
-        update(chat_state::table.filter(chat_state::pending_notification_token.eq(token)))
-            .set((
-                chat_state::pending_notification.eq(0),
-                chat_state::fcm_notification_sent.eq(false),
-            ))
-            .execute(self.conn())
-            .into_db_error(())?;
+        self.reset_pending_notification(id)?;
 
         Ok((id, notification))
     }
 
     pub fn enable_push_notification_sent_flag(
         &mut self,
-        id: AccountIdInternal,
+        session: LoginSessionId,
     ) -> Result<(), DieselDatabaseError> {
-        use model::schema::chat_state::dsl::*;
+        use model::schema::login_session::dsl::*;
 
-        update(chat_state.find(id.as_db_id()))
-            .set((fcm_notification_sent.eq(true),))
+        update(login_session.find(session))
+            .set(fcm_notification_sent.eq(true))
             .execute(self.conn())
-            .into_db_error(())?;
+            .into_db_error(session)?;
 
         Ok(())
     }
@@ -132,25 +120,43 @@ impl CurrentWriteChatPushNotifications<'_> {
         id: AccountIdInternal,
         notification_to_be_added: PendingNotification,
     ) -> Result<PushNotificationStateInfo, DieselDatabaseError> {
-        use model::schema::chat_state::dsl::*;
+        use model::schema::{chat_state, login_session};
 
-        let notification: i64 = chat_state
-            .filter(account_id.eq(id.as_db_id()))
-            .select(pending_notification)
+        let notification: i64 = chat_state::table
+            .filter(chat_state::account_id.eq(id.as_db_id()))
+            .select(chat_state::pending_notification)
             .first(self.conn())
-            .into_db_error(())?;
+            .into_db_error(id)?;
 
         let new_notification_value = notification | *notification_to_be_added.as_i64();
 
-        let (token, notification_sent) = update(chat_state.find(id.as_db_id()))
-            .set((pending_notification.eq(new_notification_value),))
-            .returning((fcm_device_token, fcm_notification_sent))
-            .get_result(self.conn())
-            .into_db_error(())?;
+        update(chat_state::table.find(id.as_db_id()))
+            .set(chat_state::pending_notification.eq(new_notification_value))
+            .execute(self.conn())
+            .into_db_error(id)?;
+
+        let devices: Vec<(LoginSessionId, Option<FcmDeviceToken>, bool)> = login_session::table
+            .filter(login_session::account_id.eq(id.as_db_id()))
+            .filter(login_session::fcm_device_token.is_not_null())
+            .select((
+                login_session::session_id,
+                login_session::fcm_device_token,
+                login_session::fcm_notification_sent,
+            ))
+            .load(self.conn())
+            .into_db_error(id)?;
 
-        Ok(PushNotificationStateInfo {
-            fcm_device_token: token,
-            fcm_notification_sent: notification_sent,
-        })
+        let devices = devices
+            .into_iter()
+            .filter_map(|(session_id, token, sent)| {
+                token.map(|fcm_device_token| PushNotificationDeviceInfo {
+                    session_id,
+                    fcm_device_token,
+                    fcm_notification_sent: sent,
+                })
+            })
+            .collect();
+
+        Ok(PushNotificationStateInfo { devices })
     }
 }
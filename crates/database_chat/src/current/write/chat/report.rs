@@ -1,7 +1,7 @@
 use database::{current::write::GetDbWriteCommandsCommon, define_current_write_commands, DieselDatabaseError};
 use diesel::{insert_into, prelude::*, ExpressionMethods};
 use error_stack::Result;
-use model::{AccountIdInternal, ReportProcessingState, ReportTypeNumberInternal};
+use model::{AccountIdInternal, ReportProcessingState, ReportTypeNumberInternal, SignedMessageInternal};
 
 use crate::IntoDatabaseError;
 
@@ -12,7 +12,8 @@ impl CurrentWriteChatReport<'_> {
         &mut self,
         creator: AccountIdInternal,
         target: AccountIdInternal,
-        message: String,
+        message_text: String,
+        message: SignedMessageInternal,
     ) -> Result<(), DieselDatabaseError> {
         let id = self.write().common().report().insert_report_content(
             creator,
@@ -27,7 +28,13 @@ impl CurrentWriteChatReport<'_> {
             insert_into(chat_report_chat_message)
                 .values((
                     report_id.eq(id),
-                    chat_message.eq(message),
+                    chat_message.eq(message_text),
+                    message_sender_account_id_uuid.eq(message.sender),
+                    message_receiver_account_id_uuid.eq(message.receiver),
+                    message_number.eq(message.mn),
+                    message_unix_time.eq(message.ut),
+                    message_bytes.eq(message.message),
+                    message_signature.eq(message.signature),
                 ))
                 .execute(self.conn())
                 .into_db_error((creator, target))?;
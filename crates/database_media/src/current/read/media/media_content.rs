@@ -1,9 +1,9 @@
 use database::{define_current_read_commands, DieselDatabaseError};
 use diesel::prelude::*;
 use error_stack::Result;
-use model::ContentIdInternal;
+use model::{AccountId, ContentIdInternal, UnixTime};
 use model_media::{
-    AccountIdInternal, ContentId, ContentIdDb, ContentModerationState, ContentSlot, CurrentAccountMediaInternal, CurrentAccountMediaRaw, MediaContentRaw
+    AccountIdInternal, ContentId, ContentIdDb, ContentModerationState, ContentSlot, CurrentAccountMediaInternal, CurrentAccountMediaRaw, MediaContentRaw, PerceptualHash, PerceptualHashBlocklistReason, SimilarBlocklistedContent, SimilarContent
 };
 
 use crate::IntoDatabaseError;
@@ -150,4 +150,112 @@ impl CurrentReadMediaMediaContent<'_> {
             .optional()
             .into_db_error((slot_owner, slot))
     }
+
+    /// Content of other accounts which is a near-duplicate of
+    /// the perceptual hash.
+    pub fn similar_content(
+        &mut self,
+        exclude_account: AccountIdInternal,
+        hash: PerceptualHash,
+    ) -> Result<Vec<SimilarContent>, DieselDatabaseError> {
+        use crate::schema::{account_id, media_content};
+
+        // SQLite does not have bit count function, so the indexed
+        // chunk columns are used to find candidates and the final
+        // comparison is done here.
+        let [chunk0, chunk1, chunk2, chunk3] = hash.similar_chunk_values();
+        let values: Vec<(AccountId, ContentId, ContentModerationState, Option<PerceptualHash>)> =
+            media_content::table
+                .inner_join(account_id::table)
+                .filter(
+                    media_content::perceptual_hash_chunk_0
+                        .eq_any(chunk0)
+                        .or(media_content::perceptual_hash_chunk_1.eq_any(chunk1))
+                        .or(media_content::perceptual_hash_chunk_2.eq_any(chunk2))
+                        .or(media_content::perceptual_hash_chunk_3.eq_any(chunk3)),
+                )
+                .filter(media_content::account_id.ne(exclude_account.as_db_id()))
+                .select((
+                    account_id::uuid,
+                    media_content::uuid,
+                    media_content::moderation_state,
+                    media_content::perceptual_hash,
+                ))
+                .load(self.conn())
+                .into_db_error(exclude_account)?;
+
+        let values = values
+            .into_iter()
+            .filter_map(|(account_id, content_id, state, other_hash)| {
+                let other_hash = other_hash?;
+                if hash.is_similar(other_hash) {
+                    Some(SimilarContent {
+                        account_id,
+                        content_id,
+                        state,
+                        distance: hash.distance(other_hash),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Ok(values)
+    }
+
+    /// Perceptual hash blocklist entries which are similar to
+    /// the perceptual hash.
+    pub fn similar_blocklisted_content(
+        &mut self,
+        hash: PerceptualHash,
+    ) -> Result<Vec<SimilarBlocklistedContent>, DieselDatabaseError> {
+        use crate::schema::{account_id, media_content_perceptual_hash_blocklist as blocklist};
+
+        let [chunk0, chunk1, chunk2, chunk3] = hash.similar_chunk_values();
+        let values: Vec<(PerceptualHash, Option<AccountId>, PerceptualHashBlocklistReason, UnixTime)> =
+            blocklist::table
+                .left_join(account_id::table)
+                .filter(
+                    blocklist::perceptual_hash_chunk_0
+                        .eq_any(chunk0)
+                        .or(blocklist::perceptual_hash_chunk_1.eq_any(chunk1))
+                        .or(blocklist::perceptual_hash_chunk_2.eq_any(chunk2))
+                        .or(blocklist::perceptual_hash_chunk_3.eq_any(chunk3)),
+                )
+                .select((
+                    blocklist::perceptual_hash,
+                    account_id::uuid.nullable(),
+                    blocklist::reason,
+                    blocklist::added_unix_time,
+                ))
+                .load(self.conn())
+                .into_db_error(())?;
+
+        let values = values
+            .into_iter()
+            .filter(|(blocklisted_hash, ..)| hash.is_similar(*blocklisted_hash))
+            .map(|(blocklisted_hash, account_id, reason, added_unix_time)| {
+                SimilarBlocklistedContent {
+                    account_id,
+                    reason,
+                    added_unix_time,
+                    distance: hash.distance(blocklisted_hash),
+                }
+            })
+            .collect();
+
+        Ok(values)
+    }
+
+    /// Returns true if the perceptual hash is similar to some blocklisted
+    /// hash or content of some other account.
+    pub fn perceptual_hash_match(
+        &mut self,
+        content_owner: AccountIdInternal,
+        hash: PerceptualHash,
+    ) -> Result<bool, DieselDatabaseError> {
+        let blocklisted = !self.similar_blocklisted_content(hash)?.is_empty();
+        Ok(blocklisted || !self.similar_content(content_owner, hash)?.is_empty())
+    }
 }
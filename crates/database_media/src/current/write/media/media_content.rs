@@ -3,7 +3,7 @@ use diesel::{delete, insert_into, prelude::*, update};
 use error_stack::{Result, ResultExt};
 use model::{ContentIdInternal, SyncVersion, UnixTime};
 use model_media::{
-    AccountIdInternal, ContentId, ContentIdDb, ContentModerationState, ContentSlot, MediaContentRaw, MediaContentType, NewContentParams, ProfileContentEditedTime, ProfileContentVersion, SetProfileContent
+    AccountIdInternal, ContentId, ContentIdDb, ContentModerationState, ContentSlot, MediaContentRaw, MediaContentType, NewContentParams, PerceptualHash, ProfileContentEditedTime, ProfileContentVersion, SetProfileContent
 };
 use simple_backend_utils::ContextExt;
 
@@ -133,7 +133,7 @@ impl CurrentWriteMediaContent<'_> {
                     .write()
                     .media_admin()
                     .media_content()
-                    .update_content_moderation_state(content_id, ContentModerationState::WaitingBotOrHumanModeration)?;
+                    .update_content_moderation_state(content_id, state.waiting_moderation_state())?;
             }
         }
 
@@ -178,7 +178,7 @@ impl CurrentWriteMediaContent<'_> {
                 .write()
                 .media_admin()
                 .media_content()
-                .update_content_moderation_state(content_id, ContentModerationState::WaitingBotOrHumanModeration)?;
+                .update_content_moderation_state(content_id, state.waiting_moderation_state())?;
         }
 
         Ok(())
@@ -205,6 +205,7 @@ impl CurrentWriteMediaContent<'_> {
         slot: Option<ContentSlot>,
         content_params: NewContentParams,
         face_detected_value: bool,
+        perceptual_hash_value: Option<PerceptualHash>,
     ) -> Result<(), DieselDatabaseError> {
         use model::schema::media_content::dsl::*;
 
@@ -212,10 +213,22 @@ impl CurrentWriteMediaContent<'_> {
 
         let account = self.read().common().account(content_uploader)?;
         let initial_content_value = account.profile_visibility().is_pending();
+        let perceptual_hash_match_value = if let Some(hash) = perceptual_hash_value {
+            self.read()
+                .media()
+                .media_content()
+                .perceptual_hash_match(content_uploader, hash)?
+        } else {
+            false
+        };
+        let chunks = perceptual_hash_value.map(|hash| hash.chunks());
         let (slot_number_value, state_value) = if let Some(slot) = slot {
             (slot, ContentModerationState::InSlot)
         } else {
-            (ContentSlot::Content0, ContentModerationState::WaitingBotOrHumanModeration)
+            (
+                ContentSlot::Content0,
+                ContentModerationState::waiting_moderation(perceptual_hash_match_value),
+            )
         };
 
         insert_into(media_content)
@@ -229,6 +242,12 @@ impl CurrentWriteMediaContent<'_> {
                 initial_content.eq(initial_content_value),
                 creation_unix_time.eq(current_time),
                 moderation_state.eq(state_value),
+                perceptual_hash.eq(perceptual_hash_value),
+                perceptual_hash_chunk_0.eq(chunks.map(|c| c[0])),
+                perceptual_hash_chunk_1.eq(chunks.map(|c| c[1])),
+                perceptual_hash_chunk_2.eq(chunks.map(|c| c[2])),
+                perceptual_hash_chunk_3.eq(chunks.map(|c| c[3])),
+                perceptual_hash_match.eq(perceptual_hash_match_value),
             ))
             .execute(self.conn())
             .into_db_error((content_uploader, content_id))?;
@@ -1,10 +1,10 @@
 use database::{current::read::GetDbReadCommandsCommon, define_current_write_commands, DieselDatabaseError};
-use diesel::{prelude::*, update};
+use diesel::{insert_or_ignore_into, prelude::*, update};
 use error_stack::Result;
-use model::{ContentIdInternal, AccountIdInternal};
-use model_media::{ContentModerationState, ProfileContentModerationRejectedReasonCategory, ProfileContentModerationRejectedReasonDetails};
+use model::{ContentIdInternal, AccountIdInternal, UnixTime};
+use model_media::{ContentModerationState, PerceptualHash, PerceptualHashBlocklistReason, ProfileContentModerationRejectedReasonCategory, ProfileContentModerationRejectedReasonDetails};
 
-use crate::IntoDatabaseError;
+use crate::{current::read::GetDbReadCommandsMedia, IntoDatabaseError};
 
 define_current_write_commands!(CurrentWriteMediaAdminMediaContent);
 
@@ -67,9 +67,68 @@ impl CurrentWriteMediaAdminMediaContent<'_> {
             .execute(self.conn())
             .into_db_error(())?;
 
+        if next_state == ContentModerationState::RejectedByHuman {
+            let content = self
+                .read()
+                .media()
+                .media_content()
+                .get_media_content_raw(content_id)?;
+            if let Some(hash) = content.perceptual_hash {
+                self.add_to_perceptual_hash_blocklist(
+                    content_id.content_owner(),
+                    hash,
+                    PerceptualHashBlocklistReason::RejectedContent,
+                )?;
+            }
+        }
+
         Ok(next_state)
     }
 
+    /// Add perceptual hashes of all account's content to the blocklist.
+    pub fn add_account_content_to_perceptual_hash_blocklist(
+        &mut self,
+        content_owner: AccountIdInternal,
+        reason: PerceptualHashBlocklistReason,
+    ) -> Result<(), DieselDatabaseError> {
+        let content = self
+            .read()
+            .media()
+            .media_content()
+            .get_account_media_content(content_owner)?;
+        for hash in content.into_iter().filter_map(|c| c.perceptual_hash) {
+            self.add_to_perceptual_hash_blocklist(content_owner, hash, reason)?;
+        }
+        Ok(())
+    }
+
+    /// The hash is not added if it already exists in the blocklist.
+    fn add_to_perceptual_hash_blocklist(
+        &mut self,
+        content_owner: AccountIdInternal,
+        hash: PerceptualHash,
+        reason_value: PerceptualHashBlocklistReason,
+    ) -> Result<(), DieselDatabaseError> {
+        use model::schema::media_content_perceptual_hash_blocklist::dsl::*;
+
+        let chunks = hash.chunks();
+        insert_or_ignore_into(media_content_perceptual_hash_blocklist)
+            .values((
+                perceptual_hash.eq(hash),
+                perceptual_hash_chunk_0.eq(chunks[0]),
+                perceptual_hash_chunk_1.eq(chunks[1]),
+                perceptual_hash_chunk_2.eq(chunks[2]),
+                perceptual_hash_chunk_3.eq(chunks[3]),
+                account_id.eq(content_owner.as_db_id()),
+                reason.eq(reason_value),
+                added_unix_time.eq(UnixTime::current_time()),
+            ))
+            .execute(self.conn())
+            .into_db_error(content_owner)?;
+
+        Ok(())
+    }
+
     pub fn move_to_human_moderation(
         &mut self,
         content_id: ContentIdInternal,
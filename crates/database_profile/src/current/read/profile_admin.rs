@@ -3,6 +3,7 @@ use database::define_current_read_commands;
 mod profile_name_allowlist;
 mod profile_text;
 mod iterator;
+mod search;
 
 define_current_read_commands!(CurrentReadProfileAdmin);
 
@@ -20,4 +21,8 @@ impl<'a> CurrentReadProfileAdmin<'a> {
     pub fn iterator(self) -> iterator::CurrentReadProfileIterator<'a> {
         iterator::CurrentReadProfileIterator::new(self.cmds)
     }
+
+    pub fn search(self) -> search::CurrentReadProfileSearch<'a> {
+        search::CurrentReadProfileSearch::new(self.cmds)
+    }
 }
@@ -0,0 +1,426 @@
+use database::{define_current_read_commands, DieselDatabaseError};
+use diesel::{
+    prelude::*,
+    sql_query,
+    sql_types::{BigInt, Bool, Nullable, Text},
+};
+use error_stack::{Result, ResultExt};
+use model_profile::{ProfileSearchResultPage, ProfileSearchResultValue, ProfileSearchSettings};
+
+define_current_read_commands!(CurrentReadProfileSearch);
+
+const PAGE_SIZE: i64 = 25;
+
+/// Join which limits results to profiles matching the full-text
+/// search query. Bind parameter `?1` is the query.
+const SEARCH_JOIN: &str = "
+INNER JOIN (
+    SELECT rowid, rank FROM profile_search_index WHERE profile_search_index MATCH ?1
+) AS search ON search.rowid = account_id.id";
+
+impl CurrentReadProfileSearch<'_> {
+    pub fn search_profiles(
+        &mut self,
+        settings: ProfileSearchSettings,
+    ) -> Result<ProfileSearchResultPage, DieselDatabaseError> {
+        let query = settings.full_text_search_query();
+
+        // Bind parameter count must stay the same, so the query
+        // parameter is referenced also when it is NULL.
+        let (search_join, search_condition, order) = if query.is_some() {
+            (SEARCH_JOIN, "1", "search.rank, account_id.id DESC")
+        } else {
+            ("", "?1 IS NULL", "account_id.id DESC")
+        };
+
+        let sql = format!(
+            "
+SELECT
+    account_id.uuid AS account_id,
+    profile.age AS age,
+    profile.name AS name,
+    profile.profile_text AS ptext,
+    shared_state.account_state_initial_setup_completed AS initial_setup_completed,
+    shared_state.account_state_banned AS banned,
+    shared_state.account_state_pending_deletion AS pending_deletion,
+    (
+        SELECT COUNT(*) FROM common_report
+        WHERE common_report.target_account_id = account_id.id
+    ) AS report_count,
+    account_state.account_created_unix_time AS account_created
+FROM account_id
+INNER JOIN profile ON profile.account_id = account_id.id
+INNER JOIN shared_state ON shared_state.account_id = account_id.id
+INNER JOIN account_state ON account_state.account_id = account_id.id
+{search_join}
+WHERE {search_condition}
+    AND (?2 IS NULL OR shared_state.account_state_initial_setup_completed = ?2)
+    AND (?3 IS NULL OR shared_state.account_state_banned = ?3)
+    AND (?4 IS NULL OR shared_state.account_state_pending_deletion = ?4)
+    AND (?5 IS NULL OR report_count >= ?5)
+    AND (?6 IS NULL OR account_state.account_created_unix_time >= ?6)
+    AND (?7 IS NULL OR account_state.account_created_unix_time <= ?7)
+ORDER BY {order}
+LIMIT ?8 OFFSET ?9"
+        );
+
+        let values: Vec<ProfileSearchResultValue> = sql_query(sql)
+            .bind::<Nullable<Text>, _>(query)
+            .bind::<Nullable<Bool>, _>(settings.initial_setup_completed)
+            .bind::<Nullable<Bool>, _>(settings.banned)
+            .bind::<Nullable<Bool>, _>(settings.pending_deletion)
+            .bind::<Nullable<BigInt>, _>(settings.min_report_count)
+            .bind::<Nullable<BigInt>, _>(settings.created_after_unix_time)
+            .bind::<Nullable<BigInt>, _>(settings.created_before_unix_time)
+            .bind::<BigInt, _>(PAGE_SIZE)
+            .bind::<BigInt, _>(PAGE_SIZE.saturating_mul(settings.page))
+            .load(self.conn())
+            .change_context(DieselDatabaseError::Execute)?;
+
+        Ok(ProfileSearchResultPage { values })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::{insert_into, Connection, SqliteConnection};
+    use diesel_migrations::MigrationHarness;
+    use model::{AccountId, AccountIdDb, AccountIdInternal};
+
+    use super::*;
+    use crate::current::write::profile::CurrentWriteProfile;
+
+    struct TestAccount {
+        name: &'static str,
+        text: &'static str,
+        initial_setup_completed: bool,
+        banned: bool,
+        pending_deletion: bool,
+        reports: usize,
+        created: i64,
+    }
+
+    impl Default for TestAccount {
+        fn default() -> Self {
+            Self {
+                name: "",
+                text: "",
+                initial_setup_completed: true,
+                banned: false,
+                pending_deletion: false,
+                reports: 0,
+                created: 0,
+            }
+        }
+    }
+
+    fn new_db() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(database::DIESEL_MIGRATIONS)
+            .unwrap();
+        conn
+    }
+
+    fn insert_account_id(conn: &mut SqliteConnection) -> AccountIdInternal {
+        use model::schema::account_id::dsl::*;
+
+        let uuid_value = AccountId::new_random();
+        let db_id: AccountIdDb = insert_into(account_id)
+            .values(uuid.eq(uuid_value))
+            .returning(id)
+            .get_result(conn)
+            .unwrap();
+        AccountIdInternal::new(db_id, uuid_value)
+    }
+
+    fn insert_account(conn: &mut SqliteConnection, account: TestAccount) -> AccountId {
+        let id = insert_account_id(conn);
+        CurrentWriteProfile::new(conn)
+            .data()
+            .insert_profile(id)
+            .unwrap();
+        sql_query("UPDATE profile SET profile_text = ? WHERE account_id = ?")
+            .bind::<Text, _>(account.text)
+            .bind::<BigInt, _>(id.as_db_id())
+            .execute(conn)
+            .unwrap();
+        // Updates also the search index
+        CurrentWriteProfile::new(conn)
+            .data()
+            .profile_name(id, account.name.to_string())
+            .unwrap();
+
+        sql_query(
+            "INSERT INTO shared_state(account_id, account_state_initial_setup_completed, \
+            account_state_banned, account_state_pending_deletion) VALUES (?, ?, ?, ?)",
+        )
+        .bind::<BigInt, _>(id.as_db_id())
+        .bind::<Bool, _>(account.initial_setup_completed)
+        .bind::<Bool, _>(account.banned)
+        .bind::<Bool, _>(account.pending_deletion)
+        .execute(conn)
+        .unwrap();
+
+        sql_query("INSERT INTO account_state(account_id, account_created_unix_time) VALUES (?, ?)")
+            .bind::<BigInt, _>(id.as_db_id())
+            .bind::<BigInt, _>(account.created)
+            .execute(conn)
+            .unwrap();
+
+        for _ in 0..account.reports {
+            let creator = insert_account_id(conn);
+            sql_query(
+                "INSERT INTO common_report(creator_account_id, target_account_id, \
+                report_type_number, creation_unix_time, processing_state_change_unix_time) \
+                VALUES (?, ?, 0, 0, 0)",
+            )
+            .bind::<BigInt, _>(creator.as_db_id())
+            .bind::<BigInt, _>(id.as_db_id())
+            .execute(conn)
+            .unwrap();
+        }
+
+        id.as_id()
+    }
+
+    fn search(conn: &mut SqliteConnection, settings: ProfileSearchSettings) -> Vec<AccountId> {
+        CurrentReadProfileSearch::new(conn)
+            .search_profiles(settings)
+            .unwrap()
+            .values
+            .into_iter()
+            .map(|v| v.account_id)
+            .collect()
+    }
+
+    fn text(text: &str) -> ProfileSearchSettings {
+        ProfileSearchSettings {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn text_matches_profile_name_and_text() {
+        let mut conn = new_db();
+        let name_match = insert_account(
+            &mut conn,
+            TestAccount {
+                name: "Examplename",
+                ..Default::default()
+            },
+        );
+        let text_match = insert_account(
+            &mut conn,
+            TestAccount {
+                name: "Other",
+                text: "Text with EXAMPLE word",
+                ..Default::default()
+            },
+        );
+        insert_account(
+            &mut conn,
+            TestAccount {
+                name: "Other",
+                text: "Nothing",
+                ..Default::default()
+            },
+        );
+
+        let mut found = search(&mut conn, text("example"));
+        found.sort_by_key(|v| v.to_string());
+        let mut expected = vec![name_match, text_match];
+        expected.sort_by_key(|v| v.to_string());
+        assert_eq!(found, expected);
+
+        assert_eq!(search(&mut conn, text("example word")), vec![text_match]);
+        assert!(search(&mut conn, text("missing")).is_empty());
+        assert_eq!(
+            search(
+                &mut conn,
+                ProfileSearchSettings {
+                    text: "exampel".to_string(),
+                    fuzzy: true,
+                    ..Default::default()
+                },
+            )
+            .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn filters_limit_results() {
+        let mut conn = new_db();
+        let default = insert_account(
+            &mut conn,
+            TestAccount {
+                created: 100,
+                ..Default::default()
+            },
+        );
+        let setup_not_completed = insert_account(
+            &mut conn,
+            TestAccount {
+                initial_setup_completed: false,
+                created: 100,
+                ..Default::default()
+            },
+        );
+        let banned = insert_account(
+            &mut conn,
+            TestAccount {
+                banned: true,
+                created: 100,
+                ..Default::default()
+            },
+        );
+        let pending_deletion = insert_account(
+            &mut conn,
+            TestAccount {
+                pending_deletion: true,
+                created: 100,
+                ..Default::default()
+            },
+        );
+        let reported = insert_account(
+            &mut conn,
+            TestAccount {
+                reports: 2,
+                created: 100,
+                ..Default::default()
+            },
+        );
+        let old = insert_account(
+            &mut conn,
+            TestAccount {
+                created: 10,
+                ..Default::default()
+            },
+        );
+        let new = insert_account(
+            &mut conn,
+            TestAccount {
+                created: 1000,
+                ..Default::default()
+            },
+        );
+
+        // Report creators do not have profiles, so those are not in
+        // the results.
+        assert_eq!(
+            search(&mut conn, ProfileSearchSettings::default()),
+            vec![
+                new,
+                old,
+                reported,
+                pending_deletion,
+                banned,
+                setup_not_completed,
+                default
+            ],
+        );
+        assert_eq!(
+            search(
+                &mut conn,
+                ProfileSearchSettings {
+                    initial_setup_completed: Some(false),
+                    ..Default::default()
+                },
+            ),
+            vec![setup_not_completed],
+        );
+        assert_eq!(
+            search(
+                &mut conn,
+                ProfileSearchSettings {
+                    banned: Some(true),
+                    ..Default::default()
+                },
+            ),
+            vec![banned],
+        );
+        assert_eq!(
+            search(
+                &mut conn,
+                ProfileSearchSettings {
+                    pending_deletion: Some(true),
+                    ..Default::default()
+                },
+            ),
+            vec![pending_deletion],
+        );
+        assert_eq!(
+            search(
+                &mut conn,
+                ProfileSearchSettings {
+                    min_report_count: Some(2),
+                    ..Default::default()
+                },
+            ),
+            vec![reported],
+        );
+        assert_eq!(
+            search(
+                &mut conn,
+                ProfileSearchSettings {
+                    created_after_unix_time: Some(1000),
+                    ..Default::default()
+                },
+            ),
+            vec![new],
+        );
+        assert_eq!(
+            search(
+                &mut conn,
+                ProfileSearchSettings {
+                    created_before_unix_time: Some(10),
+                    ..Default::default()
+                },
+            ),
+            vec![old],
+        );
+    }
+
+    #[test]
+    fn results_are_paged() {
+        let mut conn = new_db();
+        let accounts: Vec<AccountId> = (0..PAGE_SIZE + 5)
+            .map(|_| {
+                insert_account(
+                    &mut conn,
+                    TestAccount {
+                        name: "Pagename",
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+        let newest_first: Vec<AccountId> = accounts.into_iter().rev().collect();
+
+        let page = |conn: &mut SqliteConnection, page| {
+            search(
+                conn,
+                ProfileSearchSettings {
+                    page,
+                    ..Default::default()
+                },
+            )
+        };
+        assert_eq!(page(&mut conn, 0), newest_first[..PAGE_SIZE as usize]);
+        assert_eq!(page(&mut conn, 1), newest_first[PAGE_SIZE as usize..]);
+        assert!(page(&mut conn, 2).is_empty());
+
+        let text_page = |conn: &mut SqliteConnection, page| {
+            search(
+                conn,
+                ProfileSearchSettings {
+                    page,
+                    ..text("pagename")
+                },
+            )
+        };
+        assert_eq!(text_page(&mut conn, 0).len(), PAGE_SIZE as usize);
+        assert_eq!(text_page(&mut conn, 1).len(), 5);
+    }
+}
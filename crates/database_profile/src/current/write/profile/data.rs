@@ -1,6 +1,7 @@
 use database::{define_current_write_commands, DieselDatabaseError};
 use diesel::{
-    delete, insert_into, prelude::*, update, upsert::excluded, ExpressionMethods, QueryDsl,
+    delete, insert_into, prelude::*, sql_query, sql_types::BigInt, update, upsert::excluded,
+    ExpressionMethods, QueryDsl,
 };
 use error_stack::{Result, ResultExt};
 use model_profile::{
@@ -19,11 +20,32 @@ impl CurrentWriteProfileData<'_> {
         use model::schema::profile::dsl::*;
 
         let version = ProfileVersion::new_random();
-        insert_into(profile)
+        let data = insert_into(profile)
             .values((account_id.eq(id.as_db_id()), version_uuid.eq(version)))
             .returning(ProfileInternal::as_returning())
             .get_result(self.conn())
-            .into_db_error(id)
+            .into_db_error(id)?;
+
+        self.update_profile_search_index(id)?;
+
+        Ok(data)
+    }
+
+    /// Copy current profile name and profile text to the admin profile
+    /// search index.
+    fn update_profile_search_index(
+        &mut self,
+        id: AccountIdInternal,
+    ) -> Result<(), DieselDatabaseError> {
+        sql_query(
+            "INSERT OR REPLACE INTO profile_search_index(rowid, name, profile_text) \
+            SELECT account_id, name, profile_text FROM profile WHERE account_id = ?",
+        )
+        .bind::<BigInt, _>(id.as_db_id())
+        .execute(self.conn())
+        .into_db_error(id)?;
+
+        Ok(())
     }
 
     pub fn insert_profile_state(
@@ -60,6 +82,8 @@ impl CurrentWriteProfileData<'_> {
             .execute(self.conn())
             .change_context(DieselDatabaseError::Execute)?;
 
+        self.update_profile_search_index(id)?;
+
         Ok(())
     }
 
@@ -90,6 +114,8 @@ impl CurrentWriteProfileData<'_> {
             .execute(self.conn())
             .change_context(DieselDatabaseError::Execute)?;
 
+        self.update_profile_search_index(id)?;
+
         Ok(())
     }
 
@@ -236,6 +262,8 @@ impl CurrentWriteProfileData<'_> {
                 profile_created_time_filter.eq(settings.profile_created_filter),
                 profile_edited_time_filter.eq(settings.profile_edited_filter),
                 random_profile_order.eq(settings.random_profile_order),
+                hide_interacted_profiles.eq(settings.hide_interacted_profiles),
+                mutual_attribute_filters.eq(settings.mutual_attribute_filters),
             ))
             .execute(self.conn())
             .into_db_error(())?;
@@ -14,7 +14,7 @@ impl CurrentWriteProfileNameAllowlist<'_> {
         id: AccountIdInternal,
         new_name: &str,
         ram_allowlist: &ProfileNameAllowlistData,
-    ) -> Result<(), DieselDatabaseError> {
+    ) -> Result<ProfileNameModerationState, DieselDatabaseError> {
         use model::schema::profile_state;
 
         let new_name = new_name.trim().to_lowercase();
@@ -37,6 +37,6 @@ impl CurrentWriteProfileNameAllowlist<'_> {
             .execute(self.conn())
             .into_db_error(id)?;
 
-        Ok(())
+        Ok(new_state)
     }
 }
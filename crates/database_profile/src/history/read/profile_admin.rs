@@ -1,9 +1,14 @@
 use database::define_history_read_commands;
+mod moderation;
 mod statistics;
 
 define_history_read_commands!(HistoryReadProfileAdmin);
 
 impl<'a> HistoryReadProfileAdmin<'a> {
+    pub fn moderation(self) -> moderation::HistoryReadProfileAdminModeration<'a> {
+        moderation::HistoryReadProfileAdminModeration::new(self.cmds)
+    }
+
     pub fn statistics(self) -> statistics::HistoryReadProfileAdminStatistics<'a> {
         statistics::HistoryReadProfileAdminStatistics::new(self.cmds)
     }
@@ -0,0 +1,34 @@
+use database::{define_history_read_commands, DieselDatabaseError, IntoDatabaseError};
+use diesel::prelude::*;
+use error_stack::Result;
+use model_profile::{
+    AccountId, GetProfileModerationHistory, ProfileNameModerationHistoryEntry,
+    ProfileTextModerationHistoryEntry,
+};
+
+define_history_read_commands!(HistoryReadProfileAdminModeration);
+
+impl HistoryReadProfileAdminModeration<'_> {
+    pub fn profile_moderation_history(
+        &mut self,
+        owner_id: AccountId,
+    ) -> Result<GetProfileModerationHistory, DieselDatabaseError> {
+        use model::schema::{history_profile_name_moderation, history_profile_text_moderation};
+
+        let names = history_profile_name_moderation::table
+            .filter(history_profile_name_moderation::account_uuid.eq(owner_id))
+            .select(ProfileNameModerationHistoryEntry::as_select())
+            .order(history_profile_name_moderation::id.desc())
+            .load(self.conn())
+            .into_db_error(())?;
+
+        let texts = history_profile_text_moderation::table
+            .filter(history_profile_text_moderation::account_uuid.eq(owner_id))
+            .select(ProfileTextModerationHistoryEntry::as_select())
+            .order(history_profile_text_moderation::id.desc())
+            .load(self.conn())
+            .into_db_error(())?;
+
+        Ok(GetProfileModerationHistory { names, texts })
+    }
+}
@@ -1,10 +1,15 @@
 use database::define_history_write_commands;
 
+mod moderation;
 mod statistics;
 
 define_history_write_commands!(HistoryWriteProfileAdmin);
 
 impl<'a> HistoryWriteProfileAdmin<'a> {
+    pub fn moderation(self) -> moderation::HistoryWriteProfileAdminModeration<'a> {
+        moderation::HistoryWriteProfileAdminModeration::new(self.cmds)
+    }
+
     pub fn statistics(self) -> statistics::HistoryWriteProfileAdminStatistics<'a> {
         statistics::HistoryWriteProfileAdminStatistics::new(self.cmds)
     }
@@ -0,0 +1,149 @@
+use database::{define_history_write_commands, DieselDatabaseError, IntoDatabaseError};
+use diesel::{insert_into, prelude::*, update};
+use error_stack::Result;
+use model_profile::{
+    AccountId, ProfileNameModerationState, ProfileTextModerationRejectedReasonCategory,
+    ProfileTextModerationRejectedReasonDetails, ProfileTextModerationState, UnixTime,
+};
+
+define_history_write_commands!(HistoryWriteProfileAdminModeration);
+
+impl HistoryWriteProfileAdminModeration<'_> {
+    pub fn insert_profile_name(
+        &mut self,
+        name_owner_id: AccountId,
+        name: String,
+        state: ProfileNameModerationState,
+    ) -> Result<(), DieselDatabaseError> {
+        self.insert_profile_name_and_return_id(name_owner_id, name, state)?;
+        Ok(())
+    }
+
+    fn insert_profile_name_and_return_id(
+        &mut self,
+        name_owner_id: AccountId,
+        name: String,
+        state: ProfileNameModerationState,
+    ) -> Result<i64, DieselDatabaseError> {
+        use model::schema::history_profile_name_moderation::dsl::*;
+
+        insert_into(history_profile_name_moderation)
+            .values((
+                account_uuid.eq(name_owner_id),
+                profile_name.eq(name),
+                submitted_unix_time.eq(UnixTime::current_time()),
+                moderation_state.eq(state),
+            ))
+            .returning(id)
+            .get_result(self.conn())
+            .into_db_error(())
+    }
+
+    /// Update moderation result of the latest submission of the name.
+    /// If the name is not in the history, it is added to it.
+    pub fn update_profile_name_moderation(
+        &mut self,
+        name_owner_id: AccountId,
+        name: String,
+        state: ProfileNameModerationState,
+        moderator_id: Option<AccountId>,
+    ) -> Result<(), DieselDatabaseError> {
+        use model::schema::history_profile_name_moderation::dsl::*;
+
+        let latest: Option<i64> = history_profile_name_moderation
+            .filter(account_uuid.eq(name_owner_id))
+            .filter(profile_name.eq(&name))
+            .select(id)
+            .order(id.desc())
+            .first(self.conn())
+            .optional()
+            .into_db_error(())?;
+
+        let entry_id = match latest {
+            Some(entry_id) => entry_id,
+            None => self.insert_profile_name_and_return_id(name_owner_id, name, state)?,
+        };
+
+        update(history_profile_name_moderation.find(entry_id))
+            .set((
+                moderation_state.eq(state),
+                moderator_account_uuid.eq(moderator_id),
+                moderation_unix_time.eq(UnixTime::current_time()),
+            ))
+            .execute(self.conn())
+            .into_db_error(())?;
+
+        Ok(())
+    }
+
+    pub fn insert_profile_text(
+        &mut self,
+        text_owner_id: AccountId,
+        text: String,
+        state: ProfileTextModerationState,
+    ) -> Result<(), DieselDatabaseError> {
+        self.insert_profile_text_and_return_id(text_owner_id, text, state)?;
+        Ok(())
+    }
+
+    fn insert_profile_text_and_return_id(
+        &mut self,
+        text_owner_id: AccountId,
+        text: String,
+        state: ProfileTextModerationState,
+    ) -> Result<i64, DieselDatabaseError> {
+        use model::schema::history_profile_text_moderation::dsl::*;
+
+        insert_into(history_profile_text_moderation)
+            .values((
+                account_uuid.eq(text_owner_id),
+                profile_text.eq(text),
+                submitted_unix_time.eq(UnixTime::current_time()),
+                moderation_state.eq(state),
+            ))
+            .returning(id)
+            .get_result(self.conn())
+            .into_db_error(())
+    }
+
+    /// Update moderation result of the latest submission of the text.
+    /// If the text is not in the history, it is added to it.
+    pub fn update_profile_text_moderation(
+        &mut self,
+        text_owner_id: AccountId,
+        text: String,
+        state: ProfileTextModerationState,
+        moderator_id: Option<AccountId>,
+        rejected_category: Option<ProfileTextModerationRejectedReasonCategory>,
+        rejected_details: Option<ProfileTextModerationRejectedReasonDetails>,
+    ) -> Result<(), DieselDatabaseError> {
+        use model::schema::history_profile_text_moderation::dsl::*;
+
+        let latest: Option<i64> = history_profile_text_moderation
+            .filter(account_uuid.eq(text_owner_id))
+            .filter(profile_text.eq(&text))
+            .select(id)
+            .order(id.desc())
+            .first(self.conn())
+            .optional()
+            .into_db_error(())?;
+
+        let entry_id = match latest {
+            Some(entry_id) => entry_id,
+            None => self.insert_profile_text_and_return_id(text_owner_id, text, state)?,
+        };
+
+        update(history_profile_text_moderation.find(entry_id))
+            .set((
+                moderation_state.eq(state),
+                moderator_account_uuid.eq(moderator_id),
+                moderation_unix_time.eq(UnixTime::current_time()),
+                rejected_reason_category.eq(rejected_category),
+                rejected_reason_details.eq(rejected_details),
+            ))
+            .execute(self.conn())
+            .into_db_error(())?;
+
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@ use manager_api::ManagerClient;
 use manager_model::JsonRpcRequest;
 use manager_model::JsonRpcRequestType;
 use manager_model::JsonRpcResponse;
+use backup::RpcBackup;
 use scheduled_task::RpcScheduledTask;
 use secure_storage::RpcSecureStorage;
 use software::RpcSoftware;
@@ -25,6 +26,7 @@ use error_stack::{Result, ResultExt};
 use super::ClientConnectionReadWrite;
 use super::ServerError;
 
+pub mod backup;
 pub mod software;
 pub mod secure_storage;
 pub mod system_info;
@@ -46,6 +48,10 @@ pub enum JsonRpcError {
     ScheduledTaskManager,
     #[error("Update manager error")]
     UpdateManager,
+    #[error("Backup restore is not allowed")]
+    BackupRestoreNotAllowed,
+    #[error("Backup restore failed")]
+    BackupRestore,
 }
 
 pub async fn handle_json_rpc<
@@ -151,5 +157,7 @@ pub async fn handle_request_type(
             state.rpc_unschedule_task(task).await,
         JsonRpcRequestType::GetScheduledTasksStatus =>
             state.rpc_get_scheduled_tasks_status().await,
+        JsonRpcRequestType::RestoreBackup(settings) =>
+            state.rpc_restore_backup(settings).await,
     }
 }
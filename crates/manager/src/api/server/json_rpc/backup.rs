@@ -0,0 +1,48 @@
+use error_stack::report;
+use manager_model::BackupRestoreSettings;
+use manager_model::JsonRpcResponse;
+
+use crate::api::GetConfig;
+use crate::server::link::backup::encryption::BackupEncryptionKey;
+use crate::server::link::backup::target::restore::{restore_backup, BACKUP_DIR_NAME};
+
+use error_stack::{Result, ResultExt};
+
+use super::JsonRpcError;
+
+pub trait RpcBackup: GetConfig {
+    async fn rpc_restore_backup(
+        &self,
+        settings: BackupRestoreSettings,
+    ) -> Result<JsonRpcResponse, JsonRpcError> {
+        if !self.config().manual_tasks_config().allow_backup_restore {
+            return Err(report!(JsonRpcError::BackupRestoreNotAllowed));
+        }
+
+        let backup_dir = settings
+            .backup_dir
+            .unwrap_or_else(|| self.config().storage_dir().join(BACKUP_DIR_NAME));
+
+        let encryption_key = if let Some(name) = &settings.encryption_key_name {
+            let key = BackupEncryptionKey::load(self.config(), name)
+                .await
+                .change_context(JsonRpcError::BackupRestore)?;
+            Some(key)
+        } else {
+            None
+        };
+
+        let info = restore_backup(
+            &backup_dir,
+            &settings.data_dir,
+            settings.date.as_deref(),
+            encryption_key.as_ref(),
+        )
+            .await
+            .change_context(JsonRpcError::BackupRestore)?;
+
+        Ok(JsonRpcResponse::backup_restore_info(info))
+    }
+}
+
+impl <T: GetConfig> RpcBackup for T {}
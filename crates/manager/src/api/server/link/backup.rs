@@ -2,7 +2,8 @@ use std::net::SocketAddr;
 use manager_api::protocol::ClientConnectionRead;
 use manager_api::protocol::ClientConnectionWrite;
 
-use tracing::info;
+use manager_model::{BackupMessageType, TargetToSourceMessage};
+use tracing::{info, warn};
 use crate::api::utils::validate_backup_link_login;
 use crate::api::utils::BackupLinkClient;
 use crate::api::GetBackupLinkManager;
@@ -118,6 +119,15 @@ async fn handle_read_one_message<
         // Client disconnected
         return Ok(None);
     };
+    if matches!(client, BackupLinkClient::Target) && message.header.message_type == BackupMessageType::BackupVerificationStatus {
+        match TargetToSourceMessage::try_from(message) {
+            Ok(TargetToSourceMessage::BackupVerificationStatus { status }) =>
+                state.set_backup_verification_status(status).await,
+            Ok(_) => (),
+            Err(e) => warn!("Invalid backup verification status message: {}", e),
+        }
+        return Ok(Some(c));
+    }
     state.backup_link_server().receive_message(client, message).await.change_context(ServerError::BrokenChannel)?;
     Ok(Some(c))
 }
@@ -2,7 +2,7 @@
 //!
 
 use error_stack::{Result, ResultExt};
-use manager_model::{ManagerInstanceName, SoftwareInfo, SoftwareUpdateTaskType};
+use manager_model::{BackupRestoreSettings, ManagerInstanceName, SoftwareInfo, SoftwareUpdateTaskType};
 
 use manager_config::args::{ApiCommand, ManagerApiClientMode};
 use manager_api::{ClientConfig, ClientError, ManagerClient, protocol::RequestSenderCmds};
@@ -72,6 +72,13 @@ pub async fn handle_api_client_mode(args: ManagerApiClientMode) -> Result<(), Cl
                 .await
                 .change_context(ClientError::RemoteApiRequest)?
         }
+        ApiCommand::RestoreBackup { data_dir, date, backup_dir, encryption_key_name } => {
+            let encryption_key_name = encryption_key_name.map(ManagerInstanceName::new);
+            let info = client.restore_backup(BackupRestoreSettings { backup_dir, data_dir, date, encryption_key_name })
+                .await
+                .change_context(ClientError::RemoteApiRequest)?;
+            println!("{:#?}", info);
+        }
     }
 
     Ok(())
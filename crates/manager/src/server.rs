@@ -167,6 +167,7 @@ impl AppServer {
         // Start backup link manager server logic
 
         let backup_link_manager_server_quit_handle = BackupLinkManagerServer::new_manager(
+            self.config.clone(),
             backup_link_manager_server_internal_state,
             services_quit_watcher.resubscribe(),
         );
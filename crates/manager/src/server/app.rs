@@ -1,8 +1,8 @@
 use std::sync::Arc;
 
 use manager_config::Config;
-use manager_model::{ServerEvent, ServerEventType};
-use tokio::sync::watch;
+use manager_model::{BackupVerificationStatus, ServerEvent, ServerEventType};
+use tokio::sync::{watch, RwLock};
 
 use super::{backend_events::BackendEventsHandle, backend_manager::BackendManagerHandle, client::ApiManager, link::{backup::server::BackupLinkManagerHandleServer, json_rpc::server::JsonRcpLinkManagerHandleServer}, scheduled_task::ScheduledTaskManagerHandle, task::TaskManagerHandle, update::UpdateManagerHandle};
 use crate::api::{GetApiManager, GetBackendManager, GetBackupLinkManager, GetConfig, GetJsonRcpLinkManager, GetScheduledTaskManager, GetTaskManager, GetUpdateManager};
@@ -19,6 +19,7 @@ pub struct AppState {
     json_rpc_link_handle_server: Arc<JsonRcpLinkManagerHandleServer>,
     backup_link_handle_server: Arc<BackupLinkManagerHandleServer>,
     backend_manager: Arc<BackendManagerHandle>,
+    backup_verification_status: Arc<RwLock<Option<BackupVerificationStatus>>>,
 }
 
 impl AppState {
@@ -28,7 +29,18 @@ impl AppState {
                 self.scheduled_task_manager.maintenance_time_for_backend_event().await
             ),
         };
-        vec![event]
+        let mut events = vec![event];
+        if let Some(status) = *self.backup_verification_status.read().await {
+            events.push(ServerEvent {
+                event: ServerEventType::BackupVerificationStatus(status),
+            });
+        }
+        events
+    }
+
+    pub async fn set_backup_verification_status(&self, status: BackupVerificationStatus) {
+        *self.backup_verification_status.write().await = Some(status);
+        self.refresh_state_to_backend().await;
     }
 
     pub async fn refresh_state_to_backend(&self) {
@@ -115,6 +127,7 @@ impl App {
             json_rpc_link_handle_server,
             backup_link_handle_server,
             backend_manager,
+            backup_verification_status: RwLock::new(None).into(),
         };
 
         state.refresh_state_to_backend().await;
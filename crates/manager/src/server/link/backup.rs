@@ -1,2 +1,3 @@
+pub mod encryption;
 pub mod server;
 pub mod target;
@@ -0,0 +1,350 @@
+//! Backup data encryption
+//!
+//! Backup link server encrypts the backup data before it is relayed
+//! to the backup target, so the encryption key is not needed on the
+//! backup target.
+//!
+//! Encrypted data is a sequence of frames. Frame format is
+//! `[magic][flags][length][nonce][ciphertext]`, where length is
+//! little endian u32 containing nonce and ciphertext length.
+//!
+//! Additional authenticated data of a frame contains the frame index
+//! (little endian u64), flags and [BackupFileId], so frames can not be
+//! reordered or moved to other files. The last frame of a file has
+//! [FRAME_FLAG_FINAL] set, which makes truncated files detectable.
+
+use std::path::Path;
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use error_stack::{Result, ResultExt};
+use hkdf::Hkdf;
+use manager_config::Config;
+use manager_model::{ManagerInstanceName, SecureStorageEncryptionKey};
+use sha2::Sha256;
+use simple_backend_utils::{ContextExt, UuidBase64Url};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+const FRAME_MAGIC: &[u8; 4] = b"AFBE";
+const FRAME_FLAG_FINAL: u8 = 1;
+/// HKDF info for deriving the backup encryption key, so the same
+/// key text used elsewhere does not produce the same AES key.
+const KEY_DERIVATION_INFO: &[u8] = b"afrodite backup encryption v1";
+const NONCE_LEN: usize = 12;
+/// Frames are created from single backup link messages, so
+/// longer frames are treated as corrupted data.
+const MAX_FRAME_LEN: u32 = 256 * 1024 * 1024;
+
+#[derive(thiserror::Error, Debug)]
+pub enum BackupEncryptionError {
+    #[error("Encryption key not found from config")]
+    KeyNotFound,
+
+    #[error("Encryption key loading failed")]
+    KeyLoading,
+
+    #[error("Encryption key is not available")]
+    KeyNotAvailable,
+
+    #[error("Encryption failed")]
+    Encrypt,
+
+    #[error("Decryption failed")]
+    Decrypt,
+
+    #[error("Invalid frame")]
+    InvalidFrame,
+
+    #[error("Final frame is missing")]
+    FinalFrameMissing,
+
+    #[error("Invalid backup message")]
+    InvalidMessage,
+
+    #[error("Reading error")]
+    Read,
+
+    #[error("Writing error")]
+    Write,
+}
+
+/// Backup file which the encrypted data belongs to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupFileId(String);
+
+impl BackupFileId {
+    pub fn content(account_id: UuidBase64Url, content_id: UuidBase64Url) -> Self {
+        Self(format!("content/{}/{}", account_id, content_id))
+    }
+
+    /// File backup with name from [manager_model::SourceToTargetMessage::StartFileBackup].
+    pub fn file(name: &str) -> Self {
+        Self(format!("file/{}", name))
+    }
+
+    fn aad(&self, frame_index: u64, flags: u8) -> Vec<u8> {
+        let mut aad = frame_index.to_le_bytes().to_vec();
+        aad.push(flags);
+        aad.extend_from_slice(self.0.as_bytes());
+        aad
+    }
+}
+
+struct Frame {
+    flags: u8,
+    /// Nonce and ciphertext
+    data: Vec<u8>,
+}
+
+#[derive(Clone)]
+pub struct BackupEncryptionKey {
+    cipher: Aes256Gcm,
+}
+
+impl BackupEncryptionKey {
+    /// AES-256 key is derived from the key text using HKDF-SHA256.
+    pub fn new(key: &SecureStorageEncryptionKey) -> Self {
+        let hkdf = Hkdf::<Sha256>::new(None, key.key.as_bytes());
+        let mut aes_key = [0u8; 32];
+        hkdf.expand(KEY_DERIVATION_INFO, &mut aes_key)
+            .expect("AES-256 key length is valid HKDF-SHA256 output length");
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&aes_key)),
+        }
+    }
+
+    /// Load key from [manager_config::file::ServerEncryptionKey] config.
+    pub async fn load(
+        config: &Config,
+        name: &ManagerInstanceName,
+    ) -> Result<Self, BackupEncryptionError> {
+        let key = config
+            .encryption_keys()
+            .iter()
+            .find(|key| key.manager_name == *name)
+            .ok_or_else(|| BackupEncryptionError::KeyNotFound.report())
+            .attach_printable(name.0.clone())?
+            .read_encryption_key()
+            .await
+            .change_context(BackupEncryptionError::KeyLoading)?;
+
+        Ok(Self::new(&key))
+    }
+
+    /// Encrypt data to one frame. Frame indexes of a file start from zero.
+    pub fn encrypt(
+        &self,
+        file_id: &BackupFileId,
+        frame_index: u64,
+        final_frame: bool,
+        data: &[u8],
+    ) -> Result<Vec<u8>, BackupEncryptionError> {
+        let flags = if final_frame { FRAME_FLAG_FINAL } else { 0 };
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: data,
+            aad: &file_id.aad(frame_index, flags),
+        };
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, payload)
+            .map_err(|_| BackupEncryptionError::Encrypt.report())?;
+        let frame_len = TryInto::<u32>::try_into(NONCE_LEN + ciphertext.len())
+            .change_context(BackupEncryptionError::Encrypt)?;
+
+        let mut frame =
+            Vec::with_capacity(FRAME_MAGIC.len() + 1 + 4 + NONCE_LEN + ciphertext.len());
+        frame.extend_from_slice(FRAME_MAGIC);
+        frame.push(flags);
+        frame.extend_from_slice(&frame_len.to_le_bytes());
+        frame.extend_from_slice(&nonce);
+        frame.extend_from_slice(&ciphertext);
+        Ok(frame)
+    }
+
+    fn decrypt_frame(
+        &self,
+        file_id: &BackupFileId,
+        frame_index: u64,
+        frame: &Frame,
+    ) -> Result<Vec<u8>, BackupEncryptionError> {
+        let Some((nonce, ciphertext)) = frame.data.split_at_checked(NONCE_LEN) else {
+            return Err(BackupEncryptionError::InvalidFrame.report());
+        };
+        let payload = Payload {
+            msg: ciphertext,
+            aad: &file_id.aad(frame_index, frame.flags),
+        };
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), payload)
+            .map_err(|_| BackupEncryptionError::Decrypt.report())
+    }
+
+    /// Decrypt all frames from `source` and write the data to `target`.
+    pub async fn decrypt_file(
+        &self,
+        source: &Path,
+        target: &Path,
+        file_id: &BackupFileId,
+    ) -> Result<(), BackupEncryptionError> {
+        let reader = tokio::fs::File::open(source)
+            .await
+            .change_context(BackupEncryptionError::Read)?;
+        let mut writer = tokio::fs::File::create(target)
+            .await
+            .change_context(BackupEncryptionError::Write)?;
+        self.decrypt(tokio::io::BufReader::new(reader), &mut writer, file_id)
+            .await
+    }
+
+    async fn decrypt(
+        &self,
+        mut reader: impl AsyncRead + Unpin,
+        writer: &mut (impl AsyncWrite + Unpin),
+        file_id: &BackupFileId,
+    ) -> Result<(), BackupEncryptionError> {
+        let mut frame_index: u64 = 0;
+        let mut final_frame_read = false;
+        while let Some(frame) = read_frame(&mut reader).await? {
+            if final_frame_read {
+                return Err(BackupEncryptionError::InvalidFrame.report())
+                    .attach_printable("Frame after final frame");
+            }
+            let data = self.decrypt_frame(file_id, frame_index, &frame)?;
+            writer
+                .write_all(&data)
+                .await
+                .change_context(BackupEncryptionError::Write)?;
+            final_frame_read = frame.flags & FRAME_FLAG_FINAL != 0;
+            frame_index += 1;
+        }
+
+        if !final_frame_read {
+            return Err(BackupEncryptionError::FinalFrameMissing.report());
+        }
+
+        writer
+            .flush()
+            .await
+            .change_context(BackupEncryptionError::Write)
+    }
+}
+
+impl std::fmt::Debug for BackupEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("BackupEncryptionKey")
+    }
+}
+
+/// Returns `None` if all frames are read.
+async fn read_frame(
+    reader: &mut (impl AsyncRead + Unpin),
+) -> Result<Option<Frame>, BackupEncryptionError> {
+    let mut magic = [0u8; 4];
+    match reader.read_exact(&mut magic).await {
+        Ok(_) => (),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e).change_context(BackupEncryptionError::Read),
+    }
+    if &magic != FRAME_MAGIC {
+        return Err(BackupEncryptionError::InvalidFrame.report());
+    }
+
+    let flags = reader
+        .read_u8()
+        .await
+        .change_context(BackupEncryptionError::Read)?;
+
+    let frame_len = reader
+        .read_u32_le()
+        .await
+        .change_context(BackupEncryptionError::Read)?;
+    if frame_len > MAX_FRAME_LEN {
+        return Err(BackupEncryptionError::InvalidFrame.report())
+            .attach_printable(format!("frame length: {}", frame_len));
+    }
+
+    let mut data = vec![0u8; frame_len as usize];
+    reader
+        .read_exact(&mut data)
+        .await
+        .change_context(BackupEncryptionError::Read)?;
+    Ok(Some(Frame { flags, data }))
+}
+
+/// Check does the file start with an encrypted frame.
+pub async fn is_encrypted_file(file: &Path) -> Result<bool, BackupEncryptionError> {
+    let mut file = tokio::fs::File::open(file)
+        .await
+        .change_context(BackupEncryptionError::Read)?;
+    let mut magic = [0u8; 4];
+    match file.read_exact(&mut magic).await {
+        Ok(_) => Ok(&magic == FRAME_MAGIC),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e).change_context(BackupEncryptionError::Read),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(text: &str) -> BackupEncryptionKey {
+        BackupEncryptionKey::new(&SecureStorageEncryptionKey {
+            key: text.to_string(),
+        })
+    }
+
+    async fn decrypt(
+        key: &BackupEncryptionKey,
+        data: &[u8],
+        file_id: &BackupFileId,
+    ) -> Result<Vec<u8>, BackupEncryptionError> {
+        let mut decrypted = vec![];
+        key.decrypt(data, &mut decrypted, file_id).await?;
+        Ok(decrypted)
+    }
+
+    #[tokio::test]
+    async fn encrypted_frames_can_be_decrypted() {
+        let key = key("test");
+        let id = BackupFileId::file("current");
+        let mut data = key.encrypt(&id, 0, false, b"first").unwrap();
+        data.extend(key.encrypt(&id, 1, false, b"second").unwrap());
+        data.extend(key.encrypt(&id, 2, true, &[]).unwrap());
+
+        assert_eq!(decrypt(&key, &data, &id).await.unwrap(), b"firstsecond");
+        assert!(decrypt(&self::key("other"), &data, &id).await.is_err());
+        assert!(decrypt(&key, &data, &BackupFileId::file("history"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn reordered_or_truncated_frames_are_not_decrypted() {
+        let key = key("test");
+        let id = BackupFileId::file("current");
+        let first = key.encrypt(&id, 0, false, b"first").unwrap();
+        let second = key.encrypt(&id, 1, false, b"second").unwrap();
+        let last = key.encrypt(&id, 2, true, &[]).unwrap();
+
+        let reordered = [second.clone(), first.clone(), last.clone()].concat();
+        assert!(decrypt(&key, &reordered, &id).await.is_err());
+
+        let truncated = [first.clone(), second.clone()].concat();
+        assert!(decrypt(&key, &truncated, &id).await.is_err());
+
+        let extended = [first, second, last.clone(), last].concat();
+        assert!(decrypt(&key, &extended, &id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn plain_data_is_not_decrypted() {
+        let id = BackupFileId::file("current");
+        assert!(decrypt(&key("test"), b"SQLite format 3\0", &id)
+            .await
+            .is_err());
+    }
+}
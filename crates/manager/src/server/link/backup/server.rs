@@ -1,11 +1,17 @@
 
+use std::{num::Wrapping, sync::Arc};
+
 use error_stack::{ResultExt, Result};
-use manager_model::BackupMessage;
+use manager_config::Config;
+use manager_model::{BackupMessage, BackupMessageType, SourceToTargetMessage, TargetToSourceMessage};
+use simple_backend_utils::ContextExt;
 use tokio::{sync::{mpsc, oneshot}, task::JoinHandle};
 use tracing::{warn, error};
 
 use crate::{api::utils::BackupLinkClient, server::ServerQuitWatcher};
 
+use super::encryption::{BackupEncryptionError, BackupEncryptionKey, BackupFileId};
+
 #[derive(Debug)]
 pub struct BackupLinkConnectionReceiver {
     pub receiver: mpsc::Receiver<BackupMessage>,
@@ -118,10 +124,20 @@ pub struct BackupLinkManagerInternalState {
     receiver: mpsc::Receiver<BackupLinkManagerMessage>,
 }
 
+struct BackupEncryptionState {
+    key: BackupEncryptionKey,
+    /// Latest content query from the backup target
+    content_query: Option<BackupFileId>,
+    /// Current file backup and its next frame index
+    file_backup: Option<(BackupFileId, u64)>,
+}
+
 pub struct BackupLinkManagerServer {
     receiver: mpsc::Receiver<BackupLinkManagerMessage>,
     connection_target: Option<BackupLinkConnectionSender>,
     connection_source: Option<BackupLinkConnectionSender>,
+    /// Backups are not relayed when the encryption key is not available.
+    encryption: Option<BackupEncryptionState>,
 }
 
 impl BackupLinkManagerServer {
@@ -138,6 +154,7 @@ impl BackupLinkManagerServer {
     }
 
     pub fn new_manager(
+        config: Arc<Config>,
         internal_state: BackupLinkManagerInternalState,
         quit_notification: ServerQuitWatcher,
     ) -> BackupLinkManagerServerQuitHandle {
@@ -146,9 +163,10 @@ impl BackupLinkManagerServer {
             receiver: internal_state.receiver,
             connection_source: None,
             connection_target: None,
+            encryption: None,
         };
 
-        let task = tokio::spawn(manager.run(quit_notification.resubscribe()));
+        let task = tokio::spawn(manager.run(config, quit_notification.resubscribe()));
 
         BackupLinkManagerServerQuitHandle {
             task,
@@ -156,7 +174,20 @@ impl BackupLinkManagerServer {
         }
     }
 
-    async fn run(self, mut quit_notification: ServerQuitWatcher) {
+    async fn run(mut self, config: Arc<Config>, mut quit_notification: ServerQuitWatcher) {
+        if let Some(name) = config.backup_link().encryption_key_name() {
+            match BackupEncryptionKey::load(&config, name).await {
+                Ok(key) => self.encryption = Some(BackupEncryptionState {
+                    key,
+                    content_query: None,
+                    file_backup: None,
+                }),
+                Err(e) => {
+                    error!("Backup encryption key loading failed, backups are disabled. Error: {:?}", e);
+                }
+            }
+        }
+
         tokio::select! {
             _ = self.handle_messages() => (),
             _ = quit_notification.recv() => (),
@@ -186,7 +217,10 @@ impl BackupLinkManagerServer {
     ) {
         match message {
             BackupLinkManagerMessage::ReplaceSourceConnection { handle_sender } => {
-                if self.connection_target.is_some() {
+                if self.encryption.is_none() {
+                    error!("Backup source connection rejected, backup encryption key is not available");
+                    let _ = handle_sender.send(None);
+                } else if self.connection_target.is_some() {
                     let (sender, receiver) = mpsc::channel(10);
                     self.connection_source = Some(BackupLinkConnectionSender { sender });
                     let _ = handle_sender.send(Some(BackupLinkConnectionReceiver {
@@ -212,21 +246,137 @@ impl BackupLinkManagerServer {
                 }
             }
             BackupLinkManagerMessage::ReceiveMessage { client_type, message } => {
+                let result = match client_type {
+                    BackupLinkClient::Source => self.encrypt_message(message),
+                    BackupLinkClient::Target => self.track_target_message(&message)
+                        .map(|()| vec![message]),
+                };
+                let messages = match result {
+                    Ok(messages) => messages,
+                    Err(e) => {
+                        error!("Backup message encryption failed, closing backup connections. Error: {:?}", e);
+                        self.connection_source = None;
+                        self.connection_target = None;
+                        return;
+                    }
+                };
+
                 let next_location = match client_type {
                     BackupLinkClient::Source => &mut self.connection_target,
                     BackupLinkClient::Target => &mut self.connection_source,
                 };
 
                 if let Some(sender) = next_location {
-                    match sender.sender.send(message).await {
-                        Ok(()) => (),
-                        Err(_) => (),
+                    for message in messages {
+                        match sender.sender.send(message).await {
+                            Ok(()) => (),
+                            Err(_) => break,
+                        }
                     }
                 }
             },
         }
     }
 
+    /// Content queries from the backup target define the content which
+    /// the next content query answer contains.
+    fn track_target_message(
+        &mut self,
+        message: &BackupMessage,
+    ) -> Result<(), BackupEncryptionError> {
+        let Some(state) = &mut self.encryption else {
+            return Err(BackupEncryptionError::KeyNotAvailable.report());
+        };
+
+        if message.header.message_type == BackupMessageType::ContentQuery {
+            let TargetToSourceMessage::ContentQuery { account_id, content_id } =
+                TargetToSourceMessage::try_from(message.clone())
+                    .map_err(|e| BackupEncryptionError::InvalidMessage.report().attach_printable(e))?
+            else {
+                return Err(BackupEncryptionError::InvalidMessage.report());
+            };
+            state.content_query = Some(BackupFileId::content(account_id, content_id));
+        }
+
+        Ok(())
+    }
+
+    /// Encrypt content and file backup data. Empty data is not encrypted
+    /// as it has special meaning in the backup protocol. The encrypted
+    /// final frame of a file backup is sent before the empty file backup
+    /// data message, so one message might be replaced with two messages.
+    fn encrypt_message(
+        &mut self,
+        mut message: BackupMessage,
+    ) -> Result<Vec<BackupMessage>, BackupEncryptionError> {
+        let Some(state) = &mut self.encryption else {
+            return Err(BackupEncryptionError::KeyNotAvailable.report());
+        };
+
+        let backup_session = message.header.backup_session.0;
+
+        match message.header.message_type {
+            BackupMessageType::StartBackupSession => {
+                state.content_query = None;
+                state.file_backup = None;
+            }
+            BackupMessageType::ContentQueryAnswer => {
+                let content_query = state.content_query.take();
+                if !message.data.is_empty() {
+                    let Some(file_id) = content_query else {
+                        return Err(BackupEncryptionError::InvalidMessage.report())
+                            .attach_printable("Content query answer without content query");
+                    };
+                    message.data = state.key.encrypt(&file_id, 0, true, &message.data)?;
+                }
+            }
+            BackupMessageType::StartFileBackup => {
+                let file_name = String::from_utf8(message.data.clone())
+                    .change_context(BackupEncryptionError::InvalidMessage)?;
+                state.file_backup = if file_name.is_empty() {
+                    None
+                } else {
+                    Some((BackupFileId::file(&file_name), 0))
+                };
+            }
+            BackupMessageType::FileBackupData => {
+                let SourceToTargetMessage::FileBackupData { package_number, data } =
+                    SourceToTargetMessage::try_from(message)
+                        .map_err(|e| BackupEncryptionError::InvalidMessage.report().attach_printable(e))?
+                else {
+                    return Err(BackupEncryptionError::InvalidMessage.report());
+                };
+                let Some((file_id, next_frame_index)) = &mut state.file_backup else {
+                    return Err(BackupEncryptionError::InvalidMessage.report())
+                        .attach_printable("File backup data without file backup start");
+                };
+
+                let final_frame = data.is_empty();
+                let data = state.key.encrypt(file_id, *next_frame_index, final_frame, &data)?;
+                *next_frame_index += 1;
+                let encrypted = SourceToTargetMessage::FileBackupData { package_number, data }
+                    .into_message(backup_session)
+                    .map_err(|e| BackupEncryptionError::InvalidMessage.report().attach_printable(e))?;
+
+                if !final_frame {
+                    return Ok(vec![encrypted]);
+                }
+
+                state.file_backup = None;
+                let end = SourceToTargetMessage::FileBackupData {
+                    package_number: package_number + Wrapping(1),
+                    data: vec![],
+                }
+                    .into_message(backup_session)
+                    .map_err(|e| BackupEncryptionError::InvalidMessage.report().attach_printable(e))?;
+                return Ok(vec![encrypted, end]);
+            }
+            _ => (),
+        }
+
+        Ok(vec![message])
+    }
+
     pub fn connection(&mut self, client_type: BackupLinkClient) -> &mut Option<BackupLinkConnectionSender> {
         match client_type {
             BackupLinkClient::Source => &mut self.connection_source,
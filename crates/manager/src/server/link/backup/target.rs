@@ -2,12 +2,13 @@
 use std::{num::Wrapping, sync::Arc, time::Duration};
 
 use backup::{DeleteOldFileBackups, SaveContentBackup, SaveFileBackup};
+use verification::BackupVerificationTask;
 use error_stack::{FutureExt, Result, ResultExt};
 use manager_api::{protocol::{ClientConnectionReadSend, ClientConnectionWriteSend, ConnectionUtilsRead, ConnectionUtilsWrite}, ClientConfig, ManagerClient};
 use manager_config::{file::BackupLinkConfigTarget, Config};
-use manager_model::{AccountAndContent, BackupMessage, BackupMessageType, SourceToTargetMessage, TargetToSourceMessage};
+use manager_model::{AccountAndContent, BackupMessage, BackupMessageType, BackupVerificationStatus, SourceToTargetMessage, TargetToSourceMessage};
 use simple_backend_utils::{ContextExt, IntoReportFromString};
-use tokio::{sync::mpsc, task::JoinHandle};
+use tokio::{sync::{mpsc, watch, Mutex}, task::JoinHandle};
 use tracing::{error, info, warn};
 
 use crate::server::{app::S, ServerQuitWatcher};
@@ -15,6 +16,9 @@ use crate::server::{app::S, ServerQuitWatcher};
 use crate::api::GetConfig;
 
 mod backup;
+mod manifest;
+pub mod restore;
+mod verification;
 
 #[derive(thiserror::Error, Debug)]
 enum BackupTargetError {
@@ -68,6 +72,9 @@ enum BackupTargetError {
 
     #[error("Time related error")]
     Time,
+
+    #[error("Manifest error")]
+    Manifest,
 }
 
 #[derive(Debug)]
@@ -88,6 +95,7 @@ impl BackupLinkManagerTargetQuitHandle {
 
 pub struct BackupLinkManagerTarget {
     state: S,
+    backup_dir_lock: Arc<Mutex<()>>,
 }
 
 impl BackupLinkManagerTarget {
@@ -97,6 +105,7 @@ impl BackupLinkManagerTarget {
     ) -> BackupLinkManagerTargetQuitHandle {
         let manager = Self {
             state: state.clone(),
+            backup_dir_lock: Arc::new(Mutex::new(())),
         };
 
         let task = tokio::spawn(manager.run(quit_notification.resubscribe()));
@@ -108,8 +117,15 @@ impl BackupLinkManagerTarget {
 
     async fn run(self, mut quit_notification: ServerQuitWatcher) {
         if let Some(config) = self.state.config().backup_link().target.clone() {
+            let (status_sender, status_receiver) = watch::channel(None);
+            let verification = BackupVerificationTask::new(
+                self.state.clone(),
+                self.backup_dir_lock.clone(),
+                status_sender,
+            );
             tokio::select! {
-                _ = self.create_connection_loop(config) => (),
+                _ = self.create_connection_loop(config, status_receiver) => (),
+                _ = verification.run() => (),
                 _ = quit_notification.recv() => (),
             }
         } else {
@@ -120,10 +136,11 @@ impl BackupLinkManagerTarget {
     async fn create_connection_loop(
         mut self,
         config: BackupLinkConfigTarget,
+        verification_status: watch::Receiver<Option<BackupVerificationStatus>>,
     ) {
         let mut retry_wait_seconds = 2;
         loop {
-            match self.create_connection(&config).await {
+            match self.create_connection(&config, verification_status.clone()).await {
                 Ok(()) => {
                     info!("Backup target link disconnected, retrying connection in {} seconds", retry_wait_seconds);
                 }
@@ -140,6 +157,7 @@ impl BackupLinkManagerTarget {
     async fn create_connection(
         &mut self,
         config: &BackupLinkConfigTarget,
+        verification_status: watch::Receiver<Option<BackupVerificationStatus>>,
     ) -> Result<(), BackupTargetError> {
         let client = ManagerClient::connect(
             ClientConfig {
@@ -161,6 +179,7 @@ impl BackupLinkManagerTarget {
 
         tokio::select! {
             r = self.send_connection_tests(sender.clone()) => r,
+            r = self.send_verification_status(sender.clone(), verification_status) => r,
             r = self.handle_reading(reader, sender) => r,
             r = self.handle_writing(writer, receiver) => r,
         }
@@ -178,6 +197,26 @@ impl BackupLinkManagerTarget {
         }
     }
 
+    /// Send latest backup verification status when connection is
+    /// created and when the status changes.
+    async fn send_verification_status(
+        &self,
+        sender: mpsc::Sender<BackupMessage>,
+        mut verification_status: watch::Receiver<Option<BackupVerificationStatus>>,
+    ) -> Result<(), BackupTargetError> {
+        loop {
+            let status = *verification_status.borrow_and_update();
+            if let Some(status) = status {
+                sender.send(TargetToSourceMessage::BackupVerificationStatus { status }.into_message(0))
+                    .await
+                    .change_context(BackupTargetError::BrokenMessageChannel)?;
+            }
+            verification_status.changed()
+                .await
+                .change_context(BackupTargetError::BrokenMessageChannel)?;
+        }
+    }
+
     async fn handle_reading(
         &self,
         mut reader: Box<dyn ClientConnectionReadSend>,
@@ -199,6 +238,7 @@ impl BackupLinkManagerTarget {
                 BackupMessageType::StartBackupSession => {
                     let state = BackupTargetState::new(
                         self.state.config_arc().clone(),
+                        self.backup_dir_lock.clone(),
                         sender.clone(),
                         m.header.backup_session.0,
                     );
@@ -247,11 +287,13 @@ struct BackupTargetState {
 impl BackupTargetState {
     fn new(
         config: Arc<Config>,
+        backup_dir_lock: Arc<Mutex<()>>,
         sender: mpsc::Sender<BackupMessage>,
         current_backup_session: u32,
     ) -> Self {
         let (source_sender, source_receiver) = mpsc::channel(10);
         tokio::task::spawn(async move {
+            let _lock = backup_dir_lock.lock().await;
             BackupSessionTaskTarget::new(config, sender, source_receiver, current_backup_session).run().await;
         });
         Self {
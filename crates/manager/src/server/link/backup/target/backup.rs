@@ -0,0 +1,428 @@
+use std::{collections::HashSet, num::Wrapping, path::{Path, PathBuf}, sync::Arc, time::SystemTime};
+
+use chrono::Utc;
+use manager_config::Config;
+use sha2::{Digest, Sha256};
+use simple_backend_model::UnixTime;
+use simple_backend_utils::{file::overwrite_and_remove_if_exists, ContextExt, IntoReportFromString, UuidBase64Url};
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+use super::{manifest::{file_backup_manifest_path, file_sha256_hex, sha256_hex, Sha256Manifest, MANIFEST_FILE_EXTENSION, MANIFEST_FILE_NAME}, BackupTargetError};
+
+use error_stack::{Result, ResultExt};
+
+pub const BACKUP_DIR_NAME: &str = "backup";
+pub const CONTENT_DIR_NAME: &str = "content";
+pub const FILES_DIR_NAME: &str = "files";
+
+/// File backup name is `backup_{backup_name}_{time}`.
+pub const FILE_BACKUP_NAME_PREFIX: &str = "backup_";
+pub const FILE_BACKUP_TIME_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+
+const TMP_FILE: &str = "file.tmp";
+
+struct BackupDirUtils<'a> {
+    config: &'a Config,
+}
+
+impl<'a> BackupDirUtils<'a> {
+    fn new(config: &'a Config) -> Self {
+        Self {
+            config,
+        }
+    }
+
+    fn create_dir_if_needed(&self, existing_dir: &Path, dir_name: &str) -> PathBuf {
+        let dir = existing_dir.join(dir_name);
+
+        if !Path::new(&dir).exists() {
+            match std::fs::create_dir(&dir) {
+                Ok(()) => (),
+                Err(e) => {
+                    warn!(
+                        "Directory creation failed. Error: {:?}, Directory: {}",
+                        e,
+                        dir.display()
+                    );
+                }
+            }
+        }
+
+        dir
+    }
+
+    fn create_backup_dir_if_needed(&self) -> PathBuf {
+        self.create_dir_if_needed(self.config.storage_dir(), BACKUP_DIR_NAME)
+    }
+
+    fn create_content_dir_if_needed(&self) -> PathBuf {
+        self.create_dir_if_needed(&self.create_backup_dir_if_needed(), CONTENT_DIR_NAME)
+    }
+
+    fn create_account_content_dir_if_needed(&self, account: UuidBase64Url) -> PathBuf {
+        self.create_dir_if_needed(&self.create_content_dir_if_needed(), &account.to_string())
+    }
+
+    fn content_file_path(&self, account: UuidBase64Url, content: UuidBase64Url) -> PathBuf {
+        self.create_account_content_dir_if_needed(account).join(content.to_string())
+    }
+
+    fn content_manifest_path(&self, account: UuidBase64Url) -> PathBuf {
+        self.create_account_content_dir_if_needed(account).join(MANIFEST_FILE_NAME)
+    }
+
+    fn create_files_dir_if_needed(&self) -> PathBuf {
+        self.create_dir_if_needed(&self.create_backup_dir_if_needed(), FILES_DIR_NAME)
+    }
+
+    fn file_path(&self, file: &str) -> PathBuf {
+        self.create_files_dir_if_needed().join(file)
+    }
+
+    async fn remove_tmp_file_and_get_tmp_file_path(&self) -> Result<PathBuf, BackupTargetError> {
+        let path = self.create_backup_dir_if_needed().join(TMP_FILE);
+        if path.exists() {
+            overwrite_and_remove_if_exists(&path)
+                .await
+                .change_context(BackupTargetError::FileOverwritingAndRemovingFailed)?;
+        }
+        Ok(path)
+    }
+}
+
+
+pub struct SaveContentBackup {
+    config: Arc<Config>,
+    initial_accounts: HashSet<UuidBase64Url>,
+}
+
+impl SaveContentBackup {
+    pub async fn new(
+        config: Arc<Config>,
+    ) -> Result<Self, BackupTargetError> {
+        let dir = BackupDirUtils::new(&config).create_content_dir_if_needed();
+
+        let mut initial_accounts = HashSet::new();
+
+        let mut iterator = tokio::fs::read_dir(dir)
+            .await
+            .change_context(BackupTargetError::Read)?;
+
+        while let Some(e) = iterator.next_entry().await.change_context(BackupTargetError::Read)? {
+            if !e.path().is_dir() {
+                continue;
+            }
+
+            let name = e.file_name();
+            let Some(text) = name.to_str() else {
+                return Err(BackupTargetError::InvalidAccountId.report());
+            };
+
+            let account_id = UuidBase64Url::from_text(text)
+                .into_error_string(BackupTargetError::InvalidAccountId)?;
+
+            initial_accounts.insert(account_id);
+        }
+
+        Ok(Self {
+            config,
+            initial_accounts,
+        })
+    }
+
+    pub async fn update_account_content_backup(&self, account: UuidBase64Url) -> Result<UpdateAccountContent, BackupTargetError> {
+        let dir = BackupDirUtils::new(&self.config).create_account_content_dir_if_needed(account);
+
+        let mut initial_content = HashSet::new();
+
+        let mut iterator = tokio::fs::read_dir(dir)
+            .await
+            .change_context(BackupTargetError::Read)?;
+
+        while let Some(e) = iterator.next_entry().await.change_context(BackupTargetError::Read)? {
+            if !e.path().is_file() {
+                continue;
+            }
+
+            let name = e.file_name();
+            let Some(text) = name.to_str() else {
+                return Err(BackupTargetError::InvalidContentId.report());
+            };
+
+            if text.starts_with(MANIFEST_FILE_NAME) {
+                continue;
+            }
+
+            let content_id = UuidBase64Url::from_text(text)
+                .into_error_string(BackupTargetError::InvalidContentId)?;
+
+            initial_content.insert(content_id);
+        }
+
+        let manifest_path = BackupDirUtils::new(&self.config).content_manifest_path(account);
+        let manifest = Sha256Manifest::load(&manifest_path)
+            .await
+            .change_context(BackupTargetError::Manifest)?
+            .unwrap_or_default();
+
+        Ok(UpdateAccountContent {
+            config: self.config.clone(),
+            account,
+            existing_content: initial_content.clone(),
+            initial_content,
+            manifest,
+        })
+    }
+
+    pub fn mark_as_still_existing(&mut self, account: UuidBase64Url) {
+        self.initial_accounts.remove(&account);
+    }
+
+    /// Remove accounts which does not exist anymore
+    pub async fn finalize(self) -> Result<(), BackupTargetError> {
+        for &a in &self.initial_accounts {
+            let update = self.update_account_content_backup(a).await?;
+            update.finalize().await?;
+            let dir = BackupDirUtils::new(&self.config).create_account_content_dir_if_needed(a);
+            tokio::fs::remove_dir(&dir)
+                .await
+                .change_context(BackupTargetError::RemoveDir)
+                .attach_printable_lazy(move || dir.to_string_lossy().to_string())?;
+        }
+
+        Ok(())
+    }
+}
+
+
+pub struct UpdateAccountContent {
+    config: Arc<Config>,
+    account: UuidBase64Url,
+    /// Content which is not yet marked as still existing
+    initial_content: HashSet<UuidBase64Url>,
+    /// All content which existed when the update started
+    existing_content: HashSet<UuidBase64Url>,
+    manifest: Sha256Manifest,
+}
+
+
+impl UpdateAccountContent {
+    pub fn exists(&self, content: UuidBase64Url) -> bool {
+        BackupDirUtils::new(&self.config)
+            .content_file_path(self.account, content)
+            .exists()
+    }
+
+    pub fn mark_as_still_existing(&mut self, content: UuidBase64Url) {
+        self.initial_content.remove(&content);
+    }
+
+    pub async fn new_content(&mut self, content: UuidBase64Url, data: Vec<u8>) -> Result<(), BackupTargetError> {
+        let f = BackupDirUtils::new(&self.config)
+            .content_file_path(self.account, content);
+        let hash = sha256_hex(&data);
+        tokio::fs::write(&f, data)
+            .await
+            .change_context( BackupTargetError::Write)?;
+        self.manifest.insert(content.to_string(), hash);
+        Ok(())
+    }
+
+    /// Update manifest and remove content which does not exist anymore.
+    pub async fn finalize(mut self) -> Result<(), BackupTargetError> {
+        for c in &self.initial_content {
+            self.manifest.remove(&c.to_string());
+        }
+
+        // Content saved before manifests were added
+        for c in self.existing_content.difference(&self.initial_content) {
+            let name = c.to_string();
+            if !self.manifest.contains(&name) {
+                let f = BackupDirUtils::new(&self.config)
+                    .content_file_path(self.account, *c);
+                let hash = file_sha256_hex(&f)
+                    .await
+                    .change_context(BackupTargetError::Manifest)?;
+                self.manifest.insert(name, hash);
+            }
+        }
+
+        let manifest_path = BackupDirUtils::new(&self.config).content_manifest_path(self.account);
+        if self.manifest.is_empty() {
+            if manifest_path.exists() {
+                tokio::fs::remove_file(&manifest_path)
+                    .await
+                    .change_context(BackupTargetError::Manifest)?;
+            }
+        } else {
+            self.manifest.save(&manifest_path)
+                .await
+                .change_context(BackupTargetError::Manifest)?;
+        }
+
+        for c in self.initial_content {
+            let f = BackupDirUtils::new(&self.config)
+                .content_file_path(self.account, c);
+            overwrite_and_remove_if_exists(&f)
+                .await
+                .change_context(BackupTargetError::FileOverwritingAndRemovingFailed)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct SaveFileBackup {
+    expected_packet_number: Wrapping<u32>,
+    target_path: PathBuf,
+    tmp_file_path: PathBuf,
+    tmp_file: tokio::fs::File,
+    hasher: Sha256,
+}
+
+impl SaveFileBackup {
+    pub async fn new(
+        config: Arc<Config>,
+        backup_name: &str,
+    ) -> Result<Self, BackupTargetError> {
+        let tmp_file_path = BackupDirUtils::new(&config)
+            .remove_tmp_file_and_get_tmp_file_path()
+            .await?;
+        let tmp_file = tokio::fs::File::create(&tmp_file_path)
+            .await
+            .change_context(BackupTargetError::Write)?;
+
+        let time = Utc::now().format(FILE_BACKUP_TIME_FORMAT);
+        let name = format!("{}{}_{}", FILE_BACKUP_NAME_PREFIX, backup_name, time);
+        let target_path = BackupDirUtils::new(&config)
+            .file_path(&name);
+
+        if target_path.exists() {
+            return Err(BackupTargetError::FileBackupAlreadyExists.report())
+                .attach_printable(name);
+        }
+
+        Ok(Self {
+            expected_packet_number: Wrapping(0),
+            target_path,
+            tmp_file_path,
+            tmp_file,
+            hasher: Sha256::new(),
+        })
+    }
+
+    pub async fn save_packet(
+        &mut self,
+        packet_number: Wrapping<u32>,
+        data: Vec<u8>,
+    ) -> Result<(), BackupTargetError> {
+        if self.expected_packet_number != packet_number {
+            return Err(BackupTargetError::FileBackupPacketNumberMismatch.report())
+                .attach_printable(format!("expected: {}, actual: {}", self.expected_packet_number, packet_number))
+        }
+
+        self.tmp_file.write_all(&data)
+            .await
+            .change_context(BackupTargetError::Write)?;
+        self.hasher.update(&data);
+
+        self.expected_packet_number += 1;
+
+        Ok(())
+    }
+
+    pub async fn finalize(
+        mut self,
+        packet_number: Wrapping<u32>,
+    ) -> Result<(), BackupTargetError> {
+        if self.expected_packet_number != packet_number {
+            return Err(BackupTargetError::FileBackupPacketNumberMismatch.report())
+                .attach_printable(format!("expected: {}, actual: {}", self.expected_packet_number, packet_number))
+        }
+
+        self.tmp_file
+            .flush()
+            .await
+            .change_context(BackupTargetError::FileFlush)?;
+
+        self.tmp_file
+            .sync_all()
+            .await
+            .change_context(BackupTargetError::FileSync)?;
+
+        drop(self.tmp_file);
+
+        tokio::fs::rename(self.tmp_file_path, &self.target_path)
+            .await
+            .change_context(BackupTargetError::FileRename)?;
+
+        let name = self.target_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut manifest = Sha256Manifest::default();
+        manifest.insert(name, base16ct::lower::encode_string(&self.hasher.finalize()));
+        manifest.save(&file_backup_manifest_path(&self.target_path))
+            .await
+            .change_context(BackupTargetError::Manifest)?;
+
+        Ok(())
+    }
+}
+
+pub struct DeleteOldFileBackups;
+
+impl DeleteOldFileBackups {
+    /// Returns how many files were deleted.
+    pub async fn run(config: Arc<Config>) -> Result<u64, BackupTargetError> {
+        let dir = BackupDirUtils::new(&config).create_files_dir_if_needed();
+
+        let mut iterator = tokio::fs::read_dir(dir)
+            .await
+            .change_context(BackupTargetError::Read)?;
+
+        let current_time = TryInto::<u64>::try_into(UnixTime::current_time().ut)
+            .change_context(BackupTargetError::Time)?;
+
+        let mut deleted_count = 0;
+
+        while let Some(e) = iterator.next_entry().await.change_context(BackupTargetError::Read)? {
+            if !e.path().is_file() {
+                continue;
+            }
+
+            let name = e.file_name();
+            let Some(text) = name.to_str() else {
+                return Err(BackupTargetError::InvalidFileName.report());
+            };
+
+            if !text.starts_with(FILE_BACKUP_NAME_PREFIX) || text.ends_with(MANIFEST_FILE_EXTENSION) {
+                continue;
+            }
+
+            let unix_time_seconds = e.metadata()
+                .await
+                .change_context(BackupTargetError::Read)?
+                .created()
+                .change_context(BackupTargetError::Read)?
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .change_context(BackupTargetError::Read)?
+                .as_secs();
+
+            let deletion_allowed_seconds = unix_time_seconds + Into::<u64>::into(config.backup_link().file_backup_retention_time().seconds);
+
+            if current_time >= deletion_allowed_seconds {
+                overwrite_and_remove_if_exists(&e.path())
+                    .await
+                    .change_context(BackupTargetError::FileOverwritingAndRemovingFailed)?;
+                overwrite_and_remove_if_exists(&file_backup_manifest_path(&e.path()))
+                    .await
+                    .change_context(BackupTargetError::FileOverwritingAndRemovingFailed)?;
+                deleted_count += 1;
+            }
+        }
+
+        Ok(deleted_count)
+    }
+}
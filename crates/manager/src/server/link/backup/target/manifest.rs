@@ -0,0 +1,173 @@
+//! SHA-256 manifests for backup files
+//!
+//! Manifest format is the same as `sha256sum` output, so the backup
+//! files can be also checked using `sha256sum --check`.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use error_stack::{Result, ResultExt};
+use sha2::{Digest, Sha256};
+use simple_backend_utils::ContextExt;
+
+/// Manifest file name for account content directory.
+pub const MANIFEST_FILE_NAME: &str = "manifest.sha256";
+/// File backup manifest is saved next to the file backup
+/// with this extension.
+pub const MANIFEST_FILE_EXTENSION: &str = ".sha256";
+
+#[derive(thiserror::Error, Debug)]
+pub enum ManifestError {
+    #[error("Reading error")]
+    Read,
+
+    #[error("Writing error")]
+    Write,
+
+    #[error("Invalid manifest line")]
+    InvalidLine,
+
+    #[error("Blocking task failed")]
+    BlockingTaskFailed,
+}
+
+/// File names and lowercase hex encoded SHA-256 hashes.
+#[derive(Debug, Default)]
+pub struct Sha256Manifest {
+    hashes: BTreeMap<String, String>,
+}
+
+impl Sha256Manifest {
+    /// Returns `None` if the file does not exist.
+    pub async fn load(path: &Path) -> Result<Option<Self>, ManifestError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let text = tokio::fs::read_to_string(path)
+            .await
+            .change_context(ManifestError::Read)?;
+        Self::parse(&text)
+            .attach_printable_lazy(|| path.display().to_string())
+            .map(Some)
+    }
+
+    fn parse(text: &str) -> Result<Self, ManifestError> {
+        let mut hashes = BTreeMap::new();
+        for line in text.lines().filter(|line| !line.is_empty()) {
+            let Some((hash, name)) = line.split_once("  ") else {
+                return Err(ManifestError::InvalidLine.report()).attach_printable(line.to_string());
+            };
+            hashes.insert(name.to_string(), hash.to_string());
+        }
+        Ok(Self { hashes })
+    }
+
+    /// Write the manifest to temporary file first, so the
+    /// manifest is always complete.
+    pub async fn save(&self, path: &Path) -> Result<(), ManifestError> {
+        let mut tmp_path = path.to_path_buf().into_os_string();
+        tmp_path.push(".tmp");
+        tokio::fs::write(&tmp_path, self.to_text())
+            .await
+            .change_context(ManifestError::Write)?;
+        tokio::fs::rename(&tmp_path, path)
+            .await
+            .change_context(ManifestError::Write)
+    }
+
+    fn to_text(&self) -> String {
+        self.hashes
+            .iter()
+            .map(|(name, hash)| format!("{}  {}\n", hash, name))
+            .collect()
+    }
+
+    pub fn insert(&mut self, name: String, hash: String) {
+        self.hashes.insert(name, hash);
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.hashes.remove(name);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.hashes.contains_key(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Iterate file names and hashes.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.hashes
+            .iter()
+            .map(|(name, hash)| (name.as_str(), hash.as_str()))
+    }
+}
+
+/// Manifest path for file backup.
+pub fn file_backup_manifest_path(file_backup: &Path) -> PathBuf {
+    let mut path = file_backup.to_path_buf().into_os_string();
+    path.push(MANIFEST_FILE_EXTENSION);
+    path.into()
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    base16ct::lower::encode_string(&Sha256::digest(data))
+}
+
+pub async fn file_sha256_hex(path: &Path) -> Result<String, ManifestError> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut file = std::fs::File::open(path).change_context(ManifestError::Read)?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut file, &mut hasher).change_context(ManifestError::Read)?;
+        Ok(base16ct::lower::encode_string(&hasher.finalize()))
+    })
+    .await
+    .change_context(ManifestError::BlockingTaskFailed)?
+}
+
+/// Returns names of files which are missing or which do not
+/// match the hash in the manifest.
+pub async fn find_corrupted_files(
+    dir: &Path,
+    manifest: &Sha256Manifest,
+) -> Result<Vec<String>, ManifestError> {
+    let mut corrupted = vec![];
+    for (name, hash) in manifest.iter() {
+        let path = dir.join(name);
+        if !path.is_file() || file_sha256_hex(&path).await? != hash {
+            corrupted.push(name.to_string());
+        }
+    }
+    Ok(corrupted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_uses_sha256sum_format() {
+        let mut manifest = Sha256Manifest::default();
+        manifest.insert("b".to_string(), sha256_hex(b"b"));
+        manifest.insert("a".to_string(), sha256_hex(b"a"));
+
+        let text = manifest.to_text();
+        assert_eq!(
+            text,
+            "ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb  a\n\
+             3e23e8160039594a33894f6564e1b1348bbd7a0088d42c4acb73eeaed59c009d  b\n"
+        );
+
+        let parsed = Sha256Manifest::parse(&text).unwrap();
+        assert!(parsed.contains("a"));
+        assert!(parsed.contains("b"));
+        assert!(Sha256Manifest::parse("invalid").is_err());
+    }
+}
@@ -0,0 +1,715 @@
+//! Restore backend data directory from backup link target's
+//! backup directory.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+
+use chrono::{NaiveDate, NaiveDateTime};
+use error_stack::{Result, ResultExt};
+use manager_model::BackupRestoreInfo;
+use server_data::file::utils::CONTENT_DIR_NAME;
+use simple_backend_database::{
+    data::{FILE_DIR_NAME, SQLITE_DIR_NAME},
+    diesel_db::sqlite_integrity_check,
+};
+use simple_backend_utils::{ContextExt, UuidBase64Url};
+use tokio::io::AsyncReadExt;
+
+pub use super::backup::BACKUP_DIR_NAME;
+use super::{
+    backup::{
+        CONTENT_DIR_NAME as BACKUP_CONTENT_DIR_NAME, FILES_DIR_NAME as BACKUP_FILES_DIR_NAME,
+        FILE_BACKUP_NAME_PREFIX, FILE_BACKUP_TIME_FORMAT,
+    },
+    manifest::{
+        file_backup_manifest_path, find_corrupted_files, Sha256Manifest, MANIFEST_FILE_EXTENSION,
+        MANIFEST_FILE_NAME,
+    },
+};
+use crate::server::link::backup::encryption::{
+    is_encrypted_file, BackupEncryptionKey, BackupFileId,
+};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const SQLITE_FILE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+#[derive(thiserror::Error, Debug)]
+pub enum BackupRestoreError {
+    #[error("Reading error")]
+    Read,
+
+    #[error("Writing error")]
+    Write,
+
+    #[error("Data directory is not empty")]
+    DataDirNotEmpty,
+
+    #[error("Invalid date")]
+    InvalidDate,
+
+    #[error("Database backups not found")]
+    DatabaseBackupsNotFound,
+
+    #[error("Invalid database backup")]
+    InvalidDatabaseBackup,
+
+    #[error("Database integrity check failed")]
+    DatabaseIntegrityCheck,
+
+    #[error("Invalid account ID")]
+    InvalidAccountId,
+
+    #[error("Invalid content ID")]
+    InvalidContentId,
+
+    #[error("Invalid content file")]
+    InvalidContentFile,
+
+    #[error("Manifest error")]
+    Manifest,
+
+    #[error("File does not match manifest")]
+    CorruptedFile,
+
+    #[error("Backup is encrypted but encryption key is not set")]
+    EncryptionKeyRequired,
+
+    #[error("Decryption failed")]
+    Decrypt,
+}
+
+struct DatabaseBackup {
+    database_name: String,
+    time: NaiveDateTime,
+    path: PathBuf,
+}
+
+struct AccountContentBackup {
+    account_id: UuidBase64Url,
+    content: Vec<(UuidBase64Url, PathBuf)>,
+}
+
+/// Verify all backup files and copy the selected database backups and
+/// all content files to a new backend data directory. Encrypted files
+/// are decrypted using `encryption_key`.
+///
+/// If `date` is `None` the database backups are selected from the latest
+/// date which has backups for all databases.
+pub async fn restore_backup(
+    backup_dir: &Path,
+    data_dir: &Path,
+    date: Option<&str>,
+    encryption_key: Option<&BackupEncryptionKey>,
+) -> Result<BackupRestoreInfo, BackupRestoreError> {
+    let date = date
+        .map(|date| NaiveDate::parse_from_str(date, DATE_FORMAT))
+        .transpose()
+        .change_context(BackupRestoreError::InvalidDate)?;
+
+    if !dir_is_empty_or_missing(data_dir).await? {
+        return Err(BackupRestoreError::DataDirNotEmpty.report())
+            .attach_printable(data_dir.display().to_string());
+    }
+
+    let files_dir = backup_dir.join(BACKUP_FILES_DIR_NAME);
+    let databases = select_database_backups(&files_dir, date).await?;
+    let mut warnings = vec![];
+    for db in &databases {
+        let manifest = Sha256Manifest::load(&file_backup_manifest_path(&db.path))
+            .await
+            .change_context(BackupRestoreError::Manifest)?;
+        match manifest {
+            Some(manifest) => {
+                verify_manifest(&files_dir, &manifest).await?;
+                if !manifest.contains(&file_name(&db.path)) {
+                    warnings.push(format!(
+                        "Database backup is not in manifest: {}",
+                        db.path.display()
+                    ));
+                }
+            }
+            None => warnings.push(format!(
+                "Database backup manifest is missing: {}",
+                db.path.display()
+            )),
+        }
+        if is_encrypted(&db.path, encryption_key).await? {
+            // Header is checked after decryption
+            continue;
+        }
+        verify_database_backup(&db.path).await?;
+    }
+    let content = verify_content_backup(
+        &backup_dir.join(BACKUP_CONTENT_DIR_NAME),
+        encryption_key,
+        &mut warnings,
+    )
+    .await?;
+
+    let mut info = BackupRestoreInfo {
+        database_backups: vec![],
+        accounts: 0,
+        content: 0,
+        warnings,
+    };
+
+    for db in databases {
+        let db_dir = data_dir.join(SQLITE_DIR_NAME).join(&db.database_name);
+        tokio::fs::create_dir_all(&db_dir)
+            .await
+            .change_context(BackupRestoreError::Write)?;
+        let db_file = db_dir.join(format!("{}.db", db.database_name));
+        copy_backup_file(
+            &db.path,
+            &db_file,
+            &BackupFileId::file(&db.database_name),
+            encryption_key,
+        )
+        .await?;
+        verify_database_backup(&db_file).await?;
+        verify_database_integrity(&db_file).await?;
+        info.database_backups.push(file_name(&db.path));
+    }
+
+    let files_dir = data_dir.join(FILE_DIR_NAME);
+    tokio::fs::create_dir_all(&files_dir)
+        .await
+        .change_context(BackupRestoreError::Write)?;
+
+    for account in content {
+        let content_dir = files_dir
+            .join(account.account_id.to_string())
+            .join(CONTENT_DIR_NAME);
+        tokio::fs::create_dir_all(&content_dir)
+            .await
+            .change_context(BackupRestoreError::Write)?;
+        for (content_id, path) in account.content {
+            copy_backup_file(
+                &path,
+                &content_dir.join(content_id.to_string()),
+                &BackupFileId::content(account.account_id, content_id),
+                encryption_key,
+            )
+            .await?;
+            info.content += 1;
+        }
+        info.accounts += 1;
+    }
+
+    Ok(info)
+}
+
+async fn copy_backup_file(
+    source: &Path,
+    target: &Path,
+    file_id: &BackupFileId,
+    encryption_key: Option<&BackupEncryptionKey>,
+) -> Result<(), BackupRestoreError> {
+    let encrypted = is_encrypted(source, encryption_key).await?;
+    match encryption_key {
+        Some(key) if encrypted => key
+            .decrypt_file(source, target, file_id)
+            .await
+            .change_context(BackupRestoreError::Decrypt)
+            .attach_printable_lazy(|| source.display().to_string()),
+        _ => tokio::fs::copy(source, target)
+            .await
+            .change_context(BackupRestoreError::Write)
+            .map(|_| ()),
+    }
+}
+
+/// Returns error if the file is encrypted and the key is not available.
+async fn is_encrypted(
+    path: &Path,
+    encryption_key: Option<&BackupEncryptionKey>,
+) -> Result<bool, BackupRestoreError> {
+    let encrypted = is_encrypted_file(path)
+        .await
+        .change_context(BackupRestoreError::Read)?;
+    if encrypted && encryption_key.is_none() {
+        return Err(BackupRestoreError::EncryptionKeyRequired.report())
+            .attach_printable(path.display().to_string());
+    }
+    Ok(encrypted)
+}
+
+async fn verify_manifest(dir: &Path, manifest: &Sha256Manifest) -> Result<(), BackupRestoreError> {
+    let corrupted = find_corrupted_files(dir, manifest)
+        .await
+        .change_context(BackupRestoreError::Manifest)?;
+    if !corrupted.is_empty() {
+        return Err(BackupRestoreError::CorruptedFile.report())
+            .attach_printable(corrupted.join(", "));
+    }
+    Ok(())
+}
+
+async fn dir_is_empty_or_missing(dir: &Path) -> Result<bool, BackupRestoreError> {
+    if !dir.exists() {
+        return Ok(true);
+    }
+
+    let mut iterator = tokio::fs::read_dir(dir)
+        .await
+        .change_context(BackupRestoreError::Read)?;
+    let entry = iterator
+        .next_entry()
+        .await
+        .change_context(BackupRestoreError::Read)?;
+    Ok(entry.is_none())
+}
+
+/// Select the latest backup for every database from the latest date
+/// which has backups for all databases. If `date` is set, backups are
+/// selected from that date and all databases must have a backup from it.
+async fn select_database_backups(
+    files_dir: &Path,
+    date: Option<NaiveDate>,
+) -> Result<Vec<DatabaseBackup>, BackupRestoreError> {
+    let mut database_names = BTreeSet::<String>::new();
+    let mut dates = BTreeMap::<NaiveDate, HashMap<String, DatabaseBackup>>::new();
+
+    let mut iterator = tokio::fs::read_dir(files_dir)
+        .await
+        .change_context(BackupRestoreError::Read)?;
+
+    while let Some(e) = iterator
+        .next_entry()
+        .await
+        .change_context(BackupRestoreError::Read)?
+    {
+        if !e.path().is_file() {
+            continue;
+        }
+
+        let name = e.file_name();
+        if name.to_string_lossy().ends_with(MANIFEST_FILE_EXTENSION) {
+            continue;
+        }
+
+        let Some((database_name, time)) = name.to_str().and_then(parse_file_backup_name) else {
+            continue;
+        };
+
+        database_names.insert(database_name.to_string());
+
+        let selected = dates.entry(time.date()).or_default();
+        let newer_exists = selected
+            .get(database_name)
+            .is_some_and(|current| current.time >= time);
+        if !newer_exists {
+            selected.insert(
+                database_name.to_string(),
+                DatabaseBackup {
+                    database_name: database_name.to_string(),
+                    time,
+                    path: e.path(),
+                },
+            );
+        }
+    }
+
+    if database_names.is_empty() {
+        return Err(BackupRestoreError::DatabaseBackupsNotFound.report());
+    }
+
+    let selected = if let Some(date) = date {
+        let selected = dates.remove(&date).unwrap_or_default();
+        let missing: Vec<&str> = database_names
+            .iter()
+            .filter(|name| !selected.contains_key(*name))
+            .map(|name| name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            return Err(BackupRestoreError::DatabaseBackupsNotFound.report())
+                .attach_printable(format!("Missing backups: {}", missing.join(", ")));
+        }
+        selected
+    } else {
+        dates
+            .into_values()
+            .rev()
+            .find(|selected| selected.len() == database_names.len())
+            .ok_or(BackupRestoreError::DatabaseBackupsNotFound.report())
+            .attach_printable("No date has backups for all databases")?
+    };
+
+    let mut selected: Vec<DatabaseBackup> = selected.into_values().collect();
+    selected.sort_by(|a, b| a.database_name.cmp(&b.database_name));
+    Ok(selected)
+}
+
+/// Parse file backup name `backup_{database_name}_{time}`.
+fn parse_file_backup_name(name: &str) -> Option<(&str, NaiveDateTime)> {
+    let name = name.strip_prefix(FILE_BACKUP_NAME_PREFIX)?;
+    // Time contains one underscore
+    let database_name = name.rsplitn(3, '_').nth(2)?;
+    if database_name.is_empty() {
+        return None;
+    }
+    let time = &name[database_name.len() + 1..];
+    let time = NaiveDateTime::parse_from_str(time, FILE_BACKUP_TIME_FORMAT).ok()?;
+    Some((database_name, time))
+}
+
+async fn verify_database_backup(path: &Path) -> Result<(), BackupRestoreError> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .change_context(BackupRestoreError::Read)?;
+    let mut header = [0u8; SQLITE_FILE_HEADER.len()];
+    file.read_exact(&mut header)
+        .await
+        .change_context(BackupRestoreError::InvalidDatabaseBackup)
+        .attach_printable_lazy(|| path.display().to_string())?;
+
+    if &header != SQLITE_FILE_HEADER {
+        return Err(BackupRestoreError::InvalidDatabaseBackup.report())
+            .attach_printable(path.display().to_string());
+    }
+
+    Ok(())
+}
+
+async fn verify_database_integrity(path: &Path) -> Result<(), BackupRestoreError> {
+    let db_file = path.to_path_buf();
+    let errors = tokio::task::spawn_blocking(move || sqlite_integrity_check(&db_file))
+        .await
+        .change_context(BackupRestoreError::DatabaseIntegrityCheck)?
+        .change_context(BackupRestoreError::DatabaseIntegrityCheck)
+        .attach_printable_lazy(|| path.display().to_string())?;
+
+    if !errors.is_empty() {
+        return Err(BackupRestoreError::DatabaseIntegrityCheck.report())
+            .attach_printable(path.display().to_string())
+            .attach_printable(errors.join("\n"));
+    }
+
+    Ok(())
+}
+
+/// Check that account and content IDs are valid,
+/// content files are not empty and content files match the manifest.
+///
+/// Missing manifests and content files which are not in the manifest
+/// are added to `warnings`.
+async fn verify_content_backup(
+    content_dir: &Path,
+    encryption_key: Option<&BackupEncryptionKey>,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<AccountContentBackup>, BackupRestoreError> {
+    let mut accounts = vec![];
+
+    if !content_dir.exists() {
+        return Ok(accounts);
+    }
+
+    let mut iterator = tokio::fs::read_dir(content_dir)
+        .await
+        .change_context(BackupRestoreError::Read)?;
+
+    while let Some(e) = iterator
+        .next_entry()
+        .await
+        .change_context(BackupRestoreError::Read)?
+    {
+        if !e.path().is_dir() {
+            continue;
+        }
+
+        let Some(account_id) = uuid_from_file_name(&e.path()) else {
+            return Err(BackupRestoreError::InvalidAccountId.report())
+                .attach_printable(e.path().display().to_string());
+        };
+
+        let manifest = Sha256Manifest::load(&e.path().join(MANIFEST_FILE_NAME))
+            .await
+            .change_context(BackupRestoreError::Manifest)?;
+        match &manifest {
+            Some(manifest) => verify_manifest(&e.path(), manifest).await?,
+            None => warnings.push(format!(
+                "Content backup manifest is missing: {}",
+                e.path().display()
+            )),
+        }
+
+        let mut content = vec![];
+        let mut content_iterator = tokio::fs::read_dir(e.path())
+            .await
+            .change_context(BackupRestoreError::Read)?;
+
+        while let Some(e) = content_iterator
+            .next_entry()
+            .await
+            .change_context(BackupRestoreError::Read)?
+        {
+            let path = e.path();
+            if file_name(&path).starts_with(MANIFEST_FILE_NAME) {
+                continue;
+            }
+
+            let metadata = e
+                .metadata()
+                .await
+                .change_context(BackupRestoreError::Read)?;
+            if !metadata.is_file() || metadata.len() == 0 {
+                return Err(BackupRestoreError::InvalidContentFile.report())
+                    .attach_printable(path.display().to_string());
+            }
+
+            let Some(content_id) = uuid_from_file_name(&path) else {
+                return Err(BackupRestoreError::InvalidContentId.report())
+                    .attach_printable(path.display().to_string());
+            };
+            is_encrypted(&path, encryption_key).await?;
+            if let Some(manifest) = &manifest {
+                if !manifest.contains(&file_name(&path)) {
+                    warnings.push(format!(
+                        "Content backup is not in manifest: {}",
+                        path.display()
+                    ));
+                }
+            }
+            content.push((content_id, path));
+        }
+
+        accounts.push(AccountContentBackup {
+            account_id,
+            content,
+        });
+    }
+
+    Ok(accounts)
+}
+
+/// Returns `None` if the file name is not a complete UUID.
+fn uuid_from_file_name(path: &Path) -> Option<UuidBase64Url> {
+    let name = file_name(path);
+    let id = UuidBase64Url::from_text(&name).ok()?;
+    if id.to_string() == name {
+        Some(id)
+    } else {
+        None
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::{Connection, RunQueryDsl, SqliteConnection};
+    use manager_model::SecureStorageEncryptionKey;
+
+    use super::*;
+    use crate::server::link::backup::target::manifest::sha256_hex;
+
+    struct TestDir {
+        dir: PathBuf,
+    }
+
+    impl TestDir {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "afrodite-backup-restore-test-{}",
+                UuidBase64Url::new_random_id()
+            ));
+            std::fs::create_dir(&dir).unwrap();
+            Self { dir }
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[derive(diesel::QueryableByName)]
+    struct ValueRow {
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        value: String,
+    }
+
+    fn write_db_backup(files_dir: &Path, name: &str, value: &str) {
+        let path = files_dir.join(name);
+        let mut conn = SqliteConnection::establish(path.to_str().unwrap()).unwrap();
+        diesel::sql_query("CREATE TABLE test (value TEXT NOT NULL);")
+            .execute(&mut conn)
+            .unwrap();
+        diesel::sql_query(format!("INSERT INTO test (value) VALUES ('{value}');"))
+            .execute(&mut conn)
+            .unwrap();
+    }
+
+    fn read_db_value(path: &Path) -> String {
+        let mut conn = SqliteConnection::establish(path.to_str().unwrap()).unwrap();
+        let row: ValueRow = diesel::sql_query("SELECT value FROM test;")
+            .get_result(&mut conn)
+            .unwrap();
+        row.value
+    }
+
+    #[test]
+    fn file_backup_name_is_parsed() {
+        let (name, time) = parse_file_backup_name("backup_current_2025-01-02_03-04-05").unwrap();
+        assert_eq!(name, "current");
+        assert_eq!(time.to_string(), "2025-01-02 03:04:05");
+        let (name, _) = parse_file_backup_name("backup_db_name_2025-01-02_03-04-05").unwrap();
+        assert_eq!(name, "db_name");
+        assert!(parse_file_backup_name("backup__2025-01-02_03-04-05").is_none());
+        assert!(parse_file_backup_name("file.tmp").is_none());
+    }
+
+    #[tokio::test]
+    async fn backup_is_restored_to_data_dir() {
+        let test_dir = TestDir::new();
+        let backup_dir = test_dir.dir.join("backup");
+        let files_dir = backup_dir.join(BACKUP_FILES_DIR_NAME);
+        std::fs::create_dir_all(&files_dir).unwrap();
+        write_db_backup(&files_dir, "backup_current_2025-01-01_12-00-00", "old");
+        write_db_backup(&files_dir, "backup_history_2025-01-01_12-00-01", "old");
+        write_db_backup(&files_dir, "backup_current_2025-01-02_12-00-00", "new");
+        write_db_backup(&files_dir, "backup_history_2025-01-02_12-00-01", "new");
+        // Incomplete backup
+        write_db_backup(&files_dir, "backup_current_2025-01-03_12-00-00", "latest");
+
+        let account = UuidBase64Url::new_random_id();
+        let content = UuidBase64Url::new_random_id();
+        let account_dir = backup_dir
+            .join(BACKUP_CONTENT_DIR_NAME)
+            .join(account.to_string());
+        std::fs::create_dir_all(&account_dir).unwrap();
+        std::fs::write(account_dir.join(content.to_string()), b"image").unwrap();
+
+        let data_dir = test_dir.dir.join("data");
+        let info = restore_backup(&backup_dir, &data_dir, Some("2025-01-01"), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            info.database_backups,
+            vec![
+                "backup_current_2025-01-01_12-00-00".to_string(),
+                "backup_history_2025-01-01_12-00-01".to_string(),
+            ]
+        );
+        assert_eq!(info.accounts, 1);
+        assert_eq!(info.content, 1);
+        // Manifests are missing for both database backups and account content
+        assert_eq!(info.warnings.len(), 3);
+        let restored_content = data_dir
+            .join(FILE_DIR_NAME)
+            .join(account.to_string())
+            .join(CONTENT_DIR_NAME)
+            .join(content.to_string());
+        assert_eq!(std::fs::read(restored_content).unwrap(), b"image");
+        let history_db = data_dir
+            .join(SQLITE_DIR_NAME)
+            .join("history")
+            .join("history.db");
+        assert_eq!(read_db_value(&history_db), "old");
+
+        let result = restore_backup(&backup_dir, &data_dir, None, None).await;
+        assert!(result.is_err());
+
+        let result = restore_backup(
+            &backup_dir,
+            &test_dir.dir.join("incomplete"),
+            Some("2025-01-03"),
+            None,
+        )
+        .await;
+        assert!(result.is_err());
+
+        let latest_data_dir = test_dir.dir.join("latest");
+        let info = restore_backup(&backup_dir, &latest_data_dir, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            info.database_backups,
+            vec![
+                "backup_current_2025-01-02_12-00-00".to_string(),
+                "backup_history_2025-01-02_12-00-01".to_string(),
+            ]
+        );
+        let current_db = latest_data_dir
+            .join(SQLITE_DIR_NAME)
+            .join("current")
+            .join("current.db");
+        assert_eq!(read_db_value(&current_db), "new");
+    }
+
+    #[tokio::test]
+    async fn corrupted_database_backup_is_not_restored() {
+        let test_dir = TestDir::new();
+        let backup_dir = test_dir.dir.join("backup");
+        let files_dir = backup_dir.join(BACKUP_FILES_DIR_NAME);
+        std::fs::create_dir_all(&files_dir).unwrap();
+        let db_name = "backup_current_2025-01-01_12-00-00";
+        write_db_backup(&files_dir, db_name, "data");
+
+        let mut db = std::fs::read(files_dir.join(db_name)).unwrap();
+        // Keep the header and overwrite the database page size and pages
+        db.truncate(SQLITE_FILE_HEADER.len());
+        db.extend_from_slice(&[0xFF; 4096]);
+        std::fs::write(files_dir.join(db_name), db).unwrap();
+
+        let result = restore_backup(&backup_dir, &test_dir.dir.join("data"), None, None).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn encrypted_backup_is_verified_and_decrypted() {
+        let test_dir = TestDir::new();
+        let backup_dir = test_dir.dir.join("backup");
+        let files_dir = backup_dir.join(BACKUP_FILES_DIR_NAME);
+        std::fs::create_dir_all(&files_dir).unwrap();
+
+        let key = BackupEncryptionKey::new(&SecureStorageEncryptionKey {
+            key: "test".to_string(),
+        });
+        let db_name = "backup_current_2025-01-01_12-00-00";
+        write_db_backup(&test_dir.dir, db_name, "data");
+        let db = std::fs::read(test_dir.dir.join(db_name)).unwrap();
+        let encrypted_db = key
+            .encrypt(&BackupFileId::file("current"), 0, true, &db)
+            .unwrap();
+        std::fs::write(files_dir.join(db_name), &encrypted_db).unwrap();
+        let mut manifest = Sha256Manifest::default();
+        manifest.insert(db_name.to_string(), sha256_hex(&encrypted_db));
+        manifest
+            .save(&file_backup_manifest_path(&files_dir.join(db_name)))
+            .await
+            .unwrap();
+
+        let result = restore_backup(&backup_dir, &test_dir.dir.join("no_key"), None, None).await;
+        assert!(result.is_err());
+
+        let data_dir = test_dir.dir.join("data");
+        let info = restore_backup(&backup_dir, &data_dir, None, Some(&key))
+            .await
+            .unwrap();
+        assert!(info.warnings.is_empty());
+        let current_db = data_dir
+            .join(SQLITE_DIR_NAME)
+            .join("current")
+            .join("current.db");
+        assert_eq!(std::fs::read(&current_db).unwrap(), db);
+        assert_eq!(read_db_value(&current_db), "data");
+
+        std::fs::write(files_dir.join(db_name), b"corrupted").unwrap();
+        let result = restore_backup(
+            &backup_dir,
+            &test_dir.dir.join("corrupted"),
+            None,
+            Some(&key),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,169 @@
+//! Periodic verification of stored backup files using the
+//! SHA-256 manifests.
+
+use std::{path::Path, sync::Arc, time::Duration};
+
+use error_stack::{Result, ResultExt};
+use manager_config::Config;
+use manager_model::BackupVerificationStatus;
+use simple_backend_model::UnixTime;
+use tokio::{
+    sync::{watch, Mutex},
+    time::MissedTickBehavior,
+};
+use tracing::{error, info, warn};
+
+use super::{
+    backup::{BACKUP_DIR_NAME, CONTENT_DIR_NAME, FILES_DIR_NAME},
+    manifest::{find_corrupted_files, Sha256Manifest, MANIFEST_FILE_EXTENSION, MANIFEST_FILE_NAME},
+    BackupTargetError,
+};
+use crate::{api::GetConfig, server::app::S};
+
+pub struct BackupVerificationTask {
+    state: S,
+    /// Backup session and verification are not run at the same time,
+    /// so files and manifests are not checked during writing.
+    backup_dir_lock: Arc<Mutex<()>>,
+    /// Latest status is sent to the backup link server when
+    /// the backup link is connected.
+    status_sender: watch::Sender<Option<BackupVerificationStatus>>,
+}
+
+impl BackupVerificationTask {
+    pub fn new(
+        state: S,
+        backup_dir_lock: Arc<Mutex<()>>,
+        status_sender: watch::Sender<Option<BackupVerificationStatus>>,
+    ) -> Self {
+        Self {
+            state,
+            backup_dir_lock,
+            status_sender,
+        }
+    }
+
+    pub async fn run(self) {
+        let interval_seconds = self
+            .state
+            .config()
+            .backup_link()
+            .verification_interval()
+            .seconds
+            .max(1);
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds.into()));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            let result = {
+                let _lock = self.backup_dir_lock.lock().await;
+                verify_backups(self.state.config()).await
+            };
+
+            match result {
+                Ok(status) => {
+                    if status.corrupted_files > 0 {
+                        error!(
+                            "Backup verification completed, checked files: {}, corrupted files: {}",
+                            status.checked_files, status.corrupted_files,
+                        );
+                    } else {
+                        info!(
+                            "Backup verification completed, checked files: {}",
+                            status.checked_files,
+                        );
+                    }
+                    self.status_sender.send_replace(Some(status));
+                }
+                Err(e) => error!("Backup verification failed: {:?}", e),
+            }
+        }
+    }
+}
+
+async fn verify_backups(config: &Config) -> Result<BackupVerificationStatus, BackupTargetError> {
+    let backup_dir = config.storage_dir().join(BACKUP_DIR_NAME);
+    let mut status = BackupVerificationStatus {
+        time: UnixTime::current_time(),
+        checked_files: 0,
+        corrupted_files: 0,
+    };
+
+    let content_dir = backup_dir.join(CONTENT_DIR_NAME);
+    if content_dir.exists() {
+        let mut iterator = tokio::fs::read_dir(content_dir)
+            .await
+            .change_context(BackupTargetError::Read)?;
+
+        while let Some(e) = iterator
+            .next_entry()
+            .await
+            .change_context(BackupTargetError::Read)?
+        {
+            if !e.path().is_dir() {
+                continue;
+            }
+
+            let manifest = Sha256Manifest::load(&e.path().join(MANIFEST_FILE_NAME))
+                .await
+                .change_context(BackupTargetError::Manifest)?;
+            let Some(manifest) = manifest else {
+                warn!("Backup content manifest is missing: {}", e.path().display());
+                continue;
+            };
+            verify_manifest(&e.path(), &manifest, &mut status).await?;
+        }
+    }
+
+    let files_dir = backup_dir.join(FILES_DIR_NAME);
+    if files_dir.exists() {
+        let mut iterator = tokio::fs::read_dir(&files_dir)
+            .await
+            .change_context(BackupTargetError::Read)?;
+
+        while let Some(e) = iterator
+            .next_entry()
+            .await
+            .change_context(BackupTargetError::Read)?
+        {
+            if !e
+                .file_name()
+                .to_string_lossy()
+                .ends_with(MANIFEST_FILE_EXTENSION)
+            {
+                continue;
+            }
+
+            let Some(manifest) = Sha256Manifest::load(&e.path())
+                .await
+                .change_context(BackupTargetError::Manifest)?
+            else {
+                continue;
+            };
+            verify_manifest(&files_dir, &manifest, &mut status).await?;
+        }
+    }
+
+    Ok(status)
+}
+
+async fn verify_manifest(
+    dir: &Path,
+    manifest: &Sha256Manifest,
+    status: &mut BackupVerificationStatus,
+) -> Result<(), BackupTargetError> {
+    let corrupted = find_corrupted_files(dir, manifest)
+        .await
+        .change_context(BackupTargetError::Manifest)?;
+
+    for name in &corrupted {
+        error!("Corrupted backup file: {}", dir.join(name).display());
+    }
+
+    status.checked_files += manifest.iter().count() as u64;
+    status.corrupted_files += corrupted.len() as u64;
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 
 use error_stack::{report, Result, ResultExt};
 use manager_model::{BackupMessage, BackupMessageHeader, BackupMessageType, JsonRpcLinkHeader, JsonRpcLinkMessage, JsonRpcLinkMessageType, ManualTaskType, NotifyBackend, ScheduledTaskStatus, ScheduledTaskType, SoftwareUpdateTaskType};
-use manager_model::{JsonRpcRequest, JsonRpcRequestType, JsonRpcResponse, JsonRpcResponseType, ManagerInstanceName, ManagerInstanceNameList, ManagerProtocolMode, ManagerProtocolVersion, SecureStorageEncryptionKey, ServerEvent, SoftwareUpdateStatus, SystemInfo};
+use manager_model::{BackupRestoreInfo, BackupRestoreSettings, JsonRpcRequest, JsonRpcRequestType, JsonRpcResponse, JsonRpcResponseType, ManagerInstanceName, ManagerInstanceNameList, ManagerProtocolMode, ManagerProtocolVersion, SecureStorageEncryptionKey, ServerEvent, SoftwareUpdateStatus, SystemInfo};
 
 use tokio::io::AsyncWriteExt;
 use tokio::io::AsyncReadExt;
@@ -368,6 +368,22 @@ pub trait RequestSenderCmds: Sized {
         );
         self.send_request(request).await?.require_successful()
     }
+
+    async fn restore_backup(
+        self,
+        settings: BackupRestoreSettings,
+    ) -> Result<BackupRestoreInfo, ClientError> {
+        let request = JsonRpcRequest::new(
+            self.request_receiver_name(),
+            JsonRpcRequestType::RestoreBackup(settings),
+        );
+        let response = self.send_request(request).await?;
+        if let JsonRpcResponseType::BackupRestoreInfo(info) = response.into_response() {
+            Ok(info)
+        } else {
+            Err(report!(ClientError::InvalidResponse))
+        }
+    }
 }
 
 trait RpcResponseExtensions: Sized {
@@ -143,4 +143,22 @@ pub enum ApiCommand {
         name: String,
         sha256: String,
     },
+    /// Restore backend data directory from backup link target's
+    /// backup directory. Manager config must allow backup restoring.
+    RestoreBackup {
+        /// New backend data directory. It must not exist or be empty.
+        data_dir: PathBuf,
+        /// Database backup date in format YYYY-MM-DD. If not present,
+        /// the latest date which has backups for all databases is used.
+        #[arg(long, value_name = "DATE")]
+        date: Option<String>,
+        /// Backup directory. If not present, the backup directory of
+        /// the manager instance is used.
+        #[arg(long, value_name = "DIR")]
+        backup_dir: Option<PathBuf>,
+        /// Server encryption key name which was used for encrypting
+        /// the backups.
+        #[arg(long, value_name = "NAME")]
+        encryption_key_name: Option<String>,
+    },
 }
@@ -51,10 +51,13 @@ public_api = "127.0.0.1:4000"
 # password = "password"
 # url = "tls://127.0.0.1:4000"
 # file_backup_retention_time = "30d" # Default
+# verification_interval = "1d" # Default
 
 # [backup_link.server]
 # password_target = "password"
 # password_source = "password"
+# Encrypt backups using server_encryption_key with this manager name.
+# encryption_key_name = "default"
 
 # [secure_storage]
 # key_storage_manager_name = "default"
@@ -83,6 +86,7 @@ public_api = "127.0.0.1:4000"
 # [manual_tasks]
 # allow_backend_restart = true
 # allow_system_reboot = true
+# allow_backup_restore = true
 
 # [manual_tasks.allow_backend_data_reset]
 # backend_data_dir = "/path/to/backend/data"
@@ -334,6 +338,10 @@ pub struct ManualTasksConfig {
     pub allow_backend_restart: bool,
     /// Allow manaual system reboot
     pub allow_system_reboot: bool,
+    /// Allow restoring backend data directory from backup link
+    /// target's backup directory
+    #[serde(default)]
+    pub allow_backup_restore: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -425,6 +433,14 @@ impl BackupLinkConfig {
     pub fn file_backup_retention_time(&self) -> DurationValue {
         self.target.as_ref().and_then(|v| v.file_backup_retention_time).unwrap_or(DurationValue::from_days(30))
     }
+
+    pub fn encryption_key_name(&self) -> Option<&ManagerInstanceName> {
+        self.server.as_ref().map(|v| &v.encryption_key_name)
+    }
+
+    pub fn verification_interval(&self) -> DurationValue {
+        self.target.as_ref().and_then(|v| v.verification_interval).unwrap_or(DurationValue::from_days(1))
+    }
 }
 
 /// Remote manager which connects to server and receives the backups.
@@ -435,6 +451,9 @@ pub struct BackupLinkConfigTarget {
     /// Manager server address
     pub url: Url,
     file_backup_retention_time: Option<DurationValue>,
+    /// How often stored backup files are compared
+    /// to the SHA-256 manifests.
+    verification_interval: Option<DurationValue>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -443,6 +462,10 @@ pub struct BackupLinkConfigServer {
     pub password_target: String,
     /// Accepted password for client which sends the backups
     pub password_source: String,
+    /// Name of [ServerEncryptionKey] which is used for encrypting
+    /// backup data before it is sent to the backup target.
+    /// Backups are not relayed if the key is not available.
+    pub encryption_key_name: ManagerInstanceName,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
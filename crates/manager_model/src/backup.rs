@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use simple_backend_model::UnixTime;
+
+use crate::ManagerInstanceName;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct BackupRestoreSettings {
+    /// Backup link target's backup directory which contains
+    /// `content` and `files` directories. If not set, the backup
+    /// directory of the manager instance is used.
+    pub backup_dir: Option<PathBuf>,
+    /// New backend data directory. Restoring fails if the directory
+    /// exists and it is not empty.
+    pub data_dir: PathBuf,
+    /// Database backup date in format `YYYY-MM-DD`. If not set, the
+    /// latest database backups are used.
+    pub date: Option<String>,
+    /// Name of the server encryption key which was used for
+    /// encrypting the backups. The key must be available in the
+    /// manager instance which restores the backup.
+    pub encryption_key_name: Option<ManagerInstanceName>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct BackupRestoreInfo {
+    /// File names of restored database backups.
+    pub database_backups: Vec<String>,
+    pub accounts: u64,
+    pub content: u64,
+    /// Restored backup files which were not verified because
+    /// SHA-256 manifest is missing or the file is not in the manifest.
+    pub warnings: Vec<String>,
+}
+
+/// Result of comparing stored backup files to the SHA-256 manifests
+/// at the backup link target.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub struct BackupVerificationStatus {
+    pub time: UnixTime,
+    pub checked_files: u64,
+    /// Count of files which are missing or do not match the manifest.
+    pub corrupted_files: u64,
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use simple_backend_model::UnixTime;
 use utoipa::{IntoParams, ToSchema};
 
-use crate::{ManualTaskType, NotifyBackend, ScheduledTaskStatus, ScheduledTaskType, SecureStorageEncryptionKey, SoftwareUpdateStatus, SoftwareUpdateTaskType, SystemInfo};
+use crate::{BackupRestoreInfo, BackupRestoreSettings, BackupVerificationStatus, ManualTaskType, NotifyBackend, ScheduledTaskStatus, ScheduledTaskType, SecureStorageEncryptionKey, SoftwareUpdateStatus, SoftwareUpdateTaskType, SystemInfo};
 
 #[derive(Debug, Clone, Copy, PartialEq, num_enum::TryFromPrimitive)]
 #[repr(u8)]
@@ -56,6 +56,8 @@ pub enum JsonRpcRequestType {
     ScheduleTask(ScheduledTaskType, NotifyBackend),
     /// Response [JsonRpcResponseType::Successful]
     UnscheduleTask(ScheduledTaskType),
+    /// Response [JsonRpcResponseType::BackupRestoreInfo]
+    RestoreBackup(BackupRestoreSettings),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -122,6 +124,14 @@ impl JsonRpcResponse {
         }
     }
 
+    pub fn backup_restore_info(
+        info: BackupRestoreInfo,
+    ) -> Self {
+        Self {
+            response: JsonRpcResponseType::BackupRestoreInfo(info),
+        }
+    }
+
     pub fn into_response(self) -> JsonRpcResponseType {
         self.response
     }
@@ -145,6 +155,7 @@ pub enum JsonRpcResponseType {
     SystemInfo(SystemInfo),
     SoftwareUpdateStatus(SoftwareUpdateStatus),
     ScheduledTasksStatus(ScheduledTaskStatus),
+    BackupRestoreInfo(BackupRestoreInfo),
     Successful,
     RequestReceiverNotFound,
 }
@@ -177,6 +188,7 @@ pub struct ManagerInstanceNameValue {
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum ServerEventType {
     MaintenanceSchedulingStatus(Option<MaintenanceTime>),
+    BackupVerificationStatus(BackupVerificationStatus),
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
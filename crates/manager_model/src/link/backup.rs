@@ -1,7 +1,10 @@
 use std::{io::{ErrorKind, Read}, num::Wrapping};
 
+use simple_backend_model::UnixTime;
 use simple_backend_utils::UuidBase64Url;
 
+use crate::BackupVerificationStatus;
+
 #[derive(Debug, Clone, Copy, PartialEq, num_enum::TryFromPrimitive)]
 #[repr(u8)]
 pub enum BackupMessageType {
@@ -49,6 +52,16 @@ pub enum BackupMessageType {
     /// - Package number (u32, little-endian, can wrap)
     /// - Data
     FileBackupData = 7,
+    /// Target sends this to source after backup verification. Backup
+    /// link server handles this message, so it is not relayed to
+    /// the backup source client. Sequence number is 0.
+    ///
+    /// Data:
+    ///
+    /// - Verification time (i64, little-endian, Unix time)
+    /// - Checked files (u64, little-endian)
+    /// - Corrupted files (u64, little-endian)
+    BackupVerificationStatus = 8,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -146,7 +159,8 @@ impl TryFrom<BackupMessage> for SourceToTargetMessage {
         let m = match value.header.message_type {
             BackupMessageType::Empty |
             BackupMessageType::ContentListSyncDone |
-            BackupMessageType::ContentQuery =>
+            BackupMessageType::ContentQuery |
+            BackupMessageType::BackupVerificationStatus =>
                 return Err(format!("Type conversion for message type {:?} is not supported", value.header.message_type)),
             BackupMessageType::StartBackupSession =>
                 Self::StartBackupSession,
@@ -212,7 +226,10 @@ pub enum TargetToSourceMessage {
     ContentQuery {
         account_id: UuidBase64Url,
         content_id: UuidBase64Url,
-    }
+    },
+    BackupVerificationStatus {
+        status: BackupVerificationStatus,
+    },
 }
 
 impl TargetToSourceMessage {
@@ -220,6 +237,7 @@ impl TargetToSourceMessage {
         let message_type = match self {
             Self::ContentListSyncDone => BackupMessageType::ContentListSyncDone,
             Self::ContentQuery { .. } => BackupMessageType::ContentQuery,
+            Self::BackupVerificationStatus { .. } => BackupMessageType::BackupVerificationStatus,
         };
 
         let data = match self {
@@ -230,7 +248,13 @@ impl TargetToSourceMessage {
                     .iter()
                     .chain(content_id.as_bytes())
                     .copied()
-                    .collect::<Vec<u8>>()
+                    .collect::<Vec<u8>>(),
+            Self::BackupVerificationStatus { status } =>
+                status.time.ut.to_le_bytes()
+                    .into_iter()
+                    .chain(status.checked_files.to_le_bytes())
+                    .chain(status.corrupted_files.to_le_bytes())
+                    .collect::<Vec<u8>>(),
         };
 
         BackupMessage {
@@ -275,6 +299,31 @@ impl TryFrom<BackupMessage> for TargetToSourceMessage {
                     content_id
                 }
             }
+            BackupMessageType::BackupVerificationStatus => {
+                let mut data_reader = value.data.as_slice();
+
+                let mut bytes = [0u8; 8];
+
+                data_reader.read_exact(&mut bytes)
+                    .map_err(|e| e.to_string())?;
+                let time = UnixTime::new(i64::from_le_bytes(bytes));
+
+                data_reader.read_exact(&mut bytes)
+                    .map_err(|e| e.to_string())?;
+                let checked_files = u64::from_le_bytes(bytes);
+
+                data_reader.read_exact(&mut bytes)
+                    .map_err(|e| e.to_string())?;
+                let corrupted_files = u64::from_le_bytes(bytes);
+
+                Self::BackupVerificationStatus {
+                    status: BackupVerificationStatus {
+                        time,
+                        checked_files,
+                        corrupted_files,
+                    }
+                }
+            }
         };
 
         Ok(m)
@@ -184,6 +184,7 @@ define_permissions!(
     /// View public and private profiles.
     admin_view_all_profiles,
     admin_view_private_info,
+    /// View submitted profile names and texts with moderation results.
     admin_view_profile_history,
     admin_view_permissions,
     admin_find_account_by_email,
@@ -199,6 +200,11 @@ define_permissions!(
     admin_news_create,
     admin_news_edit_all,
     admin_profile_statistics,
+    /// Export data of any account to ZIP archive.
+    admin_export_data,
+    /// Search profiles using profile name, profile text and account
+    /// related filters.
+    admin_search_profiles,
 );
 
 impl Permissions {
@@ -354,10 +360,26 @@ diesel_i64_wrapper!(ClientLocalId);
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Deserialize)]
 pub enum EmailMessages {
     AccountRegistered,
+    /// Email body can contain placeholder for email confirmation token.
+    /// See [EmailMessages::EMAIL_CONFIRMATION_TOKEN_PLACEHOLDER].
+    EmailConfirmation,
+    /// Email about unread chat requests and messages. Email body can
+    /// contain placeholders for the counts. See
+    /// [EmailMessages::NEW_CHAT_REQUESTS_COUNT_PLACEHOLDER] and
+    /// [EmailMessages::NEW_MESSAGES_COUNT_PLACEHOLDER].
+    ChatNotificationDigest,
 }
 
 impl EmailMessages {
-    pub const VARIANTS: &'static [EmailMessages] = &[EmailMessages::AccountRegistered];
+    pub const VARIANTS: &'static [EmailMessages] = &[
+        EmailMessages::AccountRegistered,
+        EmailMessages::EmailConfirmation,
+        EmailMessages::ChatNotificationDigest,
+    ];
+
+    pub const EMAIL_CONFIRMATION_TOKEN_PLACEHOLDER: &'static str = "{email_confirmation_token}";
+    pub const NEW_CHAT_REQUESTS_COUNT_PLACEHOLDER: &'static str = "{new_chat_requests_count}";
+    pub const NEW_MESSAGES_COUNT_PLACEHOLDER: &'static str = "{new_messages_count}";
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
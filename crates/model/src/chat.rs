@@ -8,6 +8,9 @@ use super::sync_version_wrappers;
 mod interaction;
 pub use interaction::*;
 
+mod message_signing;
+pub use message_signing::*;
+
 #[derive(
     Debug,
     Serialize,
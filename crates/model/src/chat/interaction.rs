@@ -390,6 +390,17 @@ impl AccountInteractionInternal {
         }
     }
 
+    /// Return true if `viewer` has liked, matched or blocked
+    /// `profile_owner`.
+    pub fn is_interacted_by(
+        &self,
+        viewer: impl Into<AccountIdDb> + Copy,
+        profile_owner: impl Into<AccountIdDb> + Copy,
+    ) -> bool {
+        self.is_direction_liked(viewer, profile_owner)
+            || self.is_direction_blocked(viewer, profile_owner)
+    }
+
     pub fn set_previous_like_deleter_if_slot_available(
         &mut self,
         id_like_deleter: AccountIdInternal,
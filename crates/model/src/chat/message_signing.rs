@@ -0,0 +1,139 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use simple_backend_model::UnixTime;
+use utoipa::ToSchema;
+
+use crate::{AccountId, MessageNumber};
+
+/// Server Ed25519 signature for chat message metadata.
+///
+/// Base64 standard encoding is used.
+///
+/// The signed data is
+/// - Format version (u8, current value is 1)
+/// - Sender account ID (16 UUID bytes)
+/// - Receiver account ID (16 UUID bytes)
+/// - Message number (i64, little endian)
+/// - Unix time when server received the message (i64, little endian)
+/// - SHA-256 hash of the message bytes
+///
+/// The signature can be verified using [MessageSigningPublicKey] from
+/// client config.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq)]
+pub struct MessageSignature {
+    pub s: String,
+}
+
+impl MessageSignature {
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Self {
+            s: base64::engine::general_purpose::STANDARD.encode(data),
+        }
+    }
+
+    pub fn bytes(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        base64::engine::general_purpose::STANDARD.decode(&self.s)
+    }
+}
+
+/// Server Ed25519 signature for public key which account has uploaded.
+///
+/// Base64 standard encoding is used.
+///
+/// The signed data is
+/// - Data type (u8, current value is 2). Value 1 is used for
+///   [MessageSignature] data.
+/// - Public key owner account ID (16 UUID bytes)
+/// - Public key version (i64, little endian)
+/// - Public key ID (i64, little endian)
+/// - Unix time when server received the public key (i64, little endian)
+/// - SHA-256 hash of the public key data UTF-8 bytes
+///
+/// The signature can be verified using [MessageSigningPublicKey] from
+/// client config.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq)]
+pub struct PublicKeySignature {
+    pub s: String,
+}
+
+impl PublicKeySignature {
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Self {
+            s: base64::engine::general_purpose::STANDARD.encode(data),
+        }
+    }
+
+    pub fn bytes(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        base64::engine::general_purpose::STANDARD.decode(&self.s)
+    }
+}
+
+/// Server Ed25519 public key for verifying [MessageSignature] and
+/// [PublicKeySignature].
+///
+/// Base64 standard encoding is used.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq)]
+pub struct MessageSigningPublicKey {
+    pub key: String,
+}
+
+impl MessageSigningPublicKey {
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Self {
+            key: base64::engine::general_purpose::STANDARD.encode(data),
+        }
+    }
+}
+
+/// Message metadata and [MessageSignature] which client received
+/// from server.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq)]
+pub struct SignedMessageInfo {
+    pub sender: AccountId,
+    pub receiver: AccountId,
+    pub mn: MessageNumber,
+    /// Unix time when server received the message.
+    pub ut: UnixTime,
+    /// Base64 standard encoded message bytes.
+    pub message: String,
+    pub signature: MessageSignature,
+}
+
+/// [SignedMessageInfo] with decoded message and signature bytes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedMessageInternal {
+    pub sender: AccountId,
+    pub receiver: AccountId,
+    pub mn: MessageNumber,
+    pub ut: UnixTime,
+    pub message: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+impl SignedMessageInternal {
+    pub fn to_signed_message_info(&self) -> SignedMessageInfo {
+        SignedMessageInfo {
+            sender: self.sender,
+            receiver: self.receiver,
+            mn: self.mn,
+            ut: self.ut,
+            message: base64::engine::general_purpose::STANDARD.encode(&self.message),
+            signature: MessageSignature::from_bytes(&self.signature),
+        }
+    }
+}
+
+impl TryFrom<SignedMessageInfo> for SignedMessageInternal {
+    type Error = base64::DecodeError;
+
+    fn try_from(value: SignedMessageInfo) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sender: value.sender,
+            receiver: value.receiver,
+            mn: value.mn,
+            ut: value.ut,
+            message: base64::engine::general_purpose::STANDARD.decode(&value.message)?,
+            signature: value.signature.bytes()?,
+        })
+    }
+}
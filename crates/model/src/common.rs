@@ -29,6 +29,9 @@ pub use report::*;
 pub mod client_config;
 pub use client_config::*;
 
+pub mod data_export;
+pub use data_export::*;
+
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq)]
 pub struct BackendVersion {
     /// Backend code version.
@@ -61,6 +64,8 @@ pub enum EventType {
     InitialContentModerationCompleted,
     MediaContentChanged,
     ScheduledMaintenanceStatus,
+    /// Data: data_export_state_changed
+    DataExportStateChanged,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
@@ -95,6 +100,8 @@ pub struct EventToClient {
     content_processing_state_changed: Option<ContentProcessingStateChanged>,
     /// Data for event ScheduledMaintenanceStatus
     scheduled_maintenance_status: Option<ScheduledMaintenanceStatus>,
+    /// Data for event DataExportStateChanged
+    data_export_state_changed: Option<DataExportState>,
 }
 
 /// Internal data type for events.
@@ -120,6 +127,7 @@ pub enum EventToClientInternal {
     InitialContentModerationCompleted,
     MediaContentChanged,
     ScheduledMaintenanceStatus(ScheduledMaintenanceStatus),
+    DataExportStateChanged(DataExportState),
 }
 
 impl From<&EventToClientInternal> for EventType {
@@ -141,6 +149,7 @@ impl From<&EventToClientInternal> for EventType {
             InitialContentModerationCompleted => Self::InitialContentModerationCompleted,
             MediaContentChanged => Self::MediaContentChanged,
             ScheduledMaintenanceStatus(_) => Self::ScheduledMaintenanceStatus,
+            DataExportStateChanged(_) => Self::DataExportStateChanged,
         }
     }
 }
@@ -152,6 +161,7 @@ impl From<EventToClientInternal> for EventToClient {
             latest_viewed_message_changed: None,
             content_processing_state_changed: None,
             scheduled_maintenance_status: None,
+            data_export_state_changed: None,
         };
 
         use EventToClientInternal::*;
@@ -160,6 +170,7 @@ impl From<EventToClientInternal> for EventToClient {
             LatestViewedMessageChanged(v) => value.latest_viewed_message_changed = Some(v),
             ContentProcessingStateChanged(v) => value.content_processing_state_changed = Some(v),
             ScheduledMaintenanceStatus(v) => value.scheduled_maintenance_status = Some(v),
+            DataExportStateChanged(v) => value.data_export_state_changed = Some(v),
             AccountStateChanged
             | NewMessageReceived
             | ReceivedLikesChanged
@@ -313,11 +324,54 @@ impl std::fmt::Display for AccountId {
     }
 }
 
-#[derive(Debug, Selectable, Queryable)]
-#[diesel(table_name = crate::schema::access_token)]
+/// Login session ID.
+///
+/// One account can have multiple login sessions, for example one session
+/// per device. Each session has its own access and refresh tokens.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Deserialize,
+    Serialize,
+    ToSchema,
+    PartialEq,
+    Eq,
+    Hash,
+    FromSqlRow,
+    AsExpression,
+)]
+#[diesel(sql_type = Binary)]
+pub struct LoginSessionId {
+    pub sid: simple_backend_utils::UuidBase64Url,
+}
+
+diesel_uuid_wrapper!(LoginSessionId);
+
+impl LoginSessionId {
+    pub fn new_random() -> Self {
+        Self {
+            sid: simple_backend_utils::UuidBase64Url::new_random_id(),
+        }
+    }
+
+    fn diesel_uuid_wrapper_new(sid: simple_backend_utils::UuidBase64Url) -> Self {
+        Self { sid }
+    }
+
+    fn diesel_uuid_wrapper_as_uuid(&self) -> &simple_backend_utils::UuidBase64Url {
+        &self.sid
+    }
+}
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = crate::schema::login_session)]
 #[diesel(check_for_backend(crate::Db))]
-pub struct AccessTokenRaw {
-    pub token: Option<String>,
+pub struct LoginSessionRaw {
+    pub session_id: LoginSessionId,
+    pub fcm_device_token: Option<FcmDeviceToken>,
+    pub created_unix_time: UnixTime,
+    pub last_used_unix_time: UnixTime,
 }
 
 /// AccessToken is used as a short lived token for API access.
@@ -365,13 +419,6 @@ impl AccessToken {
     }
 }
 
-#[derive(Debug, Selectable, Queryable)]
-#[diesel(table_name = crate::schema::refresh_token)]
-#[diesel(check_for_backend(crate::Db))]
-pub struct RefreshTokenRaw {
-    pub token: Option<Vec<u8>>,
-}
-
 /// Refresh token is long lived token used for getting new access tokens.
 ///
 /// Refresh token is 3072 bit value which is Base64 encoded.
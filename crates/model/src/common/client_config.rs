@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::{CustomReportsFileHash, ProfileAttributeInfo};
+use crate::{CustomReportsFileHash, MessageSigningPublicKey, ProfileAttributeInfo};
 
 use super::ClientConfigSyncVersion;
 
@@ -13,5 +13,8 @@ pub struct ClientConfig {
     /// Profile component specific config. It is also possible
     /// that attributes are not configured.
     pub profile_attributes: Option<ProfileAttributeInfo>,
+    /// Chat component specific config. Public key for verifying
+    /// server signatures of chat messages and public keys.
+    pub message_signing_public_key: Option<MessageSigningPublicKey>,
     pub sync_version: ClientConfigSyncVersion,
 }
@@ -0,0 +1,90 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use simple_backend_model::UnixTime;
+use utils::random_bytes::random_128_bits;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::AccountId;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, ToSchema, PartialEq, Eq)]
+pub enum DataExportStateType {
+    /// Data export is not started or the export archive
+    /// download link is expired.
+    #[default]
+    Empty,
+    InProgress,
+    /// Export archive is available for downloading.
+    Done,
+    Error,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema, PartialEq)]
+pub struct DataExportState {
+    pub state: DataExportStateType,
+    /// Account which data is exported.
+    pub target: Option<AccountId>,
+    /// Export progress in percentages. Available when state is
+    /// [DataExportStateType::InProgress].
+    pub progress: Option<u8>,
+    /// Available when state is [DataExportStateType::Done].
+    pub download_token: Option<DataExportDownloadToken>,
+    /// Time when export archive is removed. Available when state is
+    /// [DataExportStateType::Done].
+    pub download_expires: Option<UnixTime>,
+}
+
+impl DataExportState {
+    pub fn in_progress(target: AccountId) -> Self {
+        Self {
+            state: DataExportStateType::InProgress,
+            target: Some(target),
+            progress: Some(0),
+            download_token: None,
+            download_expires: None,
+        }
+    }
+
+    pub fn change_progress(&mut self, progress: u8) {
+        self.progress = Some(progress.min(100));
+    }
+
+    pub fn change_to_done(&mut self, token: DataExportDownloadToken, expires: UnixTime) {
+        self.state = DataExportStateType::Done;
+        self.progress = None;
+        self.download_token = Some(token);
+        self.download_expires = Some(expires);
+    }
+
+    pub fn change_to_error(&mut self) {
+        self.state = DataExportStateType::Error;
+        self.progress = None;
+        self.download_token = None;
+        self.download_expires = None;
+    }
+
+    pub fn is_in_progress(&self) -> bool {
+        self.state == DataExportStateType::InProgress
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.state == DataExportStateType::Done
+    }
+}
+
+/// Token which is required for downloading data export archive.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema, IntoParams, PartialEq, Eq)]
+pub struct DataExportDownloadToken {
+    pub token: String,
+}
+
+impl DataExportDownloadToken {
+    pub fn generate_new() -> Self {
+        let mut token = Vec::new();
+        for _ in 1..=2 {
+            token.extend(random_128_bits())
+        }
+        Self {
+            token: base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&token),
+        }
+    }
+}
@@ -61,6 +61,12 @@ bitflags::bitflags! {
     }
 }
 
+impl PendingNotificationFlags {
+    /// Flags which trigger chat notification digest email sending if
+    /// the notification is not received before the configured delay.
+    pub const CHAT_EMAIL_NOTIFICATION: Self = Self::NEW_MESSAGE.union(Self::RECEIVED_LIKES_CHANGED);
+}
+
 impl From<PendingNotification> for PendingNotificationFlags {
     fn from(value: PendingNotification) -> Self {
         value.0.into()
@@ -130,13 +136,6 @@ impl FcmDeviceToken {
 
 diesel_string_wrapper!(FcmDeviceToken);
 
-#[derive(Debug, Selectable, Queryable)]
-#[diesel(table_name = crate::schema::chat_state)]
-#[diesel(check_for_backend(crate::Db))]
-pub struct PendingNotificationTokenRaw {
-    pub pending_notification_token: Option<PendingNotificationToken>,
-}
-
 /// PendingNotificationToken is used as a token for pending notification
 /// API access.
 ///
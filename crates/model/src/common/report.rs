@@ -197,6 +197,11 @@ pub struct UpdateReportResult {
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     #[schema(default = false)]
     pub error_too_many_reports: bool,
+    /// Chat message report specific error. The server signature
+    /// for the reported message is invalid.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[schema(default = false)]
+    pub error_invalid_message_signature: bool,
 }
 
 impl UpdateReportResult {
@@ -204,6 +209,7 @@ impl UpdateReportResult {
         Self {
             error_outdated_report_content: false,
             error_too_many_reports: false,
+            error_invalid_message_signature: false,
         }
     }
 
@@ -211,6 +217,7 @@ impl UpdateReportResult {
         Self {
             error_outdated_report_content: true,
             error_too_many_reports: false,
+            error_invalid_message_signature: false,
         }
     }
 
@@ -218,6 +225,15 @@ impl UpdateReportResult {
         Self {
             error_outdated_report_content: false,
             error_too_many_reports: true,
+            error_invalid_message_signature: false,
+        }
+    }
+
+    pub fn invalid_message_signature() -> Self {
+        Self {
+            error_outdated_report_content: false,
+            error_too_many_reports: false,
+            error_invalid_message_signature: true,
         }
     }
 }
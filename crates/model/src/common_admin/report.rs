@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use simple_backend_model::UnixTime;
 use utoipa::ToSchema;
 
-use crate::{AccountId, AccountIdDb, AccountIdInternal, ContentId, ProfileAge, ReportIdDb, ReportProcessingState, ReportTypeNumber, ReportTypeNumberInternal};
+use crate::{AccountId, AccountIdDb, AccountIdInternal, ContentId, ProfileAge, ReportIdDb, ReportProcessingState, ReportTypeNumber, ReportTypeNumberInternal, SignedMessageInfo};
 
 #[derive(Debug, Clone)]
 pub struct ReportInternal {
@@ -100,10 +100,19 @@ pub struct ReportContent {
     pub profile_name: Option<String>,
     pub profile_text: Option<String>,
     pub profile_content: Option<ContentId>,
-    pub chat_message: Option<String>,
+    pub chat_message: Option<ChatMessageReportContent>,
     pub custom_report: Option<CustomReportContent>,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, ToSchema)]
+pub struct ChatMessageReportContent {
+    /// Message text from the report creator. The server has not
+    /// verified this.
+    pub message: String,
+    /// Reported message with valid server signature.
+    pub signed_message: SignedMessageInfo,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, ToSchema)]
 pub struct CustomReportContent {
     pub boolean_value: Option<bool>,
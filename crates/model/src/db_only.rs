@@ -1,8 +1,15 @@
-use crate::{FcmDeviceToken, PendingNotificationFlags};
+use crate::{AccessToken, FcmDeviceToken, LoginSessionId, PendingNotificationFlags, UnixTime};
 
 #[derive(Debug)]
 pub struct PushNotificationStateInfo {
-    pub fcm_device_token: Option<FcmDeviceToken>,
+    /// Login sessions which have FCM device token.
+    pub devices: Vec<PushNotificationDeviceInfo>,
+}
+
+#[derive(Debug)]
+pub struct PushNotificationDeviceInfo {
+    pub session_id: LoginSessionId,
+    pub fcm_device_token: FcmDeviceToken,
     pub fcm_notification_sent: bool,
 }
 
@@ -13,3 +20,11 @@ pub enum PushNotificationStateInfoWithFlags {
         flags: PendingNotificationFlags,
     },
 }
+
+#[derive(Debug, Clone)]
+pub struct LoginSessionAccessToken {
+    pub session_id: LoginSessionId,
+    pub access_token: AccessToken,
+    /// Access token creation time
+    pub access_token_unix_time: UnixTime,
+}
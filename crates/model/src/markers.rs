@@ -66,6 +66,7 @@ enable_logging!(
     AccountIdInternal,
     AccountId,
     Option<AccountIdDb>,
+    LoginSessionId,
     // Media
     ContentId,
     ContentIdDb,
@@ -82,6 +82,37 @@ impl ContentId {
     }
 }
 
+/// Image size variant of media content.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+pub enum ContentSize {
+    /// Max width or height is 1920 pixels.
+    #[default]
+    Original,
+    /// Max width or height is 960 pixels. If the original image
+    /// is not larger than that, the original image is used.
+    Medium,
+    /// Square thumbnail which width and height is 384 pixels.
+    ///
+    /// If the content is the first profile content, the thumbnail
+    /// is cropped using the profile grid crop info. Otherwise the
+    /// largest centered square is used.
+    Small,
+}
+
+impl ContentSize {
+    pub const VARIANTS: &'static [Self] = &[Self::Medium, Self::Small];
+
+    /// Suffix for content file name. Original size does not
+    /// have a suffix.
+    pub fn file_name_suffix(&self) -> &'static str {
+        match self {
+            Self::Original => "",
+            Self::Medium => "_medium",
+            Self::Small => "_small",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct ContentIdInternal {
     aid: AccountIdInternal,
@@ -183,6 +214,9 @@ pub enum ContentProcessingStateType {
     Completed = 3,
     /// Content processing failed.
     Failed = 4,
+    /// Content processing failed because the file format is not
+    /// supported.
+    UnsupportedFormat = 5,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
@@ -238,6 +272,13 @@ impl ContentProcessingState {
         self.cid = None;
         self.fd = None;
     }
+
+    pub fn change_to_unsupported_format(&mut self) {
+        self.state = ContentProcessingStateType::UnsupportedFormat;
+        self.wait_queue_position = None;
+        self.cid = None;
+        self.fd = None;
+    }
 }
 
 /// Version UUID for public profile content.
@@ -1,20 +1,14 @@
 // @generated automatically by Diesel CLI.
 
-diesel::table! {
-    use crate::schema_sqlite_types::*;
-
-    access_token (account_id) {
-        account_id -> Integer,
-        token -> Nullable<Text>,
-    }
-}
-
 diesel::table! {
     use crate::schema_sqlite_types::*;
 
     account (account_id) {
         account_id -> Integer,
         email -> Nullable<Text>,
+        email_verified -> Bool,
+        email_confirmation_sent_unix_time -> Nullable<Integer>,
+        email_notifications_enabled -> Bool,
     }
 }
 
@@ -33,6 +27,8 @@ diesel::table! {
     account_email_sending_state (account_id) {
         account_id -> Integer,
         account_registered_state_number -> Integer,
+        email_confirmation_state_number -> Integer,
+        chat_notification_digest_state_number -> Integer,
     }
 }
 
@@ -43,6 +39,7 @@ diesel::table! {
         row_type -> Integer,
         admin_access_granted_count -> Integer,
         next_news_publication_id -> Integer,
+        email_confirmation_token_key -> Nullable<Binary>,
     }
 }
 
@@ -117,6 +114,8 @@ diesel::table! {
         admin_news_create -> Bool,
         admin_news_edit_all -> Bool,
         admin_profile_statistics -> Bool,
+        admin_export_data -> Bool,
+        admin_search_profiles -> Bool,
     }
 }
 
@@ -164,7 +163,13 @@ diesel::table! {
 
     chat_report_chat_message (report_id) {
         report_id -> Integer,
-        chat_message -> Nullable<Text>,
+        chat_message -> Text,
+        message_sender_account_id_uuid -> Binary,
+        message_receiver_account_id_uuid -> Binary,
+        message_number -> Integer,
+        message_unix_time -> Integer,
+        message_bytes -> Binary,
+        message_signature -> Binary,
     }
 }
 
@@ -179,9 +184,6 @@ diesel::table! {
         sent_likes_sync_version -> Integer,
         matches_sync_version -> Integer,
         pending_notification -> Integer,
-        pending_notification_token -> Nullable<Text>,
-        fcm_notification_sent -> Bool,
-        fcm_device_token -> Nullable<Text>,
         new_received_likes_count -> Integer,
         next_received_like_id -> Integer,
         received_like_id_at_received_likes_iterator_reset -> Nullable<Integer>,
@@ -288,6 +290,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use crate::schema_sqlite_types::*;
+
+    history_profile_name_moderation (id) {
+        id -> Integer,
+        account_uuid -> Binary,
+        profile_name -> Text,
+        submitted_unix_time -> Integer,
+        moderation_state -> Integer,
+        moderator_account_uuid -> Nullable<Binary>,
+        moderation_unix_time -> Nullable<Integer>,
+    }
+}
+
 diesel::table! {
     use crate::schema_sqlite_types::*;
 
@@ -382,6 +398,39 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use crate::schema_sqlite_types::*;
+
+    history_profile_text_moderation (id) {
+        id -> Integer,
+        account_uuid -> Binary,
+        profile_text -> Text,
+        submitted_unix_time -> Integer,
+        moderation_state -> Integer,
+        moderator_account_uuid -> Nullable<Binary>,
+        moderation_unix_time -> Nullable<Integer>,
+        rejected_reason_category -> Nullable<Integer>,
+        rejected_reason_details -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    use crate::schema_sqlite_types::*;
+
+    login_session (session_id) {
+        session_id -> Binary,
+        account_id -> Integer,
+        access_token -> Text,
+        access_token_unix_time -> Integer,
+        refresh_token -> Binary,
+        fcm_device_token -> Nullable<Text>,
+        fcm_notification_sent -> Bool,
+        pending_notification_token -> Nullable<Text>,
+        created_unix_time -> Integer,
+        last_used_unix_time -> Integer,
+    }
+}
+
 diesel::table! {
     use crate::schema_sqlite_types::*;
 
@@ -401,6 +450,27 @@ diesel::table! {
         moderation_moderator_account_id -> Nullable<Integer>,
         usage_start_unix_time -> Nullable<Integer>,
         usage_end_unix_time -> Nullable<Integer>,
+        perceptual_hash -> Nullable<Integer>,
+        perceptual_hash_chunk_0 -> Nullable<Integer>,
+        perceptual_hash_chunk_1 -> Nullable<Integer>,
+        perceptual_hash_chunk_2 -> Nullable<Integer>,
+        perceptual_hash_chunk_3 -> Nullable<Integer>,
+        perceptual_hash_match -> Bool,
+    }
+}
+
+diesel::table! {
+    use crate::schema_sqlite_types::*;
+
+    media_content_perceptual_hash_blocklist (perceptual_hash) {
+        perceptual_hash -> Integer,
+        perceptual_hash_chunk_0 -> Integer,
+        perceptual_hash_chunk_1 -> Integer,
+        perceptual_hash_chunk_2 -> Integer,
+        perceptual_hash_chunk_3 -> Integer,
+        account_id -> Nullable<Integer>,
+        reason -> Integer,
+        added_unix_time -> Integer,
     }
 }
 
@@ -475,6 +545,7 @@ diesel::table! {
         sender_client_id -> Integer,
         sender_client_local_id -> Integer,
         message_bytes -> Binary,
+        server_signature -> Nullable<Binary>,
     }
 }
 
@@ -576,6 +647,8 @@ diesel::table! {
         profile_created_time_filter -> Nullable<Integer>,
         profile_edited_time_filter -> Nullable<Integer>,
         random_profile_order -> Bool,
+        hide_interacted_profiles -> Bool,
+        mutual_attribute_filters -> Bool,
         latitude -> Double,
         longitude -> Double,
         profile_sync_version -> Integer,
@@ -605,19 +678,23 @@ diesel::table! {
 diesel::table! {
     use crate::schema_sqlite_types::*;
 
-    queue_entry (queue_number, queue_type_number) {
-        queue_number -> Integer,
-        queue_type_number -> Integer,
+    public_key_history (account_id, public_key_version, public_key_id) {
         account_id -> Integer,
+        public_key_version -> Integer,
+        public_key_id -> Integer,
+        public_key_data -> Text,
+        upload_unix_time -> Integer,
+        server_signature -> Binary,
     }
 }
 
 diesel::table! {
     use crate::schema_sqlite_types::*;
 
-    refresh_token (account_id) {
+    queue_entry (queue_number, queue_type_number) {
+        queue_number -> Integer,
+        queue_type_number -> Integer,
         account_id -> Integer,
-        token -> Nullable<Binary>,
     }
 }
 
@@ -644,6 +721,7 @@ diesel::table! {
     sign_in_with_info (account_id) {
         account_id -> Integer,
         google_account_id -> Nullable<Text>,
+        apple_account_id -> Nullable<Text>,
     }
 }
 
@@ -665,7 +743,16 @@ diesel::table! {
     }
 }
 
-diesel::joinable!(access_token -> account_id (account_id));
+diesel::table! {
+    use crate::schema_sqlite_types::*;
+
+    used_refresh_token (id) {
+        id -> Integer,
+        refresh_token -> Binary,
+        session_id -> Binary,
+    }
+}
+
 diesel::joinable!(account -> account_id (account_id));
 diesel::joinable!(account_custom_report -> common_report (report_id));
 diesel::joinable!(account_email_sending_state -> account_id (account_id));
@@ -687,7 +774,9 @@ diesel::joinable!(history_profile_statistics_count_changes_all_genders -> histor
 diesel::joinable!(history_profile_statistics_count_changes_man -> history_profile_statistics_save_time (save_time_id));
 diesel::joinable!(history_profile_statistics_count_changes_non_binary -> history_profile_statistics_save_time (save_time_id));
 diesel::joinable!(history_profile_statistics_count_changes_woman -> history_profile_statistics_save_time (save_time_id));
+diesel::joinable!(login_session -> account_id (account_id));
 diesel::joinable!(media_content -> account_id (account_id));
+diesel::joinable!(media_content_perceptual_hash_blocklist -> account_id (account_id));
 diesel::joinable!(media_report_profile_content -> common_report (report_id));
 diesel::joinable!(media_state -> account_id (account_id));
 diesel::joinable!(news -> account_id (account_id_creator));
@@ -699,14 +788,14 @@ diesel::joinable!(profile_attributes_number_list_filters -> account_id (account_
 diesel::joinable!(profile_report_profile_name -> common_report (report_id));
 diesel::joinable!(profile_report_profile_text -> common_report (report_id));
 diesel::joinable!(public_key -> account_id (account_id));
+diesel::joinable!(public_key_history -> account_id (account_id));
 diesel::joinable!(queue_entry -> account_id (account_id));
-diesel::joinable!(refresh_token -> account_id (account_id));
 diesel::joinable!(shared_state -> account_id (account_id));
 diesel::joinable!(sign_in_with_info -> account_id (account_id));
 diesel::joinable!(used_content_ids -> account_id (account_id));
+diesel::joinable!(used_refresh_token -> login_session (session_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
-    access_token,
     account,
     account_custom_report,
     account_email_sending_state,
@@ -729,6 +818,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     history_performance_statistics_metric_name,
     history_performance_statistics_metric_value,
     history_performance_statistics_save_time,
+    history_profile_name_moderation,
     history_profile_statistics_age_changes_all_genders,
     history_profile_statistics_age_changes_men,
     history_profile_statistics_age_changes_non_binary,
@@ -739,7 +829,10 @@ diesel::allow_tables_to_appear_in_same_query!(
     history_profile_statistics_count_changes_non_binary,
     history_profile_statistics_count_changes_woman,
     history_profile_statistics_save_time,
+    history_profile_text_moderation,
+    login_session,
     media_content,
+    media_content_perceptual_hash_blocklist,
     media_report_profile_content,
     media_state,
     news,
@@ -756,10 +849,11 @@ diesel::allow_tables_to_appear_in_same_query!(
     profile_report_profile_text,
     profile_state,
     public_key,
+    public_key_history,
     queue_entry,
-    refresh_token,
     shared_state,
     sign_in_with_info,
     used_account_ids,
     used_content_ids,
+    used_refresh_token,
 );
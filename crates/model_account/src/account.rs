@@ -1,7 +1,7 @@
 use chrono::NaiveDate;
 use diesel::{prelude::*, Associations};
 use model::{ClientVersion, UnixTime};
-use model_server_data::{AuthPair, EmailAddress, GoogleAccountId, PublicationId, SignInWithInfo};
+use model_server_data::{AppleAccountId, AuthPair, EmailAddress, GoogleAccountId, PublicationId, SignInWithInfo};
 use model_server_state::DemoModeToken;
 use serde::{Deserialize, Serialize};
 use utils::time::age_in_years_from_birthdate;
@@ -21,6 +21,9 @@ pub use ban::*;
 mod report;
 pub use report::*;
 
+mod login_session;
+pub use login_session::*;
+
 // TODO(prod): Also add info what sign in with service is used?
 
 #[derive(Debug, Deserialize, Serialize, ToSchema, Clone, PartialEq)]
@@ -180,10 +183,18 @@ pub struct GetAccountBanTimeResult {
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq)]
 pub struct SignInWithLoginInfo {
     pub client_info: ClientInfo,
-    pub apple_token: Option<String>,
+    pub apple: Option<SignInWithAppleInfo>,
     pub google_token: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq)]
+pub struct SignInWithAppleInfo {
+    pub token: String,
+    /// Nonce which client generated. SHA-256 hash of the nonce in
+    /// lowercase hex format must be in the token.
+    pub nonce: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Queryable, Selectable, Associations)]
 #[diesel(belongs_to(AccountIdInternal, foreign_key = account_id))]
 #[diesel(table_name = crate::schema::sign_in_with_info)]
@@ -191,12 +202,14 @@ pub struct SignInWithLoginInfo {
 pub struct SignInWithInfoRaw {
     pub account_id: AccountIdDb,
     pub google_account_id: Option<GoogleAccountId>,
+    pub apple_account_id: Option<AppleAccountId>,
 }
 
 impl From<SignInWithInfoRaw> for SignInWithInfo {
     fn from(raw: SignInWithInfoRaw) -> Self {
         Self {
             google_account_id: raw.google_account_id,
+            apple_account_id: raw.apple_account_id,
         }
     }
 }
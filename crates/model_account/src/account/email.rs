@@ -1,6 +1,7 @@
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
-use simple_backend_model::diesel_i64_try_from;
+use simple_backend_model::{diesel_i64_try_from, UnixTime};
+use utoipa::ToSchema;
 
 use crate::{schema_sqlite_types::Integer, EmailMessages, EnumParsingError};
 
@@ -53,12 +54,43 @@ impl Default for EmailSendingState {
 #[diesel(check_for_backend(crate::Db))]
 pub struct AccountEmailSendingStateRaw {
     pub account_registered_state_number: EmailSendingState,
+    pub email_confirmation_state_number: EmailSendingState,
+    pub chat_notification_digest_state_number: EmailSendingState,
 }
 
 impl AccountEmailSendingStateRaw {
     pub fn get_ref_mut_to(&mut self, message: EmailMessages) -> &mut EmailSendingState {
         match message {
             EmailMessages::AccountRegistered => &mut self.account_registered_state_number,
+            EmailMessages::EmailConfirmation => &mut self.email_confirmation_state_number,
+            EmailMessages::ChatNotificationDigest => {
+                &mut self.chat_notification_digest_state_number
+            }
         }
     }
 }
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, Queryable, Selectable)]
+#[diesel(table_name = crate::schema::account)]
+#[diesel(check_for_backend(crate::Db))]
+pub struct EmailConfirmationState {
+    /// True when the current email address is confirmed.
+    /// Changing the email address resets this to false.
+    pub email_verified: bool,
+    /// Time when the latest email confirmation email was requested.
+    pub email_confirmation_sent_unix_time: Option<UnixTime>,
+}
+
+/// Token from email confirmation email.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EmailConfirmationToken {
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct SendEmailConfirmationResult {
+    pub error_email_not_set: bool,
+    pub error_email_already_verified: bool,
+    /// New email confirmation email can be requested after this time.
+    pub error_try_again_later_unix_time: Option<UnixTime>,
+}
@@ -0,0 +1,20 @@
+use model::{LoginSessionId, UnixTime};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq)]
+pub struct LoginSessionInfo {
+    pub id: LoginSessionId,
+    pub created_unix_time: UnixTime,
+    /// Time when tokens were refreshed last time.
+    pub last_used_unix_time: UnixTime,
+    /// True if the session is the session which made the request.
+    pub current: bool,
+    /// True if FCM device token is set for the session.
+    pub push_notifications_enabled: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq)]
+pub struct LoginSessionList {
+    pub sessions: Vec<LoginSessionInfo>,
+}
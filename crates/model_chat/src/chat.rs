@@ -1,6 +1,6 @@
 use diesel::prelude::*;
 use model::{
-    FcmDeviceToken, MatchId, MatchesSyncVersion, MessageNumber, NewReceivedLikesCount, PendingNotification, PublicKeyId, PublicKeyVersion, ReceivedBlocksSyncVersion, ReceivedLikeId, ReceivedLikesSyncVersion, SentBlocksSyncVersion, SentLikesSyncVersion
+    MatchId, MatchesSyncVersion, MessageNumber, MessageSignature, NewReceivedLikesCount, PendingNotification, PublicKeyId, PublicKeyVersion, ReceivedBlocksSyncVersion, ReceivedLikeId, ReceivedLikesSyncVersion, SentBlocksSyncVersion, SentLikesSyncVersion
 };
 use model_server_data::LimitedActionStatus;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -32,8 +32,6 @@ pub struct ChatStateRaw {
     pub sent_likes_sync_version: SentLikesSyncVersion,
     pub matches_sync_version: MatchesSyncVersion,
     pub pending_notification: PendingNotification,
-    pub fcm_notification_sent: bool,
-    pub fcm_device_token: Option<FcmDeviceToken>,
     pub new_received_likes_count: NewReceivedLikesCount,
     pub next_received_like_id: ReceivedLikeId,
     pub received_like_id_at_received_likes_iterator_reset: Option<ReceivedLikeId>,
@@ -51,6 +49,7 @@ pub struct PendingMessageInternal {
     pub message_bytes: Vec<u8>,
     pub sender_client_id: ClientId,
     pub sender_client_local_id: ClientLocalId,
+    pub server_signature: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq, Default)]
@@ -83,6 +82,8 @@ pub struct PendingMessage {
     pub id: PendingMessageId,
     /// Unix time when server received the message.
     pub unix_time: UnixTime,
+    /// Server signature for the message metadata.
+    pub signature: Option<MessageSignature>,
 }
 
 #[derive(Debug, Clone)]
@@ -224,6 +225,9 @@ pub struct SendMessageResult {
     ut: Option<UnixTime>,
     /// None if error happened
     mn: Option<MessageNumber>,
+    /// Server signature for the message metadata.
+    /// None if error happened
+    signature: Option<MessageSignature>,
     // Errors
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     #[schema(default = false)]
@@ -237,6 +241,15 @@ pub struct SendMessageResult {
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     #[schema(default = false)]
     pub error_receiver_blocked_sender_or_receiver_not_found: bool,
+    /// Receiver has too many not acknowledged messages from all
+    /// conversations.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[schema(default = false)]
+    pub error_receiver_inbox_full: bool,
+    /// Message is larger than the server message size limit.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[schema(default = false)]
+    pub error_message_too_large: bool,
 }
 
 impl SendMessageResult {
@@ -245,6 +258,8 @@ impl SendMessageResult {
             || self.error_too_many_sender_acknowledgements_missing
             || self.error_receiver_public_key_outdated
             || self.error_receiver_blocked_sender_or_receiver_not_found
+            || self.error_receiver_inbox_full
+            || self.error_message_too_large
     }
 
     pub fn too_many_receiver_acknowledgements_missing() -> Self {
@@ -275,10 +290,26 @@ impl SendMessageResult {
         }
     }
 
-    pub fn successful(values: NewPendingMessageValues) -> Self {
+    pub fn receiver_inbox_full() -> Self {
+        Self {
+            error_receiver_inbox_full: true,
+            ..Self::default()
+        }
+    }
+
+    /// Message size is larger than configured max message size.
+    pub fn message_too_large() -> Self {
+        Self {
+            error_message_too_large: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn successful(values: NewPendingMessageValues, signature: MessageSignature) -> Self {
         Self {
             ut: Some(values.unix_time),
             mn: Some(values.message_number),
+            signature: Some(signature),
             ..Self::default()
         }
     }
@@ -327,6 +358,11 @@ pub struct NewPendingMessageValues {
 pub struct SendLikeResult {
     pub status: Option<LimitedActionStatus>,
     pub error_account_interaction_state_mismatch: Option<CurrentAccountInteractionState>,
+    /// The like receiver has enabled mutual attribute filters and
+    /// current account's profile attributes do not match with those.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[schema(default = false)]
+    pub error_attribute_filters_mismatch: bool,
 }
 
 impl SendLikeResult {
@@ -334,6 +370,7 @@ impl SendLikeResult {
         Self {
             status: Some(status),
             error_account_interaction_state_mismatch: None,
+            error_attribute_filters_mismatch: false,
         }
     }
 
@@ -341,6 +378,15 @@ impl SendLikeResult {
         Self {
             status: None,
             error_account_interaction_state_mismatch: Some(state),
+            error_attribute_filters_mismatch: false,
+        }
+    }
+
+    pub fn error_attribute_filters_mismatch() -> Self {
+        Self {
+            status: None,
+            error_account_interaction_state_mismatch: None,
+            error_attribute_filters_mismatch: true,
         }
     }
 }
@@ -1,5 +1,5 @@
 use diesel::sql_types::Text;
-use model::{PublicKeyId, PublicKeyVersion};
+use model::{PublicKeyId, PublicKeySignature, PublicKeyVersion, UnixTime};
 use serde::{Deserialize, Serialize};
 use simple_backend_model::diesel_string_wrapper;
 use utoipa::ToSchema;
@@ -58,3 +58,30 @@ pub struct SetPublicKey {
     pub version: PublicKeyVersion,
     pub data: PublicKeyData,
 }
+
+/// Public key from account's public key history.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq)]
+pub struct PublicKeyHistoryEntry {
+    pub id: PublicKeyId,
+    pub version: PublicKeyVersion,
+    pub data: PublicKeyData,
+    /// Unix time when server received the public key.
+    pub ut: UnixTime,
+    pub signature: PublicKeySignature,
+}
+
+/// All public keys which account has uploaded.
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq)]
+pub struct PublicKeyHistory {
+    pub keys: Vec<PublicKeyHistoryEntry>,
+}
+
+impl PublicKeyHistory {
+    /// Max public key upload count for one account.
+    pub const MAX_UPLOAD_COUNT: i64 = 1024;
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq)]
+pub struct GetPublicKeyHistoryEntry {
+    pub key: Option<PublicKeyHistoryEntry>,
+}
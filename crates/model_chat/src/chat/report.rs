@@ -1,9 +1,15 @@
-use model::AccountId;
+use model::{AccountId, SignedMessageInfo};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
 pub struct UpdateChatMessageReport {
     pub target: AccountId,
+    /// Decrypted message text for moderators. The server can not
+    /// verify this as the message bytes are end-to-end encrypted.
     pub message: String,
+    /// Server signed reported message. The report target
+    /// must be the message sender and the report creator must be the
+    /// message receiver.
+    pub signed_message: SignedMessageInfo,
 }
@@ -1,5 +1,8 @@
 use diesel::{prelude::*, sql_types::BigInt, AsExpression, FromSqlRow};
-use model::{sync_version_wrappers, ContentId, ContentIdDb, ContentSlot, ProfileContentVersion, UnixTime};
+use model::{
+    sync_version_wrappers, ContentId, ContentIdDb, ContentSize, ContentSlot, ProfileContentVersion,
+    UnixTime,
+};
 use model_server_data::{MediaContentType, ProfileContentEditedTime};
 use num_enum::TryFromPrimitive;
 use serde::{Deserialize, Serialize};
@@ -19,6 +22,9 @@ pub use content::*;
 mod report;
 pub use report::*;
 
+mod perceptual_hash;
+pub use perceptual_hash::*;
+
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema, IntoParams)]
 pub struct SlotId {
     pub slot_id: u8,
@@ -177,6 +183,15 @@ impl ContentModerationState {
         }
     }
 
+    /// State for content which is moved to moderation.
+    pub fn waiting_moderation(perceptual_hash_match: bool) -> Self {
+        if perceptual_hash_match {
+            Self::WaitingHumanModeration
+        } else {
+            Self::WaitingBotOrHumanModeration
+        }
+    }
+
     pub fn is_in_slot(&self) -> bool {
         match self {
             Self::InSlot => true,
@@ -218,6 +233,8 @@ pub struct MediaContentRaw {
     pub moderation_moderator_account_id: Option<AccountIdDb>,
     pub usage_start_unix_time: Option<UnixTime>,
     pub usage_end_unix_time: Option<UnixTime>,
+    pub perceptual_hash: Option<PerceptualHash>,
+    pub perceptual_hash_match: bool,
 }
 
 impl MediaContentRaw {
@@ -245,6 +262,10 @@ impl MediaContentRaw {
         self.id
     }
 
+    pub fn waiting_moderation_state(&self) -> ContentModerationState {
+        ContentModerationState::waiting_moderation(self.perceptual_hash_match)
+    }
+
     pub fn removable_by_user(&self, remove_wait_time: u32) -> bool {
         if self.usage_start_unix_time.is_some() {
             return false;
@@ -452,6 +473,9 @@ pub struct GetContentQueryParams {
     /// If true media content access is allowed when users are a match.
     #[serde(default)]
     pub is_match: bool,
+    /// Image size variant.
+    #[serde(default)]
+    pub size: ContentSize,
 }
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
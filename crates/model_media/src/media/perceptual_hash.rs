@@ -0,0 +1,199 @@
+use diesel::{sql_types::BigInt, AsExpression, FromSqlRow};
+use model::{AccountId, ContentId, UnixTime};
+use num_enum::TryFromPrimitive;
+use serde::{Deserialize, Serialize};
+use simple_backend_model::{diesel_i64_try_from, diesel_i64_wrapper};
+use utoipa::ToSchema;
+
+use crate::{schema_sqlite_types::Integer, ContentModerationState};
+
+/// Images are near-duplicates if the Hamming distance between
+/// the perceptual hashes is equal or less than this.
+pub const PERCEPTUAL_HASH_MAX_DISTANCE: u32 = 10;
+
+/// Perceptual hash is split to this many 16-bit chunks which are
+/// stored in indexed database columns. Similar hashes are searched
+/// using multi-index hashing.
+pub const PERCEPTUAL_HASH_CHUNK_COUNT: usize = 4;
+
+const PERCEPTUAL_HASH_CHUNK_BITS: u32 = 16;
+
+/// If hashes are similar, at least one chunk pair has Hamming
+/// distance equal or less than this (pigeonhole principle).
+const PERCEPTUAL_HASH_CHUNK_MAX_DISTANCE: u32 =
+    PERCEPTUAL_HASH_MAX_DISTANCE / PERCEPTUAL_HASH_CHUNK_COUNT as u32;
+
+/// 64-bit difference hash (dHash) of an image.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, FromSqlRow, AsExpression,
+)]
+#[diesel(sql_type = BigInt)]
+pub struct PerceptualHash {
+    value: i64,
+}
+
+impl PerceptualHash {
+    pub fn new(value: i64) -> Self {
+        Self { value }
+    }
+
+    pub fn from_u64(value: u64) -> Self {
+        Self {
+            value: value as i64,
+        }
+    }
+
+    pub fn as_i64(&self) -> &i64 {
+        &self.value
+    }
+
+    /// Hamming distance between the hashes.
+    pub fn distance(&self, other: PerceptualHash) -> u32 {
+        (self.value ^ other.value).count_ones()
+    }
+
+    pub fn is_similar(&self, other: PerceptualHash) -> bool {
+        self.distance(other) <= PERCEPTUAL_HASH_MAX_DISTANCE
+    }
+
+    /// Hash split to 16-bit chunks.
+    pub fn chunks(&self) -> [i64; PERCEPTUAL_HASH_CHUNK_COUNT] {
+        let value = self.value as u64;
+        let mut chunks = [0; PERCEPTUAL_HASH_CHUNK_COUNT];
+        for (i, chunk) in chunks.iter_mut().enumerate() {
+            let shift = i as u32 * PERCEPTUAL_HASH_CHUNK_BITS;
+            *chunk = ((value >> shift) & 0xFFFF) as i64;
+        }
+        chunks
+    }
+
+    /// Chunk values which similar hashes must have in at least
+    /// one chunk position. Index of the returned array matches with
+    /// the chunk index.
+    ///
+    /// Database candidates found with these values must be filtered
+    /// using [Self::is_similar].
+    pub fn similar_chunk_values(&self) -> [Vec<i64>; PERCEPTUAL_HASH_CHUNK_COUNT] {
+        self.chunks().map(|chunk| {
+            let mut values = vec![chunk];
+            let mut previous = vec![chunk];
+            for _ in 0..PERCEPTUAL_HASH_CHUNK_MAX_DISTANCE {
+                let mut next = vec![];
+                for value in previous {
+                    // Flip only bits higher than the highest already
+                    // flipped bit, so that every value is generated once.
+                    let flipped = value ^ chunk;
+                    let start = if flipped == 0 {
+                        0
+                    } else {
+                        64 - flipped.leading_zeros()
+                    };
+                    for bit in start..PERCEPTUAL_HASH_CHUNK_BITS {
+                        next.push(value ^ (1 << bit));
+                    }
+                }
+                values.extend_from_slice(&next);
+                previous = next;
+            }
+            values
+        })
+    }
+}
+
+diesel_i64_wrapper!(PerceptualHash);
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    TryFromPrimitive,
+    FromSqlRow,
+    AsExpression,
+)]
+#[diesel(sql_type = Integer)]
+#[repr(i64)]
+pub enum PerceptualHashBlocklistReason {
+    /// Human moderator rejected the content.
+    RejectedContent = 0,
+    /// Content owner is banned.
+    BannedAccount = 1,
+}
+
+diesel_i64_try_from!(PerceptualHashBlocklistReason);
+
+/// Content of other account which is a near-duplicate of
+/// some content.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SimilarContent {
+    pub account_id: AccountId,
+    pub content_id: ContentId,
+    pub state: ContentModerationState,
+    /// Hamming distance between the perceptual hashes.
+    pub distance: u32,
+}
+
+/// Perceptual hash blocklist entry which is similar to
+/// some content.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SimilarBlocklistedContent {
+    /// Account which uploaded the blocklisted image. Empty if the
+    /// account is deleted.
+    pub account_id: Option<AccountId>,
+    pub reason: PerceptualHashBlocklistReason,
+    pub added_unix_time: UnixTime,
+    /// Hamming distance between the perceptual hashes.
+    pub distance: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_distance() {
+        let hash = PerceptualHash::from_u64(u64::MAX);
+        assert_eq!(hash.distance(hash), 0);
+        assert_eq!(hash.distance(PerceptualHash::new(0)), 64);
+        assert!(hash.is_similar(PerceptualHash::from_u64(u64::MAX >> 10)));
+        assert!(!hash.is_similar(PerceptualHash::from_u64(u64::MAX >> 11)));
+    }
+
+    #[test]
+    fn hash_chunks() {
+        let hash = PerceptualHash::from_u64(0x0123_4567_89AB_CDEF);
+        assert_eq!(hash.chunks(), [0xCDEF, 0x89AB, 0x4567, 0x0123]);
+    }
+
+    #[test]
+    fn similar_chunk_values_are_unique() {
+        let values = PerceptualHash::from_u64(0x0123_4567_89AB_CDEF).similar_chunk_values();
+        for values in values {
+            // 1 + 16 + (16 * 15 / 2)
+            assert_eq!(values.len(), 137);
+            let mut unique = values.clone();
+            unique.sort_unstable();
+            unique.dedup();
+            assert_eq!(unique.len(), values.len());
+        }
+    }
+
+    #[test]
+    fn similar_hash_shares_chunk_value() {
+        let hash = PerceptualHash::from_u64(0x0123_4567_89AB_CDEF);
+        // Chunk distances are 1, 2, 3 and 3.
+        let other = PerceptualHash::from_u64(0x0123_4567_89AB_CDEF ^ 0x0007_0007_0003_0001);
+        assert!(hash.is_similar(other));
+        let values = hash.similar_chunk_values();
+        let matches = other
+            .chunks()
+            .iter()
+            .zip(values.iter())
+            .any(|(chunk, values)| values.contains(chunk));
+        assert!(matches);
+    }
+}
@@ -6,6 +6,7 @@ use utoipa::{IntoParams, ToSchema};
 
 use crate::{
     ProfileContentModerationRejectedReasonCategory, ProfileContentModerationRejectedReasonDetails,
+    SimilarBlocklistedContent, SimilarContent,
 };
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, ToSchema)]
@@ -43,3 +44,14 @@ pub struct PostModerateProfileContent {
     /// the content to waiting for human moderation state.
     pub move_to_human: Option<bool>,
 }
+
+/// Content which has a similar perceptual hash.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct SimilarContentList {
+    /// Similar content or blocklisted hash existed when the content
+    /// was uploaded, so the content is moderated by humans.
+    pub perceptual_hash_match: bool,
+    /// Content of other accounts.
+    pub content: Vec<SimilarContent>,
+    pub blocklist: Vec<SimilarBlocklistedContent>,
+}
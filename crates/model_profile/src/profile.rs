@@ -121,6 +121,8 @@ pub struct ProfileStateInternal {
     pub profile_created_time_filter: Option<ProfileCreatedTimeFilter>,
     pub profile_edited_time_filter: Option<ProfileEditedTimeFilter>,
     pub random_profile_order: bool,
+    pub hide_interacted_profiles: bool,
+    pub mutual_attribute_filters: bool,
     pub profile_sync_version: ProfileSyncVersion,
     pub profile_name_moderation_state: ProfileNameModerationState,
     pub profile_text_moderation_state: ProfileTextModerationState,
@@ -144,6 +146,8 @@ impl From<ProfileStateInternal> for ProfileStateCached {
             profile_created_time_filter: value.profile_created_time_filter,
             profile_edited_time_filter: value.profile_edited_time_filter,
             random_profile_order: value.random_profile_order,
+            hide_interacted_profiles: value.hide_interacted_profiles,
+            mutual_attribute_filters: value.mutual_attribute_filters,
             profile_name_moderation_state: value.profile_name_moderation_state,
             profile_text_moderation_state: value.profile_text_moderation_state,
             profile_edited_time: value.profile_edited_unix_time,
@@ -13,12 +13,24 @@ pub struct ProfileFilteringSettingsUpdate {
     filters: Vec<ProfileAttributeFilterValueUpdate>,
     last_seen_time_filter: Option<LastSeenTimeFilter>,
     unlimited_likes_filter: Option<bool>,
+    /// Show profiles until this far from current location. The value
+    /// is in kilometers.
+    ///
+    /// The value must be `None`, 1 or greater number.
     max_distance_km_filter: Option<MaxDistanceKm>,
     profile_created_filter: Option<ProfileCreatedTimeFilter>,
     profile_edited_filter: Option<ProfileEditedTimeFilter>,
+    /// Randomize iterator starting position within the profile index area
+    /// which current position and [Self::max_distance_km_filter] defines.
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     #[schema(default = false)]
     random_profile_order: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[schema(default = false)]
+    hide_interacted_profiles: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[schema(default = false)]
+    mutual_attribute_filters: bool,
 }
 
 impl ProfileFilteringSettingsUpdate {
@@ -84,6 +96,8 @@ impl ProfileFilteringSettingsUpdate {
             profile_created_filter: self.profile_created_filter,
             profile_edited_filter: self.profile_edited_filter,
             random_profile_order: self.random_profile_order,
+            hide_interacted_profiles: self.hide_interacted_profiles,
+            mutual_attribute_filters: self.mutual_attribute_filters,
         })
     }
 }
@@ -97,6 +111,8 @@ pub struct ProfileFilteringSettingsUpdateValidated {
     pub profile_created_filter: Option<ProfileCreatedTimeFilter>,
     pub profile_edited_filter: Option<ProfileEditedTimeFilter>,
     pub random_profile_order: bool,
+    pub hide_interacted_profiles: bool,
+    pub mutual_attribute_filters: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq, Eq)]
@@ -128,6 +144,18 @@ pub struct GetProfileFilteringSettings {
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     #[schema(default = false)]
     /// Randomize iterator starting position within the profile index area which
-    /// current position and [Self::max_distance_km] defines.
+    /// current position and [Self::max_distance_km_filter] defines.
     pub random_profile_order: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[schema(default = false)]
+    /// Hide profiles which current account has liked, matched or blocked.
+    /// The setting is applied starting from the next profile iterator
+    /// reset.
+    pub hide_interacted_profiles: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[schema(default = false)]
+    /// Show current profile only to accounts which profile attributes
+    /// match with current account's attribute filters. Accounts which
+    /// do not match can not send likes to current account.
+    pub mutual_attribute_filters: bool,
 }
@@ -4,6 +4,7 @@ use utoipa::ToSchema;
 
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema, PartialEq, Default)]
 pub struct ProfilePage {
+    /// Profiles sorted by distance from the current account.
     pub profiles: Vec<ProfileLink>,
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     #[schema(default = false)]
@@ -7,8 +7,14 @@ pub use iterator::*;
 mod profile_data;
 pub use profile_data::*;
 
+mod moderation_history;
+pub use moderation_history::*;
+
 mod profile_name_allowlist;
 pub use profile_name_allowlist::*;
 
 mod profile_text;
 pub use profile_text::*;
+
+mod search;
+pub use search::*;
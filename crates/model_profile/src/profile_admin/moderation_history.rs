@@ -0,0 +1,57 @@
+use diesel::prelude::*;
+use model_server_data::{ProfileNameModerationState, ProfileTextModerationState};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    AccountId, ProfileTextModerationRejectedReasonCategory,
+    ProfileTextModerationRejectedReasonDetails, UnixTime,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable)]
+#[diesel(table_name = crate::schema::history_profile_name_moderation)]
+#[diesel(check_for_backend(crate::Db))]
+pub struct ProfileNameModerationHistoryEntry {
+    #[diesel(column_name = profile_name)]
+    pub name: String,
+    #[diesel(column_name = submitted_unix_time)]
+    pub submitted: UnixTime,
+    /// Latest moderation state for the name.
+    #[diesel(column_name = moderation_state)]
+    pub state: ProfileNameModerationState,
+    /// Human or bot moderator account which made the latest
+    /// moderation decision.
+    #[diesel(column_name = moderator_account_uuid)]
+    pub moderator_id: Option<AccountId>,
+    #[diesel(column_name = moderation_unix_time)]
+    pub moderated: Option<UnixTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Queryable, Selectable)]
+#[diesel(table_name = crate::schema::history_profile_text_moderation)]
+#[diesel(check_for_backend(crate::Db))]
+pub struct ProfileTextModerationHistoryEntry {
+    #[diesel(column_name = profile_text)]
+    pub text: String,
+    #[diesel(column_name = submitted_unix_time)]
+    pub submitted: UnixTime,
+    /// Latest moderation state for the text.
+    #[diesel(column_name = moderation_state)]
+    pub state: ProfileTextModerationState,
+    /// Human or bot moderator account which made the latest
+    /// moderation decision.
+    #[diesel(column_name = moderator_account_uuid)]
+    pub moderator_id: Option<AccountId>,
+    #[diesel(column_name = moderation_unix_time)]
+    pub moderated: Option<UnixTime>,
+    pub rejected_reason_category: Option<ProfileTextModerationRejectedReasonCategory>,
+    pub rejected_reason_details: Option<ProfileTextModerationRejectedReasonDetails>,
+}
+
+/// Submitted profile names and texts of one account. The latest
+/// submission is the first item.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetProfileModerationHistory {
+    pub names: Vec<ProfileNameModerationHistoryEntry>,
+    pub texts: Vec<ProfileTextModerationHistoryEntry>,
+}
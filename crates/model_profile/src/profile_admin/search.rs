@@ -0,0 +1,146 @@
+use diesel::sql_types::{BigInt, Binary, Bool, Text};
+use model::{AccountId, ProfileAge, UnixTime};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Minimum text length which the trigram tokenizer of the
+/// profile search index can match.
+const SEARCH_TERM_MIN_CHAR_COUNT: usize = 3;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct ProfileSearchSettings {
+    /// Text which is searched from profile name and profile text.
+    /// Matching is case insensitive and whitespace separated terms
+    /// can match anywhere in the name or text, so prefixes of
+    /// words also match.
+    ///
+    /// Terms shorter than three characters are ignored. If there
+    /// are no search terms, only the other filters are used.
+    #[serde(default)]
+    #[param(default = "")]
+    pub text: String,
+    /// Allow small typing errors in the search terms. Profiles
+    /// which match only some parts of the search terms are also
+    /// returned. Results are ordered by relevance.
+    #[serde(default)]
+    #[param(default = false)]
+    pub fuzzy: bool,
+    pub initial_setup_completed: Option<bool>,
+    pub banned: Option<bool>,
+    pub pending_deletion: Option<bool>,
+    /// Minimum count of reports which other accounts have made
+    /// about the account.
+    pub min_report_count: Option<i64>,
+    /// Account creation time must be equal or greater than this.
+    pub created_after_unix_time: Option<i64>,
+    /// Account creation time must be equal or less than this.
+    pub created_before_unix_time: Option<i64>,
+    pub page: i64,
+}
+
+impl ProfileSearchSettings {
+    /// Create SQLite FTS5 query from [Self::text].
+    ///
+    /// Returns `None` if there are no search terms.
+    pub fn full_text_search_query(&self) -> Option<String> {
+        let terms = self
+            .text
+            .split_whitespace()
+            .filter(|term| term.chars().count() >= SEARCH_TERM_MIN_CHAR_COUNT);
+
+        let phrases: Vec<String> = if self.fuzzy {
+            terms
+                .flat_map(|term| {
+                    let chars: Vec<char> = term.chars().collect();
+                    chars
+                        .windows(SEARCH_TERM_MIN_CHAR_COUNT)
+                        .map(|trigram| trigram.iter().collect::<String>())
+                        .collect::<Vec<_>>()
+                })
+                .map(|trigram| quote_phrase(&trigram))
+                .collect()
+        } else {
+            terms.map(quote_phrase).collect()
+        };
+
+        if phrases.is_empty() {
+            None
+        } else if self.fuzzy {
+            Some(phrases.join(" OR "))
+        } else {
+            Some(phrases.join(" AND "))
+        }
+    }
+}
+
+fn quote_phrase(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, diesel::QueryableByName)]
+pub struct ProfileSearchResultValue {
+    #[diesel(sql_type = Binary)]
+    pub account_id: AccountId,
+    #[diesel(sql_type = BigInt)]
+    #[schema(value_type = i64)]
+    pub age: ProfileAge,
+    #[diesel(sql_type = Text)]
+    pub name: String,
+    #[diesel(sql_type = Text)]
+    pub ptext: String,
+    #[diesel(sql_type = Bool)]
+    pub initial_setup_completed: bool,
+    #[diesel(sql_type = Bool)]
+    pub banned: bool,
+    #[diesel(sql_type = Bool)]
+    pub pending_deletion: bool,
+    /// Count of reports which other accounts have made about
+    /// the account.
+    #[diesel(sql_type = BigInt)]
+    pub report_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub account_created: UnixTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProfileSearchResultPage {
+    pub values: Vec<ProfileSearchResultValue>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(text: &str, fuzzy: bool) -> ProfileSearchSettings {
+        ProfileSearchSettings {
+            text: text.to_string(),
+            fuzzy,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn short_terms_are_ignored() {
+        assert_eq!(settings("a bc", false).full_text_search_query(), None);
+        assert_eq!(
+            settings("ab test", false).full_text_search_query(),
+            Some("\"test\"".to_string())
+        );
+    }
+
+    #[test]
+    fn quotes_are_escaped() {
+        assert_eq!(
+            settings("a\"b", false).full_text_search_query(),
+            Some("\"a\"\"b\"".to_string())
+        );
+    }
+
+    #[test]
+    fn fuzzy_query_contains_trigrams() {
+        assert_eq!(
+            settings("test", true).full_text_search_query(),
+            Some("\"tes\" OR \"est\"".to_string())
+        );
+    }
+}
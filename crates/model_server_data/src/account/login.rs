@@ -5,6 +5,7 @@ use simple_backend_model::diesel_string_wrapper;
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct SignInWithInfo {
     pub google_account_id: Option<GoogleAccountId>,
+    pub apple_account_id: Option<AppleAccountId>,
 }
 
 impl SignInWithInfo {
@@ -16,8 +17,16 @@ impl SignInWithInfo {
         }
     }
 
+    pub fn apple_account_id_matches_with(&self, id: &AppleAccountId) -> bool {
+        if let Some(apple_account_id) = &self.apple_account_id {
+            apple_account_id == id
+        } else {
+            false
+        }
+    }
+
     pub fn some_sign_in_with_method_is_set(&self) -> bool {
-        self.google_account_id.is_some()
+        self.google_account_id.is_some() || self.apple_account_id.is_some()
     }
 }
 
@@ -39,3 +48,22 @@ impl GoogleAccountId {
 }
 
 diesel_string_wrapper!(GoogleAccountId);
+
+#[derive(
+    Debug, Serialize, Deserialize, Clone, PartialEq, diesel::FromSqlRow, diesel::AsExpression,
+)]
+#[diesel(sql_type = Text)]
+#[serde(transparent)]
+pub struct AppleAccountId(pub String);
+
+impl AppleAccountId {
+    pub fn new(id: String) -> Self {
+        Self(id)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+diesel_string_wrapper!(AppleAccountId);
@@ -18,6 +18,7 @@ enable_logging!(
 disable_logging!(
     // Account
     GoogleAccountId,
+    AppleAccountId,
     // Chat
     // MessageNumber,
     // // General
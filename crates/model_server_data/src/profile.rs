@@ -111,6 +111,8 @@ pub struct ProfileStateCached {
     pub profile_edited_time_filter: Option<ProfileEditedTimeFilter>,
     pub max_distance_km_filter: Option<MaxDistanceKm>,
     pub random_profile_order: bool,
+    pub hide_interacted_profiles: bool,
+    pub mutual_attribute_filters: bool,
     pub profile_name_moderation_state: ProfileNameModerationState,
     pub profile_text_moderation_state: ProfileTextModerationState,
     pub profile_edited_time: ProfileEditedTime,
@@ -1,4 +1,10 @@
-use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU16, Ordering};
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicBool, AtomicI64, AtomicU16, Ordering},
+        Arc,
+    },
+};
 
 use model::{AccountId, InitialSetupCompletedTime, ProfileAge, ProfileContentVersion};
 use nalgebra::DMatrix;
@@ -7,11 +13,18 @@ use simple_backend_model::UnixTime;
 use super::{
     ProfileCreatedTimeFilter, LastSeenTimeFilter, ProfileAttributeFilterValue, ProfileAttributesInternal, ProfileEditedTime, ProfileEditedTimeFilter, ProfileInternal, ProfileSearchAgeRangeValidated, ProfileStateCached, SearchGroupFlags, SearchGroupFlagsFilter, SortedProfileAttributes
 };
-use crate::{LastSeenTime, ProfileContentEditedTime, ProfileLink};
+use crate::{LastSeenTime, LocationInternal, MaxDistanceKm, ProfileContentEditedTime, ProfileLink};
 
 #[derive(Debug)]
 pub struct ProfileQueryMakerDetails {
+    /// Profile viewer location for sorting profiles by distance
+    pub location: LocationInternal,
+    /// Location index area is a square, so profiles in the area
+    /// corners must be filtered using the exact distance.
+    pub max_distance_km: Option<MaxDistanceKm>,
     pub age: ProfileAge,
+    /// Profile viewer attributes for mutual attribute filters
+    pub attributes: SortedProfileAttributes,
     pub search_age_range: ProfileSearchAgeRangeValidated,
     pub search_groups_filter: SearchGroupFlagsFilter,
     pub attribute_filters: Vec<ProfileAttributeFilterValue>,
@@ -19,16 +32,24 @@ pub struct ProfileQueryMakerDetails {
     pub unlimited_likes_filter: Option<bool>,
     pub profile_created_time_filter: Option<ProfileCreatedTimeFilter>,
     pub profile_edited_time_filter: Option<ProfileEditedTimeFilter>,
+    /// Profiles which are not returned from the profile iterator
+    pub hidden_profiles: Option<Arc<HashSet<AccountId>>>,
 }
 
 impl ProfileQueryMakerDetails {
     pub fn new(
+        location: LocationInternal,
         profile: &ProfileInternal,
         state: &ProfileStateCached,
+        attributes: SortedProfileAttributes,
         attribute_filters: Vec<ProfileAttributeFilterValue>,
+        hidden_profiles: Option<Arc<HashSet<AccountId>>>,
     ) -> Self {
         Self {
+            location,
+            max_distance_km: state.max_distance_km_filter,
             age: profile.age,
+            attributes,
             search_age_range: ProfileSearchAgeRangeValidated::new(
                 state.search_age_range_min,
                 state.search_age_range_max,
@@ -39,6 +60,7 @@ impl ProfileQueryMakerDetails {
             unlimited_likes_filter: state.unlimited_likes_filter,
             profile_created_time_filter: state.profile_created_time_filter,
             profile_edited_time_filter: state.profile_edited_time_filter,
+            hidden_profiles,
         }
     }
 }
@@ -53,6 +75,9 @@ pub struct LocationIndexProfileData {
     search_age_range: ProfileSearchAgeRangeValidated,
     search_groups: SearchGroupFlags,
     attributes: SortedProfileAttributes,
+    /// Profile owner's attribute filters if mutual attribute filters
+    /// are enabled.
+    mutual_attribute_filters: Option<Vec<ProfileAttributeFilterValue>>,
     unlimited_likes: bool,
     /// Possible values:
     /// - Unix timestamp
@@ -63,6 +88,9 @@ pub struct LocationIndexProfileData {
     profile_edited_time: ProfileEditedTime,
     /// Option because media component might not be enabled
     profile_content_edited_time: Option<ProfileContentEditedTime>,
+    /// Exact location for sorting profiles by distance. This must not
+    /// be returned to clients.
+    location: LocationInternal,
 }
 
 impl LocationIndexProfileData {
@@ -72,11 +100,13 @@ impl LocationIndexProfileData {
         profile: &ProfileInternal,
         state: &ProfileStateCached,
         attributes: SortedProfileAttributes,
+        attribute_filters: &[ProfileAttributeFilterValue],
         profile_content_version: Option<ProfileContentVersion>,
         unlimited_likes: bool,
         last_seen_value: Option<LastSeenTime>,
         profile_created_time: InitialSetupCompletedTime,
         profile_content_edited_time: Option<ProfileContentEditedTime>,
+        location: LocationInternal,
     ) -> Self {
         Self {
             profile_link: ProfileLink::new(id, profile.version_uuid, profile_content_version, None),
@@ -87,6 +117,11 @@ impl LocationIndexProfileData {
             ),
             search_groups: state.search_group_flags,
             attributes,
+            mutual_attribute_filters: if state.mutual_attribute_filters {
+                Some(attribute_filters.to_vec())
+            } else {
+                None
+            },
             unlimited_likes,
             last_seen_time: if let Some(last_seen_time) = last_seen_value {
                 AtomicI64::new(last_seen_time.raw())
@@ -96,9 +131,18 @@ impl LocationIndexProfileData {
             profile_created_time,
             profile_edited_time: state.profile_edited_time,
             profile_content_edited_time,
+            location,
         }
     }
 
+    pub fn set_location(&mut self, location: LocationInternal) {
+        self.location = location;
+    }
+
+    pub fn distance_km(&self, location: &LocationInternal) -> f64 {
+        self.location.distance_km(location)
+    }
+
     pub fn to_profile_link_value(&self) -> ProfileLink {
         let mut profile_link = self.profile_link;
         let last_seen_value = self.last_seen_time.load(Ordering::Relaxed);
@@ -148,9 +192,29 @@ impl LocationIndexProfileData {
             }
         }
 
+        if is_match {
+            if let Some(hidden_profiles) = &query_maker_details.hidden_profiles {
+                is_match &= !hidden_profiles.contains(&self.profile_link.id());
+            }
+        }
+
+        if is_match {
+            if let Some(attribute_info) = attribute_info {
+                is_match &= profile_attribute_filters_match(
+                    &query_maker_details.attribute_filters,
+                    &self.attributes,
+                    attribute_info,
+                );
+            }
+        }
+
         if is_match {
             if let Some(attribute_info) = attribute_info {
-                is_match &= self.attribute_filters_match(query_maker_details, attribute_info);
+                is_match &= mutual_attribute_filters_match(
+                    self.mutual_attribute_filters.as_deref(),
+                    &query_maker_details.attributes,
+                    attribute_info,
+                );
             }
         }
 
@@ -171,33 +235,46 @@ impl LocationIndexProfileData {
 
         last_seen_time_filter.is_match(current_last_seen_time, current_time)
     }
+}
 
-    fn attribute_filters_match(
-        &self,
-        query_maker_details: &ProfileQueryMakerDetails,
-        attribute_info: &ProfileAttributesInternal,
-    ) -> bool {
-        for filter in &query_maker_details.attribute_filters {
-            let attribute_info =
-                if let Some(info) = attribute_info.get_attribute(filter.id()) {
-                    info
-                } else {
-                    return false;
-                };
-
-            if let Some(value) = self.attributes.find_id(filter.id()) {
-                if !filter.is_match_with_attribute_value(value, attribute_info) {
-                    return false;
-                }
-            } else {
-                if !filter.accept_missing_attribute_enabled() {
-                    return false;
-                }
+/// Returns true if the profile attributes match all filters.
+pub fn profile_attribute_filters_match(
+    filters: &[ProfileAttributeFilterValue],
+    attributes: &SortedProfileAttributes,
+    attribute_info: &ProfileAttributesInternal,
+) -> bool {
+    for filter in filters {
+        let attribute_info = if let Some(info) = attribute_info.get_attribute(filter.id()) {
+            info
+        } else {
+            return false;
+        };
+
+        if let Some(value) = attributes.find_id(filter.id()) {
+            if !filter.is_match_with_attribute_value(value, attribute_info) {
+                return false;
             }
+        } else if !filter.accept_missing_attribute_enabled() {
+            return false;
         }
-
-        true
     }
+
+    true
+}
+
+/// Returns false if the profile owner has enabled mutual attribute filters
+/// and the viewer's profile attributes do not match all those filters.
+///
+/// The `owner_mutual_attribute_filters` is `None` if the profile owner
+/// has not enabled mutual attribute filters.
+pub fn mutual_attribute_filters_match(
+    owner_mutual_attribute_filters: Option<&[ProfileAttributeFilterValue]>,
+    viewer_attributes: &SortedProfileAttributes,
+    attribute_info: &ProfileAttributesInternal,
+) -> bool {
+    owner_mutual_attribute_filters
+        .map(|filters| profile_attribute_filters_match(filters, viewer_attributes, attribute_info))
+        .unwrap_or(true)
 }
 
 #[derive(Debug, Hash, PartialEq, Clone, Copy, Default, Eq)]
@@ -294,3 +371,95 @@ impl CellDataProvider for CellData {
         self.profiles_in_this_area.load(Ordering::Relaxed)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use model::AttributeId;
+
+    use super::mutual_attribute_filters_match;
+    use crate::{
+        AttributesFileInternal, ProfileAttributeFilterValue, ProfileAttributeValue,
+        ProfileAttributesInternal, SortedProfileAttributes,
+    };
+
+    const HELSINKI: u16 = 0;
+    const ESPOO: u16 = 1;
+
+    fn attribute_info() -> ProfileAttributesInternal {
+        let file: AttributesFileInternal = toml::from_str(
+            r#"
+            attribute_order = "OrderNumber"
+
+            [[attribute]]
+            key = "city"
+            name = "City"
+            mode = "SelectSingleFilterSingle"
+            id = 0
+            order_number = 0
+            value_order = "AlphabethicalValue"
+            values = ["Helsinki", "Espoo"]
+            "#,
+        )
+        .unwrap();
+        file.validate().unwrap()
+    }
+
+    fn viewer_attributes(
+        city: Option<u16>,
+        info: &ProfileAttributesInternal,
+    ) -> SortedProfileAttributes {
+        let attributes = city
+            .map(|city| ProfileAttributeValue::new_not_number_list(AttributeId::new(0), vec![city]))
+            .into_iter()
+            .collect();
+        SortedProfileAttributes::new(attributes, Some(info))
+    }
+
+    fn owner_filters() -> Vec<ProfileAttributeFilterValue> {
+        vec![ProfileAttributeFilterValue::new_not_number_list(
+            AttributeId::new(0),
+            vec![HELSINKI],
+            false,
+        )]
+    }
+
+    #[test]
+    fn mutual_filters_reject_mismatching_viewer() {
+        let info = attribute_info();
+        let viewer = viewer_attributes(Some(ESPOO), &info);
+        assert!(!mutual_attribute_filters_match(
+            Some(owner_filters().as_slice()),
+            &viewer,
+            &info
+        ));
+    }
+
+    #[test]
+    fn mutual_filters_reject_viewer_without_attribute() {
+        let info = attribute_info();
+        let viewer = viewer_attributes(None, &info);
+        assert!(!mutual_attribute_filters_match(
+            Some(owner_filters().as_slice()),
+            &viewer,
+            &info
+        ));
+    }
+
+    #[test]
+    fn mutual_filters_accept_matching_viewer() {
+        let info = attribute_info();
+        let viewer = viewer_attributes(Some(HELSINKI), &info);
+        assert!(mutual_attribute_filters_match(
+            Some(owner_filters().as_slice()),
+            &viewer,
+            &info
+        ));
+    }
+
+    #[test]
+    fn disabled_mutual_filters_accept_all() {
+        let info = attribute_info();
+        let viewer = viewer_attributes(Some(ESPOO), &info);
+        assert!(mutual_attribute_filters_match(None, &viewer, &info));
+    }
+}
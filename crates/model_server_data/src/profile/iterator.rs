@@ -45,6 +45,9 @@ pub struct ProfileLink {
     /// If the last seen time is not None, then it is Unix timestamp or -1 if
     /// the profile is currently online.
     last_seen_time: Option<LastSeenTime>,
+    /// Approximate distance from the profile viewer. This is only
+    /// available when the profile is from the profile iterator.
+    distance: Option<ProfileDistanceBucket>,
 }
 
 impl ProfileLink {
@@ -59,9 +62,14 @@ impl ProfileLink {
             version,
             content_version,
             last_seen_time,
+            distance: None,
         }
     }
 
+    pub fn id(&self) -> AccountId {
+        self.id
+    }
+
     pub fn last_seen_time(&self) -> Option<LastSeenTime> {
         self.last_seen_time
     }
@@ -69,6 +77,42 @@ impl ProfileLink {
     pub fn set_last_seen_time(&mut self, value: LastSeenTime) {
         self.last_seen_time = Some(value);
     }
+
+    pub fn set_distance(&mut self, value: ProfileDistanceBucket) {
+        self.distance = Some(value);
+    }
+}
+
+/// Approximate distance between the profile owner and the profile viewer.
+///
+/// The exact distance is equal or greater than [Self::min_km] and less than
+/// [Self::max_km]. If [Self::max_km] is `None`, then there is no upper
+/// limit.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, ToSchema, PartialEq, Eq)]
+pub struct ProfileDistanceBucket {
+    pub min_km: u16,
+    pub max_km: Option<u16>,
+}
+
+impl ProfileDistanceBucket {
+    const BUCKET_LIMITS_KM: &[u16] = &[5, 10, 25, 50, 100, 200, 500];
+
+    pub fn from_distance_km(distance_km: f64) -> Self {
+        let mut min_km = 0;
+        for &limit in Self::BUCKET_LIMITS_KM {
+            if distance_km < limit as f64 {
+                return Self {
+                    min_km,
+                    max_km: Some(limit),
+                };
+            }
+            min_km = limit;
+        }
+        Self {
+            min_km,
+            max_km: None,
+        }
+    }
 }
 
 /// Profile iterator max distance in kilometers.
@@ -103,3 +147,25 @@ impl MaxDistanceKm {
 }
 
 diesel_i64_wrapper!(MaxDistanceKm);
+
+#[cfg(test)]
+mod test {
+    use super::ProfileDistanceBucket;
+
+    fn bucket(min_km: u16, max_km: Option<u16>) -> ProfileDistanceBucket {
+        ProfileDistanceBucket { min_km, max_km }
+    }
+
+    #[test]
+    fn distance_bucket_lower_limit_is_inclusive() {
+        assert_eq!(ProfileDistanceBucket::from_distance_km(0.0), bucket(0, Some(5)));
+        assert_eq!(ProfileDistanceBucket::from_distance_km(5.0), bucket(5, Some(10)));
+        assert_eq!(ProfileDistanceBucket::from_distance_km(499.9), bucket(200, Some(500)));
+    }
+
+    #[test]
+    fn distance_bucket_without_upper_limit() {
+        assert_eq!(ProfileDistanceBucket::from_distance_km(500.0), bucket(500, None));
+        assert_eq!(ProfileDistanceBucket::from_distance_km(20000.0), bucket(500, None));
+    }
+}
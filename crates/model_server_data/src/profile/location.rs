@@ -63,7 +63,7 @@ impl Location {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct LocationInternal {
     latitude: f64,
     longitude: f64,
@@ -91,6 +91,18 @@ impl LocationInternal {
             longitude: new_longitude,
         }
     }
+
+    /// Great-circle distance in kilometers
+    pub fn distance_km(&self, other: &Self) -> f64 {
+        // https://en.wikipedia.org/wiki/Haversine_formula
+        const R_EARTH: f64 = 6371.0;
+        let lat1 = self.latitude.to_radians();
+        let lat2 = other.latitude.to_radians();
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * R_EARTH * a.sqrt().clamp(0.0, 1.0).asin()
+    }
 }
 
 impl From<Location> for LocationInternal {
@@ -149,4 +161,18 @@ mod test {
         assert_eq!(value.latitude(), default_location().latitude());
         assert!(value.longitude() > default_location().longitude());
     }
+
+    #[test]
+    fn distance_km_same_location() {
+        assert_eq!(default_location().distance_km(&default_location()), 0.0);
+    }
+
+    #[test]
+    fn distance_km_one_latitude_degree() {
+        let value = default_location().distance_km(&LocationInternal {
+            latitude: DEFAULT_DEGREES + 1.0,
+            longitude: DEFAULT_DEGREES,
+        });
+        assert!((value - 111.19).abs() < 0.01);
+    }
 }
@@ -3,13 +3,15 @@ use std::sync::Arc;
 use config::Config;
 use model::{AccountIdInternal, EmailMessages};
 use server_data::{
-    content_processing::ContentProcessingManagerData, db_manager::DatabaseManager,
-    write_commands::WriteCommandRunnerHandle,
+    content_processing::ContentProcessingManagerData, data_export::DataExportManagerData,
+    db_manager::DatabaseManager, write_commands::WriteCommandRunnerHandle,
 };
 use server_data_all::app::DataAllUtilsImpl;
 use server_state::{demo::DemoModeManager, StateForRouterCreation, S};
 use simple_backend::{
-    app::SimpleBackendAppState, manager_client::ManagerApiClient, perf::PerfMetricsManagerData
+    app::SimpleBackendAppState,
+    manager_client::ManagerApiClient,
+    perf::{storage::StorageMetrics, PerfMetricsManagerData},
 };
 use simple_backend_config::SimpleBackendConfig;
 use utoipa::OpenApi;
@@ -47,11 +49,14 @@ impl ApiDoc {
             .merge_from(server_api_account::account::router_delete(state.clone()).into_openapi())
             .merge_from(server_api_account::account::router_demo_mode(state.clone()).into_openapi())
             .merge_from(server_api_account::account::router_logout(state.clone()).into_openapi())
+            .merge_from(server_api_account::account::router_login_session(state.clone()).into_openapi())
+            .merge_from(server_api_account::account::router_email(state.clone()).into_openapi())
             .merge_from(server_api_account::account::router_news(state.clone()).into_openapi())
             .merge_from(server_api_account::account::router_register(state.clone()).into_openapi())
             .merge_from(server_api_account::account::router_settings(state.clone()).into_openapi())
             .merge_from(server_api_account::account::router_state(state.clone()).into_openapi())
             .merge_from(server_api_account::account::router_account_report(state.clone()).into_openapi())
+            .merge_from(server_api_account::account::router_data_export(state.clone()).into_openapi())
             .tag_routes("account");
         doc.merge(account);
         let account_admin = ApiDoc::openapi()
@@ -73,6 +78,9 @@ impl ApiDoc {
             .merge_from(
                 server_api_account::account_admin::router_admin_state(state.clone()).into_openapi(),
             )
+            .merge_from(
+                server_api_account::account_admin::router_admin_data_export(state.clone()).into_openapi(),
+            )
             .tag_routes("account_admin");
         doc.merge(account_admin);
         // Media
@@ -97,6 +105,10 @@ impl ApiDoc {
                 server_api_media::media_admin::router_admin_moderation(state.clone())
                     .into_openapi(),
             )
+            .merge_from(
+                server_api_media::media_admin::router_admin_similar_content(state.clone())
+                    .into_openapi(),
+            )
             .tag_routes("media_admin");
         doc.merge(media_admin);
         // Profile
@@ -131,10 +143,20 @@ impl ApiDoc {
                 server_api_profile::profile_admin::router_admin_iterate_profiles(state.clone())
                     .into_openapi(),
             )
+            .merge_from(
+                server_api_profile::profile_admin::router_admin_search(state.clone())
+                    .into_openapi(),
+            )
             .merge_from(
                 server_api_profile::profile_admin::router_admin_profile_data(state.clone())
                     .into_openapi(),
             )
+            .merge_from(
+                server_api_profile::profile_admin::router_admin_moderation_history(
+                    state.clone(),
+                )
+                .into_openapi(),
+            )
             .merge_from(
                 server_api_profile::profile_admin::router_admin_profile_name_allowlist(
                     state.clone(),
@@ -174,7 +196,7 @@ impl ApiDoc {
 
     pub async fn open_api_json_string() -> Result<String, serde_json::Error> {
         let config = Arc::new(SimpleBackendConfig::load_from_file_with_in_ram_database());
-        let perf_data = PerfMetricsManagerData::new(&[]).into();
+        let perf_data = PerfMetricsManagerData::new(&[], StorageMetrics::default()).into();
         let manager = ManagerApiClient::empty();
         let simple_state = SimpleBackendAppState::new(config.clone(), perf_data, manager.into())
             .await
@@ -200,6 +222,9 @@ impl ApiDoc {
         let (content_processing, _) = ContentProcessingManagerData::new();
         let content_processing = Arc::new(content_processing);
 
+        let (data_export, _) = DataExportManagerData::new();
+        let data_export = Arc::new(data_export);
+
         let demo_mode =
             DemoModeManager::new(config.demo_mode_config().cloned().unwrap_or_default())
                 .expect("Demo mode manager init failed");
@@ -209,6 +234,7 @@ impl ApiDoc {
             write_cmd_runner_handle,
             config.clone(),
             content_processing.clone(),
+            data_export,
             demo_mode,
             push_notification_sender,
             simple_state,
@@ -1,6 +1,6 @@
 use config::Config;
-use model::ContentId;
-use model_media::MediaContentType;
+use model::{ContentId, ContentSize};
+use model_media::{MediaContentType, PerceptualHash};
 use server_api::{
     app::{ContentProcessingProvider, EventManagerProvider, WriteData},
     db_write_raw,
@@ -12,8 +12,10 @@ use server_data::{
 };
 use server_data_media::write::GetWriteCommandsMedia;
 use server_state::S;
-use simple_backend::{image::ImageProcess, ServerQuitWatcher};
-use simple_backend_config::args::InputFileType;
+use simple_backend::{
+    image::{ImageProcess, ImageProcessError},
+    ServerQuitWatcher,
+};
 use simple_backend_image_process::ImageProcessingInfo;
 use tokio::task::JoinHandle;
 use tracing::{error, warn};
@@ -23,6 +25,9 @@ pub enum ContentProcessingError {
     #[error("Content processing error")]
     ContentProcessingFailed,
 
+    #[error("Unsupported content format")]
+    UnsupportedFormat,
+
     #[error("Database update error")]
     DatabaseError,
 }
@@ -90,13 +95,7 @@ impl ContentProcessingManager {
 
     pub async fn handle_content(&self, content: ProcessingState) {
         let result = match content.new_content_params.content_type {
-            MediaContentType::JpegImage => ImageProcess::start_image_process(
-                content.tmp_raw_img.as_path(),
-                InputFileType::JpegImage,
-                content.tmp_img.as_path(),
-            )
-            .await
-            .change_context(ContentProcessingError::ContentProcessingFailed),
+            MediaContentType::JpegImage => process_image(&content).await,
         };
 
         let mut write = self.state.content_processing().data().write().await;
@@ -111,7 +110,14 @@ impl ContentProcessingManager {
                         .change_to_completed(content_id, face_detected);
                 }
                 Err(e) => {
-                    state.processing_state.change_to_failed();
+                    if matches!(
+                        e.current_context(),
+                        ContentProcessingError::UnsupportedFormat
+                    ) {
+                        state.processing_state.change_to_unsupported_format();
+                    } else {
+                        state.processing_state.change_to_failed();
+                    }
                     error!("Content processing error: {:?}", e);
                 }
             }
@@ -145,6 +151,7 @@ impl ContentProcessingManager {
             } else {
                 info.face_detected
             };
+        let perceptual_hash = info.perceptual_hash.map(PerceptualHash::from_u64);
 
         let state_copy = state.clone();
         let content_id = db_write_raw!(self.state, move |cmds| {
@@ -155,6 +162,7 @@ impl ContentProcessingManager {
                     state_copy.slot,
                     state_copy.new_content_params,
                     face_detected,
+                    perceptual_hash,
                 )
                 .await
         })
@@ -168,6 +176,34 @@ impl ContentProcessingManager {
     }
 }
 
+async fn process_image(
+    content: &ProcessingState,
+) -> Result<ImageProcessingInfo, ContentProcessingError> {
+    let input_file_type = ImageProcess::detect_input_file_type(content.tmp_raw_img.as_path())
+        .await
+        .map_err(image_process_error_to_content_processing_error)?;
+    let info = ImageProcess::start_image_process(
+        content.tmp_raw_img.as_path(),
+        input_file_type,
+        content.tmp_img.as_path(),
+        content.tmp_img.size_variant(ContentSize::Medium).as_path(),
+        content.tmp_img.size_variant(ContentSize::Small).as_path(),
+    )
+    .await
+    .map_err(image_process_error_to_content_processing_error)?;
+    Ok(info)
+}
+
+fn image_process_error_to_content_processing_error(
+    e: error_stack::Report<ImageProcessError>,
+) -> error_stack::Report<ContentProcessingError> {
+    let context = match e.current_context() {
+        ImageProcessError::UnsupportedInputFormat => ContentProcessingError::UnsupportedFormat,
+        _ => ContentProcessingError::ContentProcessingFailed,
+    };
+    e.change_context(context)
+}
+
 struct ImgInfo {
     face_detected: bool,
     content_id: ContentId,
@@ -0,0 +1,181 @@
+use std::time::Duration;
+
+use model::{AccountIdInternal, DataExportDownloadToken, UnixTime};
+use server_api::app::{DataExportProvider, EventManagerProvider, ReadData};
+use server_common::result::{Result, WrappedResultExt};
+use server_data::{
+    app::GetConfig,
+    data_export::{DataExportJob, DataExportReceiver},
+    file::FileRead,
+    read::GetReadCommandsCommon,
+};
+use server_data_all::data_export::{DataExport, DataExportStep};
+use server_state::S;
+use simple_backend::ServerQuitWatcher;
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+
+/// How often expired data export archives are removed.
+const EXPIRED_ARCHIVE_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 10);
+
+#[derive(thiserror::Error, Debug)]
+pub enum DataExportError {
+    #[error("Data reading failed")]
+    DataReadingFailed,
+
+    #[error("Archive writing failed")]
+    ArchiveWritingFailed,
+}
+
+#[derive(Debug)]
+pub struct DataExportManagerQuitHandle {
+    task: JoinHandle<()>,
+}
+
+impl DataExportManagerQuitHandle {
+    pub async fn wait_quit(self) {
+        match self.task.await {
+            Ok(()) => (),
+            Err(e) => {
+                warn!("DataExportManager quit failed. Error: {:?}", e);
+            }
+        }
+    }
+}
+
+pub struct DataExportManager {
+    state: S,
+}
+
+impl DataExportManager {
+    pub fn new_manager(
+        receiver: DataExportReceiver,
+        state: S,
+        quit_notification: ServerQuitWatcher,
+    ) -> DataExportManagerQuitHandle {
+        let manager = Self { state };
+
+        let task = tokio::spawn(manager.run(receiver, quit_notification));
+
+        DataExportManagerQuitHandle { task }
+    }
+
+    pub async fn run(
+        self,
+        mut receiver: DataExportReceiver,
+        mut quit_notification: ServerQuitWatcher,
+    ) {
+        self.remove_archives_from_previous_run().await;
+
+        let mut timer = tokio::time::interval(EXPIRED_ARCHIVE_CHECK_INTERVAL);
+
+        loop {
+            tokio::select! {
+                item = receiver.0.recv() => {
+                    match item {
+                        Some(job) => self.handle_job(job).await,
+                        None => {
+                            error!("Data export event channel is broken");
+                            return;
+                        },
+                    }
+                }
+                _ = timer.tick() => {
+                    self.remove_expired_archives().await;
+                }
+                _ = quit_notification.recv() => {
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn handle_job(&self, job: DataExportJob) {
+        match self.export_data(job).await {
+            Ok(()) => {
+                let expires = UnixTime::current_time().add_seconds(
+                    self.state
+                        .config()
+                        .limits_account()
+                        .data_export_download_duration
+                        .seconds,
+                );
+                self.state
+                    .data_export()
+                    .change_to_done(
+                        self.state.event_manager(),
+                        job.requester,
+                        DataExportDownloadToken::generate_new(),
+                        expires,
+                    )
+                    .await;
+            }
+            Err(e) => {
+                error!("Data export failed: {:?}", e);
+                self.remove_archive(job.requester).await;
+                self.state
+                    .data_export()
+                    .change_to_error(self.state.event_manager(), job.requester)
+                    .await;
+            }
+        }
+    }
+
+    async fn export_data(&self, job: DataExportJob) -> Result<(), DataExportError> {
+        let mut export = DataExport::new(job.target);
+
+        let step_count = DataExportStep::ALL.len() + 1;
+        for (i, step) in DataExportStep::ALL.into_iter().enumerate() {
+            export
+                .run_step(self.state.config(), self.state.read(), job.target, step)
+                .await
+                .change_context(DataExportError::DataReadingFailed)?;
+            let progress = ((i + 1) * 100 / step_count) as u8;
+            self.state
+                .data_export()
+                .update_progress(self.state.event_manager(), job.requester, progress)
+                .await;
+        }
+
+        let archive = self.state.read().files().data_export_archive(job.requester.as_id());
+        tokio::task::spawn_blocking(move || export.write_archive(&archive))
+            .await
+            .change_context(DataExportError::ArchiveWritingFailed)?
+            .change_context(DataExportError::ArchiveWritingFailed)?;
+
+        Ok(())
+    }
+
+    async fn remove_expired_archives(&self) {
+        let expired = self
+            .state
+            .data_export()
+            .remove_expired(self.state.event_manager())
+            .await;
+        for requester in expired {
+            self.remove_archive(requester).await;
+        }
+    }
+
+    /// Data export states are not saved, so archives created before
+    /// server restart can not be downloaded.
+    async fn remove_archives_from_previous_run(&self) {
+        let accounts = match self.state.read().common().account_ids_internal_vec().await {
+            Ok(accounts) => accounts,
+            Err(e) => {
+                error!("Reading account IDs failed: {:?}", e);
+                return;
+            }
+        };
+        for id in accounts {
+            self.remove_archive(id).await;
+        }
+    }
+
+    async fn remove_archive(&self, requester: AccountIdInternal) {
+        let archive = self.state.read().files().data_export_archive(requester.as_id());
+        if let Err(e) = archive.overwrite_and_remove_if_exists().await {
+            warn!("Data export archive removing failed {:?}", e)
+        }
+    }
+}
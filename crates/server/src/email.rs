@@ -1,13 +1,19 @@
 use error_stack::ResultExt;
-use model::{AccountIdInternal, EmailMessages};
+use model::{AccountIdInternal, EmailMessages, UnixTime};
+use model_account::{EmailAddress, EmailConfirmationToken};
 use server_api::{
     app::{GetConfig, ReadData, WriteData},
     db_write_raw,
 };
-use server_data_account::{read::GetReadCommandsAccount, write::GetWriteCommandsAccount};
+use server_data_account::{
+    email_confirmation::create_email_confirmation_token, read::GetReadCommandsAccount,
+    write::GetWriteCommandsAccount,
+};
 use server_state::S;
 use simple_backend::email::{EmailData, EmailDataProvider, EmailError};
 
+use crate::email_notifications::chat_notification_counts;
+
 pub struct ServerEmailDataProvider {
     state: S,
 }
@@ -16,6 +22,33 @@ impl ServerEmailDataProvider {
     pub fn new(state: S) -> Self {
         Self { state }
     }
+
+    async fn email_confirmation_token(
+        &self,
+        receiver: AccountIdInternal,
+        email: EmailAddress,
+    ) -> error_stack::Result<EmailConfirmationToken, EmailError> {
+        let key = db_write_raw!(self.state, move |cmds| {
+            cmds.account()
+                .email()
+                .email_confirmation_token_key()
+                .await
+        })
+        .await
+        .map_err(|e| e.into_report())
+        .change_context(EmailError::GettingEmailDataFailed)?;
+
+        let expires = UnixTime::current_time().add_seconds(
+            self.state
+                .config()
+                .limits_account()
+                .email_confirmation_token_max_age
+                .seconds,
+        );
+
+        create_email_confirmation_token(&key, receiver.as_id(), email, expires)
+            .change_context(EmailError::GettingEmailDataFailed)
+    }
 }
 
 impl EmailDataProvider<AccountIdInternal, EmailMessages> for ServerEmailDataProvider {
@@ -61,10 +94,57 @@ impl EmailDataProvider<AccountIdInternal, EmailMessages> for ServerEmailDataProv
             .ok_or(EmailError::GettingEmailDataFailed)
             .attach_printable(format!("Email content for {:?} is not configured", message))?;
 
+        let body = match message {
+            EmailMessages::AccountRegistered => email_content.body.clone(),
+            EmailMessages::EmailConfirmation => {
+                let token = self
+                    .email_confirmation_token(receiver, EmailAddress(email.clone()))
+                    .await?;
+                email_content
+                    .body
+                    .replace(EmailMessages::EMAIL_CONFIRMATION_TOKEN_PLACEHOLDER, &token.token)
+            }
+            EmailMessages::ChatNotificationDigest => {
+                let email_verified = self
+                    .state
+                    .read()
+                    .account()
+                    .email()
+                    .email_confirmation_state(receiver)
+                    .await
+                    .map_err(|e| e.into_report())
+                    .change_context(EmailError::GettingEmailDataFailed)?
+                    .email_verified;
+                if !email_verified {
+                    // Email address might have changed after the
+                    // digest was queued.
+                    return Ok(None);
+                }
+                let counts = chat_notification_counts(&self.state, receiver)
+                    .await
+                    .map_err(|e| e.into_report())
+                    .change_context(EmailError::GettingEmailDataFailed)?;
+                if counts.is_empty() {
+                    // Client has received the notifications already
+                    return Ok(None);
+                }
+                email_content
+                    .body
+                    .replace(
+                        EmailMessages::NEW_CHAT_REQUESTS_COUNT_PLACEHOLDER,
+                        &counts.new_chat_requests.to_string(),
+                    )
+                    .replace(
+                        EmailMessages::NEW_MESSAGES_COUNT_PLACEHOLDER,
+                        &counts.new_messages.to_string(),
+                    )
+            }
+        };
+
         let email_data = EmailData {
             email_address: email,
             subject: email_content.subject.clone(),
-            body: email_content.body.clone(),
+            body,
         };
 
         Ok(Some(email_data))
@@ -0,0 +1,206 @@
+use std::time::Duration;
+
+use model::{AccountIdInternal, EmailMessages, UnixTime};
+use server_api::{
+    app::{EventManagerProvider, GetConfig, ReadData, WriteData},
+    db_write_raw,
+};
+use server_common::result::{Result, WrappedResultExt};
+use server_data::read::GetReadCommandsCommon;
+use server_data_account::{read::GetReadCommandsAccount, write::GetWriteCommandsAccount};
+use server_data_chat::read::GetReadCommandsChat;
+use server_state::S;
+use simple_backend::ServerQuitWatcher;
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+
+/// How often accounts with pending email notifications are checked.
+/// Shorter chat email notification delay config value makes the
+/// checking more frequent.
+const PENDING_EMAIL_NOTIFICATION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const PENDING_EMAIL_NOTIFICATION_MIN_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(thiserror::Error, Debug)]
+pub enum EmailNotificationError {
+    #[error("Database read error")]
+    DatabaseReadError,
+
+    #[error("Database write error")]
+    DatabaseWriteError,
+}
+
+#[derive(Debug)]
+pub struct EmailNotificationManagerQuitHandle {
+    task: JoinHandle<()>,
+}
+
+impl EmailNotificationManagerQuitHandle {
+    pub async fn wait_quit(self) {
+        match self.task.await {
+            Ok(()) => (),
+            Err(e) => {
+                warn!("EmailNotificationManager quit failed. Error: {:?}", e);
+            }
+        }
+    }
+}
+
+/// Sends chat notification digest emails to accounts which have not
+/// received chat notifications using WebSocket or push notifications.
+pub struct EmailNotificationManager {
+    state: S,
+}
+
+impl EmailNotificationManager {
+    pub fn new_manager(
+        state: S,
+        quit_notification: ServerQuitWatcher,
+    ) -> EmailNotificationManagerQuitHandle {
+        let manager = Self { state };
+
+        let task = tokio::spawn(manager.run(quit_notification));
+
+        EmailNotificationManagerQuitHandle { task }
+    }
+
+    pub async fn run(self, mut quit_notification: ServerQuitWatcher) {
+        let delay = self
+            .state
+            .config()
+            .limits_account()
+            .chat_email_notification_delay;
+        let check_interval = Duration::from_secs(delay.seconds.into()).clamp(
+            PENDING_EMAIL_NOTIFICATION_MIN_CHECK_INTERVAL,
+            PENDING_EMAIL_NOTIFICATION_CHECK_INTERVAL,
+        );
+        let mut timer = tokio::time::interval(check_interval);
+
+        loop {
+            tokio::select! {
+                _ = timer.tick() => {
+                    self.send_pending_email_notifications().await;
+                }
+                _ = quit_notification.recv() => {
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn send_pending_email_notifications(&self) {
+        let delay = self
+            .state
+            .config()
+            .limits_account()
+            .chat_email_notification_delay;
+        let pending_since = UnixTime {
+            ut: UnixTime::current_time().ut - delay.seconds as i64,
+        };
+
+        let accounts = self
+            .state
+            .event_manager()
+            .take_accounts_with_pending_email_notification(pending_since)
+            .await;
+
+        for id in accounts {
+            if let Err(e) = self.send_email_notification_if_needed(id).await {
+                error!("Sending chat notification digest email failed: {:?}", e);
+            }
+        }
+    }
+
+    async fn send_email_notification_if_needed(
+        &self,
+        id: AccountIdInternal,
+    ) -> Result<(), EmailNotificationError> {
+        let email_notifications_enabled = self
+            .state
+            .read()
+            .account()
+            .email()
+            .email_notifications_enabled(id)
+            .await
+            .change_context(EmailNotificationError::DatabaseReadError)?;
+        if !email_notifications_enabled {
+            return Ok(());
+        }
+
+        let email_verified = self
+            .state
+            .read()
+            .account()
+            .email()
+            .email_confirmation_state(id)
+            .await
+            .change_context(EmailNotificationError::DatabaseReadError)?
+            .email_verified;
+        if !email_verified {
+            return Ok(());
+        }
+
+        let push_notifications_enabled = self
+            .state
+            .read()
+            .common()
+            .login_sessions(id)
+            .await
+            .change_context(EmailNotificationError::DatabaseReadError)?
+            .iter()
+            .any(|session| session.fcm_device_token.is_some());
+        if push_notifications_enabled {
+            // Push notifications are used instead of emails
+            return Ok(());
+        }
+
+        let data = self
+            .state
+            .read()
+            .account()
+            .account_data(id)
+            .await
+            .change_context(EmailNotificationError::DatabaseReadError)?;
+        if data.email.is_none() {
+            return Ok(());
+        }
+
+        let counts = chat_notification_counts(&self.state, id)
+            .await
+            .change_context(EmailNotificationError::DatabaseReadError)?;
+        if counts.is_empty() {
+            return Ok(());
+        }
+
+        db_write_raw!(self.state, move |cmds| {
+            cmds.account()
+                .email()
+                .send_email(id, EmailMessages::ChatNotificationDigest)
+                .await
+        })
+        .await
+        .change_context(EmailNotificationError::DatabaseWriteError)
+    }
+}
+
+pub struct ChatNotificationCounts {
+    pub new_chat_requests: i64,
+    pub new_messages: i64,
+}
+
+impl ChatNotificationCounts {
+    pub fn is_empty(&self) -> bool {
+        self.new_chat_requests == 0 && self.new_messages == 0
+    }
+}
+
+pub async fn chat_notification_counts(
+    state: &S,
+    id: AccountIdInternal,
+) -> Result<ChatNotificationCounts, server_data::DataError> {
+    let chat_state = state.read().chat().chat_state(id).await?;
+    let new_messages = state.read().chat().pending_messages_count(id).await?;
+    Ok(ChatNotificationCounts {
+        new_chat_requests: chat_state.new_received_likes_count.c,
+        new_messages,
+    })
+}
@@ -8,7 +8,9 @@ pub mod api;
 pub mod api_doc;
 pub mod bot;
 pub mod content_processing;
+pub mod data_export;
 pub mod email;
+pub mod email_notifications;
 pub mod perf;
 pub mod push_notifications;
 pub mod hourly_tasks;
@@ -23,10 +25,12 @@ use api_doc::ApiDoc;
 use axum::Router;
 use config::Config;
 use content_processing::{ContentProcessingManager, ContentProcessingManagerQuitHandle};
+use data_export::{DataExportManager, DataExportManagerQuitHandle};
 use email::ServerEmailDataProvider;
+use email_notifications::{EmailNotificationManager, EmailNotificationManagerQuitHandle};
 use hourly_tasks::{HourlyTaskManager, HourlyTaskManagerQuitHandle};
 use model::{AccountIdInternal, EmailMessages};
-use perf::ALL_COUNTERS;
+use perf::{ServerMetricGauges, ALL_COUNTERS};
 use push_notifications::ServerPushNotificationStateProvider;
 use scheduled_tasks::{ScheduledTaskManager, ScheduledTaskManagerQuitHandle};
 use server_api::app::GetConfig;
@@ -35,6 +39,7 @@ use server_common::push_notifications::{
 };
 use server_data::{
     content_processing::ContentProcessingManagerData,
+    data_export::DataExportManagerData,
     db_manager::DatabaseManager,
     write_commands::{WriteCmdWatcher, WriteCommandRunnerHandle},
 };
@@ -72,11 +77,14 @@ impl DatingAppServer {
             write_cmd_waiter: None,
             database_manager: None,
             content_processing_quit_handle: None,
+            data_export_quit_handle: None,
             push_notifications_quit_handle: None,
             email_manager_quit_handle: None,
+            email_notifications_quit_handle: None,
             shutdown_tasks: None,
             scheduled_tasks: None,
             hourly_tasks: None,
+            metric_gauges: None,
         };
         let server = simple_backend::SimpleBackend::new(logic, self.config.simple_backend_arc());
         server.run().await;
@@ -89,11 +97,14 @@ pub struct DatingAppBusinessLogic {
     write_cmd_waiter: Option<WriteCmdWatcher>,
     database_manager: Option<DatabaseManager>,
     content_processing_quit_handle: Option<ContentProcessingManagerQuitHandle>,
+    data_export_quit_handle: Option<DataExportManagerQuitHandle>,
     push_notifications_quit_handle: Option<PushNotificationManagerQuitHandle>,
     email_manager_quit_handle: Option<EmailManagerQuitHandle>,
+    email_notifications_quit_handle: Option<EmailNotificationManagerQuitHandle>,
     shutdown_tasks: Option<ShutdownTasks>,
     scheduled_tasks: Option<ScheduledTaskManagerQuitHandle>,
     hourly_tasks: Option<HourlyTaskManagerQuitHandle>,
+    metric_gauges: Option<ServerMetricGauges>,
 }
 
 impl BusinessLogic for DatingAppBusinessLogic {
@@ -197,6 +208,13 @@ impl BusinessLogic for DatingAppBusinessLogic {
         router
     }
 
+    fn metrics_api_router(&self, state: &Self::AppState) -> Router {
+        self.metric_gauges
+            .clone()
+            .expect("Not initialized")
+            .router(state.clone())
+    }
+
     fn create_swagger_ui(&self, state: &Self::AppState) -> Option<SwaggerUi> {
         const API_DOC_URL: &str = "/api-doc/app_api.json";
         const API_DOC_URL_OBFUSCATION_DISABLED: &str = "/api-doc/app_api_obfuscation_disabled.json";
@@ -260,6 +278,9 @@ impl BusinessLogic for DatingAppBusinessLogic {
         let (content_processing, content_processing_receiver) = ContentProcessingManagerData::new();
         let content_processing = Arc::new(content_processing);
 
+        let (data_export, data_export_receiver) = DataExportManagerData::new();
+        let data_export = Arc::new(data_export);
+
         let demo_mode =
             DemoModeManager::new(self.config.demo_mode_config().cloned().unwrap_or_default())
                 .expect("Demo mode manager init failed");
@@ -269,8 +290,9 @@ impl BusinessLogic for DatingAppBusinessLogic {
             write_cmd_runner_handle,
             self.config.clone(),
             content_processing.clone(),
+            data_export,
             demo_mode,
-            push_notification_sender,
+            push_notification_sender.clone(),
             simple_state,
             &DataAllUtilsImpl,
         )
@@ -282,6 +304,12 @@ impl BusinessLogic for DatingAppBusinessLogic {
             server_quit_watcher.resubscribe(),
         );
 
+        let data_export_quit_handle = DataExportManager::new_manager(
+            data_export_receiver,
+            app_state.clone(),
+            server_quit_watcher.resubscribe(),
+        );
+
         let push_notifications_quit_handle = PushNotificationManager::new_manager(
             self.config.simple_backend(),
             server_quit_watcher.resubscribe(),
@@ -298,6 +326,12 @@ impl BusinessLogic for DatingAppBusinessLogic {
         )
         .await;
 
+        let metric_gauges = ServerMetricGauges {
+            content_processing: content_processing.clone(),
+            email_sender: email_sender.clone(),
+            push_notification_sender,
+        };
+
         StartupTasks::new(app_state.clone())
             .run_and_wait_completion(email_sender)
             .await
@@ -307,15 +341,22 @@ impl BusinessLogic for DatingAppBusinessLogic {
             ScheduledTaskManager::new_manager(app_state.clone(), server_quit_watcher.resubscribe());
         let hourly_tasks =
             HourlyTaskManager::new_manager(app_state.clone(), server_quit_watcher.resubscribe());
+        let email_notifications_quit_handle = EmailNotificationManager::new_manager(
+            app_state.clone(),
+            server_quit_watcher.resubscribe(),
+        );
 
         self.database_manager = Some(database_manager);
         self.write_cmd_waiter = Some(write_cmd_waiter);
         self.content_processing_quit_handle = Some(content_processing_quit_handle);
+        self.data_export_quit_handle = Some(data_export_quit_handle);
         self.push_notifications_quit_handle = Some(push_notifications_quit_handle);
         self.email_manager_quit_handle = Some(email_manager_quit_handle);
+        self.email_notifications_quit_handle = Some(email_notifications_quit_handle);
         self.shutdown_tasks = Some(ShutdownTasks::new(app_state.clone()));
         self.scheduled_tasks = Some(scheduled_tasks);
         self.hourly_tasks = Some(hourly_tasks);
+        self.metric_gauges = Some(metric_gauges);
         app_state
     }
 
@@ -351,6 +392,13 @@ impl BusinessLogic for DatingAppBusinessLogic {
     }
 
     async fn on_after_server_quit(self) {
+        // Email notification manager requests email sending, so
+        // wait it before email manager.
+        self.email_notifications_quit_handle
+            .expect("Not initialized")
+            .wait_quit()
+            .await;
+
         // Email and push notifications have internal shutdown tasks.
         // Wait those to finish first.
         self.email_manager_quit_handle
@@ -385,6 +433,10 @@ impl BusinessLogic for DatingAppBusinessLogic {
             .expect("Not initialized")
             .wait_quit()
             .await;
+        self.data_export_quit_handle
+            .expect("Not initialized")
+            .wait_quit()
+            .await;
         self.write_cmd_waiter
             .expect("Not initialized")
             .wait_untill_all_writing_ends()
@@ -2,20 +2,33 @@
 //!
 //!
 
+use std::sync::Arc;
+
+use axum::{extract::State, response::Response, routing::get, Router};
 use server_api::{common::COMMON_CLIENT_CONFIG_COUNTERS_LIST, common_admin::{COMMON_ADMIN_MAINTENANCE_COUNTERS_LIST, COMMON_ADMIN_REPORT_COUNTERS_LIST}, utils::API_COUNTERS_LIST};
 use server_api_account::{
-    account::{ACCOUNT_BAN_COUNTERS_LIST, ACCOUNT_LOGOUT_COUNTERS_LIST, ACCOUNT_NEWS_COUNTERS_LIST, ACCOUNT_REPORT_COUNTERS_LIST},
-    account_admin::{ACCOUNT_ADMIN_BAN_COUNTERS_LIST, ACCOUNT_ADMIN_DELETE_COUNTERS_LIST, ACCOUNT_ADMIN_NEWS_COUNTERS_LIST, ACCOUNT_ADMIN_PERMISSIONS_COUNTERS_LIST, ACCOUNT_ADMIN_SEARCH_COUNTERS_LIST, ACCOUNT_ADMIN_STATE_COUNTERS_LIST},
+    account::{ACCOUNT_BAN_COUNTERS_LIST, ACCOUNT_DATA_EXPORT_COUNTERS_LIST, ACCOUNT_EMAIL_COUNTERS_LIST, ACCOUNT_LOGIN_SESSION_COUNTERS_LIST, ACCOUNT_LOGOUT_COUNTERS_LIST, ACCOUNT_NEWS_COUNTERS_LIST, ACCOUNT_REPORT_COUNTERS_LIST},
+    account_admin::{ACCOUNT_ADMIN_BAN_COUNTERS_LIST, ACCOUNT_ADMIN_DATA_EXPORT_COUNTERS_LIST, ACCOUNT_ADMIN_DELETE_COUNTERS_LIST, ACCOUNT_ADMIN_NEWS_COUNTERS_LIST, ACCOUNT_ADMIN_PERMISSIONS_COUNTERS_LIST, ACCOUNT_ADMIN_SEARCH_COUNTERS_LIST, ACCOUNT_ADMIN_STATE_COUNTERS_LIST},
 };
 use server_api_chat::chat::{CHAT_PUBLIC_KEY_COUNTERS_LIST, CHAT_REPORT_COUNTERS_LIST};
 use server_api_media::media::{MEDIA_MEDIA_CONTENT_COUNTERS_LIST, MEDIA_REPORT_MEDIA_REPORT_COUNTERS_LIST};
 use server_api_profile::{
     profile::{PROFILE_REPORT_COUNTERS_LIST, PROFILE_STATISTICS_COUNTERS_LIST},
     profile_admin::{
-        PROFILE_ADMIN_ITERATE_PROFILES_COUNTERS_LIST, PROFILE_ADMIN_PROFILE_DATA_COUNTERS_LIST, PROFILE_ADMIN_PROFILE_NAME_ALLOWLIST_COUNTERS_LIST, PROFILE_ADMIN_PROFILE_TEXT_COUNTERS_LIST, PROFILE_ADMIN_STATISTICS_COUNTERS_LIST
+        PROFILE_ADMIN_ITERATE_PROFILES_COUNTERS_LIST, PROFILE_ADMIN_MODERATION_HISTORY_COUNTERS_LIST, PROFILE_ADMIN_PROFILE_DATA_COUNTERS_LIST, PROFILE_ADMIN_PROFILE_NAME_ALLOWLIST_COUNTERS_LIST, PROFILE_ADMIN_PROFILE_TEXT_COUNTERS_LIST, PROFILE_ADMIN_SEARCH_COUNTERS_LIST, PROFILE_ADMIN_STATISTICS_COUNTERS_LIST
+    },
+};
+use server_common::{app::EmailSenderImpl, push_notifications::PushNotificationSender};
+use server_data::content_processing::ContentProcessingManagerData;
+use server_state::AppState;
+use simple_backend::{
+    app::PerfCounterDataProvider,
+    perf::{
+        metrics::{metrics_response, MetricGauge, PATH_METRICS},
+        CounterCategory,
     },
+    SIMPLE_CONNECTION_COUNTERS_LIST,
 };
-use simple_backend::{perf::CounterCategory, SIMPLE_CONNECTION_COUNTERS_LIST};
 
 use crate::api::{
     account::{
@@ -38,7 +51,7 @@ use crate::api::{
         MEDIA_PROFILE_CONTENT_COUNTERS_LIST, MEDIA_SECURITY_CONTENT_COUNTERS_LIST,
         MEDIA_TILE_MAP_COUNTERS_LIST,
     },
-    media_admin::MEDIA_ADMIN_MODERATION_COUNTERS_LIST,
+    media_admin::{MEDIA_ADMIN_MODERATION_COUNTERS_LIST, MEDIA_ADMIN_SIMILAR_CONTENT_COUNTERS_LIST},
     profile::{
         PROFILE_FILTERS_COUNTERS_LIST, PROFILE_BENCHMARK_COUNTERS_LIST,
         PROFILE_DATA_COUNTERS_LIST, PROFILE_FAVORITE_COUNTERS_LIST,
@@ -62,6 +75,8 @@ pub static ALL_COUNTERS: &[&CounterCategory] = &[
     &CounterCategory::new("account_register", ACCOUNT_REGISTER_COUNTERS_LIST),
     &CounterCategory::new("account_login", ACCOUNT_LOGIN_COUNTERS_LIST),
     &CounterCategory::new("account_logout", ACCOUNT_LOGOUT_COUNTERS_LIST),
+    &CounterCategory::new("account_login_session", ACCOUNT_LOGIN_SESSION_COUNTERS_LIST),
+    &CounterCategory::new("account_email", ACCOUNT_EMAIL_COUNTERS_LIST),
     &CounterCategory::new("account_ban", ACCOUNT_BAN_COUNTERS_LIST),
     &CounterCategory::new("account_delete", ACCOUNT_DELETE_COUNTERS_LIST),
     &CounterCategory::new("account_settings", ACCOUNT_SETTINGS_COUNTERS_LIST),
@@ -69,6 +84,7 @@ pub static ALL_COUNTERS: &[&CounterCategory] = &[
     &CounterCategory::new("account_demo_mode", ACCOUNT_DEMO_MODE_COUNTERS_LIST),
     &CounterCategory::new("account_news", ACCOUNT_NEWS_COUNTERS_LIST),
     &CounterCategory::new("account_report", ACCOUNT_REPORT_COUNTERS_LIST),
+    &CounterCategory::new("account_data_export", ACCOUNT_DATA_EXPORT_COUNTERS_LIST),
     // Account admin
     &CounterCategory::new("account_admin_ban", ACCOUNT_ADMIN_BAN_COUNTERS_LIST),
     &CounterCategory::new("account_admin_delete", ACCOUNT_ADMIN_DELETE_COUNTERS_LIST),
@@ -76,6 +92,7 @@ pub static ALL_COUNTERS: &[&CounterCategory] = &[
     &CounterCategory::new("account_admin_search", ACCOUNT_ADMIN_SEARCH_COUNTERS_LIST),
     &CounterCategory::new("account_admin_permissions", ACCOUNT_ADMIN_PERMISSIONS_COUNTERS_LIST),
     &CounterCategory::new("account_admin_state", ACCOUNT_ADMIN_STATE_COUNTERS_LIST),
+    &CounterCategory::new("account_admin_data_export", ACCOUNT_ADMIN_DATA_EXPORT_COUNTERS_LIST),
     // Account internal
     &CounterCategory::new("account_internal", ACCOUNT_BOT_COUNTERS_LIST),
     // Media
@@ -93,6 +110,10 @@ pub static ALL_COUNTERS: &[&CounterCategory] = &[
         "media_admin_moderation",
         MEDIA_ADMIN_MODERATION_COUNTERS_LIST,
     ),
+    &CounterCategory::new(
+        "media_admin_similar_content",
+        MEDIA_ADMIN_SIMILAR_CONTENT_COUNTERS_LIST,
+    ),
     // Profile
     &CounterCategory::new("profile_filters", PROFILE_FILTERS_COUNTERS_LIST),
     &CounterCategory::new("profile_iterate", PROFILE_ITERATE_PROFILES_COUNTERS_LIST),
@@ -111,10 +132,15 @@ pub static ALL_COUNTERS: &[&CounterCategory] = &[
         "profile_admin_iterate_profiles",
         PROFILE_ADMIN_ITERATE_PROFILES_COUNTERS_LIST,
     ),
+    &CounterCategory::new("profile_admin_search", PROFILE_ADMIN_SEARCH_COUNTERS_LIST),
     &CounterCategory::new(
         "profile_admin_profile_data",
         PROFILE_ADMIN_PROFILE_DATA_COUNTERS_LIST,
     ),
+    &CounterCategory::new(
+        "profile_admin_moderation_history",
+        PROFILE_ADMIN_MODERATION_HISTORY_COUNTERS_LIST,
+    ),
     &CounterCategory::new(
         "profile_admin_profile_name_allowlist",
         PROFILE_ADMIN_PROFILE_NAME_ALLOWLIST_COUNTERS_LIST,
@@ -142,3 +168,48 @@ pub static ALL_COUNTERS: &[&CounterCategory] = &[
     // Server info
     &CounterCategory::new("server_info_connection", SIMPLE_CONNECTION_COUNTERS_LIST),
 ];
+
+/// Queues which are visible as gauges in the metrics API.
+#[derive(Clone)]
+pub struct ServerMetricGauges {
+    pub content_processing: Arc<ContentProcessingManagerData>,
+    pub email_sender: EmailSenderImpl,
+    pub push_notification_sender: PushNotificationSender,
+}
+
+impl ServerMetricGauges {
+    async fn gauges(&self) -> Vec<MetricGauge> {
+        vec![
+            MetricGauge::new(
+                "content_processing_queue_length",
+                "Count of content waiting for processing",
+                self.content_processing.queue_length().await as u64,
+            ),
+            MetricGauge::new(
+                "email_sending_queue_length",
+                "Count of emails waiting for sending",
+                self.email_sender.queue_length() as u64,
+            ),
+            MetricGauge::new(
+                "push_notification_queue_length",
+                "Count of push notifications waiting for sending",
+                self.push_notification_sender.queue_length() as u64,
+            ),
+            MetricGauge::new(
+                "push_notification_low_priority_queue_length",
+                "Count of low priority push notifications waiting for sending",
+                self.push_notification_sender.low_priority_queue_length() as u64,
+            ),
+        ]
+    }
+
+    pub fn router(self, state: AppState) -> Router {
+        Router::new()
+            .route(PATH_METRICS, get(get_metrics))
+            .with_state((state, self))
+    }
+}
+
+async fn get_metrics(State((state, gauges)): State<(AppState, ServerMetricGauges)>) -> Response {
+    metrics_response(state.perf_counter_data(), gauges.gauges().await).await
+}
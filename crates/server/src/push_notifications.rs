@@ -1,5 +1,7 @@
 use error_stack::ResultExt;
-use model::{AccountIdInternal, PendingNotificationFlags, PushNotificationStateInfoWithFlags};
+use model::{
+    AccountIdInternal, LoginSessionId, PendingNotificationFlags, PushNotificationStateInfoWithFlags,
+};
 use server_api::{
     app::{ReadData, WriteData},
     db_write_raw,
@@ -55,12 +57,13 @@ impl PushNotificationStateProvider for ServerPushNotificationStateProvider {
 
     async fn enable_push_notification_sent_flag(
         &self,
-        account_id: AccountIdInternal,
+        _account_id: AccountIdInternal,
+        session_id: LoginSessionId,
     ) -> error_stack::Result<(), PushNotificationError> {
         db_write_raw!(self.state, move |cmds| {
             cmds.chat()
                 .push_notifications()
-                .enable_push_notification_sent_flag(account_id)
+                .enable_push_notification_sent_flag(session_id)
                 .await
         })
         .await
@@ -72,12 +75,13 @@ impl PushNotificationStateProvider for ServerPushNotificationStateProvider {
 
     async fn remove_device_token(
         &self,
-        account_id: AccountIdInternal,
+        _account_id: AccountIdInternal,
+        session_id: LoginSessionId,
     ) -> error_stack::Result<(), PushNotificationError> {
         db_write_raw!(self.state, move |cmds| {
             cmds.chat()
                 .push_notifications()
-                .remove_fcm_device_token(account_id)
+                .remove_fcm_device_token(session_id)
                 .await
         })
         .await
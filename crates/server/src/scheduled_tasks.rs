@@ -84,6 +84,10 @@ impl ScheduledTaskManager {
         let mut check_cooldown = false;
         let config = self.state.config().simple_backend().scheduled_tasks();
 
+        if config.debug_run_on_start && self.state.config().simple_backend().debug_mode() {
+            self.run_tasks(&mut quit_notification).await;
+        }
+
         loop {
             tokio::select! {
                 _ = sleep(Duration::from_secs(120)), if check_cooldown => {
@@ -203,6 +207,8 @@ impl ScheduledTaskManager {
 
             let account_state = account.state();
 
+            self.logout_idle_login_sessions(id).await?;
+
             if account_state != AccountState::InitialSetup {
                 self.update_profile_age_if_needed(id, &mut age_updated)
                     .await?;
@@ -292,6 +298,23 @@ impl ScheduledTaskManager {
         Ok(())
     }
 
+    pub async fn logout_idle_login_sessions(
+        &self,
+        id: AccountIdInternal,
+    ) -> Result<(), ScheduledTaskError> {
+        let idle_expiry = self.state.config().limits_account().login_session_idle_expiry;
+
+        db_write_raw!(self.state, move |cmds| {
+            cmds.common()
+                .logout_idle_sessions(id, idle_expiry)
+                .await
+        })
+        .await
+        .change_context(ScheduledTaskError::DatabaseError)?;
+
+        Ok(())
+    }
+
     pub async fn delete_account_if_needed(
         &self,
         id: AccountIdInternal,
@@ -102,6 +102,9 @@ pub async fn backup_data(
                         .await
                         .change_context(ScheduledTaskError::Backup)?;
                 }
+                // Manager handles the verification status and sends
+                // it to backend as a server event.
+                TargetToSourceMessage::BackupVerificationStatus { .. } => (),
             }
         }
     }
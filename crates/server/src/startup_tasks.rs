@@ -76,6 +76,14 @@ impl StartupTasks {
                 &email_state.account_registered_state_number,
                 EmailMessages::AccountRegistered,
             );
+            send_if_needed(
+                &email_state.email_confirmation_state_number,
+                EmailMessages::EmailConfirmation,
+            );
+            send_if_needed(
+                &email_state.chat_notification_digest_state_number,
+                EmailMessages::ChatNotificationDigest,
+            );
 
             db_write_raw!(state, move |cmds| {
                 // FCM
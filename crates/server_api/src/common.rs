@@ -15,7 +15,7 @@ use axum_extra::TypedHeader;
 use headers::ContentType;
 use http::HeaderMap;
 use model::{
-    AccessToken, AccountIdInternal, BackendVersion, EventToClient, PendingNotificationFlags, RefreshToken, SyncDataVersionFromClient, WebSocketClientTypeNumber
+    AccessToken, AccountIdInternal, BackendVersion, EventToClient, LoginSessionId, PendingNotificationFlags, RefreshToken, SyncDataVersionFromClient, WebSocketClientTypeNumber
 };
 use model_server_data::AuthPair;
 use server_common::websocket::WebSocketError;
@@ -171,7 +171,7 @@ pub async fn get_connect_websocket(
         return Err(StatusCode::INTERNAL_SERVER_ERROR);
     }
 
-    let id = if let Some(access_token) = protocols_iterator.next() {
+    let (id, session_id) = if let Some(access_token) = protocols_iterator.next() {
         let access_token = AccessToken::new(access_token.to_string());
         state
             .access_token_exists(&access_token)
@@ -188,7 +188,7 @@ pub async fn get_connect_websocket(
 
     let response = websocket
         .protocols(["0"])
-        .on_upgrade(move |socket| handle_socket(socket, addr, id, state, ws_manager));
+        .on_upgrade(move |socket| handle_socket(socket, addr, id, session_id, state, ws_manager));
     Ok(response)
 }
 
@@ -196,6 +196,7 @@ async fn handle_socket(
     socket: WebSocket,
     address: SocketAddr,
     id: AccountIdInternal,
+    session_id: LoginSessionId,
     state: S,
     mut ws_manager: WebSocketManager,
 ) {
@@ -220,7 +221,7 @@ async fn handle_socket(
             //       Test does this code path work with client.
             let result = state.write(move |cmds| async move {
                 cmds.common()
-                    .end_connection_session(id, address)
+                    .end_connection_session(id, session_id, address)
                     .await
             }).await;
 
@@ -228,13 +229,13 @@ async fn handle_socket(
                 error!("server quit end_connection_session, {e:?}, for '{}', address: {}", id.id.as_i64(), address);
             }
         },
-        r = handle_socket_result(socket, address, id, &state) => {
+        r = handle_socket_result(socket, address, id, session_id, &state) => {
             match r {
                 Ok(()) => {
                     info!("handle_socket_result returned Ok for '{}', address: {}", id.id.as_i64(), address);
                     let result = state.write(move |cmds| async move {
                         cmds.common()
-                            .end_connection_session(id, address)
+                            .end_connection_session(id, session_id, address)
                             .await
                     }).await;
 
@@ -246,7 +247,7 @@ async fn handle_socket(
                     error!("handle_socket_result returned Err {e:?} for '{}', address: {}", id.id.as_i64(), address);
 
                     let result = state.write(move |cmds| async move {
-                        cmds.common().logout(id).await
+                        cmds.common().logout(id, session_id).await
                     }).await;
 
                     if let Err(e) = result {
@@ -277,6 +278,7 @@ async fn handle_socket_result(
     mut socket: WebSocket,
     address: SocketAddr,
     id: AccountIdInternal,
+    session_id: LoginSessionId,
     state: &S,
 ) -> crate::result::Result<(), WebSocketError> {
     info!(
@@ -324,7 +326,7 @@ async fn handle_socket_result(
     let current_refresh_token = state
         .read()
         .common()
-        .account_refresh_token(id)
+        .login_session_refresh_token(session_id)
         .await
         .change_context(WebSocketError::DatabaseNoRefreshToken)?
         .ok_or(WebSocketError::DatabaseNoRefreshToken.report())?
@@ -340,6 +342,20 @@ async fn handle_socket_result(
     {
         Message::Binary(refresh_token) => {
             if refresh_token != current_refresh_token {
+                let used_token_session = state
+                    .read()
+                    .common()
+                    .login_session_with_used_refresh_token(RefreshToken::from_bytes(
+                        &refresh_token,
+                    ))
+                    .await
+                    .change_context(WebSocketError::DatabaseSaveTokensOrOtherError)?;
+                if used_token_session.map(|v| v.1) == Some(session_id) {
+                    // Refresh token is most likely stolen. Returning error
+                    // revokes the login session.
+                    COMMON.websocket_refresh_token_reuse_detected.incr();
+                    return Err(WebSocketError::ReceiveUsedRefreshToken.report());
+                }
                 COMMON.websocket_refresh_token_not_found.incr();
                 // Returning error does the logout, so it is not needed here.
                 // For this case the logout is needed to prevent refresh
@@ -384,6 +400,7 @@ async fn handle_socket_result(
             cmds.common()
                 .set_new_auth_pair(
                     id,
+                    session_id,
                     AuthPair {
                         access: new_access_token,
                         refresh: new_refresh_token,
@@ -474,8 +491,10 @@ async fn handle_socket_result(
                         // that client has received the event.
                     },
                     None => {
-                        error!("Event receiver channel broken: id: {}, address: {}", id.id.as_i64(), address);
-                        // New connection created another event receiver.
+                        // Login session was revoked or new connection
+                        // created another event receiver.
+                        info!("Event receiver channel closed: id: {}, address: {}", id.id.as_i64(), address);
+                        let _ = socket.send(Message::Close(None)).await;
                         break;
                     },
                 }
@@ -530,6 +549,7 @@ create_counters!(
     get_connect_websocket,
     websocket_access_token_not_found,
     websocket_refresh_token_not_found,
+    websocket_refresh_token_reuse_detected,
     websocket_connected,
     websocket_disconnected,
     websocket_client_type_android,
@@ -28,9 +28,15 @@ pub async fn get_client_config(
 ) -> Result<Json<ClientConfig>, StatusCode> {
     COMMON.get_client_config.incr();
     let sync_version = state.read().common().client_config_sync_version(account_id).await?;
+    let message_signing_public_key = if state.config().components().chat {
+        Some(state.read().message_signing_key().public_key())
+    } else {
+        None
+    };
     let info = ClientConfig {
         custom_reports: state.config().custom_reports_sha256().map(|v| CustomReportsFileHash::new(v.to_string())),
         profile_attributes: state.config().profile_attributes().map(|a| a.info_for_client()).cloned(),
+        message_signing_public_key,
         sync_version,
     };
     Ok(info.into())
@@ -2,11 +2,13 @@ use axum::{
     extract::{Query, State},
     Extension,
 };
-use model::Permissions;
+use model::{Permissions, UnixTime};
+use server_data::read::GetReadCommandsCommon;
 use simple_backend::{app::PerfCounterDataProvider, create_counters};
 use simple_backend_model::{PerfMetricQuery, PerfMetricQueryResult};
 
 use crate::{
+    app::ReadData,
     create_open_api_router,
     utils::{Json, StatusCode},
     S,
@@ -16,6 +18,10 @@ const PATH_GET_PERF_DATA: &str = "/common_api/perf_data";
 
 /// Get performance data
 ///
+/// Minute values are available for the last 24 hours. Older values and
+/// values with hour or day granularity are read from the history
+/// database.
+///
 /// # Permissions
 /// Requires admin_server_maintenance_view_info.
 #[utoipa::path(
@@ -32,11 +38,25 @@ const PATH_GET_PERF_DATA: &str = "/common_api/perf_data";
 pub async fn get_perf_data(
     State(state): State<S>,
     Extension(api_caller_permissions): Extension<Permissions>,
-    Query(_query): Query<PerfMetricQuery>,
+    Query(query): Query<PerfMetricQuery>,
 ) -> Result<Json<PerfMetricQueryResult>, StatusCode> {
     COMMON_ADMIN.get_perf_data.incr();
     if api_caller_permissions.admin_server_maintenance_view_info {
-        let data = state.perf_counter_data().get_history(false).await;
+        let data = match query.history_time_granularity(UnixTime::current_time()) {
+            Some(time_granularity) => {
+                state
+                    .read()
+                    .common_history()
+                    .perf_data(query, time_granularity)
+                    .await?
+            }
+            None => {
+                state
+                    .perf_counter_data()
+                    .get_history_with_query(&query)
+                    .await
+            }
+        };
         Ok(data.into())
     } else {
         Err(StatusCode::UNAUTHORIZED)
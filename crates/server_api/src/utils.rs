@@ -29,6 +29,10 @@ pub static ACCESS_TOKEN_HEADER: header::HeaderName =
 /// "Extension(api_caller_account_id): Extension<AccountIdInternal>"
 /// to handlers is possible.
 ///
+/// Adds `LoginSessionId` extension to request, so that adding
+/// "Extension(api_caller_login_session_id): Extension<LoginSessionId>"
+/// to handlers is possible.
+///
 /// Adds `Permissions` extension to request, so that adding
 /// "Extension(api_caller_permissions): Extension<Permissions>"
 /// to handlers is possible.
@@ -49,11 +53,12 @@ pub async fn authenticate_with_access_token(
     let key_str = header.to_str().map_err(|_| StatusCode::BAD_REQUEST)?;
     let key = AccessToken::new(key_str.to_string());
 
-    if let Some((id, permissions, account_state)) =
+    if let Some((id, session_id, permissions, account_state)) =
         state.access_token_and_connection_exists(&key, addr).await
     {
         API.access_token_found.incr();
         req.extensions_mut().insert(id);
+        req.extensions_mut().insert(session_id);
         req.extensions_mut().insert(permissions);
         req.extensions_mut().insert(account_state);
         Ok(next.run(req).await)
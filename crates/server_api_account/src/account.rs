@@ -1,7 +1,10 @@
 pub mod ban;
+pub mod data_export;
 pub mod delete;
+pub mod email;
 pub mod demo;
 pub mod login;
+pub mod login_session;
 pub mod logout;
 pub mod news;
 pub mod register;
@@ -10,9 +13,12 @@ pub mod state;
 pub mod report;
 
 pub use ban::*;
+pub use data_export::*;
 pub use delete::*;
+pub use email::*;
 pub use demo::*;
 pub use login::*;
+pub use login_session::*;
 pub use logout::*;
 pub use news::*;
 pub use register::*;
@@ -0,0 +1,155 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    Extension,
+};
+use axum_extra::TypedHeader;
+use headers::{ContentLength, ContentType};
+use model_account::{AccountIdInternal, DataExportDownloadToken, DataExportState};
+use server_api::{
+    app::{DataExportProvider, EventManagerProvider},
+    create_open_api_router,
+    result::WrappedResultExt,
+    S,
+};
+use server_data::{file::FileRead, DataError};
+use simple_backend::create_counters;
+
+use crate::{
+    app::ReadData,
+    utils::{Json, StatusCode},
+};
+
+const PATH_POST_START_DATA_EXPORT: &str = "/account_api/start_data_export";
+
+/// Start exporting all data related to the current account to ZIP archive.
+///
+/// Export progress is sent using [model::EventType::DataExportStateChanged]
+/// events. When the export is completed, the archive can be downloaded using
+/// the download token from the event.
+///
+/// Starting a new export replaces the previous export archive of the
+/// current account. A new export can not be started while the current
+/// account has an export archive of another account available for
+/// download.
+///
+/// # Access
+///
+/// Unrestricted access.
+#[utoipa::path(
+    post,
+    path = PATH_POST_START_DATA_EXPORT,
+    responses(
+        (status = 200, description = "Successfull."),
+        (status = 401, description = "Unauthorized."),
+        (status = 406, description = "Data export is already in progress or export archive of another account is available for download."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn post_start_data_export(
+    State(state): State<S>,
+    Extension(id): Extension<AccountIdInternal>,
+) -> Result<(), StatusCode> {
+    ACCOUNT.post_start_data_export.incr();
+
+    let started = state
+        .data_export()
+        .queue_export(state.event_manager(), id, id)
+        .await?;
+
+    if started {
+        Ok(())
+    } else {
+        Err(StatusCode::NOT_ACCEPTABLE)
+    }
+}
+
+const PATH_GET_DATA_EXPORT_STATE: &str = "/account_api/data_export_state";
+
+/// Get current data export state.
+///
+/// The state is not saved to the database, so after server restart
+/// the state is the default state and a new export must be started.
+#[utoipa::path(
+    get,
+    path = PATH_GET_DATA_EXPORT_STATE,
+    responses(
+        (status = 200, description = "Successfull.", body = DataExportState),
+        (status = 401, description = "Unauthorized."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn get_data_export_state(
+    State(state): State<S>,
+    Extension(id): Extension<AccountIdInternal>,
+) -> Result<Json<DataExportState>, StatusCode> {
+    ACCOUNT.get_data_export_state.incr();
+
+    let export_state = state.data_export().get_state(id).await;
+    Ok(export_state.into())
+}
+
+const PATH_GET_DATA_EXPORT_ARCHIVE: &str = "/account_api/data_export_archive/{token}";
+
+/// Download data export archive.
+///
+/// The archive is available until the download token expires.
+///
+/// # Access
+///
+/// Only the account which started the export can download the archive.
+#[utoipa::path(
+    get,
+    path = PATH_GET_DATA_EXPORT_ARCHIVE,
+    params(DataExportDownloadToken),
+    responses(
+        (status = 200, description = "Get data export ZIP archive.", body = inline(model::BinaryData), content_type = "application/octet-stream"),
+        (status = 401, description = "Unauthorized."),
+        (status = 406, description = "Invalid or expired download token."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn get_data_export_archive(
+    State(state): State<S>,
+    Extension(id): Extension<AccountIdInternal>,
+    Path(token): Path<DataExportDownloadToken>,
+) -> Result<(TypedHeader<ContentType>, TypedHeader<ContentLength>, Body), StatusCode> {
+    ACCOUNT.get_data_export_archive.incr();
+
+    if !state.data_export().download_allowed(id, &token).await {
+        return Err(StatusCode::NOT_ACCEPTABLE);
+    }
+
+    let (lenght, stream) = state
+        .read()
+        .files()
+        .data_export_archive(id.as_id())
+        .byte_count_and_read_stream()
+        .await
+        .change_context(DataError::File)?;
+
+    Ok((
+        TypedHeader(ContentType::octet_stream()),
+        TypedHeader(ContentLength(lenght)),
+        Body::from_stream(stream),
+    ))
+}
+
+create_open_api_router!(
+    fn router_data_export,
+    post_start_data_export,
+    get_data_export_state,
+    get_data_export_archive,
+);
+
+create_counters!(
+    AccountCounters,
+    ACCOUNT,
+    ACCOUNT_DATA_EXPORT_COUNTERS_LIST,
+    post_start_data_export,
+    get_data_export_state,
+    get_data_export_archive,
+);
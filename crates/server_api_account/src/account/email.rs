@@ -0,0 +1,140 @@
+use axum::{extract::State, Extension};
+use model_account::{
+    AccountIdInternal, EmailConfirmationState, EmailConfirmationToken, SendEmailConfirmationResult,
+};
+use server_api::{create_open_api_router, db_write, S};
+use server_data_account::{
+    email_confirmation::validate_email_confirmation_token, read::GetReadCommandsAccount,
+    write::GetWriteCommandsAccount,
+};
+use simple_backend::create_counters;
+
+use crate::{
+    app::{GetAccounts, ReadData, WriteData},
+    utils::{Json, StatusCode},
+};
+
+pub const PATH_POST_CONFIRM_EMAIL: &str = "/account_api/confirm_email";
+
+/// Confirm email address using the token from email confirmation email.
+///
+/// The token is valid only if it is not expired and the account
+/// email address has not changed after the token was created.
+#[utoipa::path(
+    post,
+    path = PATH_POST_CONFIRM_EMAIL,
+    security(),
+    request_body = EmailConfirmationToken,
+    responses(
+        (status = 200, description = "Successfull."),
+        (status = 406, description = "Invalid or expired token."),
+        (status = 500, description = "Internal server error."),
+    ),
+)]
+pub async fn post_confirm_email(
+    State(state): State<S>,
+    Json(token): Json<EmailConfirmationToken>,
+) -> Result<(), StatusCode> {
+    ACCOUNT.post_confirm_email.incr();
+
+    let Some(key) = state
+        .read()
+        .account()
+        .email()
+        .email_confirmation_token_key()
+        .await?
+    else {
+        return Err(StatusCode::NOT_ACCEPTABLE);
+    };
+
+    let Some(info) = validate_email_confirmation_token(&key, &token) else {
+        return Err(StatusCode::NOT_ACCEPTABLE);
+    };
+
+    let Some(id) = state.get_internal_id_optional(info.aid).await else {
+        return Err(StatusCode::NOT_ACCEPTABLE);
+    };
+
+    let confirmed = db_write!(state, move |cmds| cmds
+        .account()
+        .email()
+        .confirm_email(id, info.email))?;
+
+    if confirmed {
+        Ok(())
+    } else {
+        Err(StatusCode::NOT_ACCEPTABLE)
+    }
+}
+
+const PATH_GET_EMAIL_CONFIRMATION_STATE: &str = "/account_api/email_confirmation_state";
+
+#[utoipa::path(
+    get,
+    path = PATH_GET_EMAIL_CONFIRMATION_STATE,
+    responses(
+        (status = 200, description = "Successfull.", body = EmailConfirmationState),
+        (status = 401, description = "Unauthorized."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn get_email_confirmation_state(
+    State(state): State<S>,
+    Extension(id): Extension<AccountIdInternal>,
+) -> Result<Json<EmailConfirmationState>, StatusCode> {
+    ACCOUNT.get_email_confirmation_state.incr();
+
+    let confirmation_state = state
+        .read()
+        .account()
+        .email()
+        .email_confirmation_state(id)
+        .await?;
+    Ok(confirmation_state.into())
+}
+
+const PATH_POST_SEND_EMAIL_CONFIRMATION: &str = "/account_api/send_email_confirmation";
+
+/// Send email confirmation email to the current email address.
+///
+/// Sending a new email is possible when the config file defined wait
+/// duration has elapsed since the previous request.
+#[utoipa::path(
+    post,
+    path = PATH_POST_SEND_EMAIL_CONFIRMATION,
+    responses(
+        (status = 200, description = "Successfull.", body = SendEmailConfirmationResult),
+        (status = 401, description = "Unauthorized."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn post_send_email_confirmation(
+    State(state): State<S>,
+    Extension(id): Extension<AccountIdInternal>,
+) -> Result<Json<SendEmailConfirmationResult>, StatusCode> {
+    ACCOUNT.post_send_email_confirmation.incr();
+
+    let result = db_write!(state, move |cmds| cmds
+        .account()
+        .email()
+        .send_email_confirmation(id))?;
+
+    Ok(result.into())
+}
+
+create_open_api_router!(
+        fn router_email,
+        get_email_confirmation_state,
+        post_send_email_confirmation,
+);
+
+create_counters!(
+    AccountCounters,
+    ACCOUNT,
+    ACCOUNT_EMAIL_COUNTERS_LIST,
+    post_confirm_email,
+    get_email_confirmation_state,
+    post_send_email_confirmation,
+);
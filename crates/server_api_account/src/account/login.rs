@@ -1,9 +1,9 @@
 use axum::extract::State;
 use model_account::{
-    AccessToken, AccountId, AuthPair, EmailAddress, GoogleAccountId, LoginResult, RefreshToken,
-    SignInWithInfo, SignInWithLoginInfo,
+    AccessToken, AccountId, AppleAccountId, AuthPair, EmailAddress, GoogleAccountId, LoginResult,
+    RefreshToken, SignInWithInfo, SignInWithLoginInfo,
 };
-use server_api::{app::GetConfig, db_write, db_write_multiple, S};
+use server_api::{app::GetConfig, db_write, S};
 use server_data::write::GetWriteCommandsCommon;
 use server_data_account::{read::GetReadCommandsAccount, write::GetWriteCommandsAccount};
 use simple_backend::{app::SignInWith, create_counters};
@@ -27,13 +27,8 @@ pub async fn login_impl(id: AccountId, state: S) -> Result<LoginResult, StatusCo
     let account = AuthPair { access, refresh };
     let account_clone = account.clone();
 
-    db_write_multiple!(state, move |cmds| {
-        cmds.account_chat_utils()
-            .remove_fcm_device_token_and_pending_notification_token(id)
-            .await?;
-        cmds.common()
-            .set_new_auth_pair(id, account_clone, None)
-            .await
+    db_write!(state, move |cmds| {
+        cmds.common().create_login_session(id, account_clone)
     })?;
 
     // TODO(microservice): microservice support
@@ -111,25 +106,56 @@ pub async fn post_sign_in_with_login(
                 .register_impl(
                     SignInWithInfo {
                         google_account_id: Some(google_id),
+                        apple_account_id: None,
                     },
                     Some(email),
                 )
                 .await?;
             login_impl(id.as_id(), state).await.map(|d| d.into())
         }
-    } else if let Some(apple) = tokens.apple_token {
-        let _info = state
+    } else if let Some(apple) = tokens.apple {
+        let info = state
             .sign_in_with_manager()
-            .validate_apple_token(apple)
+            .validate_apple_token(apple.token, apple.nonce)
             .await?;
 
-        // if validate_sign_in_with_apple_token(apple).await.unwrap() {
-        //     let key = AccessToken::generate_new();
-        //     Ok(key.into())
-        // } else {
-        //     Err(StatusCode::INTERNAL_SERVER_ERROR)
-        // }
-        Err(StatusCode::INTERNAL_SERVER_ERROR)
+        let email: Option<EmailAddress> = info
+            .email
+            .map(|v| v.try_into())
+            .transpose()
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        let apple_id = AppleAccountId(info.id);
+        let already_existing_account = state
+            .read()
+            .account()
+            .apple_account_id_to_account_id(apple_id.clone())
+            .await?;
+
+        if let Some(already_existing_account) = already_existing_account {
+            if let Some(email) = email {
+                db_write!(state, move |cmds| cmds
+                    .account()
+                    .email()
+                    .account_email(already_existing_account, email))?;
+            }
+
+            login_impl(already_existing_account.as_id(), state)
+                .await
+                .map(|d| d.into())
+        } else {
+            let id = state
+                .data_all_access()
+                .register_impl(
+                    SignInWithInfo {
+                        google_account_id: None,
+                        apple_account_id: Some(apple_id),
+                    },
+                    email,
+                )
+                .await?;
+            login_impl(id.as_id(), state).await.map(|d| d.into())
+        }
     } else {
         Err(StatusCode::INTERNAL_SERVER_ERROR)
     }
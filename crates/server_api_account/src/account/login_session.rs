@@ -0,0 +1,179 @@
+use axum::{extract::State, Extension};
+use model_account::{
+    AccountIdInternal, AuthPair, LoginSessionId, LoginSessionInfo, LoginSessionList, RefreshToken,
+};
+use server_api::{create_open_api_router, db_write, S};
+use server_data::{
+    read::GetReadCommandsCommon,
+    write::{common::RefreshTokenRotationResult, GetWriteCommandsCommon},
+};
+use simple_backend::create_counters;
+
+use crate::{
+    app::{ReadData, WriteData},
+    utils::{Json, StatusCode},
+};
+
+pub const PATH_POST_REFRESH_LOGIN_SESSION_TOKENS: &str =
+    "/account_api/refresh_login_session_tokens";
+
+/// Get new access and refresh tokens for the login session using
+/// the current refresh token.
+///
+/// The refresh token is rotated, so the used refresh token is not valid
+/// after this. If already used refresh token is received, the login session
+/// is revoked.
+///
+/// The new access token is usable after WebSocket connection is created.
+#[utoipa::path(
+    post,
+    path = PATH_POST_REFRESH_LOGIN_SESSION_TOKENS,
+    security(),
+    request_body = RefreshToken,
+    responses(
+        (status = 200, description = "Successfull.", body = AuthPair),
+        (status = 401, description = "Invalid refresh token."),
+        (status = 500, description = "Internal server error."),
+    ),
+)]
+pub async fn post_refresh_login_session_tokens(
+    State(state): State<S>,
+    Json(refresh_token): Json<RefreshToken>,
+) -> Result<Json<AuthPair>, StatusCode> {
+    ACCOUNT.post_refresh_login_session_tokens.incr();
+
+    let result = db_write!(state, move |cmds| {
+        cmds.common()
+            .rotate_tokens_with_refresh_token(refresh_token)
+    })?;
+
+    match result {
+        RefreshTokenRotationResult::Rotated(pair) => Ok(pair.into()),
+        RefreshTokenRotationResult::ReuseDetected => {
+            ACCOUNT.refresh_token_reuse_detected.incr();
+            Err(StatusCode::UNAUTHORIZED)
+        }
+        RefreshTokenRotationResult::Invalid => Err(StatusCode::UNAUTHORIZED),
+    }
+}
+
+const PATH_GET_LOGIN_SESSIONS: &str = "/account_api/login_sessions";
+
+/// Get all login sessions of current account.
+#[utoipa::path(
+    get,
+    path = PATH_GET_LOGIN_SESSIONS,
+    responses(
+        (status = 200, description = "Successfull.", body = LoginSessionList),
+        (status = 401, description = "Unauthorized."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn get_login_sessions(
+    State(state): State<S>,
+    Extension(id): Extension<AccountIdInternal>,
+    Extension(session_id): Extension<LoginSessionId>,
+) -> Result<Json<LoginSessionList>, StatusCode> {
+    ACCOUNT.get_login_sessions.incr();
+
+    let sessions = state
+        .read()
+        .common()
+        .login_sessions(id)
+        .await?
+        .into_iter()
+        .map(|v| LoginSessionInfo {
+            id: v.session_id,
+            created_unix_time: v.created_unix_time,
+            last_used_unix_time: v.last_used_unix_time,
+            current: v.session_id == session_id,
+            push_notifications_enabled: v.fcm_device_token.is_some(),
+        })
+        .collect();
+
+    Ok(LoginSessionList { sessions }.into())
+}
+
+const PATH_POST_REVOKE_LOGIN_SESSION: &str = "/account_api/revoke_login_session";
+
+/// Revoke specific login session of current account.
+///
+/// Revoking current login session is also possible. WebSocket
+/// connection of the revoked login session is closed.
+#[utoipa::path(
+    post,
+    path = PATH_POST_REVOKE_LOGIN_SESSION,
+    request_body = LoginSessionId,
+    responses(
+        (status = 200, description = "Successfull."),
+        (status = 401, description = "Unauthorized."),
+        (status = 406, description = "Login session does not exist."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn post_revoke_login_session(
+    State(state): State<S>,
+    Extension(id): Extension<AccountIdInternal>,
+    Json(session_id): Json<LoginSessionId>,
+) -> Result<(), StatusCode> {
+    ACCOUNT.post_revoke_login_session.incr();
+
+    let session_exists = state
+        .read()
+        .common()
+        .login_session_exists(id, session_id)
+        .await?;
+
+    if !session_exists {
+        return Err(StatusCode::NOT_ACCEPTABLE);
+    }
+
+    db_write!(state, move |cmds| cmds.common().logout(id, session_id))?;
+
+    Ok(())
+}
+
+const PATH_POST_REVOKE_ALL_LOGIN_SESSIONS: &str = "/account_api/revoke_all_login_sessions";
+
+/// Revoke all login sessions of current account including
+/// the current session.
+#[utoipa::path(
+    post,
+    path = PATH_POST_REVOKE_ALL_LOGIN_SESSIONS,
+    responses(
+        (status = 200, description = "Successfull."),
+        (status = 401, description = "Unauthorized."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn post_revoke_all_login_sessions(
+    State(state): State<S>,
+    Extension(id): Extension<AccountIdInternal>,
+) -> Result<(), StatusCode> {
+    ACCOUNT.post_revoke_all_login_sessions.incr();
+
+    db_write!(state, move |cmds| cmds.common().logout_all_sessions(id))?;
+
+    Ok(())
+}
+
+create_open_api_router!(
+        fn router_login_session,
+        get_login_sessions,
+        post_revoke_login_session,
+        post_revoke_all_login_sessions,
+);
+
+create_counters!(
+    AccountCounters,
+    ACCOUNT,
+    ACCOUNT_LOGIN_SESSION_COUNTERS_LIST,
+    post_refresh_login_session_tokens,
+    refresh_token_reuse_detected,
+    get_login_sessions,
+    post_revoke_login_session,
+    post_revoke_all_login_sessions,
+);
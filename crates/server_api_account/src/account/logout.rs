@@ -1,8 +1,7 @@
 use axum::{extract::State, Extension};
-use model::AccountIdInternal;
-use server_api::{create_open_api_router, db_write_multiple, S};
+use model::{AccountIdInternal, LoginSessionId};
+use server_api::{create_open_api_router, db_write, S};
 use server_data::write::GetWriteCommandsCommon;
-use server_data_account::write::GetWriteCommandsAccount;
 use simple_backend::create_counters;
 
 use super::super::utils::StatusCode;
@@ -10,6 +9,10 @@ use crate::app::WriteData;
 
 const PATH_POST_LOGOUT: &str = "/account_api/logout";
 
+/// Logout current login session.
+///
+/// Access token, refresh token and FCM device token of the current
+/// login session are removed. Other login sessions are not affected.
 #[utoipa::path(
     post,
     path = PATH_POST_LOGOUT,
@@ -23,15 +26,11 @@ const PATH_POST_LOGOUT: &str = "/account_api/logout";
 pub async fn post_logout(
     State(state): State<S>,
     Extension(account_id): Extension<AccountIdInternal>,
+    Extension(session_id): Extension<LoginSessionId>,
 ) -> Result<(), StatusCode> {
     ACCOUNT.post_logout.incr();
 
-    db_write_multiple!(state, move |cmds| {
-        cmds.common().logout(account_id).await?;
-        cmds.account_chat_utils()
-            .remove_fcm_device_token_and_pending_notification_token(account_id)
-            .await
-    })?;
+    db_write!(state, move |cmds| cmds.common().logout(account_id, session_id))?;
 
     Ok(())
 }
@@ -88,6 +88,8 @@ const PATH_ACCOUNT_COMPLETE_SETUP: &str = "/account_api/complete_setup";
 /// Requirements:
 ///  - Account must be in `InitialSetup` state.
 ///  - Account must have a valid AccountSetup info set.
+///  - Account email address must be confirmed if server is configured
+///    to require that.
 ///
 #[utoipa::path(
     post,
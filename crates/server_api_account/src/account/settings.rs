@@ -156,12 +156,73 @@ pub async fn put_setting_unlimited_likes(
     Ok(())
 }
 
+const PATH_GET_SETTING_EMAIL_NOTIFICATIONS: &str = "/account_api/settings/email_notifications";
+
+/// Get chat notification digest email setting.
+#[utoipa::path(
+    get,
+    path = PATH_GET_SETTING_EMAIL_NOTIFICATIONS,
+    responses(
+        (status = 200, description = "Successfull.", body = BooleanSetting),
+        (status = 401, description = "Unauthorized."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn get_setting_email_notifications(
+    State(state): State<S>,
+    Extension(id): Extension<AccountIdInternal>,
+) -> Result<Json<BooleanSetting>, StatusCode> {
+    ACCOUNT.get_setting_email_notifications.incr();
+
+    let value = state
+        .read()
+        .account()
+        .email()
+        .email_notifications_enabled(id)
+        .await?;
+
+    Ok(BooleanSetting { value }.into())
+}
+
+const PATH_PUT_SETTING_EMAIL_NOTIFICATIONS: &str = "/account_api/settings/email_notifications";
+
+/// Enable or disable chat notification digest emails.
+///
+/// The emails are sent only when push notifications are not
+/// available for the account.
+#[utoipa::path(
+    put,
+    path = PATH_PUT_SETTING_EMAIL_NOTIFICATIONS,
+    request_body(content = BooleanSetting),
+    responses(
+        (status = 200, description = "Update successfull."),
+        (status = 401, description = "Unauthorized."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn put_setting_email_notifications(
+    State(state): State<S>,
+    Extension(id): Extension<AccountIdInternal>,
+    Json(new_value): Json<BooleanSetting>,
+) -> Result<(), StatusCode> {
+    ACCOUNT.put_setting_email_notifications.incr();
+
+    db_write!(state, move |cmds| cmds
+        .account()
+        .email()
+        .set_email_notifications_enabled(id, new_value.value))
+}
+
 create_open_api_router!(
         fn router_settings,
         get_account_data,
         post_account_data,
         put_setting_profile_visiblity,
         put_setting_unlimited_likes,
+        get_setting_email_notifications,
+        put_setting_email_notifications,
 );
 
 create_counters!(
@@ -172,4 +233,6 @@ create_counters!(
     post_account_data,
     put_setting_profile_visiblity,
     put_setting_unlimited_likes,
+    get_setting_email_notifications,
+    put_setting_email_notifications,
 );
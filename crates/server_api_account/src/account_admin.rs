@@ -1,4 +1,5 @@
 pub mod ban;
+pub mod data_export;
 pub mod delete;
 pub mod news;
 pub mod search;
@@ -6,6 +7,7 @@ pub mod permissions;
 pub mod state;
 
 pub use ban::*;
+pub use data_export::*;
 pub use delete::*;
 pub use news::*;
 pub use search::*;
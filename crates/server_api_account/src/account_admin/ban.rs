@@ -39,7 +39,8 @@ pub async fn post_set_ban_state(
 
     let internal_id = state.get_internal_id(ban_info.account).await?;
 
-    db_write_multiple!(state, move |cmds| {
+    let banned = ban_info.ban_until.is_some();
+    let ban_state_changed = db_write_multiple!(state, move |cmds| {
         let new_account = cmds.account_admin().ban().set_account_ban_state(
             internal_id,
             Some(api_caller_id),
@@ -57,9 +58,16 @@ pub async fn post_set_ban_state(
                 .await?;
         }
 
-        Ok(())
+        Ok(new_account.is_some())
     })?;
 
+    if banned && ban_state_changed {
+        state
+            .data_all_access()
+            .add_banned_account_content_to_blocklist(internal_id)
+            .await?;
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,71 @@
+use axum::{extract::{Path, State}, Extension};
+use model::{AccountId, AccountIdInternal, Permissions};
+use server_api::{
+    app::{DataExportProvider, EventManagerProvider, GetAccounts},
+    create_open_api_router, S,
+};
+use simple_backend::create_counters;
+
+use crate::utils::StatusCode;
+
+const PATH_POST_START_ADMIN_DATA_EXPORT: &str = "/account_api/start_admin_data_export/{aid}";
+
+/// Start exporting all data related to the selected account to ZIP archive.
+///
+/// Export progress and download token are sent to the admin account
+/// like when exporting own account data. The archive can be downloaded
+/// using the same API as own account data export archive.
+///
+/// The admin account has only one export archive, so a new export
+/// can not be started while the archive of another account is
+/// available for download.
+///
+/// # Access
+///
+/// Permission [model_account::Permissions::admin_export_data] is required.
+#[utoipa::path(
+    post,
+    path = PATH_POST_START_ADMIN_DATA_EXPORT,
+    params(AccountId),
+    responses(
+        (status = 200, description = "Successfull."),
+        (status = 401, description = "Unauthorized."),
+        (status = 406, description = "Data export is already in progress or export archive of another account is available for download."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn post_start_admin_data_export(
+    State(state): State<S>,
+    Extension(api_caller): Extension<AccountIdInternal>,
+    Extension(permissions): Extension<Permissions>,
+    Path(account): Path<AccountId>,
+) -> Result<(), StatusCode> {
+    ACCOUNT_ADMIN.post_start_admin_data_export.incr();
+
+    if !permissions.admin_export_data {
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let target = state.get_internal_id(account).await?;
+
+    let started = state
+        .data_export()
+        .queue_export(state.event_manager(), api_caller, target)
+        .await?;
+
+    if started {
+        Ok(())
+    } else {
+        Err(StatusCode::NOT_ACCEPTABLE)
+    }
+}
+
+create_open_api_router!(fn router_admin_data_export, post_start_admin_data_export,);
+
+create_counters!(
+    AccountCounters,
+    ACCOUNT_ADMIN,
+    ACCOUNT_ADMIN_DATA_EXPORT_COUNTERS_LIST,
+    post_start_admin_data_export,
+);
@@ -25,6 +25,8 @@ pub use server_common::{data::DataError, result};
     paths(
         // Account
         account::post_sign_in_with_login,
+        account::post_refresh_login_session_tokens,
+        account::post_confirm_email,
         // Account bot API
         account_bot::post_bot_register,
         account_bot::post_bot_login,
@@ -19,6 +19,11 @@ const PATH_POST_SEND_LIKE: &str = "/chat_api/send_like";
 
 /// Send a like to some account. If both will like each other, then
 /// the accounts will be a match.
+///
+/// If the like receiver has enabled mutual attribute filters, then
+/// current account's profile attributes must match with the receiver's
+/// attribute filters. The check is skipped if the receiver has already
+/// liked current account.
 #[utoipa::path(
     post,
     path = PATH_POST_SEND_LIKE,
@@ -82,10 +87,13 @@ pub async fn post_send_like(
         };
 
         if allow_action {
-            let changes = cmds
+            let Some(changes) = cmds
                 .chat()
                 .like_or_match_profile(id, requested_profile)
-                .await?;
+                .await?
+            else {
+                return Ok(SendLikeResult::error_attribute_filters_mismatch());
+            };
             cmds.events()
                 .handle_chat_state_changes(changes.sender)
                 .await?;
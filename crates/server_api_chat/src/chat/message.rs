@@ -34,8 +34,6 @@ use crate::{
 //   signed by server to make sure that those really are from the user.
 //   Also the sign then encrypt will make reliable message reporting
 //   possible as the messages are signed.
-// - Server should store all public keys and max uploads for public keys
-//   should be 1024.
 // - Update pgp to new version and change keys to use X25519 and Ed25519.
 
 const PATH_GET_PENDING_MESSAGES: &str = "/chat_api/pending_messages";
@@ -202,8 +200,9 @@ const PATH_POST_SEND_MESSAGE: &str = "/chat_api/send_message";
 
 /// Send message to a match.
 ///
-/// Max pending message count is 50.
-/// Max message size is u16::MAX.
+/// Max pending message counts and max message size are configurable.
+/// Max pending message count for one conversation is 50 by default.
+/// Message size can not be larger than u16::MAX.
 ///
 /// The sender message ID must be value which server expects.
 ///
@@ -5,7 +5,8 @@ use axum::{
     Extension,
 };
 use model_chat::{
-    AccountId, AccountIdInternal, GetPublicKey, PublicKeyId, PublicKeyVersion, SetPublicKey,
+    AccountId, AccountIdInternal, GetPublicKey, GetPublicKeyHistoryEntry, PublicKeyHistory,
+    PublicKeyId, PublicKeyVersion, SetPublicKey,
 };
 use server_api::{
     app::{GetAccounts, WriteData},
@@ -54,6 +55,9 @@ const PATH_POST_PUBLIC_KEY: &str = "/chat_api/public_key";
 /// This must be called only when needed as this route will
 /// fail every time if current public key ID number is i64::MAX.
 ///
+/// The new public key is also added to the account's public key history.
+/// Max public key upload count is 1024.
+///
 /// Only version 1 public keys are currently supported.
 #[utoipa::path(
     post,
@@ -62,7 +66,7 @@ const PATH_POST_PUBLIC_KEY: &str = "/chat_api/public_key";
     responses(
         (status = 200, description = "Success.", body = PublicKeyId),
         (status = 401, description = "Unauthorized."),
-        (status = 406, description = "Unsupported public key version"),
+        (status = 406, description = "Unsupported public key version or public key upload limit reached."),
         (status = 500, description = "Internal server error."),
     ),
     security(("access_token" = [])),
@@ -82,10 +86,83 @@ async fn post_public_key(
         cmds.chat().set_public_key(id, new_key)
     })?;
 
-    Ok(new_key.into())
+    match new_key {
+        Some(new_key) => Ok(new_key.into()),
+        None => Err(StatusCode::NOT_ACCEPTABLE),
+    }
+}
+
+const PATH_GET_PUBLIC_KEY_HISTORY: &str = "/chat_api/public_key_history/{aid}";
+
+/// Get all public keys which some account has uploaded
+///
+/// The keys are ordered by public key version and ID. Each key has
+/// server signature which can be verified using the message signing
+/// public key from client config.
+#[utoipa::path(
+    get,
+    path = PATH_GET_PUBLIC_KEY_HISTORY,
+    params(AccountId),
+    responses(
+        (status = 200, description = "Success.", body = PublicKeyHistory),
+        (status = 401, description = "Unauthorized."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+async fn get_public_key_history(
+    State(state): State<S>,
+    Path(requested_id): Path<AccountId>,
+) -> Result<Json<PublicKeyHistory>, StatusCode> {
+    CHAT.get_public_key_history.incr();
+
+    let requested_internal_id = state.get_internal_id(requested_id).await?;
+    let history = state
+        .read()
+        .chat()
+        .public_key_history(requested_internal_id)
+        .await?;
+    Ok(history.into())
 }
 
-create_open_api_router!(fn router_public_key, get_public_key, post_public_key,);
+const PATH_GET_PUBLIC_KEY_HISTORY_ENTRY: &str = "/chat_api/public_key_history_entry/{aid}";
+
+/// Get specific public key from some account's public key history
+#[utoipa::path(
+    get,
+    path = PATH_GET_PUBLIC_KEY_HISTORY_ENTRY,
+    params(AccountId, PublicKeyVersion, PublicKeyId),
+    responses(
+        (status = 200, description = "Success.", body = GetPublicKeyHistoryEntry),
+        (status = 401, description = "Unauthorized."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("access_token" = [])),
+)]
+async fn get_public_key_history_entry(
+    State(state): State<S>,
+    Path(requested_id): Path<AccountId>,
+    Query(key_version): Query<PublicKeyVersion>,
+    Query(key_id): Query<PublicKeyId>,
+) -> Result<Json<GetPublicKeyHistoryEntry>, StatusCode> {
+    CHAT.get_public_key_history_entry.incr();
+
+    let requested_internal_id = state.get_internal_id(requested_id).await?;
+    let key = state
+        .read()
+        .chat()
+        .public_key_history_entry(requested_internal_id, key_version, key_id)
+        .await?;
+    Ok(key.into())
+}
+
+create_open_api_router!(
+        fn router_public_key,
+        get_public_key,
+        post_public_key,
+        get_public_key_history,
+        get_public_key_history_entry,
+);
 
 create_counters!(
     ChatCounters,
@@ -93,4 +170,6 @@ create_counters!(
     CHAT_PUBLIC_KEY_COUNTERS_LIST,
     get_public_key,
     post_public_key,
+    get_public_key_history,
+    get_public_key_history_entry,
 );
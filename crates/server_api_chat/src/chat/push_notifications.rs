@@ -1,6 +1,6 @@
 use axum::{extract::State, Extension};
 use model::{
-    AccountIdInternal, FcmDeviceToken, PendingNotificationToken, PendingNotificationWithData,
+    FcmDeviceToken, LoginSessionId, PendingNotificationToken, PendingNotificationWithData,
 };
 use server_api::{create_open_api_router, S};
 use server_data_chat::write::GetWriteCommandsChat;
@@ -9,13 +9,15 @@ use simple_backend::create_counters;
 use super::super::utils::{Json, StatusCode};
 use crate::{app::WriteData, db_write};
 
-// TODO(prod): Logout route should remove the device and pending notification
-// tokens.
 // TOOD(microservice): Most likely public ID will not be sent from account
 // to other servers.
 
 const PATH_POST_SET_DEVICE_TOKEN: &str = "/chat_api/set_device_token";
 
+/// Set FCM device token for current login session.
+///
+/// The device token is removed from other login sessions if
+/// some other session is using the same token.
 #[utoipa::path(
     post,
     path = PATH_POST_SET_DEVICE_TOKEN,
@@ -29,7 +31,7 @@ const PATH_POST_SET_DEVICE_TOKEN: &str = "/chat_api/set_device_token";
 )]
 pub async fn post_set_device_token(
     State(state): State<S>,
-    Extension(id): Extension<AccountIdInternal>,
+    Extension(session_id): Extension<LoginSessionId>,
     Json(device_token): Json<FcmDeviceToken>,
 ) -> Result<Json<PendingNotificationToken>, StatusCode> {
     CHAT.post_set_device_token.incr();
@@ -37,7 +39,7 @@ pub async fn post_set_device_token(
     let pending_notification_token = db_write!(state, move |cmds| {
         cmds.chat()
             .push_notifications()
-            .set_device_token(id, device_token)
+            .set_device_token(session_id, device_token)
     })?;
 
     Ok(pending_notification_token.into())
@@ -15,7 +15,8 @@ const PATH_POST_CHAT_MESSAGE_REPORT: &str = "/chat_api/chat_message_report";
 
 /// Report chat message.
 ///
-/// The report target must be a match.
+/// The report target must be a match. The report is accepted only if
+/// the server signature of the reported message is valid.
 #[utoipa::path(
     post,
     path = PATH_POST_CHAT_MESSAGE_REPORT,
@@ -39,7 +40,7 @@ pub async fn post_chat_message_report(
     let result = db_write!(state, move |cmds| cmds
         .chat()
         .report()
-        .report_chat_message(account_id, target, update.message))?;
+        .report_chat_message(account_id, target, update.message, update.signed_message))?;
 
     Ok(result.into())
 }
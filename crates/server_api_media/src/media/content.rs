@@ -1,15 +1,16 @@
 use axum::{
     body::Body,
     extract::{Path, Query, State},
+    http::HeaderMap,
+    response::Response,
     Extension,
 };
-use axum_extra::TypedHeader;
-use headers::{ContentLength, ContentType};
+use headers::ContentType;
 use model::EventToClientInternal;
 use model_media::{
     AccountContent, AccountId, AccountIdInternal, AccountState, ContentId, ContentProcessingId,
-    ContentProcessingState, ContentSlot, GetContentQueryParams, NewContentParams, Permissions,
-    SlotId,
+    ContentProcessingState, ContentSize, ContentSlot, GetContentQueryParams, NewContentParams,
+    Permissions, SlotId,
 };
 use server_api::{app::GetConfig, create_open_api_router, db_write_multiple, result::WrappedResultExt, S};
 use server_data::{
@@ -18,7 +19,10 @@ use server_data::{
     DataError,
 };
 use server_data_media::{read::GetReadMediaCommands, write::GetWriteCommandsMedia};
-use simple_backend::create_counters;
+use simple_backend::{
+    create_counters,
+    file_response::{FileCache, FileResponse},
+};
 
 use crate::{
     app::{ContentProcessingProvider, GetAccounts, ReadData, WriteData},
@@ -45,12 +49,25 @@ const PATH_GET_CONTENT: &str = "/media_api/content/{aid}/{cid}";
 /// If the previous is not true, then permission `admin_view_all_profiles` or
 /// `admin_moderate_media_content` is required.
 ///
+/// # Caching
+///
+/// The response has a strong `ETag` header. Requests with `If-None-Match`
+/// and single range `Range` headers are supported.
+///
+/// Content does not change after it is stored, so `Cache-Control` header
+/// marks the response immutable. [model::ContentSize::Small] is an
+/// exception as it changes when profile grid crop info changes, so
+/// it must be revalidated using the `ETag`.
+///
 #[utoipa::path(
     get,
     path = PATH_GET_CONTENT,
     params(AccountId, ContentId, GetContentQueryParams),
     responses(
         (status = 200, description = "Get content file.", body = inline(model::BinaryData), content_type = "application/octet-stream"),
+        (status = 206, description = "Get part of content file.", body = inline(model::BinaryData), content_type = "application/octet-stream"),
+        (status = 304, description = "Not modified."),
+        (status = 416, description = "Range not satisfiable."),
         (status = 401, description = "Unauthorized."),
         (status = 500),
     ),
@@ -64,26 +81,36 @@ pub async fn get_content(
     Path(requested_profile): Path<AccountId>,
     Path(requested_content_id): Path<ContentId>,
     Query(params): Query<GetContentQueryParams>,
-) -> Result<(TypedHeader<ContentType>, TypedHeader<ContentLength>, Body), StatusCode> {
+    request_headers: HeaderMap,
+) -> Result<Response, StatusCode> {
     MEDIA.get_content.incr();
 
     let send_content = || async {
         let data = state
             .read()
             .media()
-            .content_data(requested_profile, requested_content_id)
+            .content_data_with_size(requested_profile, requested_content_id, params.size)
             .await?;
 
-        let (lenght, stream) = data
-            .byte_count_and_read_stream()
+        let cache = if params.size == ContentSize::Small {
+            FileCache::Revalidate
+        } else {
+            FileCache::Immutable
+        };
+        let etag_id = format!(
+            "{}{}",
+            requested_content_id.cid,
+            params.size.file_name_suffix()
+        );
+        let response =
+            FileResponse::open(data.path(), &etag_id, ContentType::octet_stream(), cache)
+                .await
+                .change_context(DataError::File)?;
+
+        response
+            .into_response(&request_headers)
             .await
-            .change_context(DataError::File)?;
-
-        Ok((
-            TypedHeader(ContentType::octet_stream()),
-            TypedHeader(ContentLength(lenght)),
-            Body::from_stream(stream),
-        ))
+            .change_context(DataError::File)
     };
 
     if account_id.as_id() == requested_profile {
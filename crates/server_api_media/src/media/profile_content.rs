@@ -145,10 +145,16 @@ pub async fn put_profile_content(
 ) -> Result<(), StatusCode> {
     MEDIA.put_profile_content.incr();
 
+    let small_image_variants = state
+        .read()
+        .media()
+        .create_small_image_variants(api_caller_account_id, &new)
+        .await?;
+
     db_write_multiple!(state, move |cmds| {
         let info = cmds
             .media()
-            .update_profile_content(api_caller_account_id, new).await?;
+            .update_profile_content(api_caller_account_id, new, small_image_variants).await?;
 
         match info {
             InitialContentModerationResult::AllAccepted { .. } => {
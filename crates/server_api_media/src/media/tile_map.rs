@@ -1,9 +1,8 @@
 use axum::{
-    body::Body,
     extract::{Path, State},
+    http::HeaderMap,
+    response::Response,
 };
-use axum_extra::TypedHeader;
-use headers::{ContentLength, ContentType};
 use model_media::{MapTileX, MapTileY, MapTileZ};
 use server_api::{create_open_api_router, S};
 use simple_backend::{app::GetTileMap, create_counters};
@@ -16,12 +15,19 @@ const PATH_GET_MAP_TILE: &str = "/media_api/map_tile/{z}/{x}/{y}";
 /// Get map tile PNG file.
 ///
 /// Returns a .png even if the URL does not have it.
+///
+/// The response has a strong `ETag` and immutable `Cache-Control` header.
+/// Requests with `If-None-Match` and single range `Range` headers are
+/// supported.
 #[utoipa::path(
     get,
     path = PATH_GET_MAP_TILE,
     params(MapTileZ, MapTileX, MapTileY),
     responses(
         (status = 200, description = "Get map tile PNG file.", body = inline(model::BinaryData), content_type = "image/png"),
+        (status = 206, description = "Get part of map tile PNG file.", body = inline(model::BinaryData), content_type = "image/png"),
+        (status = 304, description = "Not modified."),
+        (status = 416, description = "Range not satisfiable."),
         (status = 401, description = "Unauthorized."),
         (status = 500),
     ),
@@ -32,7 +38,8 @@ pub async fn get_map_tile(
     Path(z): Path<MapTileZ>,
     Path(x): Path<MapTileX>,
     Path(y): Path<MapTileY>,
-) -> Result<(TypedHeader<ContentType>, TypedHeader<ContentLength>, Body), StatusCode> {
+    request_headers: HeaderMap,
+) -> Result<Response, StatusCode> {
     MEDIA.get_map_tile.incr();
 
     let y_string = y.y.trim_end_matches(".png");
@@ -40,21 +47,20 @@ pub async fn get_map_tile(
         .parse::<u32>()
         .map_err(|_| StatusCode::NOT_ACCEPTABLE)?;
 
-    let byte_count_and_data_stream = state
+    let tile = state
         .tile_map()
-        .map_tile_byte_count_and_byte_stream(z.z, x.x, y)
+        .map_tile_response(z.z, x.x, y)
         .await
         .map_err(|e| {
             error!("{:?}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
-    match byte_count_and_data_stream {
-        Some((byte_count, data_stream)) => Ok((
-            TypedHeader(ContentType::png()),
-            TypedHeader(ContentLength(byte_count)),
-            Body::from_stream(data_stream),
-        )),
+    match tile {
+        Some(tile) => tile.into_response(&request_headers).await.map_err(|e| {
+            error!("{:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }),
         None => Err(StatusCode::NOT_FOUND),
     }
 }
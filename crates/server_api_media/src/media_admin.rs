@@ -1,3 +1,5 @@
 pub mod moderation;
+pub mod similar_content;
 
 pub use moderation::*;
+pub use similar_content::*;
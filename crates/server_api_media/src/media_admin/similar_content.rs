@@ -0,0 +1,76 @@
+use axum::{
+    extract::{Path, State},
+    Extension,
+};
+use model_media::{AccountId, ContentId, Permissions, SimilarContentList};
+use server_api::{
+    app::{GetAccounts, ReadData},
+    create_open_api_router, S,
+};
+use server_data_media::read::GetReadMediaCommands;
+use simple_backend::create_counters;
+
+use crate::utils::{Json, StatusCode};
+
+const PATH_GET_SIMILAR_CONTENT: &str = "/media_api/admin/similar_content/{aid}/{cid}";
+
+/// Get content of other accounts and blocklisted images which are
+/// near-duplicates of the content.
+///
+/// Near-duplicates are detected using perceptual hashes. The blocklist
+/// contains images which human moderators have rejected and images
+/// uploaded by banned accounts.
+///
+/// # Access
+///
+/// Permission [model_media::Permissions::admin_moderate_media_content]
+/// is required.
+#[utoipa::path(
+    get,
+    path = PATH_GET_SIMILAR_CONTENT,
+    params(AccountId, ContentId),
+    responses(
+        (status = 200, description = "Successful", body = SimilarContentList),
+        (status = 401, description = "Unauthorized"),
+        (
+            status = 500,
+            description = "Internal server error",
+        ),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn get_similar_content(
+    State(state): State<S>,
+    Extension(permissions): Extension<Permissions>,
+    Path(content_owner): Path<AccountId>,
+    Path(content_id): Path<ContentId>,
+) -> Result<Json<SimilarContentList>, StatusCode> {
+    MEDIA_ADMIN.get_similar_content.incr();
+
+    if !permissions.admin_moderate_media_content {
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let content_owner = state.get_internal_id(content_owner).await?;
+    let content_id = state
+        .read()
+        .media()
+        .content_id_internal(content_owner, content_id)
+        .await?;
+    let r = state
+        .read()
+        .media_admin()
+        .similar_content(content_id)
+        .await?;
+
+    Ok(r.into())
+}
+
+create_open_api_router!(fn router_admin_similar_content, get_similar_content,);
+
+create_counters!(
+    MediaAdminCounters,
+    MEDIA_ADMIN,
+    MEDIA_ADMIN_SIMILAR_CONTENT_COUNTERS_LIST,
+    get_similar_content,
+);
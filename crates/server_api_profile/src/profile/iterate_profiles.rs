@@ -1,10 +1,13 @@
+use std::sync::Arc;
+
 use axum::{extract::State, Extension};
 use model_profile::{AccountIdInternal, ProfileIteratorSessionId, ProfilePage};
 use server_api::{create_open_api_router, S};
+use server_data_profile::read::GetReadProfileCommands;
 use simple_backend::create_counters;
 
 use crate::{
-    app::WriteData,
+    app::{ReadData, WriteData},
     utils::{Json, StatusCode},
 };
 
@@ -51,16 +54,21 @@ pub async fn post_get_next_profile_page(
     }
 }
 
-// TODO(prod): Consider adding support for optional random iterator initial
-//             position and max distance. Could filtering settings
-//             include those?
-
 const PATH_POST_RESET_PROFILE_PAGING: &str = "/profile_api/page/reset";
 
 /// Reset profile paging.
 ///
 /// After this request getting next profiles will continue from the nearest
 /// profiles.
+///
+/// Profile filtering settings `max_distance_km_filter` and
+/// `random_profile_order` define the iterator area and the starting
+/// position. If max distance is set, only profiles within that distance
+/// are returned.
+///
+/// If profile filtering setting `hide_interacted_profiles` is enabled,
+/// profiles which the account has liked, matched or blocked are not
+/// returned in the new profile iterator session.
 #[utoipa::path(
     post,
     path = PATH_POST_RESET_PROFILE_PAGING,
@@ -76,9 +84,26 @@ pub async fn post_reset_profile_paging(
     Extension(account_id): Extension<AccountIdInternal>,
 ) -> Result<Json<ProfileIteratorSessionId>, StatusCode> {
     PROFILE.post_reset_profile_paging.incr();
+
+    let hide_interacted_profiles = state
+        .read()
+        .profile()
+        .profile_state(account_id)
+        .await?
+        .hide_interacted_profiles;
+    let hidden_profiles = if hide_interacted_profiles {
+        let accounts = state
+            .data_all_access()
+            .liked_matched_and_blocked_accounts(account_id)
+            .await?;
+        Some(Arc::new(accounts))
+    } else {
+        None
+    };
+
     let iterator_session_id: ProfileIteratorSessionId = state
         .concurrent_write_profile_blocking(account_id.as_id(), move |cmds| {
-            cmds.reset_profile_iterator(account_id)
+            cmds.reset_profile_iterator(account_id, hidden_profiles)
         })
         .await??
         .into();
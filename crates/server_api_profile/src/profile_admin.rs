@@ -1,11 +1,15 @@
+pub mod moderation_history;
 pub mod profile_data;
 pub mod profile_name_allowlist;
 pub mod profile_text;
 pub mod statistics;
 pub mod iterate_profiles;
+pub mod search;
 
+pub use moderation_history::*;
 pub use profile_data::*;
 pub use profile_name_allowlist::*;
 pub use profile_text::*;
 pub use statistics::*;
 pub use iterate_profiles::*;
+pub use search::*;
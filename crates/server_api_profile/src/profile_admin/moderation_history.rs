@@ -0,0 +1,69 @@
+use axum::{
+    extract::{Path, State},
+    Extension,
+};
+use model::AccountId;
+use model_profile::{GetProfileModerationHistory, Permissions};
+use server_api::{create_open_api_router, S};
+use server_data_profile::read::GetReadProfileCommands;
+use simple_backend::create_counters;
+
+use crate::{
+    app::ReadData,
+    utils::{Json, StatusCode},
+};
+
+const PATH_GET_PROFILE_MODERATION_HISTORY: &str =
+    "/profile_api/get_profile_moderation_history/{aid}";
+
+/// Get submitted profile names and texts of an account with
+/// moderation results.
+///
+/// # Access
+/// - Permission [model::Permissions::admin_view_profile_history]
+#[utoipa::path(
+    get,
+    path = PATH_GET_PROFILE_MODERATION_HISTORY,
+    params(AccountId),
+    responses(
+        (status = 200, description = "Successful.", body = GetProfileModerationHistory),
+        (status = 401, description = "Unauthorized."),
+        (
+            status = 500,
+            description = "Internal server error.",
+        ),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn get_profile_moderation_history(
+    State(state): State<S>,
+    Extension(permissions): Extension<Permissions>,
+    Path(account_id): Path<AccountId>,
+) -> Result<Json<GetProfileModerationHistory>, StatusCode> {
+    PROFILE.get_profile_moderation_history.incr();
+
+    if !permissions.admin_view_profile_history {
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let r = state
+        .read()
+        .profile_admin_history()
+        .moderation()
+        .profile_moderation_history(account_id)
+        .await?;
+
+    Ok(r.into())
+}
+
+create_open_api_router!(
+    fn router_admin_moderation_history,
+    get_profile_moderation_history,
+);
+
+create_counters!(
+    ProfileCounters,
+    PROFILE,
+    PROFILE_ADMIN_MODERATION_HISTORY_COUNTERS_LIST,
+    get_profile_moderation_history,
+);
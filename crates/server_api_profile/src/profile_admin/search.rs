@@ -0,0 +1,67 @@
+use axum::{
+    extract::{Query, State},
+    Extension,
+};
+use model_profile::{Permissions, ProfileSearchResultPage, ProfileSearchSettings};
+use server_api::{create_open_api_router, S};
+use server_data_profile::read::GetReadProfileCommands;
+use simple_backend::create_counters;
+
+use crate::{
+    app::ReadData,
+    utils::{Json, StatusCode},
+};
+
+const PATH_GET_ADMIN_PROFILE_SEARCH_PAGE: &str = "/profile_api/get_admin_profile_search_page";
+
+/// Search profiles using profile name and profile text.
+///
+/// Returns both public and private profiles. Results are ordered by
+/// search relevance if search text is used. Otherwise the most recently
+/// created accounts are returned first.
+///
+/// # Access
+/// - Permission [model::Permissions::admin_search_profiles]
+#[utoipa::path(
+    get,
+    path = PATH_GET_ADMIN_PROFILE_SEARCH_PAGE,
+    params(ProfileSearchSettings),
+    responses(
+        (status = 200, description = "Successful.", body = ProfileSearchResultPage),
+        (status = 401, description = "Unauthorized."),
+        (
+            status = 500,
+            description = "Internal server error.",
+        ),
+    ),
+    security(("access_token" = [])),
+)]
+pub async fn get_admin_profile_search_page(
+    State(state): State<S>,
+    Extension(permissions): Extension<Permissions>,
+    Query(settings): Query<ProfileSearchSettings>,
+) -> Result<Json<ProfileSearchResultPage>, StatusCode> {
+    PROFILE.get_admin_profile_search_page.incr();
+
+    if !permissions.admin_search_profiles {
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let r = state
+        .read()
+        .profile_admin()
+        .search()
+        .search_profiles(settings)
+        .await?;
+
+    Ok(r.into())
+}
+
+create_open_api_router!(fn router_admin_search, get_admin_profile_search_page,);
+
+create_counters!(
+    ProfileCounters,
+    PROFILE,
+    PROFILE_ADMIN_SEARCH_COUNTERS_LIST,
+    get_admin_profile_search_page,
+);
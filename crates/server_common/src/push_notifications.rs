@@ -6,7 +6,9 @@ use fcm::{
     response::{RecomendedAction, RecomendedWaitTime},
     FcmClient,
 };
-use model::{AccountIdInternal, PendingNotificationFlags, PushNotificationStateInfoWithFlags};
+use model::{
+    AccountIdInternal, LoginSessionId, PendingNotificationFlags, PushNotificationStateInfoWithFlags,
+};
 use serde_json::json;
 use simple_backend::ServerQuitWatcher;
 use simple_backend_config::SimpleBackendConfig;
@@ -68,6 +70,16 @@ pub struct PushNotificationSender {
 }
 
 impl PushNotificationSender {
+    /// Count of notifications waiting in the internal channel.
+    pub fn queue_length(&self) -> usize {
+        self.sender.max_capacity() - self.sender.capacity()
+    }
+
+    /// Count of low priority notifications waiting in the internal channel.
+    pub fn low_priority_queue_length(&self) -> usize {
+        self.sender_low_priority.max_capacity() - self.sender_low_priority.capacity()
+    }
+
     pub fn send(&self, account_id: AccountIdInternal) {
         let notification = SendPushNotification { account_id };
         match self.sender.try_send(notification) {
@@ -104,11 +116,13 @@ pub trait PushNotificationStateProvider {
     fn enable_push_notification_sent_flag(
         &self,
         account_id: AccountIdInternal,
+        session_id: LoginSessionId,
     ) -> impl Future<Output = Result<(), PushNotificationError>> + Send;
 
     fn remove_device_token(
         &self,
         account_id: AccountIdInternal,
+        session_id: LoginSessionId,
     ) -> impl Future<Output = Result<(), PushNotificationError>> + Send;
 
     /// Avoid saving the cached notification to DB when server closes.
@@ -264,11 +278,9 @@ impl<T: PushNotificationStateProvider + Send + 'static> PushNotificationManager<
         send_push_notification: SendPushNotification,
         sending_logic: &mut FcmSendingLogic,
     ) -> Result<(), PushNotificationError> {
-        let fcm = if let Some(fcm) = &self.fcm {
-            fcm
-        } else {
+        if self.fcm.is_none() {
             return Ok(());
-        };
+        }
 
         let info = self
             .state
@@ -283,76 +295,52 @@ impl<T: PushNotificationStateProvider + Send + 'static> PushNotificationManager<
             PushNotificationStateInfoWithFlags::WithFlags { info, flags } => (info, flags),
         };
 
-        if info.fcm_notification_sent {
-            self.state
-                .remove_specific_notification_flags_from_cache(
-                    send_push_notification.account_id,
-                    flags,
-                )
-                .await
-                .change_context(
-                    PushNotificationError::RemoveSpecificNotificationFlagsFromCacheFailed,
-                )?;
-            return Ok(());
-        }
-
-        let token = match info.fcm_device_token {
-            Some(token) => token,
-            None => {
-                self.state
-                    .remove_specific_notification_flags_from_cache(
-                        send_push_notification.account_id,
-                        flags,
-                    )
-                    .await
-                    .change_context(
-                        PushNotificationError::RemoveSpecificNotificationFlagsFromCacheFailed,
-                    )?;
+        // Send the notification to every login session which has
+        // a device token and which has not already received a notification.
+        for device in info.devices.into_iter().filter(|d| !d.fcm_notification_sent) {
+            let fcm = if let Some(fcm) = &self.fcm {
+                fcm
+            } else {
                 return Ok(());
-            }
-        };
+            };
 
-        let message = Message {
-            data: Some(json!({
-                "check_notifications": "",
-            })),
-            target: Target::Token(token.into_string()),
-            android: None,
-            apns: None,
-            webpush: None,
-            fcm_options: None,
-            notification: None,
-        };
+            let message = Message {
+                data: Some(json!({
+                    "check_notifications": "",
+                })),
+                target: Target::Token(device.fcm_device_token.into_string()),
+                android: None,
+                apns: None,
+                webpush: None,
+                fcm_options: None,
+                notification: None,
+            };
 
-        match sending_logic.send_push_notification(message, fcm).await {
-            Ok(()) => {
-                self.state
-                    .enable_push_notification_sent_flag(send_push_notification.account_id)
-                    .await
-                    .change_context(PushNotificationError::SettingPushNotificationSentFlagFailed)?;
-                self.state
-                    .remove_specific_notification_flags_from_cache(
+            match sending_logic.send_push_notification(message, fcm).await {
+                Ok(()) => self
+                    .state
+                    .enable_push_notification_sent_flag(
                         send_push_notification.account_id,
-                        flags,
+                        device.session_id,
                     )
                     .await
-                    .change_context(
-                        PushNotificationError::RemoveSpecificNotificationFlagsFromCacheFailed,
-                    )?;
-                Ok(())
-            }
-            Err(action) => match action {
-                UnusualAction::DisablePushNotificationSupport => {
+                    .change_context(PushNotificationError::SettingPushNotificationSentFlagFailed)?,
+                Err(UnusualAction::DisablePushNotificationSupport) => {
                     self.fcm = None;
-                    Ok(())
+                    return Ok(());
                 }
-                UnusualAction::RemoveDeviceToken => self
+                Err(UnusualAction::RemoveDeviceToken) => self
                     .state
-                    .remove_device_token(send_push_notification.account_id)
+                    .remove_device_token(send_push_notification.account_id, device.session_id)
                     .await
-                    .change_context(PushNotificationError::RemoveDeviceTokenFailed),
-            },
+                    .change_context(PushNotificationError::RemoveDeviceTokenFailed)?,
+            }
         }
+
+        self.state
+            .remove_specific_notification_flags_from_cache(send_push_notification.account_id, flags)
+            .await
+            .change_context(PushNotificationError::RemoveSpecificNotificationFlagsFromCacheFailed)
     }
 }
 
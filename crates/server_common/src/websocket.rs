@@ -8,6 +8,8 @@ pub enum WebSocketError {
     ClientVersionUnsupported,
     #[error("Received wrong refresh token")]
     ReceiveWrongRefreshToken,
+    #[error("Received already used refresh token")]
+    ReceiveUsedRefreshToken,
     #[error("Websocket data sending error")]
     Send,
     #[error("Websocket closing failed")]
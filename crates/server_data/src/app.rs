@@ -1,4 +1,4 @@
-use std::{future::Future, sync::Arc};
+use std::{collections::HashSet, future::Future, sync::Arc};
 
 use axum::extract::ws::WebSocket;
 use config::{file::EmailAddress, Config};
@@ -108,6 +108,15 @@ pub trait DataAllUtils: Send + Sync + 'static {
         unlimited_likes: bool,
     ) -> BoxFuture<'a, server_common::result::Result<(), DataError>>;
 
+    /// Add perceptual hashes of banned account's images to the
+    /// blocklist, so that near-duplicate images uploaded by other
+    /// accounts are moderated by humans.
+    fn add_banned_account_content_to_blocklist<'a>(
+        &self,
+        write_command_runner: &'a WriteCommandRunnerHandle,
+        id: AccountIdInternal,
+    ) -> BoxFuture<'a, server_common::result::Result<(), DataError>>;
+
     fn register_impl<'a>(
         &self,
         write_command_runner: &'a WriteCommandRunnerHandle,
@@ -148,4 +157,10 @@ pub trait DataAllUtils: Send + Sync + 'static {
         account0: AccountIdInternal,
         account1: AccountIdInternal,
     ) -> BoxFuture<'a, server_common::result::Result<bool, DataError>>;
+
+    fn liked_matched_and_blocked_accounts<'a>(
+        &self,
+        read_handle: &'a RouterDatabaseReadHandle,
+        id: AccountIdInternal,
+    ) -> BoxFuture<'a, server_common::result::Result<HashSet<AccountId>, DataError>>;
 }
@@ -1,16 +1,23 @@
-use std::{collections::HashMap, fmt::Debug, net::SocketAddr, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    net::SocketAddr,
+    sync::Arc,
+};
 
 use account::CachedAccountComponentData;
 use chat::CachedChatComponentData;
 use error_stack::Result;
 use media::CachedMedia;
 use model::{
-    AccessToken, AccountId, AccountIdInternal, AccountState, AccountStateRelatedSharedState, OtherSharedState, PendingNotificationFlags, Permissions
+    AccessToken, AccountId, AccountIdInternal, AccountState, AccountStateRelatedSharedState,
+    LoginSessionId, OtherSharedState, PendingNotificationFlags, Permissions,
 };
 use model_server_data::{LastSeenTime, LocationIndexKey, LocationIndexProfileData};
 use profile::CachedProfile;
 pub use server_common::data::cache::CacheError;
 use simple_backend_model::UnixTime;
+use simple_backend_utils::time::DurationValue;
 use tokio::sync::RwLock;
 
 use crate::{
@@ -38,10 +45,29 @@ pub struct AccountEntry {
     pub cache: RwLock<CacheEntry>,
 }
 
+/// Access token related login session.
+#[derive(Debug, Clone)]
+pub struct LoginSessionEntry {
+    pub account: Arc<AccountEntry>,
+    pub session_id: LoginSessionId,
+    /// Access token creation time
+    pub access_token_unix_time: UnixTime,
+}
+
+impl LoginSessionEntry {
+    pub fn access_token_expired(&self, access_token_max_age: DurationValue) -> bool {
+        let valid_until = self
+            .access_token_unix_time
+            .add_seconds(access_token_max_age.seconds);
+        *UnixTime::current_time().as_i64() >= *valid_until.as_i64()
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct DatabaseCache {
-    /// Accounts which are logged in (have valid access token).
-    access_tokens: RwLock<HashMap<AccessToken, Arc<AccountEntry>>>,
+    /// Login sessions which have valid access token. One account
+    /// can have multiple login sessions.
+    access_tokens: RwLock<HashMap<AccessToken, LoginSessionEntry>>,
     /// All accounts registered in the service.
     accounts: RwLock<HashMap<AccountId, Arc<AccountEntry>>>,
 }
@@ -55,7 +81,7 @@ impl DatabaseCache {
         &self.accounts
     }
 
-    pub fn access_tokens(&self) -> &RwLock<HashMap<AccessToken, Arc<AccountEntry>>> {
+    pub fn access_tokens(&self) -> &RwLock<HashMap<AccessToken, LoginSessionEntry>> {
         &self.access_tokens
     }
 
@@ -80,12 +106,14 @@ impl DatabaseCache {
         }
     }
 
-    /// Creates new event channel if address is Some.
+    /// Creates new event channel for the login session if address is Some.
     pub async fn update_access_token_and_connection(
         &self,
         id: AccountId,
+        session_id: LoginSessionId,
         current_access_token: Option<AccessToken>,
         new_access_token: AccessToken,
+        new_access_token_unix_time: UnixTime,
         address: Option<SocketAddr>,
     ) -> Result<Option<(EventReceiver, Option<LastSeenTimeUpdated>)>, CacheError> {
         let cache_entry = self
@@ -107,10 +135,13 @@ impl DatabaseCache {
             let event_receiver = if let Some(address) = address {
                 let (sender, receiver) = event_channel();
                 let mut write = cache_entry.cache.write().await;
-                write.common.current_connection = Some(ConnectionInfo {
-                    connection: address,
-                    event_sender: sender,
-                });
+                write.common.connections.insert(
+                    session_id,
+                    ConnectionInfo {
+                        connection: address,
+                        event_sender: sender,
+                    },
+                );
                 let last_seen_time_update = write.profile.as_ref().map(|v| LastSeenTimeUpdated {
                     last_seen_time: LastSeenTime::ONLINE,
                     current_position: v.location.current_position.profile_location(),
@@ -120,7 +151,14 @@ impl DatabaseCache {
                 Ok(None)
             };
 
-            tokens.insert(new_access_token, cache_entry);
+            tokens.insert(
+                new_access_token,
+                LoginSessionEntry {
+                    account: cache_entry,
+                    session_id,
+                    access_token_unix_time: new_access_token_unix_time,
+                },
+            );
 
             event_receiver
         } else {
@@ -128,11 +166,15 @@ impl DatabaseCache {
         }
     }
 
-    /// Delete current connection or specific connection.
+    /// Delete login session's current connection or specific connection.
     /// Also delete access token if it is Some.
+    ///
+    /// Last seen time is updated when the account does not have
+    /// any connections after the removal.
     pub async fn delete_connection_and_specific_access_token(
         &self,
         id: AccountId,
+        session_id: LoginSessionId,
         connection: Option<SocketAddr>,
         token: Option<AccessToken>,
     ) -> Result<Option<LastSeenTimeUpdated>, CacheError> {
@@ -148,16 +190,17 @@ impl DatabaseCache {
 
         {
             let mut cache_entry_write = cache_entry.cache.write().await;
-            if connection.is_none()
-                || (connection.is_some()
-                    && cache_entry_write
-                        .common
-                        .current_connection
-                        .as_ref()
-                        .map(|info| info.connection)
-                        == connection)
-            {
-                cache_entry_write.common.current_connection = None;
+            let current_connection = cache_entry_write
+                .common
+                .connections
+                .get(&session_id)
+                .map(|info| info.connection);
+            let removed = current_connection.is_some()
+                && (connection.is_none() || current_connection == connection);
+            if removed {
+                cache_entry_write.common.connections.remove(&session_id);
+            }
+            if removed && cache_entry_write.common.connections.is_empty() {
                 let last_seen_time = UnixTime::current_time();
                 if let Some(profile_entry) = cache_entry_write.profile.as_mut() {
                     profile_entry.last_seen_time = Some(last_seen_time);
@@ -186,29 +229,45 @@ impl DatabaseCache {
         self.accounts.write().await.remove(&id);
     }
 
-    pub async fn access_token_exists(&self, token: &AccessToken) -> Option<AccountIdInternal> {
+    /// Expired access tokens are not accepted.
+    pub async fn access_token_exists(
+        &self,
+        token: &AccessToken,
+        access_token_max_age: DurationValue,
+    ) -> Option<(AccountIdInternal, LoginSessionId)> {
         let tokens = self.access_tokens.read().await;
-        tokens.get(token).map(|entry| entry.account_id_internal)
+        tokens
+            .get(token)
+            .filter(|entry| !entry.access_token_expired(access_token_max_age))
+            .map(|entry| (entry.account.account_id_internal, entry.session_id))
     }
 
-    /// Checks that connection comes from the same IP address. WebSocket is
-    /// using the cached SocketAddr, so check the IP only.
+    /// Checks that connection comes from the same IP address as the login
+    /// session's WebSocket connection. WebSocket is using the cached
+    /// SocketAddr, so check the IP only.
+    ///
+    /// Expired access tokens are not accepted.
     pub async fn access_token_and_connection_exists(
         &self,
         access_token: &AccessToken,
         connection: SocketAddr,
-    ) -> Option<(AccountIdInternal, Permissions, AccountState)> {
+        access_token_max_age: DurationValue,
+    ) -> Option<(AccountIdInternal, LoginSessionId, Permissions, AccountState)> {
         let tokens = self.access_tokens.read().await;
-        if let Some(entry) = tokens.get(access_token) {
-            let r = entry.cache.read().await;
+        let entry = tokens
+            .get(access_token)
+            .filter(|entry| !entry.access_token_expired(access_token_max_age));
+        if let Some(entry) = entry {
+            let r = entry.account.cache.read().await;
             if r.common
-                .current_connection
-                .as_ref()
+                .connections
+                .get(&entry.session_id)
                 .map(|a| a.connection.ip())
                 == Some(connection.ip())
             {
                 Some((
-                    entry.account_id_internal,
+                    entry.account.account_id_internal,
+                    entry.session_id,
                     r.common.permissions.clone(),
                     r.common
                         .account_state_related_shared_state
@@ -242,8 +301,12 @@ impl DatabaseCache {
 
     pub async fn read_cache_for_logged_in_clients(&self, cache_operation: impl Fn(&CacheEntry)) {
         let guard = self.access_tokens.read().await;
+        let mut handled = HashSet::new();
         for v in guard.values() {
-            let cache_entry = v.cache.read().await;
+            if !handled.insert(v.account.account_id_internal) {
+                continue;
+            }
+            let cache_entry = v.account.cache.read().await;
             cache_operation(&cache_entry)
         }
     }
@@ -324,9 +387,13 @@ impl DatabaseCache {
         cache_operation: impl Fn(AccountIdInternal, &mut CacheEntry),
     ) {
         let guard = self.access_tokens.read().await;
+        let mut handled = HashSet::new();
         for v in guard.values() {
-            let mut cache_entry = v.cache.write().await;
-            cache_operation(v.account_id_internal, &mut cache_entry)
+            if !handled.insert(v.account.account_id_internal) {
+                continue;
+            }
+            let mut cache_entry = v.account.cache.write().await;
+            cache_operation(v.account.account_id_internal, &mut cache_entry)
         }
     }
 
@@ -396,20 +463,23 @@ impl DatabaseCache {
 }
 
 pub trait TopLevelCacheOperations {
-    /// Creates new event channel if address is Some.
+    /// Creates new event channel for the login session if address is Some.
     async fn update_access_token_and_connection(
         &self,
         id: AccountId,
+        session_id: LoginSessionId,
         current_access_token: Option<AccessToken>,
         new_access_token: AccessToken,
+        new_access_token_unix_time: UnixTime,
         address: Option<SocketAddr>,
     ) -> Result<Option<(EventReceiver, Option<LastSeenTimeUpdated>)>, CacheError>;
 
-    /// Delete current connection or specific connection.
+    /// Delete login session's current connection or specific connection.
     /// Also delete access token if it is Some.
     async fn delete_connection_and_specific_access_token(
         &self,
         id: AccountId,
+        session_id: LoginSessionId,
         connection: Option<SocketAddr>,
         token: Option<AccessToken>,
     ) -> Result<Option<LastSeenTimeUpdated>, CacheError>;
@@ -419,23 +489,33 @@ impl<I: InternalWriting> TopLevelCacheOperations for I {
     async fn delete_connection_and_specific_access_token(
         &self,
         id: AccountId,
+        session_id: LoginSessionId,
         connection: Option<SocketAddr>,
         token: Option<AccessToken>,
     ) -> Result<Option<LastSeenTimeUpdated>, CacheError> {
         self.cache()
-            .delete_connection_and_specific_access_token(id, connection, token)
+            .delete_connection_and_specific_access_token(id, session_id, connection, token)
             .await
     }
 
     async fn update_access_token_and_connection(
         &self,
         id: AccountId,
+        session_id: LoginSessionId,
         current_access_token: Option<AccessToken>,
         new_access_token: AccessToken,
+        new_access_token_unix_time: UnixTime,
         address: Option<SocketAddr>,
     ) -> Result<Option<(EventReceiver, Option<LastSeenTimeUpdated>)>, CacheError> {
         self.cache()
-            .update_access_token_and_connection(id, current_access_token, new_access_token, address)
+            .update_access_token_and_connection(
+                id,
+                session_id,
+                current_access_token,
+                new_access_token,
+                new_access_token_unix_time,
+                address,
+            )
             .await
     }
 }
@@ -517,18 +597,25 @@ pub struct CacheEntryCommon {
     pub permissions: Permissions,
     pub account_state_related_shared_state: AccountStateRelatedSharedState,
     pub other_shared_state: OtherSharedState,
-    pub current_connection: Option<ConnectionInfo>,
+    /// WebSocket connections of login sessions.
+    pub connections: HashMap<LoginSessionId, ConnectionInfo>,
     /// The cached pending notification flags indicates not yet handled
     /// notification which PushNotificationManager will handle as soon as
     /// possible.
     pub pending_notification_flags: PendingNotificationFlags,
+    /// Time when chat notification which was not sent to any connected
+    /// client was created. Chat notification digest email sending uses
+    /// this.
+    ///
+    /// This is not stored to database. Server startup sets this to the
+    /// startup time if the stored pending notification flags contain chat
+    /// notifications, so a digest delayed by a restart is still sent.
+    pub email_notification_pending_since: Option<UnixTime>,
 }
 
 impl CacheEntryCommon {
-    pub fn connection_event_sender(&self) -> Option<&EventSender> {
-        self.current_connection
-            .as_ref()
-            .map(|info| &info.event_sender)
+    pub fn connection_event_senders(&self) -> impl Iterator<Item = &EventSender> {
+        self.connections.values().map(|info| &info.event_sender)
     }
 }
 
@@ -552,8 +639,9 @@ impl CacheEntry {
                 permissions: Permissions::default(),
                 account_state_related_shared_state: AccountStateRelatedSharedState::default(),
                 other_shared_state: OtherSharedState::default(),
-                current_connection: None,
+                connections: HashMap::new(),
                 pending_notification_flags: PendingNotificationFlags::empty(),
+                email_notification_pending_since: None,
             },
         }
     }
@@ -625,11 +713,13 @@ impl CacheEntry {
             &profile.data,
             &profile.state,
             profile.attributes.clone(),
+            &profile.filters,
             self.media.as_ref().map(|m| m.profile_content_version),
             self.common.other_shared_state.unlimited_likes,
             profile.last_seen_time(&self.common),
             self.common.other_shared_state.initial_setup_completed_unix_time,
             self.media.as_ref().map(|m| m.profile_content_edited_time),
+            profile.location.current_location,
         ))
     }
 }
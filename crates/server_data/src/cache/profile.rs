@@ -1,8 +1,10 @@
+use std::{collections::HashSet, sync::Arc};
+
 use config::Config;
 use error_stack::{Result, ResultExt};
 use model::{AccountId, AccountIdInternal, NextNumberStorage, UnixTime};
 use model_server_data::{
-    LastSeenTime, ProfileAttributeFilterValue, ProfileAttributeValue,
+    LastSeenTime, LocationInternal, ProfileAttributeFilterValue, ProfileAttributeValue,
     ProfileInternal, ProfileIteratorSessionIdInternal, ProfileQueryMakerDetails,
     ProfileStateCached, SortedProfileAttributes,
 };
@@ -24,6 +26,11 @@ pub struct CachedProfile {
     pub last_seen_time: Option<UnixTime>,
     pub profile_iterator_session_id: Option<ProfileIteratorSessionIdInternal>,
     pub profile_iterator_session_id_storage: NextNumberStorage,
+    /// Liked, matched and blocked profiles which are hidden from
+    /// the current profile iterator session. Loaded when the profile
+    /// iterator is reset and updated when likes, matches and blocks
+    /// change.
+    pub profile_iterator_hidden_profiles: Option<Arc<HashSet<AccountId>>>,
 }
 
 impl CachedProfile {
@@ -41,6 +48,7 @@ impl CachedProfile {
             data,
             state,
             location: LocationData {
+                current_location: LocationInternal::default(),
                 current_position: LocationIndexArea::default(),
                 current_iterator: LocationIndexIteratorState::completed(),
             },
@@ -49,11 +57,19 @@ impl CachedProfile {
             last_seen_time,
             profile_iterator_session_id: None,
             profile_iterator_session_id_storage: NextNumberStorage::default(),
+            profile_iterator_hidden_profiles: None,
         }
     }
 
     pub fn filters(&self) -> ProfileQueryMakerDetails {
-        ProfileQueryMakerDetails::new(&self.data, &self.state, self.filters.clone())
+        ProfileQueryMakerDetails::new(
+            self.location.current_location,
+            &self.data,
+            &self.state,
+            self.attributes.clone(),
+            self.filters.clone(),
+            self.profile_iterator_hidden_profiles.clone(),
+        )
     }
 
     pub fn last_seen_time_for_db(&self) -> Option<UnixTime> {
@@ -61,7 +77,7 @@ impl CachedProfile {
     }
 
     pub fn last_seen_time(&self, common: &CacheEntryCommon) -> Option<LastSeenTime> {
-        if common.current_connection.is_some() {
+        if !common.connections.is_empty() {
             Some(LastSeenTime::ONLINE)
         } else {
             self.last_seen_time.map(|v| v.into())
@@ -71,6 +87,8 @@ impl CachedProfile {
 
 #[derive(Debug, Clone)]
 pub struct LocationData {
+    /// Exact profile location
+    pub current_location: LocationInternal,
     pub current_position: LocationIndexArea,
     pub current_iterator: LocationIndexIteratorState,
 }
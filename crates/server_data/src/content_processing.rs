@@ -43,6 +43,11 @@ impl ContentProcessingManagerData {
         &self.data
     }
 
+    /// Count of content waiting for processing.
+    pub async fn queue_length(&self) -> usize {
+        self.data.read().await.queue.len()
+    }
+
     /// Queue new content. Old one will be replaced.
     pub async fn queue_new_content(
         &self,
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use model::{
+    AccountIdDb, AccountIdInternal, DataExportDownloadToken, DataExportState, UnixTime,
+};
+use server_common::result::WrappedResultExt;
+use tokio::sync::{
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
+    RwLock,
+};
+use tracing::warn;
+
+use crate::{event::EventManagerWithCacheReference, result::Result};
+
+#[derive(thiserror::Error, Debug)]
+pub enum DataExportError {
+    #[error("Event sending failed")]
+    EventSendingFailed,
+}
+
+#[derive(Debug)]
+pub struct DataExportReceiver(pub UnboundedReceiver<DataExportJob>);
+
+#[derive(Debug, Clone, Copy)]
+pub struct DataExportJob {
+    /// Account which receives the export archive.
+    pub requester: AccountIdInternal,
+    /// Account which data is exported.
+    pub target: AccountIdInternal,
+}
+
+#[derive(Debug, Clone)]
+struct ExportState {
+    job: DataExportJob,
+    state: DataExportState,
+}
+
+/// Data export state for every account which has requested data export.
+///
+/// Only one data export per requester is possible at the same time.
+/// Export archive is stored per requester, so an export which archive
+/// is available for download blocks exporting other accounts until the
+/// download token expires.
+///
+/// The states are only in memory, so server restart cancels exports
+/// which are in progress and download tokens become invalid. Export
+/// archives from the previous server run are removed when the server
+/// starts.
+pub struct DataExportManagerData {
+    event_queue: UnboundedSender<DataExportJob>,
+    states: RwLock<HashMap<AccountIdDb, ExportState>>,
+}
+
+impl DataExportManagerData {
+    pub fn new() -> (Self, DataExportReceiver) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let data = Self {
+            event_queue: sender,
+            states: RwLock::new(HashMap::new()),
+        };
+        (data, DataExportReceiver(receiver))
+    }
+
+    /// Queue new data export. Returns false if the requester has
+    /// an export already in progress or if the requester has an
+    /// export archive of another account available for download.
+    pub async fn queue_export(
+        &self,
+        events: EventManagerWithCacheReference<'_>,
+        requester: AccountIdInternal,
+        target: AccountIdInternal,
+    ) -> Result<bool, DataExportError> {
+        let mut write = self.states.write().await;
+        if let Some(current) = write.get(&requester.id) {
+            if current.state.is_in_progress()
+                || (current.state.is_done() && current.job.target.id != target.id)
+            {
+                return Ok(false);
+            }
+        }
+
+        let job = DataExportJob { requester, target };
+        let state = ExportState {
+            job,
+            state: DataExportState::in_progress(target.as_id()),
+        };
+        notify_client(&events, &state).await;
+        write.insert(requester.id, state);
+        drop(write);
+
+        self.event_queue
+            .send(job)
+            .change_context(DataExportError::EventSendingFailed)?;
+
+        Ok(true)
+    }
+
+    pub async fn get_state(&self, requester: AccountIdInternal) -> DataExportState {
+        self.states
+            .read()
+            .await
+            .get(&requester.id)
+            .map(|v| v.state.clone())
+            .unwrap_or_default()
+    }
+
+    pub async fn update_progress(
+        &self,
+        events: EventManagerWithCacheReference<'_>,
+        requester: AccountIdInternal,
+        progress: u8,
+    ) {
+        self.modify_state(events, requester, |s| s.change_progress(progress))
+            .await
+    }
+
+    pub async fn change_to_done(
+        &self,
+        events: EventManagerWithCacheReference<'_>,
+        requester: AccountIdInternal,
+        token: DataExportDownloadToken,
+        expires: UnixTime,
+    ) {
+        self.modify_state(events, requester, |s| s.change_to_done(token, expires))
+            .await
+    }
+
+    pub async fn change_to_error(
+        &self,
+        events: EventManagerWithCacheReference<'_>,
+        requester: AccountIdInternal,
+    ) {
+        self.modify_state(events, requester, |s| s.change_to_error())
+            .await
+    }
+
+    async fn modify_state(
+        &self,
+        events: EventManagerWithCacheReference<'_>,
+        requester: AccountIdInternal,
+        action: impl FnOnce(&mut DataExportState),
+    ) {
+        let mut write = self.states.write().await;
+        if let Some(state) = write.get_mut(&requester.id) {
+            action(&mut state.state);
+            notify_client(&events, state).await;
+        } else {
+            warn!("Data export state not found");
+        }
+    }
+
+    /// Check that download token is valid and not expired.
+    pub async fn download_allowed(
+        &self,
+        requester: AccountIdInternal,
+        token: &DataExportDownloadToken,
+    ) -> bool {
+        let read = self.states.read().await;
+        let Some(state) = read.get(&requester.id) else {
+            return false;
+        };
+        match (&state.state.download_token, state.state.download_expires) {
+            (Some(current_token), Some(expires)) => {
+                current_token == token && UnixTime::current_time().ut < expires.ut
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove expired download states. Returns requesters which
+    /// export archive should be removed.
+    pub async fn remove_expired(
+        &self,
+        events: EventManagerWithCacheReference<'_>,
+    ) -> Vec<AccountIdInternal> {
+        let current_time = UnixTime::current_time();
+        let mut write = self.states.write().await;
+        let expired: Vec<AccountIdInternal> = write
+            .values()
+            .filter(|v| {
+                v.state
+                    .download_expires
+                    .map(|expires| expires.ut <= current_time.ut)
+                    .unwrap_or_default()
+            })
+            .map(|v| v.job.requester)
+            .collect();
+
+        for requester in &expired {
+            if let Some(mut state) = write.remove(&requester.id) {
+                state.state = DataExportState::default();
+                notify_client(&events, &state).await;
+            }
+        }
+
+        expired
+    }
+}
+
+async fn notify_client(events: &EventManagerWithCacheReference<'_>, state: &ExportState) {
+    if let Err(e) = events
+        .send_connected_event(
+            state.job.requester,
+            model::EventToClientInternal::DataExportStateChanged(state.state.clone()),
+        )
+        .await
+    {
+        warn!("Event sending failed {}", e);
+    }
+}
@@ -21,6 +21,7 @@ use crate::{
     event::EventManagerWithCacheReference,
     file::utils::FileDir,
     index::{LocationIndexIteratorHandle, LocationIndexManager, LocationIndexWriteHandle},
+    message_signing_key::MessageSigningKey,
     utils::{AccessTokenManager, AccountIdManager},
     write_concurrent::WriteCommandsConcurrent,
 };
@@ -122,6 +123,9 @@ impl DatabaseManager {
             )
             .await?;
 
+        let message_signing_key: Arc<MessageSigningKey> =
+            MessageSigningKey::load_or_create(&config)?.into();
+
         let index = LocationIndexManager::new(config.clone());
         let current_read_handle = CurrentReadHandle(current_read);
         let current_write_handle = CurrentWriteHandle(current_write);
@@ -139,6 +143,7 @@ impl DatabaseManager {
                 root: root.into(),
                 cache: cache.into(),
                 config: config.clone(),
+                message_signing_key: message_signing_key.clone(),
             },
             config: config.clone(),
             current_write_handle: current_write_handle.clone(),
@@ -156,6 +161,7 @@ impl DatabaseManager {
             root,
             cache,
             config,
+            message_signing_key,
         };
 
         let database_manager = DatabaseManager {
@@ -214,6 +220,10 @@ impl RouterDatabaseWriteHandle {
     pub fn location_raw(&self) -> &LocationIndexManager {
         &self.location
     }
+
+    pub fn message_signing_key(&self) -> &MessageSigningKey {
+        &self.read.message_signing_key
+    }
 }
 
 pub trait InternalWriting {
@@ -225,6 +235,7 @@ pub trait InternalWriting {
     fn current_read_handle(&self) -> &CurrentReadHandle;
     fn history_read_handle(&self) -> &HistoryReadHandle;
     fn cache(&self) -> &DatabaseCache;
+    fn cache_arc(&self) -> Arc<DatabaseCache>;
     fn location(&self) -> &LocationIndexManager;
     fn push_notification_sender(&self) -> &PushNotificationSender;
     fn email_sender(&self) -> &EmailSenderImpl;
@@ -326,6 +337,10 @@ impl InternalWriting for &RouterDatabaseWriteHandle {
         &self.read.cache
     }
 
+    fn cache_arc(&self) -> Arc<DatabaseCache> {
+        self.read.cache.clone()
+    }
+
     fn location(&self) -> &LocationIndexManager {
         &self.location
     }
@@ -357,11 +372,12 @@ pub struct RouterDatabaseReadHandle {
     history_read_handle: HistoryReadHandle,
     cache: Arc<DatabaseCache>,
     config: Arc<Config>,
+    message_signing_key: Arc<MessageSigningKey>,
 }
 
 impl RouterDatabaseReadHandle {
     pub fn access_token_manager(&self) -> AccessTokenManager<'_> {
-        AccessTokenManager::new(&self.cache)
+        AccessTokenManager::new(&self.cache, &self.config)
     }
 
     pub fn account_id_manager(&self) -> AccountIdManager<'_> {
@@ -375,6 +391,10 @@ impl RouterDatabaseReadHandle {
     pub fn read_handle_raw(&self) -> &CurrentReadHandle {
         &self.current_read_handle
     }
+
+    pub fn message_signing_key(&self) -> &MessageSigningKey {
+        &self.message_signing_key
+    }
 }
 
 pub struct ReadAdapter<'a> {
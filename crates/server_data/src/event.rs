@@ -3,7 +3,7 @@
 use database_chat::current::write::chat::ChatStateChanges;
 use model::{
     AccountId, AccountIdInternal, EventToClient, EventToClientInternal, NotificationEvent,
-    PendingNotificationFlags,
+    PendingNotificationFlags, UnixTime,
 };
 use server_common::{data::IntoDataError, push_notifications::PushNotificationSender};
 use tokio::sync::mpsc::{self, error::TrySendError};
@@ -80,24 +80,28 @@ impl<'a> EventManagerWithCacheReference<'a> {
         }
     }
 
-    async fn access_connection_event_sender<T: Send + 'static>(
+    /// Access event senders of all connected login sessions
+    /// of the account.
+    async fn access_connection_event_senders<T: Send + 'static>(
         &'a self,
         id: model::AccountId,
-        action: impl FnOnce(Option<&EventSender>) -> T + Send,
+        action: impl FnOnce(&mut dyn Iterator<Item = &EventSender>) -> T + Send,
     ) -> Result<T, DataError> {
         self.cache
-            .read_cache_common(id, move |entry| Ok(action(entry.connection_event_sender())))
+            .read_cache_common(id, move |entry| {
+                Ok(action(&mut entry.connection_event_senders()))
+            })
             .await
             .into_data_error(id)
     }
 
-    async fn access_connection_event_sender_for_logged_in_clients(
+    async fn access_connection_event_senders_for_logged_in_clients(
         &'a self,
-        action: impl Fn(Option<&EventSender>),
+        action: impl Fn(&mut dyn Iterator<Item = &EventSender>),
     ) {
         self.cache
             .read_cache_common_for_logged_in_clients(|entry| {
-                action(entry.connection_event_sender())
+                action(&mut entry.connection_event_senders())
             })
             .await
     }
@@ -106,8 +110,8 @@ impl<'a> EventManagerWithCacheReference<'a> {
     ///
     /// Event will be skipped if event queue is full.
     pub async fn send_connected_event_to_logged_in_clients(&'a self, event: EventToClientInternal) {
-        self.access_connection_event_sender_for_logged_in_clients(move |sender| {
-            if let Some(sender) = sender {
+        self.access_connection_event_senders_for_logged_in_clients(move |senders| {
+            for sender in senders {
                 // Ignore errors
                 let _ = sender
                     .sender
@@ -125,12 +129,12 @@ impl<'a> EventManagerWithCacheReference<'a> {
         account: impl Into<AccountId>,
         event: EventToClientInternal,
     ) -> Result<(), DataError> {
-        self.access_connection_event_sender(account.into(), move |sender| {
-            if let Some(sender) = sender {
+        self.access_connection_event_senders(account.into(), move |senders| {
+            for sender in senders {
                 // Ignore errors
                 let _ = sender
                     .sender
-                    .try_send(InternalEventType::NormalEvent(event));
+                    .try_send(InternalEventType::NormalEvent(event.clone()));
             }
         })
         .await
@@ -153,24 +157,28 @@ impl<'a> EventManagerWithCacheReference<'a> {
             .into_data_error(account)?;
 
         let sent = self
-            .access_connection_event_sender(account.into(), move |sender| {
-                if let Some(sender) = sender {
-                    match sender
-                        .sender
-                        .try_send(InternalEventType::Notification(event))
-                    {
-                        Ok(()) => true,
-                        Err(TrySendError::Closed(_) | TrySendError::Full(_)) => false,
-                    }
-                } else {
-                    false
-                }
+            .access_connection_event_senders(account.into(), move |senders| {
+                send_notification_to_connections(senders, event)
             })
             .await
             .change_context(DataError::EventModeAccessFailed)?;
 
         if !sent {
-            self.push_notification_sender.send(account)
+            self.push_notification_sender.send(account);
+
+            if PendingNotificationFlags::from(event)
+                .intersects(PendingNotificationFlags::CHAT_EMAIL_NOTIFICATION)
+            {
+                self.cache
+                    .write_cache_common(account, move |entry| {
+                        entry
+                            .email_notification_pending_since
+                            .get_or_insert_with(UnixTime::current_time);
+                        Ok(())
+                    })
+                    .await
+                    .into_data_error(account)?;
+            }
         }
 
         Ok(())
@@ -183,17 +191,8 @@ impl<'a> EventManagerWithCacheReference<'a> {
         self.cache
             .write_cache_common_for_logged_in_clients(|account_id, entry| {
                 entry.pending_notification_flags |= event.into();
-                let sent = if let Some(sender) = entry.connection_event_sender() {
-                    match sender
-                        .sender
-                        .try_send(InternalEventType::Notification(event))
-                    {
-                        Ok(()) => true,
-                        Err(TrySendError::Closed(_) | TrySendError::Full(_)) => false,
-                    }
-                } else {
-                    false
-                };
+                let sent =
+                    send_notification_to_connections(&mut entry.connection_event_senders(), event);
 
                 if !sent {
                     self.push_notification_sender.send_low_priority(account_id)
@@ -231,6 +230,9 @@ impl<'a> EventManagerWithCacheReference<'a> {
             .cache
             .write_cache_common(account, move |entry| {
                 entry.pending_notification_flags -= flags;
+                if flags.intersects(PendingNotificationFlags::CHAT_EMAIL_NOTIFICATION) {
+                    entry.email_notification_pending_since = None;
+                }
                 Ok(())
             })
             .await
@@ -242,6 +244,48 @@ impl<'a> EventManagerWithCacheReference<'a> {
         }
     }
 
+    /// Get accounts which have had not received chat notifications
+    /// since `pending_since` or earlier. The pending email notification
+    /// state is removed from the returned accounts.
+    pub async fn take_accounts_with_pending_email_notification(
+        &'a self,
+        pending_since: UnixTime,
+    ) -> Vec<AccountIdInternal> {
+        let mut accounts = vec![];
+        let read_result = self
+            .cache
+            .read_cache_for_all_accounts(|id, entry| {
+                if let Some(time) = entry.common.email_notification_pending_since {
+                    if time.ut <= pending_since.ut {
+                        accounts.push(*id);
+                    }
+                }
+                Ok(())
+            })
+            .await;
+
+        if let Err(e) = read_result {
+            error!("Failed to read pending email notifications: {:?}", e);
+        }
+
+        for id in &accounts {
+            let edit_result = self
+                .cache
+                .write_cache_common(*id, |entry| {
+                    entry.email_notification_pending_since = None;
+                    Ok(())
+                })
+                .await
+                .into_data_error(*id);
+
+            if let Err(e) = edit_result {
+                error!("Failed to edit pending email notification state: {:?}", e);
+            }
+        }
+
+        accounts
+    }
+
     pub async fn handle_chat_state_changes(&'a self, c: ChatStateChanges) -> Result<(), DataError> {
         if c.received_blocks_sync_version.is_some() {
             self.send_connected_event(c.id, EventToClientInternal::ReceivedBlocksChanged)
@@ -272,3 +316,21 @@ impl<'a> EventManagerWithCacheReference<'a> {
         Ok(())
     }
 }
+
+/// Returns true if at least one connection received the notification.
+fn send_notification_to_connections(
+    senders: &mut dyn Iterator<Item = &EventSender>,
+    event: NotificationEvent,
+) -> bool {
+    let mut sent = false;
+    for sender in senders {
+        match sender
+            .sender
+            .try_send(InternalEventType::Notification(event))
+        {
+            Ok(()) => sent = true,
+            Err(TrySendError::Closed(_) | TrySendError::Full(_)) => (),
+        }
+    }
+    sent
+}
@@ -2,7 +2,7 @@ use std::path::{Path, PathBuf};
 
 use axum::body::BodyDataStream;
 use error_stack::{Result, ResultExt};
-use model::{AccountId, ContentId, ContentProcessingId};
+use model::{AccountId, ContentId, ContentProcessingId, ContentSize};
 use simple_backend_utils::{file::overwrite_and_remove_if_exists, ContextExt};
 use tokio::io::AsyncWriteExt;
 use tokio_stream::{wrappers::ReadDirStream, StreamExt};
@@ -13,6 +13,7 @@ use super::super::FileError;
 pub const TMP_DIR_NAME: &str = "tmp";
 pub const CONTENT_DIR_NAME: &str = "content";
 pub const EXPORT_DIR_NAME: &str = "export";
+pub const DATA_EXPORT_ARCHIVE_FILE_NAME: &str = "data_export.zip";
 
 const MAX_TMP_FILE_SIZE: usize = 1024 * 1024 * 1024 * 10; // 10 MiB
 
@@ -44,6 +45,19 @@ impl FileDir {
         self.account_dir(id).content_dir().media_content(content_id)
     }
 
+    /// Small image variant which is created before it replaces
+    /// the current small image variant of the content.
+    pub fn small_image_variant_upload(&self, id: AccountId, content_id: ContentId) -> TmpContentFile {
+        self.account_dir(id)
+            .tmp_dir()
+            .small_image_variant_upload(content_id)
+    }
+
+    /// Data export archive which the account has requested.
+    pub fn data_export_archive(&self, id: AccountId) -> DataExportArchiveFile {
+        self.account_dir(id).export_dir().data_export_archive()
+    }
+
     pub fn account_dir(&self, id: AccountId) -> AccountDir {
         let mut dir = self.dir.clone();
         dir.push(id.to_string());
@@ -70,8 +84,7 @@ impl AccountDir {
         TmpDir { dir: self.dir }
     }
 
-    // TODO(prod): Remove if not used
-    fn _export_dir(mut self) -> ExportDir {
+    fn export_dir(mut self) -> ExportDir {
         self.dir.push(EXPORT_DIR_NAME);
         ExportDir { dir: self.dir }
     }
@@ -147,6 +160,14 @@ impl TmpDir {
             path: PathToFile { path: self.dir },
         }
     }
+
+    pub fn small_image_variant_upload(mut self, id: ContentId) -> TmpContentFile {
+        self.dir.push(id.content_file_name());
+        TmpContentFile {
+            path: PathToFile { path: self.dir },
+        }
+        .size_variant(ContentSize::Small)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -176,6 +197,38 @@ impl ExportDir {
     pub fn path(&self) -> &PathBuf {
         &self.dir
     }
+
+    pub fn data_export_archive(mut self) -> DataExportArchiveFile {
+        self.dir.push(DATA_EXPORT_ARCHIVE_FILE_NAME);
+        DataExportArchiveFile {
+            path: PathToFile { path: self.dir },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DataExportArchiveFile {
+    path: PathToFile,
+}
+
+impl DataExportArchiveFile {
+    pub fn path(&self) -> &PathBuf {
+        self.path.as_path()
+    }
+
+    pub fn create_parent_dirs_blocking(&self) -> Result<(), FileError> {
+        self.path.create_parent_dirs_blocking()
+    }
+
+    pub async fn overwrite_and_remove_if_exists(self) -> Result<(), FileError> {
+        self.path.overwrite_and_remove_if_exists().await
+    }
+
+    pub async fn byte_count_and_read_stream(
+        &self,
+    ) -> Result<(u64, ReaderStream<tokio::fs::File>), FileError> {
+        self.path.byte_count_and_read_stream().await
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -188,10 +241,31 @@ impl ContentFile {
         self.path.as_path()
     }
 
+    /// File for image size variant of this content.
+    pub fn size_variant(&self, size: ContentSize) -> ContentFile {
+        ContentFile {
+            path: self.path.with_file_name_suffix(size.file_name_suffix()),
+        }
+    }
+
+    pub async fn exists(&self) -> Result<bool, FileError> {
+        self.path.exists().await
+    }
+
     pub async fn overwrite_and_remove_if_exists(self) -> Result<(), FileError> {
         self.path.overwrite_and_remove_if_exists().await
     }
 
+    /// Remove also all image size variant files.
+    pub async fn overwrite_and_remove_with_size_variants_if_exists(self) -> Result<(), FileError> {
+        for size in ContentSize::VARIANTS {
+            self.size_variant(*size)
+                .overwrite_and_remove_if_exists()
+                .await?;
+        }
+        self.overwrite_and_remove_if_exists().await
+    }
+
     pub async fn byte_count_and_read_stream(
         &self,
     ) -> Result<(u64, ReaderStream<tokio::fs::File>), FileError> {
@@ -221,6 +295,37 @@ impl TmpContentFile {
         self.path.move_to_blocking(&new_location.path)
     }
 
+    /// Move also all image size variant files. Missing size variant
+    /// files are skipped, so the original content is used instead
+    /// of them.
+    pub fn move_with_size_variants_to_blocking(
+        self,
+        new_location: &ContentFile,
+    ) -> Result<(), FileError> {
+        for size in ContentSize::VARIANTS {
+            let variant = self.size_variant(*size);
+            if variant.exists_blocking() {
+                variant.move_to_blocking(&new_location.size_variant(*size))?;
+            }
+        }
+        self.move_to_blocking(new_location)
+    }
+
+    /// File for image size variant of this content.
+    pub fn size_variant(&self, size: ContentSize) -> TmpContentFile {
+        TmpContentFile {
+            path: self.path.with_file_name_suffix(size.file_name_suffix()),
+        }
+    }
+
+    pub fn exists_blocking(&self) -> bool {
+        self.path.as_path().exists()
+    }
+
+    pub async fn create_parent_dirs(&self) -> Result<(), FileError> {
+        self.path.create_parent_dirs().await
+    }
+
     pub async fn overwrite_and_remove_if_exists(self) -> Result<(), FileError> {
         self.path.overwrite_and_remove_if_exists().await
     }
@@ -240,6 +345,18 @@ impl PathToFile {
         &self.path
     }
 
+    fn with_file_name_suffix(&self, suffix: &str) -> Self {
+        let mut path = self.path.clone().into_os_string();
+        path.push(suffix);
+        Self { path: path.into() }
+    }
+
+    pub async fn exists(&self) -> Result<bool, FileError> {
+        tokio::fs::try_exists(&self.path)
+            .await
+            .change_context(FileError::IoFileMetadata)
+    }
+
     pub async fn create_parent_dirs(&self) -> Result<(), FileError> {
         if let Some(parent_dir) = self.path.parent() {
             if !parent_dir.exists() {
@@ -10,13 +10,16 @@ use config::{file::LocationConfig, Config};
 use error_stack::ResultExt;
 use model::{AccountId, UnixTime};
 use model_server_data::{
-    CellData, Location, LocationIndexKey, LocationIndexProfileData, LocationInternal, MaxDistanceKm, ProfileLink, ProfileQueryMakerDetails
+    CellData, Location, LocationIndexKey, LocationIndexProfileData, LocationInternal, MaxDistanceKm, ProfileDistanceBucket, ProfileLink, ProfileQueryMakerDetails
 };
 use server_common::data::index::IndexError;
 use tokio::sync::RwLock;
 use tracing::info;
 
-use self::location::{IndexUpdater, LocationIndex, LocationIndexIteratorState};
+use self::location::{
+    CellProfileOrder, IndexUpdater, LocationIndex, LocationIndexIteratorState,
+    PartiallyIteratedCell,
+};
 use crate::{cache::LastSeenTimeUpdated, db_manager::InternalWriting};
 
 pub mod location;
@@ -160,7 +163,13 @@ fn format_size_in_bytes(size: usize) -> String {
 enum IteratorResultInternal {
     NoProfiles,
     TryAgain,
-    MatchingProfilesFound { profiles: Vec<ProfileLink> },
+    MatchingProfilesFound { profiles: Vec<ProfileAndOrder> },
+}
+
+#[derive(Clone)]
+struct ProfileAndOrder {
+    profile: ProfileLink,
+    order: CellProfileOrder,
 }
 
 #[derive(Debug)]
@@ -179,44 +188,69 @@ impl<'a> LocationIndexIteratorHandle<'a> {
         }
     }
 
-    pub fn next_profiles(
+    /// Get next profile page. Profiles in the page are sorted by
+    /// distance from the profile viewer.
+    ///
+    /// One index cell can have more profiles than fits to one page.
+    /// In that case the next page continues from the same cell.
+    pub fn next_profile_page(
         &self,
         previous_iterator_state: LocationIndexIteratorState,
         query_maker_details: &ProfileQueryMakerDetails,
-    ) -> (LocationIndexIteratorState, Option<Vec<ProfileLink>>) {
+        page_size: usize,
+    ) -> (LocationIndexIteratorState, Vec<ProfileLink>) {
         let current_time = UnixTime::current_time();
         let mut iterator_state = previous_iterator_state;
-        loop {
-            let (new_state, result) =
-                self.next_profiles_internal(iterator_state, query_maker_details, &current_time);
+        let mut profiles = vec![];
+        while profiles.len() < page_size {
+            let (new_state, result) = self.next_profiles_internal(
+                iterator_state,
+                query_maker_details,
+                &current_time,
+                page_size - profiles.len(),
+            );
             iterator_state = new_state;
             match result {
-                IteratorResultInternal::NoProfiles => {
-                    return (iterator_state, None);
-                }
-                IteratorResultInternal::MatchingProfilesFound { profiles } => {
-                    return (iterator_state, Some(profiles));
-                }
-                IteratorResultInternal::TryAgain => {
-                    continue;
-                }
+                IteratorResultInternal::NoProfiles => break,
+                IteratorResultInternal::MatchingProfilesFound {
+                    profiles: new_profiles,
+                } => profiles.extend(new_profiles),
+                IteratorResultInternal::TryAgain => continue,
             }
         }
+
+        profiles.sort_by(|a, b| a.order.cmp(&b.order));
+        let profiles = profiles
+            .into_iter()
+            .map(|p| {
+                let mut profile = p.profile;
+                profile.set_distance(ProfileDistanceBucket::from_distance_km(p.order.distance_km));
+                profile
+            })
+            .collect();
+
+        (iterator_state, profiles)
     }
 
-    /// Iterate to next index cell which has profiles and get all matching
-    /// profiles.
+    /// Iterate to next index cell which has profiles and get at most
+    /// `max_count` matching profiles. If the previous cell has more
+    /// matching profiles, those are returned instead.
     fn next_profiles_internal(
         &self,
         previous_iterator_state: LocationIndexIteratorState,
         query_maker_details: &ProfileQueryMakerDetails,
         current_time: &UnixTime,
+        max_count: usize,
     ) -> (LocationIndexIteratorState, IteratorResultInternal) {
-        let index = self.index.clone();
-        let (iterator, key) = {
-            let mut iterator = previous_iterator_state.into_iterator(index);
-            let key = iterator.next();
-            (iterator, key)
+        let mut iterator_state = previous_iterator_state;
+        let (key, last_returned) = match iterator_state.take_partially_iterated_cell() {
+            Some(cell) => (Some(cell.key), Some(cell.last_returned)),
+            None => {
+                let mut iterator = iterator_state.into_iterator(self.index.clone());
+                let key = iterator.next();
+                iterator_state = iterator.into();
+                (key, None)
+            }
         };
         let result = match key {
             None => IteratorResultInternal::NoProfiles,
@@ -224,11 +258,8 @@ impl<'a> LocationIndexIteratorHandle<'a> {
                 // Possible data race occurred where profile was removed
                 // from the data storage when iterating the index.
                 None => IteratorResultInternal::TryAgain,
-                // TODO(perf): Currently all profiles in one index cell are
-                // sent to client, which might cause issues if everyone will
-                // set profile to same location.
                 Some(profiles) => {
-                    let matches: Vec<ProfileLink> = profiles
+                    let matches: Vec<ProfileAndOrder> = profiles
                         .profiles
                         .values()
                         .filter(|p| {
@@ -238,8 +269,29 @@ impl<'a> LocationIndexIteratorHandle<'a> {
                                 current_time,
                             )
                         })
-                        .map(|p| p.to_profile_link_value())
+                        .map(|p| {
+                            let profile = p.to_profile_link_value();
+                            let order = CellProfileOrder {
+                                distance_km: p.distance_km(&query_maker_details.location),
+                                id: profile.id(),
+                            };
+                            ProfileAndOrder { profile, order }
+                        })
+                        .filter(|p| {
+                            query_maker_details
+                                .max_distance_km
+                                .map(|max| p.order.distance_km <= max.value as f64)
+                                .unwrap_or(true)
+                        })
                         .collect();
+                    let (matches, last_returned) =
+                        select_cell_page(matches, last_returned, max_count);
+                    if let Some(last_returned) = last_returned {
+                        iterator_state.set_partially_iterated_cell(PartiallyIteratedCell {
+                            key,
+                            last_returned,
+                        });
+                    }
                     if matches.is_empty() {
                         IteratorResultInternal::TryAgain
                     } else {
@@ -248,7 +300,7 @@ impl<'a> LocationIndexIteratorHandle<'a> {
                 }
             },
         };
-        (iterator.into(), result)
+        (iterator_state, result)
     }
 
     pub fn new_iterator_state(
@@ -260,6 +312,27 @@ impl<'a> LocationIndexIteratorHandle<'a> {
     }
 }
 
+/// Select next page from matching profiles of one index cell.
+///
+/// Profiles returned from previous pages are skipped. If all remaining
+/// profiles do not fit to the page, the order of the last selected profile
+/// is also returned, so that the next page can continue from the same cell.
+fn select_cell_page(
+    mut matches: Vec<ProfileAndOrder>,
+    last_returned: Option<CellProfileOrder>,
+    max_count: usize,
+) -> (Vec<ProfileAndOrder>, Option<CellProfileOrder>) {
+    matches.retain(|p| last_returned.map(|last| p.order > last).unwrap_or(true));
+    matches.sort_by(|a, b| a.order.cmp(&b.order));
+    if matches.len() > max_count {
+        matches.truncate(max_count);
+        let last_returned = matches.last().map(|p| p.order);
+        (matches, last_returned)
+    } else {
+        (matches, None)
+    }
+}
+
 #[derive(Debug)]
 pub struct LocationIndexWriteHandle<'a> {
     index: &'a Arc<LocationIndex>,
@@ -291,14 +364,23 @@ impl<'a> LocationIndexWriteHandle<'a> {
         account_id: AccountId,
         previous_key: LocationIndexKey,
         new_key: LocationIndexKey,
+        new_location: LocationInternal,
     ) -> error_stack::Result<(), IndexError> {
+        let mut profiles = self.profiles.write().await;
+
         if previous_key == new_key {
-            // No update needed. If return would not be here then
-            // if new_size == 0 check would make profile disappear.
+            // Only exact location update is needed. If return would not
+            // be here then if new_size == 0 check would make profile
+            // disappear.
+            if let Some(profile) = profiles
+                .get_mut(&previous_key)
+                .and_then(|p| p.profiles.get_mut(&account_id))
+            {
+                profile.set_location(new_location);
+            }
             return Ok(());
         }
 
-        let mut profiles = self.profiles.write().await;
         let data = match profiles.get_mut(&previous_key) {
             Some(p) => {
                 let current_profile = p.profiles.remove(&account_id);
@@ -310,7 +392,8 @@ impl<'a> LocationIndexWriteHandle<'a> {
         if let Some((current_profile, new_size)) = data {
             let mut updater = IndexUpdater::new(self.index.clone());
 
-            if let Some(profile) = current_profile {
+            if let Some(mut profile) = current_profile {
+                profile.set_location(new_location);
                 match profiles.get_mut(&new_key) {
                     Some(some_other_profiles_also) => {
                         let update_index = some_other_profiles_also.profiles.is_empty();
@@ -614,8 +697,10 @@ mod test {
     use std::num::NonZeroU8;
 
     use config::file::LocationConfig;
+    use model::AccountId;
+    use model_server_data::{ProfileLink, ProfileVersion};
 
-    use super::CoordinateManager;
+    use super::{select_cell_page, CellProfileOrder, CoordinateManager, ProfileAndOrder};
 
     fn manager() -> CoordinateManager {
         CoordinateManager::new(LocationConfig {
@@ -650,4 +735,58 @@ mod test {
         let manager = manager();
         assert_eq!(manager.calculate_index_x_key(10.0), manager.width() - 1);
     }
+
+    fn cell_profiles(distances_km: &[f64]) -> Vec<ProfileAndOrder> {
+        distances_km
+            .iter()
+            .map(|&distance_km| {
+                let profile = ProfileLink::new(
+                    AccountId::new_random(),
+                    ProfileVersion::new_random(),
+                    None,
+                    None,
+                );
+                let order = CellProfileOrder {
+                    distance_km,
+                    id: profile.id(),
+                };
+                ProfileAndOrder { profile, order }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cell_paging_continues_from_partially_iterated_cell() {
+        let profiles = cell_profiles(&[4.0, 1.0, 3.0, 1.0, 2.0]);
+        let mut expected: Vec<CellProfileOrder> = profiles.iter().map(|p| p.order).collect();
+        expected.sort();
+
+        let mut returned = vec![];
+        let mut last_returned = None;
+        let mut page_count = 0;
+        loop {
+            let (page, new_last_returned) = select_cell_page(profiles.clone(), last_returned, 2);
+            page_count += 1;
+            returned.extend(page.iter().map(|p| p.order));
+            match new_last_returned {
+                Some(last) => {
+                    assert_eq!(page.len(), 2);
+                    assert_eq!(page.last().map(|p| p.order), Some(last));
+                    last_returned = Some(last);
+                }
+                None => break,
+            }
+        }
+
+        assert_eq!(page_count, 3);
+        assert_eq!(returned, expected);
+    }
+
+    #[test]
+    fn cell_paging_without_partially_iterated_cell() {
+        let (page, last_returned) = select_cell_page(cell_profiles(&[2.0, 1.0]), None, 2);
+        assert_eq!(page.len(), 2);
+        assert!(page[0].order < page[1].order);
+        assert!(last_returned.is_none());
+    }
 }
@@ -22,8 +22,9 @@
 //!
 //! Matrix indexes are used like a key for HashMap<(u16,u16), Vec<AccountId>>
 
-use std::{fmt::Debug, num::NonZeroU16, sync::Arc};
+use std::{cmp::Ordering, fmt::Debug, num::NonZeroU16, sync::Arc};
 
+use model::AccountId;
 use model_server_data::{CellData, CellDataProvider, LocationIndexKey};
 use nalgebra::{DMatrix, Dyn, VecStorage};
 use tracing::error;
@@ -141,6 +142,44 @@ impl IndexLimit {
     }
 }
 
+/// Profile order inside one location index cell. Profiles are
+/// ordered by distance and then by account ID.
+#[derive(Debug, Clone, Copy)]
+pub struct CellProfileOrder {
+    pub distance_km: f64,
+    pub id: AccountId,
+}
+
+impl PartialEq for CellProfileOrder {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for CellProfileOrder {}
+
+impl PartialOrd for CellProfileOrder {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CellProfileOrder {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance_km
+            .total_cmp(&other.distance_km)
+            .then_with(|| self.id.aid.as_bytes().cmp(other.id.aid.as_bytes()))
+    }
+}
+
+/// Location index cell which profiles were not all returned
+/// because of page size limit.
+#[derive(Debug, Clone, Copy)]
+pub struct PartiallyIteratedCell {
+    pub key: LocationIndexKey,
+    pub last_returned: CellProfileOrder,
+}
+
 /// Iterator for location index
 ///
 /// Start from one cell and enlarge area clockwise.
@@ -163,6 +202,8 @@ pub struct LocationIndexIteratorState {
     visited_max_corners: VisitedMaxCorners,
     index_limit_top_left: IndexLimit,
     index_limit_bottom_right: IndexLimit,
+    /// Continue from this cell before moving to the next cell.
+    partially_iterated_cell: Option<PartiallyIteratedCell>,
 }
 
 impl LocationIndexIteratorState {
@@ -180,6 +221,7 @@ impl LocationIndexIteratorState {
             visited_max_corners: VisitedMaxCorners::default(),
             index_limit_top_left: IndexLimit::default(),
             index_limit_bottom_right: IndexLimit::default(),
+            partially_iterated_cell: None,
         }
     }
 
@@ -205,9 +247,18 @@ impl LocationIndexIteratorState {
             visited_max_corners: VisitedMaxCorners::default(),
             index_limit_top_left: IndexLimit::new(area.top_left),
             index_limit_bottom_right: IndexLimit::new(area.bottom_right),
+            partially_iterated_cell: None,
         }
     }
 
+    pub fn take_partially_iterated_cell(&mut self) -> Option<PartiallyIteratedCell> {
+        self.partially_iterated_cell.take()
+    }
+
+    pub fn set_partially_iterated_cell(&mut self, cell: PartiallyIteratedCell) {
+        self.partially_iterated_cell = Some(cell);
+    }
+
     pub fn into_iterator<T: ReadIndex>(self, reader: T) -> LocationIndexIterator<T> {
         LocationIndexIterator::new(self, reader)
     }
@@ -14,6 +14,7 @@ use self::file::{utils::FileDir, FileError};
 pub mod app;
 pub mod cache;
 pub mod content_processing;
+pub mod data_export;
 pub mod db_manager;
 pub mod demo;
 pub mod event;
@@ -21,6 +22,7 @@ pub mod file;
 pub mod id;
 pub mod index;
 pub mod macros;
+pub mod message_signing_key;
 pub mod read;
 pub mod statistics;
 pub mod utils;
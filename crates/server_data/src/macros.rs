@@ -85,6 +85,10 @@ macro_rules! define_cmd_wrapper_write {
                 $crate::db_manager::InternalWriting::cache(&self.0)
             }
 
+            fn cache_arc(&self) -> std::sync::Arc<$crate::cache::DatabaseCache> {
+                $crate::db_manager::InternalWriting::cache_arc(&self.0)
+            }
+
             fn location(&self) -> &$crate::index::LocationIndexManager {
                 $crate::db_manager::InternalWriting::location(&self.0)
             }
@@ -0,0 +1,172 @@
+//! Server Ed25519 key for signing chat message metadata and public keys
+
+use std::path::{Path, PathBuf};
+
+use config::Config;
+use ed25519_dalek::{Signature, Signer, SigningKey};
+use error_stack::{Result, ResultExt};
+use model::MessageSigningPublicKey;
+use server_common::data::DataError;
+use tracing::{info, warn};
+use utils::random_bytes::random_128_bits;
+
+const MESSAGE_SIGNING_KEY_FILE_NAME: &str = "message_signing_key";
+
+/// Data which server signs
+pub trait SignedData {
+    fn to_signed_bytes(&self) -> Vec<u8>;
+}
+
+/// Key for signing chat message metadata and public keys. Only one key
+/// exists and it is loaded or created when server starts.
+#[derive(Clone)]
+pub struct MessageSigningKey {
+    key: SigningKey,
+}
+
+impl MessageSigningKey {
+    /// Returns `None` if key length is invalid.
+    pub fn from_bytes(key: &[u8]) -> Option<Self> {
+        let key: [u8; 32] = key.try_into().ok()?;
+        Some(Self {
+            key: SigningKey::from_bytes(&key),
+        })
+    }
+
+    pub fn generate_new() -> Self {
+        let mut key = [0u8; 32];
+        key[..16].copy_from_slice(&random_128_bits());
+        key[16..].copy_from_slice(&random_128_bits());
+        Self {
+            key: SigningKey::from_bytes(&key),
+        }
+    }
+
+    /// Load the key file from secure storage directory or create a new key
+    /// if the file does not exist. If secure storage directory is not
+    /// configured, the data directory is used.
+    ///
+    /// The key is not stored to the database, so it is not included in
+    /// database backups.
+    ///
+    /// Runs blocking file system code.
+    pub fn load_or_create(config: &Config) -> Result<Self, DataError> {
+        let key_file = Self::key_file_path(config);
+
+        if key_file.exists() {
+            let key = std::fs::read(&key_file).change_context(DataError::Io)?;
+            return Self::from_bytes(&key)
+                .ok_or(DataError::Init)
+                .attach_printable(format!(
+                    "Invalid message signing key file {}",
+                    key_file.display()
+                ));
+        }
+
+        let key = Self::generate_new();
+        write_key_file(&key_file, &key.key.to_bytes())?;
+        info!("Message signing key created to file {}", key_file.display());
+        Ok(key)
+    }
+
+    fn key_file_path(config: &Config) -> PathBuf {
+        let dir = match config.simple_backend().secure_storage_dir() {
+            Some(dir) => dir,
+            None => {
+                warn!("Secure storage directory is not configured, using data directory for message signing key");
+                config.simple_backend().data_dir()
+            }
+        };
+        dir.join(MESSAGE_SIGNING_KEY_FILE_NAME)
+    }
+
+    pub fn public_key(&self) -> MessageSigningPublicKey {
+        MessageSigningPublicKey::from_bytes(self.key.verifying_key().as_bytes())
+    }
+
+    /// Returns signature bytes.
+    pub fn sign(&self, data: &impl SignedData) -> Vec<u8> {
+        self.key.sign(&data.to_signed_bytes()).to_bytes().to_vec()
+    }
+
+    /// Returns false if the signature is invalid.
+    pub fn verify(&self, data: &impl SignedData, signature: &[u8]) -> bool {
+        let Ok(signature) = Signature::from_slice(signature) else {
+            return false;
+        };
+        self.key
+            .verifying_key()
+            .verify_strict(&data.to_signed_bytes(), &signature)
+            .is_ok()
+    }
+}
+
+impl std::fmt::Debug for MessageSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MessageSigningKey")
+    }
+}
+
+fn write_key_file(path: &Path, key: &[u8]) -> Result<(), DataError> {
+    #[cfg(unix)]
+    {
+        use std::{io::Write, os::unix::fs::OpenOptionsExt};
+
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)
+            .change_context(DataError::Io)?;
+        file.write_all(key).change_context(DataError::Io)?;
+        file.sync_all().change_context(DataError::Io)?;
+    }
+
+    #[cfg(not(unix))]
+    std::fs::write(path, key).change_context(DataError::Io)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestData(&'static [u8]);
+
+    impl SignedData for TestData {
+        fn to_signed_bytes(&self) -> Vec<u8> {
+            self.0.to_vec()
+        }
+    }
+
+    #[test]
+    fn key_from_bytes_verifies_signature() {
+        let key = MessageSigningKey::generate_new();
+        let signature = key.sign(&TestData(b"test"));
+        let key_from_bytes = MessageSigningKey::from_bytes(&key.key.to_bytes()).unwrap();
+        assert!(key_from_bytes.verify(&TestData(b"test"), &signature));
+        assert!(!key_from_bytes.verify(&TestData(b"modified"), &signature));
+    }
+
+    #[test]
+    fn invalid_key_length_is_rejected() {
+        assert!(MessageSigningKey::from_bytes(&[0; 31]).is_none());
+    }
+
+    #[test]
+    fn key_file_is_written() {
+        let dir = std::env::temp_dir().join(format!(
+            "message_signing_key_test_{}",
+            u128::from_le_bytes(random_128_bits())
+        ));
+        std::fs::create_dir(&dir).unwrap();
+        let path = dir.join(MESSAGE_SIGNING_KEY_FILE_NAME);
+        let key = MessageSigningKey::generate_new();
+        write_key_file(&path, &key.key.to_bytes()).unwrap();
+        let loaded = MessageSigningKey::from_bytes(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(loaded.public_key(), key.public_key());
+        assert!(write_key_file(&path, &key.key.to_bytes()).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,37 +1,66 @@
 use chrono::NaiveDate;
 use database::current::read::GetDbReadCommandsCommon;
 use model::{
-    AccessToken, Account, AccountId, AccountIdInternal, ClientConfigSyncVersion, PendingNotificationFlags, RefreshToken
+    Account, AccountId, AccountIdInternal, ClientConfigSyncVersion, LoginSessionId,
+    LoginSessionRaw, PendingNotificationFlags, RefreshToken, ReportDetailed,
 };
 use server_common::data::IntoDataError;
 
 use super::{super::DataError, DbRead};
 use crate::{
-    cache::CacheReadCommon, db_manager::InternalReading, define_cmd_wrapper_read, id::ToAccountIdInternal, result::Result
+    cache::CacheReadCommon, db_manager::InternalReading, define_cmd_wrapper_read, result::Result,
 };
 
 define_cmd_wrapper_read!(ReadCommandsCommon);
 
 impl ReadCommandsCommon<'_> {
-    pub async fn account_access_token(
+    pub async fn login_session_refresh_token(
         &self,
-        id: AccountId,
-    ) -> Result<Option<AccessToken>, DataError> {
-        let id = self.to_account_id_internal(id).await.into_data_error(id)?;
-        self.db_read(move |mut cmds| cmds.common().token().access_token(id))
+        session: LoginSessionId,
+    ) -> Result<Option<RefreshToken>, DataError> {
+        self.db_read(move |mut cmds| cmds.common().login_session().refresh_token(session))
             .await
             .into_error()
     }
 
-    pub async fn account_refresh_token(
+    /// Find login session using already rotated refresh token.
+    pub async fn login_session_with_used_refresh_token(
+        &self,
+        token: RefreshToken,
+    ) -> Result<Option<(AccountIdInternal, LoginSessionId)>, DataError> {
+        self.db_read(move |mut cmds| {
+            cmds.common()
+                .login_session()
+                .login_session_with_used_refresh_token(token)
+        })
+        .await
+        .into_error()
+    }
+
+    pub async fn login_sessions(
         &self,
         id: AccountIdInternal,
-    ) -> Result<Option<RefreshToken>, DataError> {
-        self.db_read(move |mut cmds| cmds.common().token().refresh_token(id))
+    ) -> Result<Vec<LoginSessionRaw>, DataError> {
+        self.db_read(move |mut cmds| cmds.common().login_session().login_sessions(id))
             .await
             .into_error()
     }
 
+    /// Check that the login session is owned by the account.
+    pub async fn login_session_exists(
+        &self,
+        id: AccountIdInternal,
+        session: LoginSessionId,
+    ) -> Result<bool, DataError> {
+        self.db_read(move |mut cmds| {
+            cmds.common()
+                .login_session()
+                .login_session_exists(id, session)
+        })
+        .await
+        .into_error()
+    }
+
     /// Account is available on all servers as account server will sync it to
     /// others if server is running in microservice mode.
     pub async fn account(&self, id: AccountIdInternal) -> Result<Account, DataError> {
@@ -87,6 +116,20 @@ impl ReadCommandsCommon<'_> {
             .into_error()
     }
 
+    pub async fn reports_created_by(
+        &self,
+        id: AccountIdInternal,
+    ) -> Result<Vec<ReportDetailed>, DataError> {
+        let components = self.config().components();
+        self.db_read(move |mut cmds| {
+            cmds.common()
+                .report()
+                .get_all_detailed_reports_created_by(id, components)
+        })
+        .await
+        .into_error()
+    }
+
     pub async fn backup_current_database(&self, file_name: String) -> Result<(), DataError> {
         self.db_read_raw_no_transaction(move |mut cmds| cmds.common().backup_current_database(file_name))
             .await
@@ -1,9 +1,10 @@
-
 use database::history::read::GetDbHistoryReadCommandsCommon;
+use model::UnixTime;
 use server_common::data::IntoDataError;
+use simple_backend_model::{PerfMetricQuery, PerfMetricQueryResult, TimeGranularity};
 
 use crate::{
-    db_manager::InternalReading, define_cmd_wrapper_read, result::Result, DataError
+    db_manager::InternalReading, define_cmd_wrapper_read, read::DbRead, result::Result, DataError
 };
 
 define_cmd_wrapper_read!(ReadCommandsCommonHistory);
@@ -17,4 +18,30 @@ impl ReadCommandsCommonHistory<'_> {
             .await
             .into_error()
     }
+
+    /// Get performance metrics from the history database.
+    pub async fn perf_data(
+        &self,
+        query: PerfMetricQuery,
+        time_granularity: TimeGranularity,
+    ) -> Result<PerfMetricQueryResult, DataError> {
+        let start_time = query.start_time.unwrap_or_default();
+        let end_time = query.end_time.unwrap_or_else(UnixTime::current_time);
+        let names = query.metric_names();
+        self.db_read_history(move |mut cmds| {
+            let save_times = cmds
+                .common_history()
+                .perf_data_save_times(start_time, end_time)?;
+            let values = cmds
+                .common_history()
+                .perf_data_values(start_time, end_time, names)?;
+            Ok(PerfMetricQueryResult::from_history(
+                &save_times,
+                values,
+                time_granularity,
+            ))
+        })
+        .await
+        .into_error()
+    }
 }
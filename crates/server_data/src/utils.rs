@@ -1,32 +1,43 @@
 use std::net::SocketAddr;
 
-use model::{AccessToken, AccountId, AccountIdInternal, AccountState, Permissions};
+use config::Config;
+use model::{AccessToken, AccountId, AccountIdInternal, AccountState, LoginSessionId, Permissions};
+use simple_backend_utils::time::DurationValue;
 
 use super::{cache::DatabaseCache, DataError, IntoDataError};
 use crate::result::Result;
 
 pub struct AccessTokenManager<'a> {
     cache: &'a DatabaseCache,
+    access_token_max_age: DurationValue,
 }
 
 impl<'a> AccessTokenManager<'a> {
-    pub fn new(cache: &'a DatabaseCache) -> Self {
-        Self { cache }
+    pub fn new(cache: &'a DatabaseCache, config: &Config) -> Self {
+        Self {
+            cache,
+            access_token_max_age: config.limits_account().access_token_max_age,
+        }
     }
 
-    pub async fn access_token_exists(&self, token: &AccessToken) -> Option<AccountIdInternal> {
-        self.cache.access_token_exists(token).await
+    pub async fn access_token_exists(
+        &self,
+        token: &AccessToken,
+    ) -> Option<(AccountIdInternal, LoginSessionId)> {
+        self.cache
+            .access_token_exists(token, self.access_token_max_age)
+            .await
     }
 
-    /// Check that token and current connection IP and port matches
+    /// Check that token and login session's current connection IP matches
     /// with WebSocket connection.
     pub async fn access_token_and_connection_exists(
         &self,
         token: &AccessToken,
         connection: SocketAddr,
-    ) -> Option<(AccountIdInternal, Permissions, AccountState)> {
+    ) -> Option<(AccountIdInternal, LoginSessionId, Permissions, AccountState)> {
         self.cache
-            .access_token_and_connection_exists(token, connection)
+            .access_token_and_connection_exists(token, connection, self.access_token_max_age)
             .await
     }
 }
@@ -1,7 +1,13 @@
 use std::net::SocketAddr;
 
-use database::current::{read::GetDbReadCommandsCommon, write::GetDbWriteCommandsCommon};
-use model::{Account, AccountId, AccountIdInternal, ReportTypeNumberInternal, UnixTime};
+use database::{
+    current::{read::GetDbReadCommandsCommon, write::GetDbWriteCommandsCommon},
+    DbWriteMode, DieselDatabaseError,
+};
+use model::{
+    AccessToken, Account, AccountId, AccountIdInternal, LoginSessionAccessToken, LoginSessionId,
+    RefreshToken, ReportTypeNumberInternal, UnixTime,
+};
 use model_server_data::AuthPair;
 use server_common::data::cache::CacheError;
 use simple_backend_utils::time::DurationValue;
@@ -13,36 +19,126 @@ use crate::{
     define_cmd_wrapper_write,
     event::EventReceiver,
     file::FileWrite,
+    read::DbRead,
     result::Result,
     write::db_transaction,
     DataError, IntoDataError,
 };
 
+pub enum RefreshTokenRotationResult {
+    Rotated(AuthPair),
+    /// Refresh token was already used, so the related login session
+    /// was revoked.
+    ReuseDetected,
+    Invalid,
+}
+
+/// Delete login sessions from database. Returns access tokens of the
+/// deleted sessions, so that the sessions can be removed from cache.
+fn delete_login_sessions(
+    cmds: &mut DbWriteMode,
+    id: AccountIdInternal,
+    session_ids: &[LoginSessionId],
+) -> error_stack::Result<Vec<LoginSessionAccessToken>, DieselDatabaseError> {
+    let sessions: Vec<LoginSessionAccessToken> = cmds
+        .read()
+        .common()
+        .login_session()
+        .access_tokens(id)?
+        .into_iter()
+        .filter(|v| session_ids.contains(&v.session_id))
+        .collect();
+    for session in session_ids {
+        cmds.common()
+            .login_session()
+            .delete_login_session(*session)?;
+    }
+    Ok(sessions)
+}
+
 define_cmd_wrapper_write!(WriteCommandsCommon);
 
 impl WriteCommandsCommon<'_> {
+    /// Create new login session for the account.
+    ///
+    /// If the account already has the max amount of login sessions,
+    /// the least recently used sessions are revoked.
+    pub async fn create_login_session(
+        &self,
+        id: AccountIdInternal,
+        pair: AuthPair,
+    ) -> Result<LoginSessionId, DataError> {
+        let session_id = LoginSessionId::new_random();
+        let access = pair.access.clone();
+        let max_sessions: usize = self
+            .config()
+            .limits_account()
+            .max_login_sessions_per_account
+            .max(1)
+            .into();
+        let (access_token_unix_time, revoked_sessions) = db_transaction!(self, move |mut cmds| {
+            let mut sessions = cmds.read().common().login_session().login_sessions(id)?;
+            sessions.sort_by_key(|v| v.last_used_unix_time.ut);
+            let revoke_count = (sessions.len() + 1).saturating_sub(max_sessions);
+            let revoked_session_ids: Vec<LoginSessionId> = sessions
+                .iter()
+                .take(revoke_count)
+                .map(|v| v.session_id)
+                .collect();
+            let revoked_sessions = delete_login_sessions(&mut cmds, id, &revoked_session_ids)?;
+            let access_token_unix_time = cmds
+                .common()
+                .login_session()
+                .insert_login_session(id, session_id, access, pair.refresh)?;
+            Ok((access_token_unix_time, revoked_sessions))
+        })?;
+
+        self.remove_login_sessions_from_cache(id, revoked_sessions)
+            .await?;
+
+        self.update_access_token_and_connection(
+            id.as_id(),
+            session_id,
+            None,
+            pair.access,
+            access_token_unix_time,
+            None,
+        )
+        .await
+        .into_data_error(id)?;
+
+        Ok(session_id)
+    }
+
     /// Creates new event channel if address is Some.
     pub async fn set_new_auth_pair(
         &self,
         id: AccountIdInternal,
+        session_id: LoginSessionId,
         pair: AuthPair,
         address: Option<SocketAddr>,
     ) -> Result<Option<EventReceiver>, DataError> {
         let access = pair.access.clone();
-        let current_access_token = db_transaction!(self, move |mut cmds| {
-            let current_access_token = cmds.read().common().token().access_token(id)?;
-            cmds.common().token().access_token(id, Some(access))?;
-            cmds.common()
-                .token()
-                .refresh_token(id, Some(pair.refresh))?;
-            Ok(current_access_token)
+        let (current_access_token, access_token_unix_time) = db_transaction!(self, move |mut cmds| {
+            let current_access_token = cmds
+                .read()
+                .common()
+                .login_session()
+                .access_token(session_id)?;
+            let access_token_unix_time = cmds
+                .common()
+                .login_session()
+                .update_tokens(session_id, access, pair.refresh)?;
+            Ok((current_access_token, access_token_unix_time))
         })?;
 
         let option = self
             .update_access_token_and_connection(
                 id.as_id(),
+                session_id,
                 current_access_token,
                 pair.access,
+                access_token_unix_time,
                 address,
             )
             .await
@@ -56,17 +152,34 @@ impl WriteCommandsCommon<'_> {
         Ok(option.map(|v| v.0))
     }
 
-    /// Remove current connection address, access and refresh tokens.
-    pub async fn logout(&self, id: AccountIdInternal) -> Result<(), DataError> {
+    /// Remove login session's connection, access and refresh tokens.
+    ///
+    /// Removing the connection drops the login session's event channel
+    /// sender, which closes the login session's WebSocket connection.
+    pub async fn logout(
+        &self,
+        id: AccountIdInternal,
+        session_id: LoginSessionId,
+    ) -> Result<(), DataError> {
         let current_access_token = db_transaction!(self, move |mut cmds| {
-            let current_access_token = cmds.read().common().token().access_token(id);
-            cmds.common().token().access_token(id, None)?;
-            cmds.common().token().refresh_token(id, None)?;
-            current_access_token
+            let current_access_token = cmds
+                .read()
+                .common()
+                .login_session()
+                .access_token(session_id)?;
+            cmds.common()
+                .login_session()
+                .delete_login_session(session_id)?;
+            Ok(current_access_token)
         })?;
 
         let last_seen_time_update = self
-            .delete_connection_and_specific_access_token(id.as_id(), None, current_access_token)
+            .delete_connection_and_specific_access_token(
+                id.as_id(),
+                session_id,
+                None,
+                current_access_token,
+            )
             .await
             .into_data_error(id)?;
 
@@ -78,14 +191,125 @@ impl WriteCommandsCommon<'_> {
         Ok(())
     }
 
+    /// Rotate login session tokens using the current refresh token
+    /// of the session.
+    ///
+    /// If the refresh token is already rotated, the token is most likely
+    /// stolen, so the login session is revoked.
+    pub async fn rotate_tokens_with_refresh_token(
+        &self,
+        refresh_token: RefreshToken,
+    ) -> Result<RefreshTokenRotationResult, DataError> {
+        let token = refresh_token.clone();
+        let session = self
+            .db_read(move |mut cmds| {
+                cmds.common()
+                    .login_session()
+                    .login_session_with_refresh_token(token)
+            })
+            .await?;
+
+        if let Some((id, session_id)) = session {
+            let pair = AuthPair::new(RefreshToken::generate_new(), AccessToken::generate_new());
+            self.set_new_auth_pair(id, session_id, pair.clone(), None)
+                .await?;
+            return Ok(RefreshTokenRotationResult::Rotated(pair));
+        }
+
+        let used_token_session = self
+            .db_read(move |mut cmds| {
+                cmds.common()
+                    .login_session()
+                    .login_session_with_used_refresh_token(refresh_token)
+            })
+            .await?;
+
+        if let Some((id, session_id)) = used_token_session {
+            self.logout(id, session_id).await?;
+            Ok(RefreshTokenRotationResult::ReuseDetected)
+        } else {
+            Ok(RefreshTokenRotationResult::Invalid)
+        }
+    }
+
+    /// Remove all login sessions of the account.
+    pub async fn logout_all_sessions(&self, id: AccountIdInternal) -> Result<(), DataError> {
+        let sessions = db_transaction!(self, move |mut cmds| {
+            let sessions = cmds.read().common().login_session().access_tokens(id)?;
+            cmds.common().login_session().delete_all_login_sessions(id)?;
+            Ok(sessions)
+        })?;
+
+        self.remove_login_sessions_from_cache(id, sessions).await
+    }
+
+    /// Remove login sessions which are not used during `idle_expiry`.
+    pub async fn logout_idle_sessions(
+        &self,
+        id: AccountIdInternal,
+        idle_expiry: DurationValue,
+    ) -> Result<(), DataError> {
+        let current_time = UnixTime::current_time();
+        let sessions = db_transaction!(self, move |mut cmds| {
+            let idle_session_ids: Vec<LoginSessionId> = cmds
+                .read()
+                .common()
+                .login_session()
+                .login_sessions(id)?
+                .into_iter()
+                .filter(|v| {
+                    let expires = v.last_used_unix_time.add_seconds(idle_expiry.seconds);
+                    current_time.ut >= expires.ut
+                })
+                .map(|v| v.session_id)
+                .collect();
+            delete_login_sessions(&mut cmds, id, &idle_session_ids)
+        })?;
+
+        self.remove_login_sessions_from_cache(id, sessions).await
+    }
+
+    /// Remove connections and access tokens of already deleted
+    /// login sessions.
+    async fn remove_login_sessions_from_cache(
+        &self,
+        id: AccountIdInternal,
+        sessions: Vec<LoginSessionAccessToken>,
+    ) -> Result<(), DataError> {
+        for session in sessions {
+            let last_seen_time_update = self
+                .delete_connection_and_specific_access_token(
+                    id.as_id(),
+                    session.session_id,
+                    None,
+                    Some(session.access_token),
+                )
+                .await
+                .into_data_error(id)?;
+
+            if let Some(last_seen_time_update) = last_seen_time_update {
+                self.update_last_seen_time(id.uuid, last_seen_time_update)
+                    .await;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Remove specific connection session.
     pub async fn end_connection_session(
         &self,
         id: AccountIdInternal,
+        session_id: LoginSessionId,
         session_address: SocketAddr,
     ) -> Result<(), DataError> {
         let last_seen_time_update = self
-            .delete_connection_and_specific_access_token(id.as_id(), Some(session_address), None)
+            .delete_connection_and_specific_access_token(
+                id.as_id(),
+                session_id,
+                Some(session_address),
+                None,
+            )
             .await
             .into_data_error(id)?;
 
@@ -1,7 +1,7 @@
 //! Write commands that can be run concurrently also with synchronous
 //! write commands.
 
-use std::{collections::HashMap, fmt::{self, Debug}, sync::{atomic::{AtomicI64, Ordering}, Arc}};
+use std::{collections::{HashMap, HashSet}, fmt::{self, Debug}, sync::{atomic::{AtomicI64, Ordering}, Arc}};
 
 use axum::body::BodyDataStream;
 use config::Config;
@@ -220,10 +220,11 @@ impl ConcurrentWriteProfileHandleBlocking {
     pub fn reset_profile_iterator(
         &self,
         id: AccountIdInternal,
+        hidden_profiles: Option<Arc<HashSet<AccountId>>>,
     ) -> Result<ProfileIteratorSessionIdInternal, DataError> {
         self.write
             .user_write_commands_account()
-            .reset_profile_iterator(id)
+            .reset_profile_iterator(id, hidden_profiles)
     }
 
     pub fn next_received_likes_iterator_state(
@@ -341,30 +342,11 @@ impl<'a> WriteCommandsConcurrent<'a> {
             return Ok(None);
         }
 
-        let (mut next_state, profiles) = self
-            .location
-            .next_profiles(location.current_iterator, &query_maker_filters);
-
-        let (next_state, profiles) = if let Some(mut profiles) = profiles {
-            loop {
-                if profiles.len() >= PROFILE_ITERATOR_PAGE_SIZE {
-                    break (next_state, profiles);
-                } else {
-                    let (new_next_state, new_profiles) = self
-                        .location
-                        .next_profiles(next_state, &query_maker_filters);
-                    next_state = new_next_state;
-
-                    if let Some(new_profiles) = new_profiles {
-                        profiles.extend(new_profiles);
-                    } else {
-                        break (next_state, profiles);
-                    }
-                }
-            }
-        } else {
-            (next_state, vec![])
-        };
+        let (next_state, profiles) = self.location.next_profile_page(
+            location.current_iterator,
+            &query_maker_filters,
+            PROFILE_ITERATOR_PAGE_SIZE,
+        );
 
         self.cache
             .write_cache_blocking(id.as_id(), |e| {
@@ -378,9 +360,12 @@ impl<'a> WriteCommandsConcurrent<'a> {
         Ok(Some(profiles))
     }
 
+    /// Reset profile iterator and replace hidden profiles for the new
+    /// profile iterator session.
     pub fn reset_profile_iterator(
         &self,
         id: AccountIdInternal,
+        hidden_profiles: Option<Arc<HashSet<AccountId>>>,
     ) -> Result<ProfileIteratorSessionIdInternal, DataError> {
         self.cache
             .write_cache_blocking(id.as_id(), |e| {
@@ -396,6 +381,7 @@ impl<'a> WriteCommandsConcurrent<'a> {
                     );
                 p.location.current_iterator = next_state;
                 p.profile_iterator_session_id = Some(new_id);
+                p.profile_iterator_hidden_profiles = hidden_profiles;
                 Ok(new_id)
             })
             .into_data_error(id)
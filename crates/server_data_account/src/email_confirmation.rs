@@ -0,0 +1,148 @@
+//! Signed and expiring tokens for email address confirmation
+
+use error_stack::{Result, ResultExt};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use model::UnixTime;
+use model_account::{AccountId, EmailAddress, EmailConfirmationToken};
+use serde::{Deserialize, Serialize};
+use utils::random_bytes::random_128_bits;
+
+#[derive(thiserror::Error, Debug)]
+pub enum EmailConfirmationTokenError {
+    #[error("Token encoding failed")]
+    Encoding,
+}
+
+/// Key for signing email confirmation tokens. Only one key exists and
+/// it is stored to the database.
+#[derive(Clone)]
+pub struct EmailConfirmationTokenKey {
+    key: Vec<u8>,
+}
+
+impl EmailConfirmationTokenKey {
+    pub fn new(key: Vec<u8>) -> Self {
+        Self { key }
+    }
+
+    pub fn generate_new() -> Self {
+        let mut key = Vec::new();
+        for _ in 1..=2 {
+            key.extend(random_128_bits())
+        }
+        Self { key }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.key
+    }
+}
+
+impl std::fmt::Debug for EmailConfirmationTokenKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EmailConfirmationTokenKey")
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EmailConfirmationTokenClaims {
+    aid: AccountId,
+    /// Token is valid only for the email address which was
+    /// set when the token was created.
+    email: EmailAddress,
+    /// Expiration time
+    exp: i64,
+}
+
+/// Info from valid email confirmation token
+#[derive(Debug, Clone)]
+pub struct EmailConfirmationTokenInfo {
+    pub aid: AccountId,
+    pub email: EmailAddress,
+}
+
+pub fn create_email_confirmation_token(
+    key: &EmailConfirmationTokenKey,
+    aid: AccountId,
+    email: EmailAddress,
+    expires: UnixTime,
+) -> Result<EmailConfirmationToken, EmailConfirmationTokenError> {
+    let claims = EmailConfirmationTokenClaims {
+        aid,
+        email,
+        exp: expires.ut,
+    };
+
+    let token = jsonwebtoken::encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(&key.key),
+    )
+    .change_context(EmailConfirmationTokenError::Encoding)?;
+
+    Ok(EmailConfirmationToken { token })
+}
+
+/// Returns `None` if token signature is invalid or token is expired.
+pub fn validate_email_confirmation_token(
+    key: &EmailConfirmationTokenKey,
+    token: &EmailConfirmationToken,
+) -> Option<EmailConfirmationTokenInfo> {
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.leeway = 0;
+
+    let data = jsonwebtoken::decode::<EmailConfirmationTokenClaims>(
+        &token.token,
+        &DecodingKey::from_secret(&key.key),
+        &validation,
+    )
+    .ok()?;
+
+    Some(EmailConfirmationTokenInfo {
+        aid: data.claims.aid,
+        email: data.claims.email,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn email() -> EmailAddress {
+        EmailAddress("test@example.com".to_string())
+    }
+
+    fn create(key: &EmailConfirmationTokenKey, expires: UnixTime) -> EmailConfirmationToken {
+        create_email_confirmation_token(key, AccountId::for_debugging_only_zero(), email(), expires)
+            .unwrap()
+    }
+
+    #[test]
+    fn valid_token_is_accepted() {
+        let key = EmailConfirmationTokenKey::generate_new();
+        let token = create(&key, UnixTime::current_time().add_seconds(60));
+        let info = validate_email_confirmation_token(&key, &token).unwrap();
+        assert_eq!(info.aid, AccountId::for_debugging_only_zero());
+        assert_eq!(info.email, email());
+    }
+
+    #[test]
+    fn expired_token_is_rejected() {
+        let key = EmailConfirmationTokenKey::generate_new();
+        let expired = UnixTime {
+            ut: UnixTime::current_time().ut - 1,
+        };
+        let token = create(&key, expired);
+        assert!(validate_email_confirmation_token(&key, &token).is_none());
+    }
+
+    #[test]
+    fn token_signed_with_other_key_is_rejected() {
+        let key = EmailConfirmationTokenKey::generate_new();
+        let token = create(
+            &EmailConfirmationTokenKey::generate_new(),
+            UnixTime::current_time().add_seconds(60),
+        );
+        assert!(validate_email_confirmation_token(&key, &token).is_none());
+    }
+}
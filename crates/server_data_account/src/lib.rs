@@ -32,6 +32,7 @@ macro_rules! _db_transaction_history {
 
 pub mod cache;
 pub mod demo;
+pub mod email_confirmation;
 pub mod read;
 pub mod write;
 pub mod write_concurrent;
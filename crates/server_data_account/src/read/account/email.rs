@@ -1,7 +1,9 @@
 use database_account::current::read::GetDbReadCommandsAccount;
-use model_account::{AccountEmailSendingStateRaw, AccountIdInternal};
+use model_account::{AccountEmailSendingStateRaw, AccountIdInternal, EmailConfirmationState};
 use server_data::{define_cmd_wrapper_read, read::DbRead, result::Result, DataError};
 
+use crate::email_confirmation::EmailConfirmationTokenKey;
+
 define_cmd_wrapper_read!(ReadCommandsAccountEmail);
 
 impl ReadCommandsAccountEmail<'_> {
@@ -14,4 +16,33 @@ impl ReadCommandsAccountEmail<'_> {
             .await?;
         Ok(state)
     }
+
+    pub async fn email_confirmation_state(
+        &self,
+        id: AccountIdInternal,
+    ) -> Result<EmailConfirmationState, DataError> {
+        let state = self
+            .db_read(move |mut cmds| cmds.account().email().email_confirmation_state(id))
+            .await?;
+        Ok(state)
+    }
+
+    pub async fn email_notifications_enabled(
+        &self,
+        id: AccountIdInternal,
+    ) -> Result<bool, DataError> {
+        let enabled = self
+            .db_read(move |mut cmds| cmds.account().email().email_notifications_enabled(id))
+            .await?;
+        Ok(enabled)
+    }
+
+    pub async fn email_confirmation_token_key(
+        &self,
+    ) -> Result<Option<EmailConfirmationTokenKey>, DataError> {
+        let key = self
+            .db_read(move |mut cmds| cmds.account().email().email_confirmation_token_key())
+            .await?;
+        Ok(key.map(EmailConfirmationTokenKey::new))
+    }
 }
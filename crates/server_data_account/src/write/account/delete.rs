@@ -58,7 +58,7 @@ impl WriteCommandsAccountDelete<'_> {
         &self,
         id: AccountIdInternal,
     ) -> Result<(), DataError> {
-        self.handle().common().logout(id).await?;
+        self.handle().common().logout_all_sessions(id).await?;
 
         // Delete account from location index
         self.handle().account().update_syncable_account_data(id, None, |_, _, visibility| {
@@ -1,9 +1,18 @@
-use database_account::current::write::GetDbWriteCommandsAccount;
-use model_account::{AccountIdInternal, EmailAddress, EmailMessages, EmailSendingState};
+use database_account::current::{read::GetDbReadCommandsAccount, write::GetDbWriteCommandsAccount};
+use model::UnixTime;
+use model_account::{
+    AccountIdInternal, EmailAddress, EmailMessages, EmailSendingState, SendEmailConfirmationResult,
+};
 use server_data::{
-    app::GetEmailSender, define_cmd_wrapper_write, result::Result, write::DbTransaction, DataError,
+    app::{GetConfig, GetEmailSender},
+    define_cmd_wrapper_write,
+    result::Result,
+    write::DbTransaction,
+    DataError,
 };
 
+use crate::email_confirmation::EmailConfirmationTokenKey;
+
 define_cmd_wrapper_write!(WriteCommandsAccountEmail);
 
 impl WriteCommandsAccountEmail<'_> {
@@ -43,6 +52,38 @@ impl WriteCommandsAccountEmail<'_> {
         Ok(())
     }
 
+    /// Request email sending even if the email is already sent
+    /// previously.
+    pub async fn send_email(
+        &self,
+        id: AccountIdInternal,
+        email: EmailMessages,
+    ) -> Result<(), DataError> {
+        db_transaction!(self, move |mut cmds| {
+            cmds.account()
+                .email()
+                .modify_email_sending_states(id, |state| {
+                    *state.get_ref_mut_to(email) = EmailSendingState::SendRequested;
+                })
+        })?;
+
+        self.email_sender().send(id, email);
+
+        Ok(())
+    }
+
+    pub async fn set_email_notifications_enabled(
+        &self,
+        id: AccountIdInternal,
+        enabled: bool,
+    ) -> Result<(), DataError> {
+        db_transaction!(self, move |mut cmds| {
+            cmds.account()
+                .email()
+                .set_email_notifications_enabled(id, enabled)
+        })
+    }
+
     pub async fn mark_email_as_sent(
         &self,
         id: AccountIdInternal,
@@ -59,4 +100,102 @@ impl WriteCommandsAccountEmail<'_> {
 
         Ok(())
     }
+
+    /// Request email confirmation email if the email address is not
+    /// already confirmed and enough time has passed since the previous
+    /// request.
+    pub async fn send_email_confirmation(
+        &self,
+        id: AccountIdInternal,
+    ) -> Result<SendEmailConfirmationResult, DataError> {
+        let resend_wait = self
+            .config()
+            .limits_account()
+            .email_confirmation_resend_wait_duration;
+        let (result, send_needed) = db_transaction!(self, move |mut cmds| {
+            let data = cmds.read().account().data().account_data(id)?;
+            if data.email.is_none() {
+                let result = SendEmailConfirmationResult {
+                    error_email_not_set: true,
+                    ..Default::default()
+                };
+                return Ok((result, false));
+            }
+
+            let state = cmds.read().account().email().email_confirmation_state(id)?;
+            if state.email_verified {
+                let result = SendEmailConfirmationResult {
+                    error_email_already_verified: true,
+                    ..Default::default()
+                };
+                return Ok((result, false));
+            }
+
+            let current_time = UnixTime::current_time();
+            if let Some(previous) = state.email_confirmation_sent_unix_time {
+                let next_allowed = previous.add_seconds(resend_wait.seconds);
+                if current_time.ut < next_allowed.ut {
+                    let result = SendEmailConfirmationResult {
+                        error_try_again_later_unix_time: Some(next_allowed),
+                        ..Default::default()
+                    };
+                    return Ok((result, false));
+                }
+            }
+
+            cmds.account()
+                .email()
+                .update_email_confirmation_sent_time(id, current_time)?;
+            cmds.account()
+                .email()
+                .modify_email_sending_states(id, |state| {
+                    state.email_confirmation_state_number = EmailSendingState::SendRequested;
+                })?;
+
+            Ok((SendEmailConfirmationResult::default(), true))
+        })?;
+
+        if send_needed {
+            self.email_sender()
+                .send(id, EmailMessages::EmailConfirmation);
+        }
+
+        Ok(result)
+    }
+
+    /// Mark email address as confirmed if the email address has not
+    /// changed after the email confirmation token was created.
+    /// Returns false if the email address has changed.
+    pub async fn confirm_email(
+        &self,
+        id: AccountIdInternal,
+        email_from_token: EmailAddress,
+    ) -> Result<bool, DataError> {
+        db_transaction!(self, move |mut cmds| {
+            let data = cmds.read().account().data().account_data(id)?;
+            if data.email.as_ref() != Some(&email_from_token) {
+                return Ok(false);
+            }
+            cmds.account().email().set_email_verified(id, true)?;
+            Ok(true)
+        })
+    }
+
+    /// Get key for signing email confirmation tokens. The key is
+    /// created if it does not exist.
+    pub async fn email_confirmation_token_key(
+        &self,
+    ) -> Result<EmailConfirmationTokenKey, DataError> {
+        db_transaction!(self, move |mut cmds| {
+            let key = cmds.read().account().email().email_confirmation_token_key()?;
+            if let Some(key) = key {
+                return Ok(EmailConfirmationTokenKey::new(key));
+            }
+            let key = EmailConfirmationTokenKey::generate_new();
+            cmds.account()
+                .email()
+                .upsert_email_confirmation_token_key(key.clone().into_bytes())?;
+            Ok(key)
+        })
+    }
 }
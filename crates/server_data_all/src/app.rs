@@ -1,10 +1,10 @@
-use std::ops::Deref;
+use std::{collections::HashSet, ops::Deref};
 
 use axum::extract::ws::WebSocket;
 use config::Config;
 use futures::{future::BoxFuture, FutureExt};
 use model::{
-    Account, AccountIdInternal, EmailMessages, PendingNotification,
+    Account, AccountId, AccountIdInternal, EmailMessages, PendingNotification,
     PendingNotificationWithData, SyncDataVersionFromClient,
 };
 use model_account::{EmailAddress, SignInWithInfo};
@@ -17,7 +17,9 @@ use server_data_account::write::GetWriteCommandsAccount;
 use server_data_chat::read::GetReadChatCommands;
 use simple_backend::manager_client::ManagerApiClient;
 
-use crate::{register::RegisterAccount, unlimited_likes::UnlimitedLikesUpdate};
+use crate::{
+    ban::BannedAccountContent, register::RegisterAccount, unlimited_likes::UnlimitedLikesUpdate,
+};
 
 pub struct DataAllUtilsImpl;
 
@@ -40,6 +42,23 @@ impl DataAllUtils for DataAllUtilsImpl {
         .boxed()
     }
 
+    fn add_banned_account_content_to_blocklist<'a>(
+        &self,
+        write_command_runner: &'a WriteCommandRunnerHandle,
+        id: AccountIdInternal,
+    ) -> BoxFuture<'a, server_common::result::Result<(), DataError>> {
+        async move {
+            write_command_runner
+                .write(move |cmds| async move {
+                    BannedAccountContent::new(cmds.deref())
+                        .add_to_perceptual_hash_blocklist(id)
+                        .await
+                })
+                .await
+        }
+        .boxed()
+    }
+
     fn register_impl<'a>(
         &self,
         write_command_runner: &'a WriteCommandRunnerHandle,
@@ -155,4 +174,18 @@ impl DataAllUtils for DataAllUtilsImpl {
         }
         .boxed()
     }
+
+    fn liked_matched_and_blocked_accounts<'a>(
+        &self,
+        read_handle: &'a RouterDatabaseReadHandle,
+        id: AccountIdInternal,
+    ) -> BoxFuture<'a, server_common::result::Result<HashSet<AccountId>, DataError>> {
+        async move {
+            read_handle
+                .chat()
+                .liked_matched_and_blocked_accounts(id)
+                .await
+        }
+        .boxed()
+    }
 }
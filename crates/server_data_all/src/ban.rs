@@ -0,0 +1,24 @@
+use model::AccountIdInternal;
+use server_data::{app::GetConfig, define_cmd_wrapper_write, result::Result, DataError};
+use server_data_media::write::GetWriteCommandsMedia;
+
+define_cmd_wrapper_write!(BannedAccountContent);
+
+impl BannedAccountContent<'_> {
+    /// Account component handles bans but media component owns the
+    /// perceptual hash blocklist, so this code is located in this crate.
+    pub async fn add_to_perceptual_hash_blocklist(
+        &self,
+        id: AccountIdInternal,
+    ) -> Result<(), DataError> {
+        if !self.config().components().media {
+            return Ok(());
+        }
+
+        self.handle()
+            .media_admin()
+            .content()
+            .add_banned_account_content_to_perceptual_hash_blocklist(id)
+            .await
+    }
+}
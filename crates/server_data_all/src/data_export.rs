@@ -0,0 +1,378 @@
+//! Account data export to ZIP archive
+
+use std::{
+    io::{Seek, Write},
+    path::PathBuf,
+};
+
+use config::Config;
+use model::{Account, AccountId, AccountIdInternal, ReportDetailed, UnixTime};
+use model_account::{
+    AccountData, AccountSetup, AppleAccountId, GoogleAccountId, UnreadNewsCountResult,
+};
+use model_chat::{AllMatchesPage, SentBlocksPage, SentLikesPage};
+use model_media::ContentInfoDetailed;
+use model_profile::{
+    GetMyProfileResult, GetProfileFilteringSettings, GetProfileModerationHistory, Location,
+};
+use serde::Serialize;
+use server_common::result::WrappedResultExt;
+use server_data::{
+    db_manager::RouterDatabaseReadHandle, file::utils::DataExportArchiveFile,
+    read::GetReadCommandsCommon, DataError,
+};
+use server_data_account::read::GetReadCommandsAccount;
+use server_data_chat::read::GetReadChatCommands;
+use server_data_media::read::GetReadMediaCommands;
+use server_data_profile::read::GetReadProfileCommands;
+use simple_backend_utils::ContextExt;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+const MANIFEST_FILE_NAME: &str = "data.json";
+const MEDIA_DIR_NAME: &str = "media";
+
+/// Data export is done in steps so that progress info can be
+/// sent to the client.
+#[derive(Debug, Clone, Copy)]
+pub enum DataExportStep {
+    Account,
+    Profile,
+    Media,
+    Chat,
+    Reports,
+    History,
+}
+
+impl DataExportStep {
+    pub const ALL: [Self; 6] = [
+        Self::Account,
+        Self::Profile,
+        Self::Media,
+        Self::Chat,
+        Self::Reports,
+        Self::History,
+    ];
+}
+
+/// JSON manifest which is saved to the export archive.
+#[derive(Debug, Serialize)]
+pub struct DataExportManifest {
+    pub account_id: AccountId,
+    pub export_time: UnixTime,
+    pub account: Option<AccountExportData>,
+    pub profile: Option<ProfileExportData>,
+    pub media: Option<MediaExportData>,
+    pub chat: Option<ChatExportData>,
+    /// Reports which the account has created.
+    pub reports: Vec<ReportDetailed>,
+    /// Account related data from history database.
+    pub history: Option<HistoryExportData>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountExportData {
+    pub account: Account,
+    pub data: AccountData,
+    pub setup: AccountSetup,
+    pub google_account_id: Option<GoogleAccountId>,
+    pub apple_account_id: Option<AppleAccountId>,
+    pub login_sessions: Vec<LoginSessionExportData>,
+    pub news: UnreadNewsCountResult,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoginSessionExportData {
+    pub created_unix_time: UnixTime,
+    pub last_used_unix_time: UnixTime,
+    pub push_notifications_enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProfileExportData {
+    pub profile: GetMyProfileResult,
+    pub location: Location,
+    pub filtering_settings: GetProfileFilteringSettings,
+    pub favorites: Vec<AccountId>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MediaExportData {
+    /// Content files are in the archive's media directory.
+    pub content: Vec<ContentInfoDetailed>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChatExportData {
+    pub sent_likes: SentLikesPage,
+    /// Accounts which have liked the account and the like is
+    /// not yet a match.
+    pub received_likes: Vec<AccountId>,
+    pub matches: AllMatchesPage,
+    pub sent_blocks: SentBlocksPage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HistoryExportData {
+    /// Submitted profile names and texts. Moderator account IDs
+    /// are not included.
+    pub profile_moderation: Option<GetProfileModerationHistory>,
+}
+
+struct MediaFile {
+    name_in_archive: String,
+    path: PathBuf,
+}
+
+pub struct DataExport {
+    manifest: DataExportManifest,
+    media_files: Vec<MediaFile>,
+}
+
+impl DataExport {
+    pub fn new(target: AccountIdInternal) -> Self {
+        Self {
+            manifest: DataExportManifest {
+                account_id: target.as_id(),
+                export_time: UnixTime::current_time(),
+                account: None,
+                profile: None,
+                media: None,
+                chat: None,
+                reports: vec![],
+                history: None,
+            },
+            media_files: vec![],
+        }
+    }
+
+    pub async fn run_step(
+        &mut self,
+        config: &Config,
+        read_handle: &RouterDatabaseReadHandle,
+        target: AccountIdInternal,
+        step: DataExportStep,
+    ) -> server_common::result::Result<(), DataError> {
+        let components = config.components();
+        match step {
+            DataExportStep::Account if components.account => {
+                self.manifest.account = Some(account_data(read_handle, target).await?);
+            }
+            DataExportStep::Profile if components.profile => {
+                self.manifest.profile = Some(profile_data(read_handle, target).await?);
+            }
+            DataExportStep::Media if components.media => {
+                let content = read_handle
+                    .media()
+                    .all_account_media_content(target)
+                    .await?;
+                let content: Vec<ContentInfoDetailed> =
+                    content.into_iter().map(|v| v.into()).collect();
+                for c in &content {
+                    let file = read_handle
+                        .media()
+                        .content_data(target.as_id(), c.cid)
+                        .await?;
+                    let file_exists = tokio::fs::try_exists(file.path())
+                        .await
+                        .change_context(DataError::Io)?;
+                    if file_exists {
+                        self.media_files.push(MediaFile {
+                            name_in_archive: format!(
+                                "{}/{}.{}",
+                                MEDIA_DIR_NAME,
+                                c.cid.content_file_name(),
+                                c.ctype.file_extension(),
+                            ),
+                            path: file.path().clone(),
+                        });
+                    }
+                }
+                self.manifest.media = Some(MediaExportData { content });
+            }
+            DataExportStep::Chat if components.chat => {
+                self.manifest.chat = Some(ChatExportData {
+                    sent_likes: read_handle.chat().all_sent_likes(target).await?,
+                    received_likes: read_handle.chat().all_received_likes(target).await?,
+                    matches: read_handle.chat().all_matches(target).await?,
+                    sent_blocks: read_handle.chat().all_sent_blocks(target).await?,
+                });
+            }
+            DataExportStep::Reports => {
+                self.manifest.reports = read_handle.common().reports_created_by(target).await?;
+            }
+            DataExportStep::History => {
+                let profile_moderation = if components.profile {
+                    Some(profile_moderation_history(read_handle, target).await?)
+                } else {
+                    None
+                };
+                self.manifest.history = Some(HistoryExportData { profile_moderation });
+            }
+            DataExportStep::Account
+            | DataExportStep::Profile
+            | DataExportStep::Media
+            | DataExportStep::Chat => (),
+        }
+        Ok(())
+    }
+
+    /// Write the export to ZIP archive. This is blocking.
+    pub fn write_archive(
+        &self,
+        archive: &DataExportArchiveFile,
+    ) -> server_common::result::Result<(), DataError> {
+        archive.create_parent_dirs_blocking()?;
+
+        let file = std::fs::File::create(archive.path()).change_context(DataError::Io)?;
+        let writer = self.write_zip(std::io::BufWriter::new(file))?;
+        writer.get_ref().sync_all().change_context(DataError::Io)?;
+
+        Ok(())
+    }
+
+    fn write_zip<W: Write + Seek>(&self, writer: W) -> server_common::result::Result<W, DataError> {
+        let mut zip = ZipWriter::new(writer);
+
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        zip.start_file(MANIFEST_FILE_NAME, options)
+            .change_context(DataError::Io)?;
+        serde_json::to_writer_pretty(&mut zip, &self.manifest).change_context(DataError::Io)?;
+
+        // Media content is already compressed
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        for media in &self.media_files {
+            zip.start_file(media.name_in_archive.as_str(), options)
+                .change_context(DataError::Io)?;
+            let mut content = std::fs::File::open(&media.path).change_context(DataError::Io)?;
+            std::io::copy(&mut content, &mut zip).change_context(DataError::Io)?;
+        }
+
+        let mut writer = zip.finish().change_context(DataError::Io)?;
+        writer.flush().change_context(DataError::Io)?;
+
+        Ok(writer)
+    }
+}
+
+async fn account_data(
+    read_handle: &RouterDatabaseReadHandle,
+    id: AccountIdInternal,
+) -> server_common::result::Result<AccountExportData, DataError> {
+    let sign_in_with = read_handle.account().account_sign_in_with_info(id).await?;
+    let login_sessions = read_handle
+        .common()
+        .login_sessions(id)
+        .await?
+        .into_iter()
+        .map(|v| LoginSessionExportData {
+            created_unix_time: v.created_unix_time,
+            last_used_unix_time: v.last_used_unix_time,
+            push_notifications_enabled: v.fcm_device_token.is_some(),
+        })
+        .collect();
+
+    Ok(AccountExportData {
+        account: read_handle.common().account(id).await?,
+        data: read_handle.account().account_data(id).await?,
+        setup: read_handle.account().account_setup(id).await?,
+        google_account_id: sign_in_with.google_account_id,
+        apple_account_id: sign_in_with.apple_account_id,
+        login_sessions,
+        news: read_handle.account().news().unread_news_count(id).await?,
+    })
+}
+
+async fn profile_data(
+    read_handle: &RouterDatabaseReadHandle,
+    id: AccountIdInternal,
+) -> server_common::result::Result<ProfileExportData, DataError> {
+    let favorites = read_handle
+        .profile()
+        .favorite_profiles(id)
+        .await?
+        .into_iter()
+        .map(|v| v.as_id())
+        .collect();
+
+    Ok(ProfileExportData {
+        profile: read_handle.profile().my_profile(id).await?,
+        location: read_handle.profile().profile_location(id).await?,
+        filtering_settings: read_handle.profile().profile_filtering_settings(id).await?,
+        favorites,
+    })
+}
+
+async fn profile_moderation_history(
+    read_handle: &RouterDatabaseReadHandle,
+    id: AccountIdInternal,
+) -> server_common::result::Result<GetProfileModerationHistory, DataError> {
+    let mut history = read_handle
+        .profile_admin_history()
+        .moderation()
+        .profile_moderation_history(id.as_id())
+        .await?;
+    for entry in &mut history.names {
+        entry.moderator_id = None;
+    }
+    for entry in &mut history.texts {
+        entry.moderator_id = None;
+    }
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read};
+
+    use model::AccountIdDb;
+    use zip::ZipArchive;
+
+    use super::*;
+
+    #[test]
+    fn export_archive_contains_manifest_and_media_files() {
+        let id = AccountIdInternal::new(AccountIdDb(1), AccountId::new_random());
+        let media_path =
+            std::env::temp_dir().join(format!("afrodite-data-export-test-{}", id.as_id()));
+        std::fs::write(&media_path, b"image").unwrap();
+
+        let mut export = DataExport::new(id);
+        export.media_files.push(MediaFile {
+            name_in_archive: format!("{}/test.jpg", MEDIA_DIR_NAME),
+            path: media_path.clone(),
+        });
+
+        let result = export.write_zip(Cursor::new(Vec::new()));
+        std::fs::remove_file(&media_path).unwrap();
+        let archive_data = result.unwrap().into_inner();
+
+        let mut archive = ZipArchive::new(Cursor::new(archive_data)).unwrap();
+        assert_eq!(archive.len(), 2);
+
+        let mut manifest = String::new();
+        archive
+            .by_name(MANIFEST_FILE_NAME)
+            .unwrap()
+            .read_to_string(&mut manifest)
+            .unwrap();
+        let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+        assert_eq!(
+            manifest["account_id"],
+            serde_json::to_value(id.as_id()).unwrap()
+        );
+        assert_eq!(
+            manifest["export_time"],
+            serde_json::to_value(export.manifest.export_time).unwrap()
+        );
+        assert!(manifest["account"].is_null());
+        assert_eq!(manifest["reports"], serde_json::json!([]));
+
+        let mut media = vec![];
+        archive
+            .by_name("media/test.jpg")
+            .unwrap()
+            .read_to_end(&mut media)
+            .unwrap();
+        assert_eq!(media, b"image");
+    }
+}
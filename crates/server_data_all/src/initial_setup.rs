@@ -51,6 +51,10 @@ pub async fn complete_initial_setup(
         };
 
     let is_bot_account = read_handle.account().is_bot_account(id).await?;
+    let email_confirmation_required = config
+        .limits_account()
+        .email_confirmation_required_for_initial_setup
+        && !is_bot_account;
 
     let new_account =
         write_handle
@@ -67,6 +71,18 @@ pub async fn complete_initial_setup(
                     return Err(DataError::NotAllowed.report());
                 }
 
+                if email_confirmation_required {
+                    let email_state = cmds
+                        .read()
+                        .account()
+                        .email()
+                        .email_confirmation_state(id)
+                        .await?;
+                    if !email_state.email_verified {
+                        return Err(DataError::NotAllowed.report());
+                    }
+                }
+
                 // TODO(microservice): API for setting initial profile age
                 cmds.profile()
                     .set_initial_profile_age_from_current_profile(id)
@@ -17,6 +17,8 @@ macro_rules! db_transaction {
 }
 
 pub mod app;
+pub mod ban;
+pub mod data_export;
 pub mod initial_setup;
 pub mod load;
 pub mod push_notification;
@@ -8,13 +8,13 @@ use database_chat::current::read::GetDbReadCommandsChat;
 use database_media::current::read::GetDbReadCommandsMedia;
 use database_profile::current::read::GetDbReadCommandsProfile;
 use error_stack::{Result, ResultExt};
-use model::AccountIdInternal;
+use model::{AccountIdInternal, PendingNotificationFlags, UnixTime};
 pub use server_common::data::cache::CacheError;
 use server_common::data::WithInfo;
 use server_data::{
     cache::{
         account::CachedAccountComponentData, chat::CachedChatComponentData, media::CachedMedia,
-        profile::CachedProfile, DatabaseCache,
+        profile::CachedProfile, DatabaseCache, LoginSessionEntry,
     },
     index::{LocationIndexIteratorHandle, LocationIndexManager, LocationIndexWriteHandle},
 };
@@ -74,14 +74,18 @@ impl DbDataToCacheLoader {
             .ok_or(CacheError::KeyNotExists.report())?;
 
         let db = DbReaderAll::new(DbReaderRaw::new(current_db));
-        let access_token = db
-            .db_read(move |mut cmds| cmds.common().token().access_token(account_id))
+        let login_sessions = db
+            .db_read(move |mut cmds| cmds.common().login_session().access_tokens(account_id))
             .await?;
-        if let Some(key) = access_token {
+        for session in login_sessions {
             let mut access_tokens = cache.access_tokens().write().await;
-            match access_tokens.entry(key) {
+            match access_tokens.entry(session.access_token) {
                 Entry::Vacant(e) => {
-                    e.insert(account_entry.clone());
+                    e.insert(LoginSessionEntry {
+                        account: account_entry.clone(),
+                        session_id: session.session_id,
+                        access_token_unix_time: session.access_token_unix_time,
+                    });
                 }
                 Entry::Occupied(_) => return Err(CacheError::AlreadyExists.report()),
             }
@@ -166,6 +170,7 @@ impl DbDataToCacheLoader {
             );
 
             let location_area = index_writer.coordinates_to_area(profile_location, profile_data.state.max_distance_km_filter);
+            profile_data.location.current_location = profile_location.into();
             profile_data.location.current_position = location_area.clone();
             profile_data.location.current_iterator =
                 index_iterator.new_iterator_state(
@@ -194,9 +199,18 @@ impl DbDataToCacheLoader {
             let chat_state = db
                 .db_read(move |mut cmds| cmds.chat().chat_state(account_id))
                 .await?;
-            // Try retry sending of not already sent notifications
-            if !chat_state.fcm_notification_sent {
-                entry.common.pending_notification_flags = chat_state.pending_notification.into();
+            // Try retry sending of not already sent notifications.
+            // Devices which already received the notification are skipped.
+            entry.common.pending_notification_flags = chat_state.pending_notification.into();
+            // Pending chat notification digest email state is not stored
+            // to database, so restart the digest delay for accounts which
+            // have not received all chat notifications.
+            if entry
+                .common
+                .pending_notification_flags
+                .intersects(PendingNotificationFlags::CHAT_EMAIL_NOTIFICATION)
+            {
+                entry.common.email_notification_pending_since = Some(UnixTime::current_time());
             }
 
             entry.chat = Some(CachedChatComponentData::default().into());
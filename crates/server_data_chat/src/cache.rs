@@ -1,8 +1,11 @@
+use std::sync::Arc;
+
 use error_stack::Result;
 use model::AccountId;
+use model_chat::{mutual_attribute_filters_match, AccountIdInternal, AccountInteractionInternal};
 use server_data::{
-    cache::{chat::CachedChatComponentData, CacheError},
-    db_manager::InternalWriting,
+    cache::{chat::CachedChatComponentData, CacheError, DatabaseCache},
+    db_manager::{handle_types::Config, InternalWriting},
 };
 
 pub trait CacheReadChat {
@@ -32,3 +35,113 @@ impl<I: InternalWriting> CacheWriteChat for I {
             .await
     }
 }
+
+pub trait CacheReadMutualAttributeFilters {
+    /// Get [MutualAttributeFiltersReader] which can be moved to
+    /// a database transaction.
+    fn mutual_attribute_filters_reader(&self) -> MutualAttributeFiltersReader;
+}
+
+impl<I: InternalWriting> CacheReadMutualAttributeFilters for I {
+    fn mutual_attribute_filters_reader(&self) -> MutualAttributeFiltersReader {
+        MutualAttributeFiltersReader {
+            cache: self.cache_arc(),
+            config: self.config_arc(),
+        }
+    }
+}
+
+pub struct MutualAttributeFiltersReader {
+    cache: Arc<DatabaseCache>,
+    config: Arc<Config>,
+}
+
+impl MutualAttributeFiltersReader {
+    /// Returns false if `profile_owner` has enabled mutual attribute
+    /// filters and `viewer` profile attributes do not match with
+    /// `profile_owner` attribute filters.
+    ///
+    /// Returns true if profile component or profile attributes are
+    /// not enabled.
+    ///
+    /// The cache is read using blocking locks, so call this only
+    /// from a database transaction.
+    pub fn filters_match_blocking(
+        &self,
+        viewer: AccountIdInternal,
+        profile_owner: AccountIdInternal,
+    ) -> Result<bool, CacheError> {
+        let Some(attribute_info) = self.config.profile_attributes() else {
+            return Ok(true);
+        };
+
+        let owner_filters = self.cache.read_cache_blocking(profile_owner, |e| {
+            e.profile
+                .as_deref()
+                .filter(|p| p.state.mutual_attribute_filters)
+                .map(|p| p.filters.clone())
+        })?;
+        let Some(owner_filters) = owner_filters else {
+            return Ok(true);
+        };
+
+        let viewer_attributes = self.cache.read_cache_blocking(viewer, |e| {
+            e.profile.as_deref().map(|p| p.attributes.clone())
+        })?;
+        let Some(viewer_attributes) = viewer_attributes else {
+            return Ok(true);
+        };
+
+        Ok(mutual_attribute_filters_match(
+            Some(owner_filters.as_slice()),
+            &viewer_attributes,
+            attribute_info,
+        ))
+    }
+}
+
+pub trait CacheWriteProfileIteratorHiddenProfiles {
+    /// Update profile iterator hidden profiles of both accounts
+    /// after the interaction between the accounts has changed.
+    ///
+    /// Hidden profiles are only updated if the current profile
+    /// iterator session hides liked, matched and blocked profiles.
+    async fn update_profile_iterator_hidden_profiles(
+        &self,
+        interaction: &AccountInteractionInternal,
+        id1: AccountIdInternal,
+        id2: AccountIdInternal,
+    ) -> Result<(), CacheError>;
+}
+
+impl<I: InternalWriting> CacheWriteProfileIteratorHiddenProfiles for I {
+    async fn update_profile_iterator_hidden_profiles(
+        &self,
+        interaction: &AccountInteractionInternal,
+        id1: AccountIdInternal,
+        id2: AccountIdInternal,
+    ) -> Result<(), CacheError> {
+        for (viewer, profile_owner) in [(id1, id2), (id2, id1)] {
+            let hidden = interaction.is_interacted_by(viewer, profile_owner);
+            self.cache()
+                .write_cache(viewer, |e| {
+                    let hidden_profiles = e
+                        .profile
+                        .as_deref_mut()
+                        .and_then(|p| p.profile_iterator_hidden_profiles.as_mut());
+                    if let Some(hidden_profiles) = hidden_profiles {
+                        let hidden_profiles = Arc::make_mut(hidden_profiles);
+                        if hidden {
+                            hidden_profiles.insert(profile_owner.as_id());
+                        } else {
+                            hidden_profiles.remove(&profile_owner.as_id());
+                        }
+                    }
+                    Ok(())
+                })
+                .await?;
+        }
+
+        Ok(())
+    }
+}
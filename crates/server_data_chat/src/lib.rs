@@ -18,5 +18,6 @@ macro_rules! db_transaction {
 }
 
 pub mod cache;
+pub mod message_signing;
 pub mod read;
 pub mod write;
@@ -0,0 +1,93 @@
+//! Server signatures for chat message metadata and public keys
+
+use model::{AccountId, MessageNumber, PublicKeyId, PublicKeyVersion, UnixTime};
+pub use server_data::message_signing_key::{MessageSigningKey, SignedData};
+use sha2::{Digest, Sha256};
+
+const SIGNED_DATA_TYPE_MESSAGE: u8 = 1;
+const SIGNED_DATA_TYPE_PUBLIC_KEY: u8 = 2;
+
+/// Message metadata which server signs. See [MessageSignature]
+/// for the signed data format.
+#[derive(Debug, Clone)]
+pub struct SignedMessageData {
+    pub sender: AccountId,
+    pub receiver: AccountId,
+    pub mn: MessageNumber,
+    pub unix_time: UnixTime,
+    pub message_sha256: [u8; 32],
+}
+
+impl SignedData for SignedMessageData {
+    fn to_signed_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![SIGNED_DATA_TYPE_MESSAGE];
+        bytes.extend_from_slice(self.sender.aid.as_bytes());
+        bytes.extend_from_slice(self.receiver.aid.as_bytes());
+        bytes.extend_from_slice(&self.mn.mn.to_le_bytes());
+        bytes.extend_from_slice(&self.unix_time.ut.to_le_bytes());
+        bytes.extend_from_slice(&self.message_sha256);
+        bytes
+    }
+}
+
+/// Public key info which server signs. See [model::PublicKeySignature]
+/// for the signed data format.
+#[derive(Debug, Clone)]
+pub struct SignedPublicKeyData {
+    pub owner: AccountId,
+    pub version: PublicKeyVersion,
+    pub id: PublicKeyId,
+    pub unix_time: UnixTime,
+    pub data_sha256: [u8; 32],
+}
+
+impl SignedData for SignedPublicKeyData {
+    fn to_signed_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![SIGNED_DATA_TYPE_PUBLIC_KEY];
+        bytes.extend_from_slice(self.owner.aid.as_bytes());
+        bytes.extend_from_slice(&self.version.version.to_le_bytes());
+        bytes.extend_from_slice(&self.id.id.to_le_bytes());
+        bytes.extend_from_slice(&self.unix_time.ut.to_le_bytes());
+        bytes.extend_from_slice(&self.data_sha256);
+        bytes
+    }
+}
+
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(message: &[u8]) -> SignedMessageData {
+        SignedMessageData {
+            sender: AccountId::for_debugging_only_zero(),
+            receiver: AccountId::new_random(),
+            mn: MessageNumber::new(1),
+            unix_time: UnixTime::current_time(),
+            message_sha256: sha256(message),
+        }
+    }
+
+    #[test]
+    fn valid_signature_is_accepted() {
+        let key = MessageSigningKey::generate_new();
+        let data = data(b"test");
+        let signature = key.sign(&data);
+        assert!(key.verify(&data, &signature));
+    }
+
+    #[test]
+    fn signature_for_modified_message_is_rejected() {
+        let key = MessageSigningKey::generate_new();
+        let data = data(b"test");
+        let signature = key.sign(&data);
+        let modified = SignedMessageData {
+            message_sha256: sha256(b"modified"),
+            ..data
+        };
+        assert!(!key.verify(&modified, &signature));
+    }
+}
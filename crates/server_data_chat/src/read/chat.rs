@@ -1,9 +1,12 @@
+use std::collections::HashSet;
+
 use database_chat::current::read::GetDbReadCommandsChat;
 use model_chat::{
     AccountId, AccountIdInternal, AccountInteractionInternal, AccountInteractionState,
-    AllMatchesPage, ChatStateRaw, GetPublicKey, MatchId, MessageNumber, PageItemCountForNewLikes,
-    PendingMessageAndMessageData, PublicKeyVersion, ReceivedBlocksPage, ReceivedLikeId,
-    SentBlocksPage, SentLikesPage, SentMessageId,
+    AllMatchesPage, ChatStateRaw, GetPublicKey, GetPublicKeyHistoryEntry, MatchId, MessageNumber,
+    PageItemCountForNewLikes, PendingMessageAndMessageData, PublicKeyHistory, PublicKeyId,
+    PublicKeyVersion, ReceivedBlocksPage, ReceivedLikeId, SentBlocksPage, SentLikesPage,
+    SentMessageId,
 };
 use server_data::{
     cache::{
@@ -16,18 +19,8 @@ use server_data::{
     DataError, IntoDataError,
 };
 
-use self::push_notifications::ReadCommandsChatPushNotifications;
-
-mod push_notifications;
-
 define_cmd_wrapper_read!(ReadCommandsChat);
 
-impl<'a> ReadCommandsChat<'a> {
-    pub fn push_notifications(self) -> ReadCommandsChatPushNotifications<'a> {
-        ReadCommandsChatPushNotifications::new(self.0)
-    }
-}
-
 impl ReadCommandsChat<'_> {
     pub async fn chat_state(&self, id: AccountIdInternal) -> Result<ChatStateRaw, DataError> {
         self.db_read(move |mut cmds| cmds.chat().chat_state(id))
@@ -49,6 +42,21 @@ impl ReadCommandsChat<'_> {
         .into_error()
     }
 
+    /// Accounts which have liked the account and the like is not
+    /// yet a match.
+    pub async fn all_received_likes(
+        &self,
+        id: AccountIdInternal,
+    ) -> Result<Vec<AccountId>, DataError> {
+        self.db_read(move |mut cmds| {
+            cmds.chat()
+                .interaction()
+                .all_receiver_account_interactions(id, AccountInteractionState::Like)
+        })
+        .await
+        .into_error()
+    }
+
     pub async fn received_likes_page(
         &self,
         id: AccountIdInternal,
@@ -100,6 +108,35 @@ impl ReadCommandsChat<'_> {
         .into_error()
     }
 
+    /// Accounts which are liked, matched or blocked by the account.
+    pub async fn liked_matched_and_blocked_accounts(
+        &self,
+        id: AccountIdInternal,
+    ) -> Result<HashSet<AccountId>, DataError> {
+        self.db_read(move |mut cmds| {
+            let mut accounts = HashSet::new();
+            for state in [
+                AccountInteractionState::Like,
+                AccountInteractionState::Match,
+            ] {
+                accounts.extend(
+                    cmds.chat()
+                        .interaction()
+                        .all_sender_account_interactions(id, state, false)?,
+                );
+            }
+            accounts.extend(
+                cmds.chat()
+                    .interaction()
+                    .all_receiver_account_interactions(id, AccountInteractionState::Match)?,
+            );
+            accounts.extend(cmds.chat().interaction().all_sent_blocks(id)?);
+            Ok(accounts)
+        })
+        .await
+        .into_error()
+    }
+
     pub async fn all_received_blocks(
         &self,
         id: AccountIdInternal,
@@ -159,6 +196,17 @@ impl ReadCommandsChat<'_> {
             .into_error()
     }
 
+    /// Count of messages which the receiver has not yet received.
+    pub async fn pending_messages_count(&self, id: AccountIdInternal) -> Result<i64, DataError> {
+        self.db_read(move |mut cmds| {
+            cmds.chat()
+                .message()
+                .receiver_acknowledgements_missing_count(id)
+        })
+        .await
+        .into_error()
+    }
+
     pub async fn all_pending_message_sender_account_ids(
         &self,
         id: AccountIdInternal,
@@ -252,4 +300,26 @@ impl ReadCommandsChat<'_> {
             .map(|key| GetPublicKey { key })
             .into_error()
     }
+
+    pub async fn public_key_history(
+        &self,
+        id: AccountIdInternal,
+    ) -> Result<PublicKeyHistory, DataError> {
+        self.db_read(move |mut cmds| cmds.chat().public_key_history(id))
+            .await
+            .map(|keys| PublicKeyHistory { keys })
+            .into_error()
+    }
+
+    pub async fn public_key_history_entry(
+        &self,
+        id: AccountIdInternal,
+        version: PublicKeyVersion,
+        key_id: PublicKeyId,
+    ) -> Result<GetPublicKeyHistoryEntry, DataError> {
+        self.db_read(move |mut cmds| cmds.chat().public_key_history_entry(id, version, key_id))
+            .await
+            .map(|key| GetPublicKeyHistoryEntry { key })
+            .into_error()
+    }
 }
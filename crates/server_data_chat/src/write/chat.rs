@@ -11,20 +11,30 @@ use database_chat::current::{
 use error_stack::ResultExt;
 use model_chat::{
     AccountIdInternal, ChatStateRaw, ClientId, ClientLocalId, MatchesIteratorSessionIdInternal,
-    MessageNumber, NewReceivedLikesCount, PendingMessageId, PendingMessageIdInternal,
-    PendingNotificationFlags, PublicKeyId, PublicKeyVersion,
-    ReceivedLikesIteratorSessionIdInternal, ReceivedLikesSyncVersion, SendMessageResult,
-    SentMessageId, SetPublicKey, SyncVersionUtils,
+    MessageNumber, MessageSignature, NewReceivedLikesCount, PendingMessageId,
+    PendingMessageIdInternal, PendingNotificationFlags, PublicKeyHistory, PublicKeyId,
+    PublicKeyVersion, ReceivedLikesIteratorSessionIdInternal, ReceivedLikesSyncVersion,
+    SendMessageResult, SentMessageId, SetPublicKey, SyncVersionUtils, UnixTime,
 };
 use server_data::{
-    app::EventManagerProvider, cache::chat::limit::ChatLimits, define_cmd_wrapper_write,
-    id::ToAccountIdInternal, read::DbRead, result::Result, write::DbTransaction, DataError,
-    DieselDatabaseError, IntoDataError,
+    app::{EventManagerProvider, GetConfig},
+    cache::chat::limit::ChatLimits,
+    define_cmd_wrapper_write,
+    id::ToAccountIdInternal,
+    read::DbRead,
+    result::Result,
+    write::DbTransaction,
+    DataError, DieselDatabaseError, IntoDataError,
 };
 use simple_backend_utils::ContextExt;
 
 use self::push_notifications::WriteCommandsChatPushNotifications;
-use crate::cache::CacheWriteChat;
+use crate::{
+    cache::{
+        CacheReadMutualAttributeFilters, CacheWriteChat, CacheWriteProfileIteratorHiddenProfiles,
+    },
+    message_signing::{sha256, SignedMessageData, SignedPublicKeyData},
+};
 
 define_cmd_wrapper_write!(WriteCommandsChat);
 
@@ -64,13 +74,19 @@ impl WriteCommandsChat<'_> {
 
     /// Like or match a profile.
     ///
+    /// If the like receiver has enabled mutual attribute filters and
+    /// the like sender's profile attributes do not match with those,
+    /// then new like is not created and `None` is returned. The check is
+    /// skipped if the like receiver has already liked the like sender.
+    ///
     /// Returns Ok only if the state change happened.
     pub async fn like_or_match_profile(
         &self,
         id_like_sender: AccountIdInternal,
         id_like_receiver: AccountIdInternal,
-    ) -> Result<SenderAndReceiverStateChanges, DataError> {
-        db_transaction!(self, move |mut cmds| {
+    ) -> Result<Option<SenderAndReceiverStateChanges>, DataError> {
+        let attribute_filters = self.mutual_attribute_filters_reader();
+        let result = db_transaction!(self, move |mut cmds| {
             let interaction = cmds
                 .chat()
                 .interaction()
@@ -94,6 +110,11 @@ impl WriteCommandsChat<'_> {
                 updated_interaction
             } else if interaction.is_match() {
                 return Err(DieselDatabaseError::AlreadyDone.report());
+            } else if !attribute_filters
+                .filters_match_blocking(id_like_sender, id_like_receiver)
+                .change_context(DieselDatabaseError::NotFound)?
+            {
+                return Ok(None);
             } else {
                 let next_id = cmds
                     .read()
@@ -139,8 +160,24 @@ impl WriteCommandsChat<'_> {
                 }
             })?;
 
-            Ok(SenderAndReceiverStateChanges { sender, receiver })
-        })
+            Ok(Some((
+                SenderAndReceiverStateChanges { sender, receiver },
+                updated,
+            )))
+        })?;
+
+        let Some((changes, interaction)) = result else {
+            return Ok(None);
+        };
+        self.update_profile_iterator_hidden_profiles(
+            &interaction,
+            id_like_sender,
+            id_like_receiver,
+        )
+        .await
+        .into_error()?;
+
+        Ok(Some(changes))
     }
 
     /// Delete a like.
@@ -151,7 +188,7 @@ impl WriteCommandsChat<'_> {
         id_sender: AccountIdInternal,
         id_receiver: AccountIdInternal,
     ) -> Result<SenderAndReceiverStateChanges, DataError> {
-        db_transaction!(self, move |mut cmds| {
+        let (changes, interaction) = db_transaction!(self, move |mut cmds| {
             let interaction = cmds
                 .chat()
                 .interaction()
@@ -174,7 +211,7 @@ impl WriteCommandsChat<'_> {
 
             cmds.chat()
                 .interaction()
-                .update_account_interaction(updated)?;
+                .update_account_interaction(updated.clone())?;
 
             let sender = cmds.chat().modify_chat_state(id_sender, |s| {
                 s.sent_likes_sync_version.increment_if_not_max_value_mut();
@@ -190,8 +227,14 @@ impl WriteCommandsChat<'_> {
                 }
             })?;
 
-            Ok(SenderAndReceiverStateChanges { sender, receiver })
-        })
+            Ok((SenderAndReceiverStateChanges { sender, receiver }, updated))
+        })?;
+
+        self.update_profile_iterator_hidden_profiles(&interaction, id_sender, id_receiver)
+            .await
+            .into_error()?;
+
+        Ok(changes)
     }
 
     /// Block a profile.
@@ -202,7 +245,7 @@ impl WriteCommandsChat<'_> {
         id_block_sender: AccountIdInternal,
         id_block_receiver: AccountIdInternal,
     ) -> Result<SenderAndReceiverStateChanges, DataError> {
-        db_transaction!(self, move |mut cmds| {
+        let (changes, interaction) = db_transaction!(self, move |mut cmds| {
             let interaction = cmds
                 .chat()
                 .interaction()
@@ -216,7 +259,7 @@ impl WriteCommandsChat<'_> {
                 .add_block(id_block_sender, id_block_receiver);
             cmds.chat()
                 .interaction()
-                .update_account_interaction(updated)?;
+                .update_account_interaction(updated.clone())?;
 
             let sender = cmds.chat().modify_chat_state(id_block_sender, |s| {
                 s.sent_blocks_sync_version.increment_if_not_max_value_mut();
@@ -227,8 +270,18 @@ impl WriteCommandsChat<'_> {
                     .increment_if_not_max_value_mut();
             })?;
 
-            Ok(SenderAndReceiverStateChanges { sender, receiver })
-        })
+            Ok((SenderAndReceiverStateChanges { sender, receiver }, updated))
+        })?;
+
+        self.update_profile_iterator_hidden_profiles(
+            &interaction,
+            id_block_sender,
+            id_block_receiver,
+        )
+        .await
+        .into_error()?;
+
+        Ok(changes)
     }
 
     /// Delete block.
@@ -239,7 +292,7 @@ impl WriteCommandsChat<'_> {
         id_block_sender: AccountIdInternal,
         id_block_receiver: AccountIdInternal,
     ) -> Result<SenderAndReceiverStateChanges, DataError> {
-        db_transaction!(self, move |mut cmds| {
+        let (changes, interaction) = db_transaction!(self, move |mut cmds| {
             let interaction = cmds
                 .chat()
                 .interaction()
@@ -253,7 +306,7 @@ impl WriteCommandsChat<'_> {
                 .delete_block(id_block_sender, id_block_receiver);
             cmds.chat()
                 .interaction()
-                .update_account_interaction(updated)?;
+                .update_account_interaction(updated.clone())?;
 
             let sender = cmds.chat().modify_chat_state(id_block_sender, |s| {
                 s.sent_blocks_sync_version.increment_if_not_max_value_mut();
@@ -264,8 +317,18 @@ impl WriteCommandsChat<'_> {
                     .increment_if_not_max_value_mut();
             })?;
 
-            Ok(SenderAndReceiverStateChanges { sender, receiver })
-        })
+            Ok((SenderAndReceiverStateChanges { sender, receiver }, updated))
+        })?;
+
+        self.update_profile_iterator_hidden_profiles(
+            &interaction,
+            id_block_sender,
+            id_block_receiver,
+        )
+        .await
+        .into_error()?;
+
+        Ok(changes)
     }
 
     // TODO(prod): Change SQLite settings that delete is overwriting.
@@ -372,9 +435,11 @@ impl WriteCommandsChat<'_> {
     /// Receiver public key check is for preventing client from
     /// sending messages encrypted with outdated public key.
     ///
-    /// Max receiver acknowledgements missing count is 50.
+    /// Max receiver and sender acknowledgements missing counts for one
+    /// conversation and max receiver acknowledgements missing count for
+    /// all conversations are configurable.
     ///
-    /// Max sender acknowledgements missing count is 50.
+    /// Server signs the message metadata.
     ///
     #[allow(clippy::too_many_arguments)]
     pub async fn insert_pending_message_if_match_and_not_blocked(
@@ -387,6 +452,14 @@ impl WriteCommandsChat<'_> {
         client_id_value: ClientId,
         client_local_id_value: ClientLocalId,
     ) -> Result<(SendMessageResult, Option<PushNotificationAllowed>), DataError> {
+        let limits = self.config().limits_chat();
+        if is_message_too_large(message.len(), limits.max_message_size_bytes) {
+            return Ok((SendMessageResult::message_too_large(), None));
+        }
+        let max_per_conversation: i64 = limits.max_pending_messages_per_conversation.into();
+        let max_per_account: i64 = limits.max_pending_messages_per_account.into();
+        let message_sha256 = sha256(&message);
+        let signing_key = self.handle().message_signing_key().clone();
         db_transaction!(self, move |mut cmds| {
             let current_key = cmds
                 .read()
@@ -396,30 +469,30 @@ impl WriteCommandsChat<'_> {
                 return Ok((SendMessageResult::public_key_outdated(), None));
             }
 
-            let receiver_acknowledgements_missing = cmds
-                .read()
-                .chat()
-                .message()
-                .receiver_acknowledgements_missing_count_for_one_conversation(sender, receiver)?;
-
-            if receiver_acknowledgements_missing >= 50 {
-                return Ok((
-                    SendMessageResult::too_many_receiver_acknowledgements_missing(),
-                    None,
-                ));
-            }
-
-            let sender_acknowledgements_missing = cmds
-                .read()
-                .chat()
-                .message()
-                .sender_acknowledgements_missing_count_for_one_conversation(sender, receiver)?;
+            let counts = PendingMessageCounts {
+                receiver_acknowledgements_missing: cmds
+                    .read()
+                    .chat()
+                    .message()
+                    .receiver_acknowledgements_missing_count_for_one_conversation(
+                        sender, receiver,
+                    )?,
+                sender_acknowledgements_missing: cmds
+                    .read()
+                    .chat()
+                    .message()
+                    .sender_acknowledgements_missing_count_for_one_conversation(
+                        sender, receiver,
+                    )?,
+                receiver_acknowledgements_missing_all_conversations: cmds
+                    .read()
+                    .chat()
+                    .message()
+                    .receiver_acknowledgements_missing_count(receiver)?,
+            };
 
-            if sender_acknowledgements_missing >= 50 {
-                return Ok((
-                    SendMessageResult::too_many_sender_acknowledgements_missing(),
-                    None,
-                ));
+            if let Some(error) = counts.limit_error(max_per_conversation, max_per_account) {
+                return Ok((error, None));
             }
 
             let message_values = cmds
@@ -443,26 +516,72 @@ impl WriteCommandsChat<'_> {
                 }
             };
 
-            let push_notification_allowd = if receiver_acknowledgements_missing == 0 {
+            let signature = signing_key.sign(&SignedMessageData {
+                sender: sender.as_id(),
+                receiver: receiver.as_id(),
+                mn: message_values.message_number,
+                unix_time: message_values.unix_time,
+                message_sha256,
+            });
+            cmds.chat().message().set_pending_message_server_signature(
+                sender,
+                receiver,
+                message_values.message_number,
+                signature.clone(),
+            )?;
+
+            let push_notification_allowd = if counts.receiver_acknowledgements_missing == 0 {
                 Some(PushNotificationAllowed)
             } else {
                 None
             };
 
             Ok((
-                SendMessageResult::successful(message_values),
+                SendMessageResult::successful(
+                    message_values,
+                    MessageSignature::from_bytes(&signature),
+                ),
                 push_notification_allowd,
             ))
         })
     }
 
+    /// Replace current public key and add the new key to public key
+    /// history. Server signs the public key history entry.
+    ///
+    /// Returns `None` if the public key upload limit is reached.
     pub async fn set_public_key(
         &self,
         id: AccountIdInternal,
         data: SetPublicKey,
-    ) -> Result<PublicKeyId, DataError> {
+    ) -> Result<Option<PublicKeyId>, DataError> {
+        let data_sha256 = sha256(data.data.as_str().as_bytes());
+        let signing_key = self.handle().message_signing_key().clone();
         db_transaction!(self, move |mut cmds| {
-            cmds.chat().set_public_key(id, data)
+            let upload_count = cmds.read().chat().public_key_history_count(id)?;
+            if upload_count >= PublicKeyHistory::MAX_UPLOAD_COUNT {
+                return Ok(None);
+            }
+
+            let key_id = cmds.chat().set_public_key(id, data.clone())?;
+
+            let upload_time = UnixTime::current_time();
+            let signature = signing_key.sign(&SignedPublicKeyData {
+                owner: id.as_id(),
+                version: data.version,
+                id: key_id,
+                unix_time: upload_time,
+                data_sha256,
+            });
+            cmds.chat().insert_public_key_history_entry(
+                id,
+                data,
+                key_id,
+                upload_time,
+                signature,
+            )?;
+
+            Ok(Some(key_id))
         })
     }
 
@@ -541,3 +660,90 @@ pub struct SenderAndReceiverStateChanges {
 /// This avoids sending multiple push notifications if client is running
 /// in background and can receive push notifications.
 pub struct PushNotificationAllowed;
+
+/// Pending message counts which are compared to configurable pending
+/// message limits when a new message is sent.
+struct PendingMessageCounts {
+    receiver_acknowledgements_missing: i64,
+    sender_acknowledgements_missing: i64,
+    receiver_acknowledgements_missing_all_conversations: i64,
+}
+
+impl PendingMessageCounts {
+    fn limit_error(
+        &self,
+        max_per_conversation: i64,
+        max_per_account: i64,
+    ) -> Option<SendMessageResult> {
+        if self.receiver_acknowledgements_missing >= max_per_conversation {
+            Some(SendMessageResult::too_many_receiver_acknowledgements_missing())
+        } else if self.sender_acknowledgements_missing >= max_per_conversation {
+            Some(SendMessageResult::too_many_sender_acknowledgements_missing())
+        } else if self.receiver_acknowledgements_missing_all_conversations >= max_per_account {
+            Some(SendMessageResult::receiver_inbox_full())
+        } else {
+            None
+        }
+    }
+}
+
+fn is_message_too_large(message_len: usize, max_message_size_bytes: u16) -> bool {
+    message_len > max_message_size_bytes.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_PER_CONVERSATION: i64 = 50;
+    const MAX_PER_ACCOUNT: i64 = 1000;
+
+    fn counts(
+        receiver_acknowledgements_missing: i64,
+        sender_acknowledgements_missing: i64,
+        receiver_acknowledgements_missing_all_conversations: i64,
+    ) -> Option<SendMessageResult> {
+        PendingMessageCounts {
+            receiver_acknowledgements_missing,
+            sender_acknowledgements_missing,
+            receiver_acknowledgements_missing_all_conversations,
+        }
+        .limit_error(MAX_PER_CONVERSATION, MAX_PER_ACCOUNT)
+    }
+
+    #[test]
+    fn counts_below_limits_are_allowed() {
+        assert_eq!(counts(49, 49, 999), None);
+    }
+
+    #[test]
+    fn per_conversation_receiver_limit() {
+        assert_eq!(
+            counts(50, 0, 50),
+            Some(SendMessageResult::too_many_receiver_acknowledgements_missing())
+        );
+    }
+
+    #[test]
+    fn per_conversation_sender_limit() {
+        assert_eq!(
+            counts(0, 50, 0),
+            Some(SendMessageResult::too_many_sender_acknowledgements_missing())
+        );
+    }
+
+    #[test]
+    fn per_account_limit() {
+        assert_eq!(
+            counts(0, 0, 1000),
+            Some(SendMessageResult::receiver_inbox_full())
+        );
+    }
+
+    #[test]
+    fn message_size_limit() {
+        assert!(!is_message_too_large(100, 100));
+        assert!(is_message_too_large(101, 100));
+        assert!(!is_message_too_large(u16::MAX.into(), u16::MAX));
+    }
+}
@@ -1,7 +1,7 @@
 use database_chat::current::write::GetDbWriteCommandsChat;
 use model::{
-    AccountIdInternal, FcmDeviceToken, PendingNotification, PendingNotificationToken,
-    PushNotificationStateInfo,
+    AccountIdInternal, FcmDeviceToken, LoginSessionId, PendingNotification,
+    PendingNotificationToken, PushNotificationStateInfo,
 };
 use server_data::{
     cache::CacheReadCommon, define_cmd_wrapper_write, result::Result, write::DbTransaction,
@@ -11,9 +11,11 @@ use server_data::{
 define_cmd_wrapper_write!(WriteCommandsChatPushNotifications);
 
 impl WriteCommandsChatPushNotifications<'_> {
-    pub async fn remove_fcm_device_token(&self, id: AccountIdInternal) -> Result<(), DataError> {
+    pub async fn remove_fcm_device_token(&self, session: LoginSessionId) -> Result<(), DataError> {
         db_transaction!(self, move |mut cmds| {
-            cmds.chat().push_notifications().remove_fcm_device_token(id)
+            cmds.chat()
+                .push_notifications()
+                .remove_fcm_device_token(session)
         })?;
 
         Ok(())
@@ -21,14 +23,14 @@ impl WriteCommandsChatPushNotifications<'_> {
 
     pub async fn set_device_token(
         &self,
-        id: AccountIdInternal,
+        session: LoginSessionId,
         token: FcmDeviceToken,
     ) -> Result<PendingNotificationToken, DataError> {
         let token_clone = token.clone();
         let token = db_transaction!(self, move |mut cmds| {
             cmds.chat()
                 .push_notifications()
-                .update_fcm_device_token_and_generate_new_notification_token(id, token_clone)
+                .update_fcm_device_token_and_generate_new_notification_token(session, token_clone)
         })?;
 
         Ok(token)
@@ -55,12 +57,12 @@ impl WriteCommandsChatPushNotifications<'_> {
 
     pub async fn enable_push_notification_sent_flag(
         &self,
-        id: AccountIdInternal,
+        session: LoginSessionId,
     ) -> Result<(), DataError> {
         db_transaction!(self, move |mut cmds| {
             cmds.chat()
                 .push_notifications()
-                .enable_push_notification_sent_flag(id)
+                .enable_push_notification_sent_flag(session)
         })
     }
 
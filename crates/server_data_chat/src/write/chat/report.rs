@@ -1,21 +1,32 @@
 use database_chat::current::write::GetDbWriteCommandsChat;
-use model::{AccountIdInternal, ReportTypeNumber, ReportTypeNumberInternal, UpdateReportResult};
+use model::{
+    AccountIdInternal, ReportTypeNumber, ReportTypeNumberInternal, SignedMessageInfo,
+    SignedMessageInternal, UpdateReportResult,
+};
 use server_data::{
     app::GetConfig, define_cmd_wrapper_write, read::DbRead, result::{Result, WrappedContextExt}, write::DbTransaction, DataError
 };
 use database::current::read::GetDbReadCommandsCommon;
 
-use crate::read::GetReadChatCommands;
+use crate::{
+    message_signing::{sha256, MessageSigningKey, SignedMessageData},
+    read::GetReadChatCommands,
+};
 
 define_cmd_wrapper_write!(WriteCommandsChatReport);
 
 impl WriteCommandsChatReport<'_> {
     /// The users must be a match.
+    ///
+    /// The server signature of the reported message must be valid.
+    /// The verified message bytes and metadata are saved to the report
+    /// with the unverified message text.
     pub async fn report_chat_message(
         &self,
         creator: AccountIdInternal,
         target: AccountIdInternal,
-        message: String,
+        message_text: String,
+        signed_message: SignedMessageInfo,
     ) -> Result<UpdateReportResult, DataError> {
         let interaction = self.handle().read().chat().account_interaction(creator, target).await?;
         let is_match = interaction.map(|v| v.is_match()).unwrap_or_default();
@@ -23,6 +34,14 @@ impl WriteCommandsChatReport<'_> {
             return Err(DataError::NotAllowed.report());
         }
 
+        let Ok(message) = SignedMessageInternal::try_from(signed_message) else {
+            return Ok(UpdateReportResult::invalid_message_signature());
+        };
+        let signing_key = self.handle().message_signing_key();
+        if !is_valid_signed_message(signing_key, creator, target, &message) {
+            return Ok(UpdateReportResult::invalid_message_signature());
+        }
+
         let components = self.config().components();
         let reports = self
             .db_read(move |mut cmds| cmds.common().report().get_all_detailed_reports(creator, target, ReportTypeNumberInternal::ChatMessage, components))
@@ -32,7 +51,9 @@ impl WriteCommandsChatReport<'_> {
             return Ok(UpdateReportResult::too_many_reports());
         }
 
-        let current_report = reports.iter().find(|v| v.report.content.chat_message.as_deref() == Some(&message));
+        let current_report = reports
+            .iter()
+            .find(|v| v.report.content.chat_message.as_ref().map(|v| v.signed_message.mn) == Some(message.mn));
         if current_report.is_some() {
             // Already reported
             return Ok(UpdateReportResult::success());
@@ -41,10 +62,112 @@ impl WriteCommandsChatReport<'_> {
         db_transaction!(self, move |mut cmds| {
             cmds.chat()
                 .report()
-                .insert_chat_message_report(creator, target, message)?;
+                .insert_chat_message_report(creator, target, message_text, message)?;
             Ok(())
         })?;
 
         Ok(UpdateReportResult::success())
     }
 }
+
+fn is_valid_signed_message(
+    signing_key: &MessageSigningKey,
+    creator: AccountIdInternal,
+    target: AccountIdInternal,
+    message: &SignedMessageInternal,
+) -> bool {
+    if message.sender != target.as_id() || message.receiver != creator.as_id() {
+        return false;
+    }
+
+    let data = SignedMessageData {
+        sender: message.sender,
+        receiver: message.receiver,
+        mn: message.mn,
+        unix_time: message.ut,
+        message_sha256: sha256(&message.message),
+    };
+
+    signing_key.verify(&data, &message.signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use model::{AccountId, AccountIdDb, MessageNumber, UnixTime};
+
+    use super::*;
+
+    fn accounts() -> (AccountIdInternal, AccountIdInternal) {
+        (
+            AccountIdInternal::new(AccountIdDb(1), AccountId::new_random()),
+            AccountIdInternal::new(AccountIdDb(2), AccountId::new_random()),
+        )
+    }
+
+    fn signed_message(
+        key: &MessageSigningKey,
+        sender: AccountIdInternal,
+        receiver: AccountIdInternal,
+    ) -> SignedMessageInternal {
+        let mut message = SignedMessageInternal {
+            sender: sender.as_id(),
+            receiver: receiver.as_id(),
+            mn: MessageNumber::new(1),
+            ut: UnixTime::current_time(),
+            message: b"test".to_vec(),
+            signature: vec![],
+        };
+        message.signature = key.sign(&SignedMessageData {
+            sender: message.sender,
+            receiver: message.receiver,
+            mn: message.mn,
+            unix_time: message.ut,
+            message_sha256: sha256(&message.message),
+        });
+        message
+    }
+
+    #[test]
+    fn message_received_by_report_creator_is_valid() {
+        let key = MessageSigningKey::generate_new();
+        let (creator, target) = accounts();
+        let message = signed_message(&key, target, creator);
+        assert!(is_valid_signed_message(&key, creator, target, &message));
+    }
+
+    #[test]
+    fn message_sent_by_report_creator_is_invalid() {
+        let key = MessageSigningKey::generate_new();
+        let (creator, target) = accounts();
+        let message = signed_message(&key, creator, target);
+        assert!(!is_valid_signed_message(&key, creator, target, &message));
+    }
+
+    #[test]
+    fn modified_message_is_invalid() {
+        let key = MessageSigningKey::generate_new();
+        let (creator, target) = accounts();
+        let message = SignedMessageInternal {
+            message: b"modified".to_vec(),
+            ..signed_message(&key, target, creator)
+        };
+        assert!(!is_valid_signed_message(&key, creator, target, &message));
+    }
+
+    #[test]
+    fn message_signed_with_other_key_is_invalid() {
+        let key = MessageSigningKey::generate_new();
+        let (creator, target) = accounts();
+        let message = signed_message(&MessageSigningKey::generate_new(), target, creator);
+        assert!(!is_valid_signed_message(&key, creator, target, &message));
+    }
+
+    #[test]
+    fn signed_message_info_round_trip() {
+        let key = MessageSigningKey::generate_new();
+        let (creator, target) = accounts();
+        let message = signed_message(&key, target, creator);
+        let info = message.to_signed_message_info();
+        assert_eq!(SignedMessageInternal::try_from(info).ok(), Some(message));
+    }
+}
@@ -1,7 +1,8 @@
 use database_media::current::read::GetDbReadCommandsMedia;
+use error_stack::ResultExt;
 use model::ContentIdInternal;
 use model_media::{
-    AccountId, AccountIdInternal, ContentId, CurrentAccountMediaInternal, MediaContentRaw, MediaContentSyncVersion,
+    AccountId, AccountIdInternal, ContentId, ContentSize, CurrentAccountMediaInternal, MediaContentRaw, MediaContentSyncVersion, SetProfileContent,
 };
 use server_common::{
     data::{DataError, IntoDataError},
@@ -12,6 +13,11 @@ use server_data::{
     file::{utils::ContentFile, FileRead},
     read::DbRead,
 };
+use simple_backend::image::ImageProcess;
+use simple_backend_config::args::SmallImageCrop;
+use tracing::error;
+
+use crate::write::media::{SmallImageVariant, SmallImageVariants};
 
 define_cmd_wrapper_read!(ReadCommandsMedia);
 
@@ -25,6 +31,86 @@ impl ReadCommandsMedia<'_> {
         Ok(c)
     }
 
+    /// Get image size variant of the content. If the variant does not
+    /// exist, the original content is returned.
+    pub async fn content_data_with_size(
+        &self,
+        account_id: AccountId,
+        content_id: ContentId,
+        size: ContentSize,
+    ) -> Result<ContentFile, DataError> {
+        let c = self.files().media_content(account_id, content_id);
+        let variant = c.size_variant(size);
+        if variant.exists().await? {
+            Ok(variant)
+        } else {
+            Ok(c)
+        }
+    }
+
+    /// Create small image variants to account's tmp dir if
+    /// the profile content update changes the first profile content
+    /// or grid crop info. The first profile content thumbnail uses
+    /// the grid crop info.
+    ///
+    /// Image processing is done here, so that profile content update
+    /// write command only moves the files.
+    ///
+    /// Errors are only logged as the thumbnail is not critical data.
+    pub async fn create_small_image_variants(
+        &self,
+        account_id: AccountIdInternal,
+        new: &SetProfileContent,
+    ) -> Result<SmallImageVariants, DataError> {
+        let previous = self.current_account_media(account_id).await?;
+        let mut variants = SmallImageVariants::new(&previous, new);
+
+        if let Some(previous_first) = variants.previous.first {
+            if Some(previous_first) != variants.current.first {
+                self.create_small_image_variant(account_id, previous_first, None, &mut variants)
+                    .await;
+            }
+        }
+
+        if let Some(current_first) = variants.current.first {
+            if variants.current != variants.previous {
+                let crop = variants.current.crop;
+                self.create_small_image_variant(account_id, current_first, crop, &mut variants)
+                    .await;
+            }
+        }
+
+        Ok(variants)
+    }
+
+    async fn create_small_image_variant(
+        &self,
+        account_id: AccountIdInternal,
+        content_id: ContentId,
+        crop: Option<SmallImageCrop>,
+        variants: &mut SmallImageVariants,
+    ) {
+        let content = self.files().media_content(account_id.as_id(), content_id);
+        let tmp_file = self
+            .files()
+            .small_image_variant_upload(account_id.as_id(), content_id);
+        let result = match tmp_file.create_parent_dirs().await {
+            Ok(()) => {
+                ImageProcess::start_small_image_process(content.path(), crop, tmp_file.as_path())
+                    .await
+                    .change_context(DataError::ImageProcess)
+            }
+            Err(e) => Err(e.change_context(DataError::File)),
+        };
+        match result {
+            Ok(()) => variants.variants.push(SmallImageVariant {
+                content_id,
+                tmp_file,
+            }),
+            Err(e) => error!("Small image variant creation failed: {:?}", e),
+        }
+    }
+
     pub async fn current_account_media(
         &self,
         account_id: AccountIdInternal,
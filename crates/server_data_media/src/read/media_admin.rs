@@ -1,6 +1,6 @@
 use database_media::current::read::GetDbReadCommandsMedia;
-use model::AccountIdInternal;
-use model_media::{GetProfileContentPendingModerationList, GetProfileContentPendingModerationParams};
+use model::{AccountIdInternal, ContentIdInternal};
+use model_media::{GetProfileContentPendingModerationList, GetProfileContentPendingModerationParams, SimilarContentList};
 use server_data::{define_cmd_wrapper_read, read::DbRead, DataError, IntoDataError};
 
 use server_common::result::Result;
@@ -21,4 +21,30 @@ impl ReadCommandsMediaAdmin<'_> {
         .await
         .into_error()
     }
+
+    /// Content of other accounts and blocklisted perceptual hashes
+    /// which are similar to the content.
+    pub async fn similar_content(
+        &self,
+        content_id: ContentIdInternal,
+    ) -> Result<SimilarContentList, DataError> {
+        self.db_read(move |mut cmds| {
+            let content = cmds.media().media_content().get_media_content_raw(content_id)?;
+            let Some(hash) = content.perceptual_hash else {
+                return Ok(SimilarContentList::default());
+            };
+            let content_list = cmds
+                .media()
+                .media_content()
+                .similar_content(content_id.content_owner(), hash)?;
+            let blocklist = cmds.media().media_content().similar_blocklisted_content(hash)?;
+            Ok(SimilarContentList {
+                perceptual_hash_match: content.perceptual_hash_match,
+                content: content_list,
+                blocklist,
+            })
+        })
+        .await
+        .into_error()
+    }
 }
@@ -5,11 +5,13 @@ use database_media::current::{read::GetDbReadCommandsMedia, write::GetDbWriteCom
 use error_stack::ResultExt;
 use model::{Account, AccountState, ContentIdInternal, ProfileVisibility};
 use model_media::{
-    AccountIdInternal, ContentId, ContentIdDb, ContentSlot, CurrentAccountMediaInternal, NewContentParams, ProfileContent, ProfileContentEditedTime, ProfileContentVersion, SetProfileContent
+    AccountIdInternal, ContentId, ContentIdDb, ContentSize, ContentSlot, CurrentAccountMediaInternal, NewContentParams, PerceptualHash, ProfileContent, ProfileContentEditedTime, ProfileContentVersion, SetProfileContent
 };
 use server_data::{
     app::GetConfig, cache::profile::UpdateLocationCacheState, define_cmd_wrapper_write, file::{utils::TmpContentFile, FileWrite}, read::DbRead, result::{Result, WrappedContextExt}, write::{DbTransaction, GetWriteCommandsCommon}, DataError, DieselDatabaseError
 };
+use simple_backend_config::args::SmallImageCrop;
+use tracing::error;
 
 use crate::cache::CacheWriteMedia;
 
@@ -30,6 +32,56 @@ pub struct DeleteContentResult {
     pub current_media_content_refresh_needed: bool,
 }
 
+/// First profile content and grid crop info which the small
+/// image variant of the first profile content uses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FirstProfileContent {
+    pub first: Option<ContentId>,
+    pub crop: Option<SmallImageCrop>,
+}
+
+impl FirstProfileContent {
+    fn from_media(media: &CurrentAccountMediaInternal) -> Self {
+        Self {
+            first: media
+                .iter_current_profile_content()
+                .next()
+                .map(|c| c.content_id()),
+            crop: grid_crop(media.grid_crop_size, media.grid_crop_x, media.grid_crop_y),
+        }
+    }
+
+    fn from_update(new: &SetProfileContent) -> Self {
+        Self {
+            first: new.c.first().copied(),
+            crop: grid_crop(new.grid_crop_size, new.grid_crop_x, new.grid_crop_y),
+        }
+    }
+}
+
+pub struct SmallImageVariant {
+    pub content_id: ContentId,
+    pub tmp_file: TmpContentFile,
+}
+
+/// Small image variants which are created to account's tmp dir
+/// before profile content update.
+pub struct SmallImageVariants {
+    pub previous: FirstProfileContent,
+    pub current: FirstProfileContent,
+    pub variants: Vec<SmallImageVariant>,
+}
+
+impl SmallImageVariants {
+    pub fn new(previous: &CurrentAccountMediaInternal, new: &SetProfileContent) -> Self {
+        Self {
+            previous: FirstProfileContent::from_media(previous),
+            current: FirstProfileContent::from_update(new),
+            variants: vec![],
+        }
+    }
+}
+
 define_cmd_wrapper_write!(WriteCommandsMedia);
 
 impl<'a> WriteCommandsMedia<'a> {
@@ -47,6 +99,7 @@ impl WriteCommandsMedia<'_> {
         slot: ContentSlot,
         new_content_params: NewContentParams,
         face_detected: bool,
+        perceptual_hash: Option<PerceptualHash>,
     ) -> Result<ContentId, DataError> {
         let account = self.db_read(move |mut cmds| cmds.common().account(id)).await?;
         let slot = if account.state() == AccountState::InitialSetup {
@@ -67,7 +120,7 @@ impl WriteCommandsMedia<'_> {
 
             if let Some(content) = current_content_in_slot {
                 let path = self.files().media_content(id.as_id(), content.into());
-                path.overwrite_and_remove_if_exists()
+                path.overwrite_and_remove_with_size_variants_if_exists()
                     .await
                     .change_context(DataError::File)?;
                 self.db_transaction(move |mut cmds| {
@@ -96,11 +149,12 @@ impl WriteCommandsMedia<'_> {
                     slot,
                     new_content_params,
                     face_detected,
+                    perceptual_hash,
                 )?;
 
             // Move content from tmp dir to content dir
             tmp_img
-                .move_to_blocking(&processed_content_path)
+                .move_with_size_variants_to_blocking(&processed_content_path)
                 .map_err(|e| e.change_context(DieselDatabaseError::File))?;
             // If moving fails, diesel rollbacks the transaction.
 
@@ -111,15 +165,22 @@ impl WriteCommandsMedia<'_> {
         Ok(content_id)
     }
 
+    /// Small image variants are moved from tmp dir to content dir
+    /// if the first profile content and grid crop info before and after
+    /// the update are the same as when the variants were created.
+    /// Otherwise the variants are ignored.
     pub async fn update_profile_content(
         &self,
         id: AccountIdInternal,
         new: SetProfileContent,
+        small_image_variants: SmallImageVariants,
     ) -> Result<InitialContentModerationResult, DataError> {
         let content_before_update = self.db_read(move |mut cmds| cmds.media().media_content().current_account_media(id)).await?;
 
         let version = ProfileContentVersion::new_random();
         let edit_time = ProfileContentEditedTime::current_time();
+        let first_content_before_update = FirstProfileContent::from_media(&content_before_update);
+        let files = self.files().clone();
 
         db_transaction!(self, move |mut cmds| {
             cmds.media().media_content().required_changes_for_public_profile_content_update(
@@ -131,7 +192,25 @@ impl WriteCommandsMedia<'_> {
                 id,
                 new,
             )?;
-            cmds.media().media_content().increment_media_content_sync_version(id)
+            cmds.media().media_content().increment_media_content_sync_version(id)?;
+
+            let content_after_update = cmds.read().media().media_content().current_account_media(id)?;
+            if small_image_variants.previous == first_content_before_update
+                && small_image_variants.current == FirstProfileContent::from_media(&content_after_update)
+            {
+                for v in small_image_variants.variants {
+                    let small = files
+                        .media_content(id.as_id(), v.content_id)
+                        .size_variant(ContentSize::Small);
+                    // Errors are only logged as the thumbnail is not
+                    // critical data.
+                    if let Err(e) = v.tmp_file.move_to_blocking(&small) {
+                        error!("Moving small image variant failed: {:?}", e);
+                    }
+                }
+            }
+
+            Ok(())
         })?;
 
         self.public_profile_content_cache_update(id, (version, edit_time)).await?;
@@ -199,7 +278,7 @@ impl WriteCommandsMedia<'_> {
             self.public_profile_content_cache_update(content_id.content_owner(), update).await?;
         }
 
-        self.files().media_content(content_id.content_owner().into(), content_id.content_id()).overwrite_and_remove_if_exists().await?;
+        self.files().media_content(content_id.content_owner().into(), content_id.content_id()).overwrite_and_remove_with_size_variants_if_exists().await?;
 
         Ok(r)
     }
@@ -331,3 +410,11 @@ impl WriteCommandsMedia<'_> {
         })
     }
 }
+
+fn grid_crop(size: Option<f64>, x: Option<f64>, y: Option<f64>) -> Option<SmallImageCrop> {
+    Some(SmallImageCrop {
+        size: size?,
+        x: x?,
+        y: y?,
+    })
+}
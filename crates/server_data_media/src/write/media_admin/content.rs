@@ -1,6 +1,6 @@
 use database_media::current::{read::GetDbReadCommandsMedia, write::GetDbWriteCommandsMedia};
 use model::{ContentIdInternal, AccountIdInternal, ProfileContentVersion};
-use model_media::{ProfileContentEditedTime, ProfileContentModerationRejectedReasonCategory, ProfileContentModerationRejectedReasonDetails};
+use model_media::{PerceptualHashBlocklistReason, ProfileContentEditedTime, ProfileContentModerationRejectedReasonCategory, ProfileContentModerationRejectedReasonDetails};
 use server_data::{define_cmd_wrapper_write, read::DbRead, result::WrappedContextExt, write::DbTransaction, DataError};
 
 use server_common::result::Result;
@@ -83,6 +83,20 @@ impl WriteCommandsProfileAdminContent<'_> {
             moderation_result: visibility_change,
         })
     }
+
+    pub async fn add_banned_account_content_to_perceptual_hash_blocklist(
+        &self,
+        content_owner: AccountIdInternal,
+    ) -> Result<(), DataError> {
+        db_transaction!(self, move |mut cmds| {
+            cmds.media_admin()
+                .media_content()
+                .add_account_content_to_perceptual_hash_blocklist(
+                    content_owner,
+                    PerceptualHashBlocklistReason::BannedAccount,
+                )
+        })
+    }
 }
 
 pub enum ContentModerationMode {
@@ -102,6 +102,8 @@ impl ReadCommandsProfile<'_> {
                 profile_created_filter: state.profile_created_time_filter,
                 profile_edited_filter: state.profile_edited_time_filter,
                 random_profile_order: state.random_profile_order,
+                hide_interacted_profiles: state.hide_interacted_profiles,
+                mutual_attribute_filters: state.mutual_attribute_filters,
             })
         })
         .await
@@ -3,6 +3,7 @@ use server_data::define_cmd_wrapper_read;
 mod profile_name_allowlist;
 mod profile_text;
 mod iterator;
+mod search;
 
 define_cmd_wrapper_read!(ReadCommandsProfileAdmin);
 
@@ -20,4 +21,8 @@ impl<'a> ReadCommandsProfileAdmin<'a> {
     pub fn iterator(self) -> iterator::ReadCommandsProfileIterator<'a> {
         iterator::ReadCommandsProfileIterator::new(self.0)
     }
+
+    pub fn search(self) -> search::ReadCommandsProfileSearch<'a> {
+        search::ReadCommandsProfileSearch::new(self.0)
+    }
 }
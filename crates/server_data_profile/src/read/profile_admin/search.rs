@@ -0,0 +1,18 @@
+use database_profile::current::read::GetDbReadCommandsProfile;
+use model_profile::{ProfileSearchResultPage, ProfileSearchSettings};
+use server_data::{
+    define_cmd_wrapper_read, read::DbRead, result::Result, DataError, IntoDataError,
+};
+
+define_cmd_wrapper_read!(ReadCommandsProfileSearch);
+
+impl ReadCommandsProfileSearch<'_> {
+    pub async fn search_profiles(
+        &self,
+        settings: ProfileSearchSettings,
+    ) -> Result<ProfileSearchResultPage, DataError> {
+        self.db_read(move |mut cmds| cmds.profile_admin().search().search_profiles(settings))
+            .await
+            .into_error()
+    }
+}
@@ -1,10 +1,15 @@
 use server_data::define_cmd_wrapper_read;
 
+mod moderation;
 mod statistics;
 
 define_cmd_wrapper_read!(ReadCommandsProfileAdminHistory);
 
 impl<'a> ReadCommandsProfileAdminHistory<'a> {
+    pub fn moderation(self) -> moderation::ReadCommandsProfileAdminHistoryModeration<'a> {
+        moderation::ReadCommandsProfileAdminHistoryModeration::new(self.0)
+    }
+
     pub fn statistics(self) -> statistics::ReadCommandsProfileAdminHistoryStatistics<'a> {
         statistics::ReadCommandsProfileAdminHistoryStatistics::new(self.0)
     }
@@ -0,0 +1,22 @@
+use database_profile::history::read::GetDbReadCommandsProfileHistory;
+use model_profile::{AccountId, GetProfileModerationHistory};
+use server_data::{define_cmd_wrapper_read, result::Result, DataError, IntoDataError};
+
+use crate::read::DbReadProfileHistory;
+
+define_cmd_wrapper_read!(ReadCommandsProfileAdminHistoryModeration);
+
+impl ReadCommandsProfileAdminHistoryModeration<'_> {
+    pub async fn profile_moderation_history(
+        &self,
+        owner_id: AccountId,
+    ) -> Result<GetProfileModerationHistory, DataError> {
+        self.db_read_history(move |mut cmds| {
+            cmds.profile_admin()
+                .moderation()
+                .profile_moderation_history(owner_id)
+        })
+        .await
+        .into_data_error(())
+    }
+}
@@ -1,5 +1,8 @@
 use database::current::{read::GetDbReadCommandsCommon, write::GetDbWriteCommandsCommon};
-use database_profile::current::{read::GetDbReadCommandsProfile, write::GetDbWriteCommandsProfile};
+use database_profile::{
+    current::{read::GetDbReadCommandsProfile, write::GetDbWriteCommandsProfile},
+    history::write::GetDbHistoryWriteCommandsProfile,
+};
 use model_profile::{
     AccountIdInternal, Location, ProfileEditedTime, ProfileFilteringSettingsUpdateValidated, ProfileSearchAgeRangeValidated, ProfileStateInternal, ProfileUpdateValidated, ProfileVersion, ValidatedSearchGroups
 };
@@ -10,7 +13,7 @@ use server_data::{
     index::LocationWrite,
     read::DbRead,
     result::Result,
-    write::DbTransaction,
+    write::{DbTransaction, DbTransactionHistory},
     DataError, IntoDataError,
 };
 use tracing::info;
@@ -44,7 +47,7 @@ impl WriteCommandsProfile<'_> {
 
         let new_location_area = self.location().coordinates_to_area(coordinates, max_distance);
         self.location()
-            .update_profile_location(id.as_id(), location.current_position.profile_location(), new_location_area.profile_location())
+            .update_profile_location(id.as_id(), location.current_position.profile_location(), new_location_area.profile_location(), coordinates.into())
             .await?;
 
         let new_iterator_state = self
@@ -54,6 +57,7 @@ impl WriteCommandsProfile<'_> {
                 random_profile_order,
             );
         self.write_cache_profile(id, |p| {
+            p.location.current_location = coordinates.into();
             p.location.current_position = new_location_area;
             p.location.current_iterator = new_iterator_state;
             Ok(())
@@ -84,7 +88,7 @@ impl WriteCommandsProfile<'_> {
         let config = self.config_arc().clone();
         let profile_version = ProfileVersion::new_random();
         let edit_time = ProfileEditedTime::current_time();
-        let profile_text_moderation_state_update = db_transaction!(self, move |mut cmds| {
+        let (profile_name_moderation_state_update, profile_text_moderation_state_update) = db_transaction!(self, move |mut cmds| {
             let (name_update_detected, text_update_detected) = {
                 let current_profile = cmds.read().profile().data().profile(id)?;
                 (
@@ -99,15 +103,19 @@ impl WriteCommandsProfile<'_> {
                 config.profile_attributes(),
             )?;
             cmds.profile().data().required_changes_for_profile_update(id, profile_version, edit_time)?;
-            if name_update_detected {
-                cmds.profile()
-                    .profile_name_allowlist()
-                    .reset_profile_name_moderation_state(
-                        id,
-                        &profile_data.name,
-                        config.profile_name_allowlist(),
-                    )?;
-            }
+            let profile_name_moderation_state_update = if name_update_detected {
+                Some(
+                    cmds.profile()
+                        .profile_name_allowlist()
+                        .reset_profile_name_moderation_state(
+                            id,
+                            &profile_data.name,
+                            config.profile_name_allowlist(),
+                        )?,
+                )
+            } else {
+                None
+            };
             let profile_text_moderation_state_update = if text_update_detected {
                 Some(
                     cmds.profile()
@@ -120,7 +128,7 @@ impl WriteCommandsProfile<'_> {
             } else {
                 None
             };
-            Ok(profile_text_moderation_state_update)
+            Ok((profile_name_moderation_state_update, profile_text_moderation_state_update))
         })?;
 
         self.write_cache_profile(id.as_id(), |p| {
@@ -138,6 +146,24 @@ impl WriteCommandsProfile<'_> {
 
         self.update_location_cache_profile(id).await?;
 
+        if profile_name_moderation_state_update.is_some()
+            || profile_text_moderation_state_update.is_some()
+        {
+            db_transaction_history!(self, move |mut cmds| {
+                if let Some(state) = profile_name_moderation_state_update {
+                    cmds.profile_admin_history()
+                        .moderation()
+                        .insert_profile_name(id.as_id(), data.name, state)?;
+                }
+                if let Some(state) = profile_text_moderation_state_update {
+                    cmds.profile_admin_history()
+                        .moderation()
+                        .insert_profile_text(id.as_id(), data.ptext, state)?;
+                }
+                Ok(())
+            })?;
+        }
+
         Ok(())
     }
 
@@ -194,6 +220,8 @@ impl WriteCommandsProfile<'_> {
             p.state.profile_created_time_filter = filters.profile_created_filter;
             p.state.profile_edited_time_filter = filters.profile_edited_filter;
             p.state.random_profile_order = filters.random_profile_order;
+            p.state.hide_interacted_profiles = filters.hide_interacted_profiles;
+            p.state.mutual_attribute_filters = filters.mutual_attribute_filters;
 
             p.location.current_position = self.location().coordinates_to_area(location, filters.max_distance_km_filter);
 
@@ -202,6 +230,10 @@ impl WriteCommandsProfile<'_> {
         .await
         .into_data_error(id)?;
 
+        // Location index contains attribute filters when mutual
+        // attribute filters are enabled.
+        self.update_location_cache_profile(id).await?;
+
         Ok(())
     }
 
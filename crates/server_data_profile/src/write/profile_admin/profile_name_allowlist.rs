@@ -1,11 +1,14 @@
-use database_profile::current::{read::GetDbReadCommandsProfile, write::GetDbWriteCommandsProfile};
+use database_profile::{
+    current::{read::GetDbReadCommandsProfile, write::GetDbWriteCommandsProfile},
+    history::write::GetDbHistoryWriteCommandsProfile,
+};
 use model_profile::{AccountIdInternal, ProfileEditedTime, ProfileVersion};
 use server_data::{
     cache::profile::UpdateLocationCacheState,
     define_cmd_wrapper_write,
     read::DbRead,
     result::{Result, WrappedContextExt},
-    write::DbTransaction,
+    write::{DbTransaction, DbTransactionHistory},
     DataError, IntoDataError,
 };
 
@@ -40,6 +43,7 @@ impl WriteCommandsProfileAdminProfileNameAllowlist<'_> {
         // Profile name accepted value is part of Profile, so update it's version
         let new_profile_version = ProfileVersion::new_random();
         let edit_time = ProfileEditedTime::current_time();
+        let history_name = name.clone();
         let new_state = db_transaction!(self, move |mut cmds| {
             cmds.profile()
                 .data()
@@ -62,6 +66,17 @@ impl WriteCommandsProfileAdminProfileNameAllowlist<'_> {
 
         self.update_location_cache_profile(name_owner_id).await?;
 
+        db_transaction_history!(self, move |mut cmds| {
+            cmds.profile_admin_history()
+                .moderation()
+                .update_profile_name_moderation(
+                    name_owner_id.as_id(),
+                    history_name,
+                    new_state,
+                    Some(moderator_id.as_id()),
+                )
+        })?;
+
         Ok(())
     }
 }
@@ -1,4 +1,7 @@
-use database_profile::current::{read::GetDbReadCommandsProfile, write::GetDbWriteCommandsProfile};
+use database_profile::{
+    current::{read::GetDbReadCommandsProfile, write::GetDbWriteCommandsProfile},
+    history::write::GetDbHistoryWriteCommandsProfile,
+};
 use model_profile::{
     AccountIdInternal, ProfileEditedTime, ProfileTextModerationRejectedReasonCategory, ProfileTextModerationRejectedReasonDetails, ProfileVersion
 };
@@ -7,7 +10,7 @@ use server_data::{
     define_cmd_wrapper_write,
     read::DbRead,
     result::{Result, WrappedContextExt},
-    write::DbTransaction,
+    write::{DbTransaction, DbTransactionHistory},
     DataError, IntoDataError,
 };
 
@@ -42,6 +45,19 @@ impl WriteCommandsProfileAdminProfileText<'_> {
         // Profile text accepted value is part of Profile, so update it's version
         let new_profile_version = ProfileVersion::new_random();
         let edit_time = ProfileEditedTime::current_time();
+        let history_update = match &mode {
+            ModerateProfileTextMode::MoveToHumanModeration => (None, None, None),
+            ModerateProfileTextMode::Moderate {
+                moderator_id,
+                rejected_category,
+                rejected_details,
+                ..
+            } => (
+                Some(moderator_id.as_id()),
+                *rejected_category,
+                rejected_details.clone(),
+            ),
+        };
         let new_state = db_transaction!(self, move |mut cmds| {
             cmds.profile()
                 .data()
@@ -78,6 +94,20 @@ impl WriteCommandsProfileAdminProfileText<'_> {
 
         self.update_location_cache_profile(data_owner_id).await?;
 
+        let (moderator_id, rejected_category, rejected_details) = history_update;
+        db_transaction_history!(self, move |mut cmds| {
+            cmds.profile_admin_history()
+                .moderation()
+                .update_profile_text_moderation(
+                    data_owner_id.as_id(),
+                    text,
+                    new_state,
+                    moderator_id,
+                    rejected_category,
+                    rejected_details,
+                )
+        })?;
+
         Ok(())
     }
 }
@@ -53,6 +53,14 @@ pub fn create_account_server_router(state: StateForRouterCreation) -> Router {
             api::account::PATH_SIGN_IN_WITH_LOGIN,
             post(api::account::post_sign_in_with_login),
         )
+        .route(
+            api::account::PATH_POST_REFRESH_LOGIN_SESSION_TOKENS,
+            post(api::account::post_refresh_login_session_tokens),
+        )
+        .route(
+            api::account::PATH_POST_CONFIRM_EMAIL,
+            post(api::account::post_confirm_email),
+        )
         .with_state(state.s.clone());
 
     let public = if state.s.config().demo_mode_config().is_some() {
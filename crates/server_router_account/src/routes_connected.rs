@@ -35,18 +35,22 @@ impl ConnectedApp {
         let private = Router::new()
             .merge(api::account::router_register(self.state.clone()))
             .merge(api::account::router_logout(self.state.clone()))
+            .merge(api::account::router_login_session(self.state.clone()))
+            .merge(api::account::router_email(self.state.clone()))
             .merge(api::account::router_ban(self.state.clone()))
             .merge(api::account::router_delete(self.state.clone()))
             .merge(api::account::router_settings(self.state.clone()))
             .merge(api::account::router_state(self.state.clone()))
             .merge(api::account::router_news(self.state.clone()))
             .merge(api::account::router_account_report(self.state.clone()))
+            .merge(api::account::router_data_export(self.state.clone()))
             .merge(api::account_admin::router_admin_ban(self.state.clone()))
             .merge(api::account_admin::router_admin_delete(self.state.clone()))
             .merge(api::account_admin::router_admin_news(self.state.clone()))
             .merge(api::account_admin::router_admin_search(self.state.clone()))
             .merge(api::account_admin::router_admin_permissions(self.state.clone()))
-            .merge(api::account_admin::router_admin_state(self.state.clone()));
+            .merge(api::account_admin::router_admin_state(self.state.clone()))
+            .merge(api::account_admin::router_admin_data_export(self.state.clone()));
 
         private.route_layer({
             middleware::from_fn_with_state(
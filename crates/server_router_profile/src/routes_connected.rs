@@ -35,9 +35,15 @@ impl ConnectedApp {
             .merge(api::profile_admin::router_admin_iterate_profiles(
                 self.state.clone(),
             ))
+            .merge(api::profile_admin::router_admin_search(
+                self.state.clone(),
+            ))
             .merge(api::profile_admin::router_admin_profile_data(
                 self.state.clone(),
             ))
+            .merge(api::profile_admin::router_admin_moderation_history(
+                self.state.clone(),
+            ))
             .merge(api::profile_admin::router_admin_profile_name_allowlist(
                 self.state.clone(),
             ))
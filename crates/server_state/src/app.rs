@@ -1,8 +1,11 @@
 use std::net::SocketAddr;
 
-use model::{AccessToken, AccountIdInternal, AccountState, Permissions};
+use model::{AccessToken, AccountIdInternal, AccountState, LoginSessionId, Permissions};
 pub use server_data::app::*;
-use server_data::{content_processing::ContentProcessingManagerData, DataError};
+use server_data::{
+    content_processing::ContentProcessingManagerData, data_export::DataExportManagerData,
+    DataError,
+};
 
 use crate::internal_api::InternalApiClient;
 
@@ -16,21 +19,27 @@ pub trait GetAccessTokens {
     fn access_token_exists(
         &self,
         token: &AccessToken,
-    ) -> impl std::future::Future<Output = Option<AccountIdInternal>> + Send;
+    ) -> impl std::future::Future<Output = Option<(AccountIdInternal, LoginSessionId)>> + Send;
 
-    /// Check that token and current connection IP and port matches
+    /// Check that token and login session's current connection IP matches
     /// with WebSocket connection.
     fn access_token_and_connection_exists(
         &self,
         token: &AccessToken,
         connection: SocketAddr,
-    ) -> impl std::future::Future<Output = Option<(AccountIdInternal, Permissions, AccountState)>> + Send;
+    ) -> impl std::future::Future<
+        Output = Option<(AccountIdInternal, LoginSessionId, Permissions, AccountState)>,
+    > + Send;
 }
 
 pub trait ContentProcessingProvider {
     fn content_processing(&self) -> &ContentProcessingManagerData;
 }
 
+pub trait DataExportProvider {
+    fn data_export(&self) -> &DataExportManagerData;
+}
+
 pub trait IsMatch: ReadData {
     /// Account interaction is in match state and there is no one or two way block.
     fn is_match(
@@ -3,12 +3,12 @@
 #![deny(unused_features)]
 #![warn(unused_crate_dependencies)]
 
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use axum::extract::ws::WebSocket;
 use config::Config;
 use model::{
-    Account, AccountIdInternal, PendingNotification, PendingNotificationWithData,
+    Account, AccountId, AccountIdInternal, PendingNotification, PendingNotificationWithData,
     SyncDataVersionFromClient,
 };
 use model_chat::SignInWithInfo;
@@ -16,7 +16,7 @@ use model_server_data::EmailAddress;
 use server_common::{push_notifications::PushNotificationSender, websocket::WebSocketError};
 use server_data::{
     app::{DataAllUtils, GetConfig}, content_processing::ContentProcessingManagerData,
-    db_manager::RouterDatabaseReadHandle, statistics::ProfileStatisticsCache,
+    data_export::DataExportManagerData, db_manager::RouterDatabaseReadHandle, statistics::ProfileStatisticsCache,
     write_commands::WriteCommandRunnerHandle,
 };
 use simple_backend::app::SimpleBackendAppState;
@@ -47,6 +47,7 @@ struct AppStateInternal {
     internal_api: Arc<InternalApiClient>,
     config: Arc<Config>,
     content_processing: Arc<ContentProcessingManagerData>,
+    data_export: Arc<DataExportManagerData>,
     demo_mode: DemoModeManager,
     push_notification_sender: PushNotificationSender,
     simple_backend_state: SimpleBackendAppState,
@@ -61,6 +62,7 @@ impl AppState {
         write_queue: WriteCommandRunnerHandle,
         config: Arc<Config>,
         content_processing: Arc<ContentProcessingManagerData>,
+        data_export: Arc<DataExportManagerData>,
         demo_mode: DemoModeManager,
         push_notification_sender: PushNotificationSender,
         simple_backend_state: SimpleBackendAppState,
@@ -73,6 +75,7 @@ impl AppState {
             write_queue: Arc::new(write_queue),
             internal_api: InternalApiClient::new(config.external_service_urls().clone()).into(),
             content_processing,
+            data_export,
             demo_mode,
             push_notification_sender,
             simple_backend_state,
@@ -126,6 +129,16 @@ impl DataAllAccess<'_> {
         cmd.await
     }
 
+    pub async fn add_banned_account_content_to_blocklist(
+        &self,
+        id: AccountIdInternal,
+    ) -> server_common::result::Result<(), DataError> {
+        let cmd = self
+            .utils()
+            .add_banned_account_content_to_blocklist(self.write(), id);
+        cmd.await
+    }
+
     pub async fn register_impl(
         &self,
         sign_in_with: SignInWithInfo,
@@ -184,6 +197,16 @@ impl DataAllAccess<'_> {
         let cmd = self.utils().is_match(self.read(), account0, account1);
         cmd.await
     }
+
+    pub async fn liked_matched_and_blocked_accounts(
+        &self,
+        id: AccountIdInternal,
+    ) -> server_common::result::Result<HashSet<AccountId>, DataError> {
+        let cmd = self
+            .utils()
+            .liked_matched_and_blocked_accounts(self.read(), id);
+        cmd.await
+    }
 }
 
 /// Macro for writing data with different code style.
@@ -5,10 +5,11 @@ use error_stack::ResultExt;
 use futures::Future;
 use manager_model::ServerEventType;
 use model::{
-    AccessToken, AccountId, AccountIdInternal, AccountState, BackendConfig, BackendVersion, EventToClientInternal, Permissions, ScheduledMaintenanceStatus
+    AccessToken, AccountId, AccountIdInternal, AccountState, BackendConfig, BackendVersion, EventToClientInternal, LoginSessionId, Permissions, ScheduledMaintenanceStatus
 };
 use server_data::{
     content_processing::ContentProcessingManagerData,
+    data_export::DataExportManagerData,
     db_manager::RouterDatabaseReadHandle,
     event::EventManagerWithCacheReference,
     write_commands::WriteCmds,
@@ -24,6 +25,7 @@ use simple_backend::{
     }, file_package::FilePackageManager, manager_client::{ManagerApiClient, ManagerEventHandler}, map::TileMapManager, perf::PerfMetricsManagerData, sign_in_with::SignInWithManager
 };
 use simple_backend_config::SimpleBackendConfig;
+use tracing::error;
 
 use super::S;
 pub use crate::app::*;
@@ -191,7 +193,10 @@ impl GetInternalApi for S {
 }
 
 impl GetAccessTokens for S {
-    async fn access_token_exists(&self, token: &AccessToken) -> Option<AccountIdInternal> {
+    async fn access_token_exists(
+        &self,
+        token: &AccessToken,
+    ) -> Option<(AccountIdInternal, LoginSessionId)> {
         self.state.database
             .access_token_manager()
             .access_token_exists(token)
@@ -202,7 +207,7 @@ impl GetAccessTokens for S {
         &self,
         token: &AccessToken,
         connection: SocketAddr,
-    ) -> Option<(AccountIdInternal, Permissions, AccountState)> {
+    ) -> Option<(AccountIdInternal, LoginSessionId, Permissions, AccountState)> {
         self.state.database
             .access_token_manager()
             .access_token_and_connection_exists(token, connection)
@@ -216,6 +221,12 @@ impl ContentProcessingProvider for S {
     }
 }
 
+impl DataExportProvider for S {
+    fn data_export(&self) -> &DataExportManagerData {
+        &self.state.data_export
+    }
+}
+
 // Simple backend
 
 impl SignInWith for S {
@@ -265,6 +276,16 @@ impl ManagerEventHandler for S {
                     EventToClientInternal::ScheduledMaintenanceStatus(status),
                 ).await
             }
+            ServerEventType::BackupVerificationStatus(status) => {
+                if status.corrupted_files > 0 {
+                    error!(
+                        "Backup verification found corrupted files, time: {}, checked files: {}, corrupted files: {}",
+                        status.time.ut,
+                        status.checked_files,
+                        status.corrupted_files,
+                    );
+                }
+            }
         }
     }
 }
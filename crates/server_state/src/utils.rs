@@ -2,7 +2,9 @@ use axum::response::{IntoResponse, Response};
 use config::file::ConfigFileError;
 use manager_api::ClientError;
 use server_common::{data::cache::CacheError, internal_api::InternalApiError};
-use server_data::{content_processing::ContentProcessingError, event::EventError};
+use server_data::{
+    content_processing::ContentProcessingError, data_export::DataExportError, event::EventError,
+};
 use simple_backend::sign_in_with::{apple::SignInWithAppleError, google::SignInWithGoogleError};
 
 use crate::DataError;
@@ -63,6 +65,8 @@ enum RequestError {
     EventError,
     #[error("Content processing error")]
     ContentProcessingError,
+    #[error("Data export error")]
+    DataExportError,
 }
 
 /// Convert error to status code. This is workaround for track_caller seems
@@ -129,3 +133,4 @@ impl_error_to_status_code!(ClientError, RequestError::ManagerClientError);
 impl_error_to_status_code!(ConfigFileError, RequestError::ConfigFileError);
 impl_error_to_status_code!(EventError, RequestError::EventError);
 impl_error_to_status_code!(ContentProcessingError, RequestError::ContentProcessingError);
+impl_error_to_status_code!(DataExportError, RequestError::DataExportError);
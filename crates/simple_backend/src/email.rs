@@ -77,6 +77,11 @@ pub struct EmailSender<R, M> {
 }
 
 impl<R, M> EmailSender<R, M> {
+    /// Count of emails waiting in the internal channel.
+    pub fn queue_length(&self) -> usize {
+        self.sender.max_capacity() - self.sender.capacity()
+    }
+
     pub fn send(&self, receiver: R, message: M) {
         let email_send_cmd = SendEmail { receiver, message };
         match self.sender.try_send(email_send_cmd) {
@@ -151,7 +156,15 @@ impl<
         receiver: EmailReceiver<R, M>,
     ) -> EmailManagerQuitHandle {
         let email_sender = if let Some(config) = simple_backend_config.email_sending() {
-            let email_sender = if config.use_starttls_instead_of_smtps {
+            let debug_smtp_port = config
+                .debug_smtp_port
+                .filter(|_| simple_backend_config.debug_mode());
+            let email_sender = if let Some(port) = debug_smtp_port {
+                Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                    &config.smtp_server_address,
+                )
+                .port(port))
+            } else if config.use_starttls_instead_of_smtps {
                 AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_server_address)
             } else {
                 AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_server_address)
@@ -282,17 +295,12 @@ impl<
             return Ok(());
         };
 
-        let address = Address::from_str(&info.email_address)
-            .change_context(EmailError::AccountEmailAddressParsingFailed)?;
-
-        let message = Message::builder()
-            .from(email_sender.config.email_from_header.0.clone())
-            .to(Mailbox::new(None, address))
-            .subject(info.subject)
-            .body(info.body)
-            .change_context(EmailError::MessageBuildingFailed)?;
+        let message = build_message(&email_sender.config, info)?;
 
-        match sending_logic.send_email(message, email_sender).await {
+        match sending_logic
+            .send_email(message, &email_sender.sender, &email_sender.config)
+            .await
+        {
             Ok(()) => {
                 self.state
                     .mark_as_sent(send_cmd.receiver, send_cmd.message)
@@ -303,6 +311,18 @@ impl<
     }
 }
 
+fn build_message(config: &EmailSendingConfig, info: EmailData) -> Result<Message, EmailError> {
+    let address = Address::from_str(&info.email_address)
+        .change_context(EmailError::AccountEmailAddressParsingFailed)?;
+
+    Message::builder()
+        .from(config.email_from_header.0.clone())
+        .to(Mailbox::new(None, address))
+        .subject(info.subject)
+        .body(info.body)
+        .change_context(EmailError::MessageBuildingFailed)
+}
+
 pub struct EmailSendingLogic {
     send_count_per_minute: SendCounter,
     send_count_per_day: SendCounter,
@@ -319,22 +339,22 @@ impl EmailSendingLogic {
     async fn send_email(
         &mut self,
         message: Message,
-        sender: &EmailSenderData,
+        sender: &AsyncSmtpTransport<Tokio1Executor>,
+        config: &EmailSendingConfig,
     ) -> Result<(), EmailError> {
         self.send_count_per_minute
-            .wait_until_allowed(sender.config.send_limit_per_minute)
+            .wait_until_allowed(config.send_limit_per_minute)
             .await;
         self.send_count_per_day
-            .wait_until_allowed(sender.config.send_limit_per_day)
+            .wait_until_allowed(config.send_limit_per_day)
             .await;
 
         self.send_count_per_minute
-            .increment(sender.config.send_limit_per_minute);
+            .increment(config.send_limit_per_minute);
         self.send_count_per_day
-            .increment(sender.config.send_limit_per_day);
+            .increment(config.send_limit_per_day);
 
         let response = sender
-            .sender
             .send(message)
             .await
             .change_context(EmailError::SendingFailed)?;
@@ -400,3 +420,91 @@ impl SendCounter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use simple_backend_config::file::EmailFromHeader;
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    /// Minimal local SMTP server which accepts one email and
+    /// returns the received email data.
+    async fn smtp_stand_in(listener: TcpListener) -> String {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (read, mut write) = stream.into_split();
+        let mut lines = BufReader::new(read).lines();
+        write.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+
+        let mut data = String::new();
+        let mut data_started = false;
+        while let Some(line) = lines.next_line().await.unwrap() {
+            if data_started {
+                if line == "." {
+                    write.write_all(b"250 OK\r\n").await.unwrap();
+                    break;
+                }
+                data.push_str(&line);
+                data.push('\n');
+                continue;
+            }
+
+            let command = line.to_ascii_uppercase();
+            let response: &[u8] = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                b"250 localhost\r\n"
+            } else if command.starts_with("DATA") {
+                data_started = true;
+                b"354 Start mail input\r\n"
+            } else {
+                b"250 OK\r\n"
+            };
+            write.write_all(response).await.unwrap();
+        }
+
+        data
+    }
+
+    #[tokio::test]
+    async fn email_is_sent_to_smtp_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(smtp_stand_in(listener));
+
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous("127.0.0.1")
+            .port(port)
+            .build();
+        let config = EmailSendingConfig {
+            smtp_server_address: "127.0.0.1".to_string(),
+            use_starttls_instead_of_smtps: false,
+            username: String::new(),
+            password: String::new(),
+            email_from_header: EmailFromHeader::try_from("Test <no-reply@example.com>".to_string())
+                .unwrap(),
+            send_limit_per_minute: NonZeroU32::new(10),
+            send_limit_per_day: None,
+            debug_smtp_port: None,
+        };
+        let message = build_message(
+            &config,
+            EmailData {
+                email_address: "test@example.com".to_string(),
+                subject: "Confirm email address".to_string(),
+                body: "Confirmation token: abc".to_string(),
+            },
+        )
+        .unwrap();
+
+        let mut logic = EmailSendingLogic::new();
+        logic.send_email(message, &transport, &config).await.unwrap();
+        assert_eq!(logic.send_count_per_minute.count, 1);
+        assert_eq!(logic.send_count_per_day.count, 0);
+
+        let data = server.await.unwrap();
+        assert!(data.contains("To: test@example.com"));
+        assert!(data.contains("Subject: Confirm email address"));
+        assert!(data.contains("Confirmation token: abc"));
+    }
+}
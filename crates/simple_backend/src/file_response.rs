@@ -0,0 +1,259 @@
+//! File responses with HTTP caching and range request support.
+
+use std::{io::SeekFrom, ops::Bound, path::Path, str::FromStr, time::Duration};
+
+use axum::{
+    body::Body,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use error_stack::{Result, ResultExt};
+use headers::{
+    AcceptRanges, CacheControl, ContentLength, ContentRange, ContentType, ETag, HeaderMapExt,
+    IfNoneMatch, IfRange, Range,
+};
+use simple_backend_utils::ContextExt;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+/// Max age for [FileCache::Immutable] files.
+const IMMUTABLE_FILE_MAX_AGE: Duration = Duration::from_secs(60 * 60 * 24 * 365);
+
+#[derive(thiserror::Error, Debug)]
+pub enum FileResponseError {
+    #[error("File open failed")]
+    IoFileOpen,
+    #[error("Getting file metadata failed")]
+    IoFileMetadata,
+    #[error("File seek failed")]
+    IoFileSeek,
+    #[error("Invalid ETag")]
+    InvalidETag,
+    #[error("Invalid content range")]
+    InvalidContentRange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileCache {
+    /// File does not change after it is created.
+    Immutable,
+    /// File might change, so client must revalidate the cached file
+    /// using the ETag.
+    Revalidate,
+}
+
+impl FileCache {
+    fn cache_control(&self) -> CacheControl {
+        match self {
+            Self::Immutable => CacheControl::new()
+                .with_private()
+                .with_immutable()
+                .with_max_age(IMMUTABLE_FILE_MAX_AGE),
+            Self::Revalidate => CacheControl::new().with_private().with_no_cache(),
+        }
+    }
+}
+
+/// File which can be sent using
+/// - strong ETag (`If-None-Match` request header support),
+/// - `Cache-Control` header and
+/// - single range `Range` requests (`If-Range` request header support).
+pub struct FileResponse {
+    file: tokio::fs::File,
+    len: u64,
+    content_type: ContentType,
+    etag: ETag,
+    cache: FileCache,
+}
+
+impl FileResponse {
+    /// Open file for sending. The ETag is created from `id` and
+    /// file modification time, so `id` must not contain
+    /// double quote characters.
+    pub async fn open(
+        path: &Path,
+        id: &str,
+        content_type: ContentType,
+        cache: FileCache,
+    ) -> Result<Self, FileResponseError> {
+        let file = tokio::fs::File::open(path)
+            .await
+            .change_context(FileResponseError::IoFileOpen)?;
+        let metadata = file
+            .metadata()
+            .await
+            .change_context(FileResponseError::IoFileMetadata)?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|time| time.as_nanos())
+            .unwrap_or_default();
+        let etag = ETag::from_str(&format!("\"{}-{:x}\"", id, modified))
+            .map_err(|_| FileResponseError::InvalidETag.report())?;
+
+        Ok(Self {
+            file,
+            len: metadata.len(),
+            content_type,
+            etag,
+            cache,
+        })
+    }
+
+    pub async fn into_response(
+        mut self,
+        request_headers: &HeaderMap,
+    ) -> Result<Response, FileResponseError> {
+        let mut headers = HeaderMap::new();
+        headers.typed_insert(self.etag.clone());
+        headers.typed_insert(self.cache.cache_control());
+        headers.typed_insert(AcceptRanges::bytes());
+
+        if let Some(if_none_match) = request_headers.typed_get::<IfNoneMatch>() {
+            if !if_none_match.precondition_passes(&self.etag) {
+                return Ok((StatusCode::NOT_MODIFIED, headers).into_response());
+            }
+        }
+
+        let range_allowed = request_headers
+            .typed_get::<IfRange>()
+            .map(|if_range| !if_range.is_modified(Some(&self.etag), None))
+            .unwrap_or(true);
+        let range = request_headers
+            .typed_get::<Range>()
+            .filter(|_| range_allowed);
+
+        match select_range(range.as_ref(), self.len) {
+            RangeSelection::Full => {
+                headers.typed_insert(self.content_type);
+                headers.typed_insert(ContentLength(self.len));
+                let body = Body::from_stream(ReaderStream::new(self.file));
+                Ok((StatusCode::OK, headers, body).into_response())
+            }
+            RangeSelection::Partial { start, end } => {
+                self.file
+                    .seek(SeekFrom::Start(start))
+                    .await
+                    .change_context(FileResponseError::IoFileSeek)?;
+                let range_len = end - start + 1;
+                headers.typed_insert(self.content_type);
+                headers.typed_insert(ContentLength(range_len));
+                headers.typed_insert(
+                    ContentRange::bytes(start..=end, self.len)
+                        .map_err(|_| FileResponseError::InvalidContentRange.report())?,
+                );
+                let body = Body::from_stream(ReaderStream::new(self.file.take(range_len)));
+                Ok((StatusCode::PARTIAL_CONTENT, headers, body).into_response())
+            }
+            RangeSelection::Unsatisfiable => {
+                headers.typed_insert(ContentRange::unsatisfied_bytes(self.len));
+                Ok((StatusCode::RANGE_NOT_SATISFIABLE, headers).into_response())
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum RangeSelection {
+    Full,
+    /// Byte range with inclusive end.
+    Partial {
+        start: u64,
+        end: u64,
+    },
+    Unsatisfiable,
+}
+
+/// Multiple ranges are not supported, so the full file is selected
+/// for those requests.
+fn select_range(range: Option<&Range>, len: u64) -> RangeSelection {
+    let Some(range) = range else {
+        return RangeSelection::Full;
+    };
+
+    let mut ranges = range.satisfiable_ranges(len);
+    let (start, end) = match (ranges.next(), ranges.next()) {
+        (Some(range), None) => range,
+        (None, _) => return RangeSelection::Unsatisfiable,
+        (Some(_), Some(_)) => return RangeSelection::Full,
+    };
+
+    let Some(last_byte) = len.checked_sub(1) else {
+        return RangeSelection::Unsatisfiable;
+    };
+    let start = match start {
+        Bound::Included(start) => start,
+        Bound::Excluded(start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match end {
+        Bound::Included(end) => end.min(last_byte),
+        Bound::Excluded(end) => match end.checked_sub(1) {
+            Some(end) => end.min(last_byte),
+            None => return RangeSelection::Unsatisfiable,
+        },
+        Bound::Unbounded => last_byte,
+    };
+
+    if start > end {
+        RangeSelection::Unsatisfiable
+    } else {
+        RangeSelection::Partial { start, end }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(value: &str) -> Range {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::header::RANGE,
+            value.parse().expect("Invalid header value"),
+        );
+        headers.typed_get().expect("Invalid range")
+    }
+
+    #[test]
+    fn single_range_is_selected() {
+        assert_eq!(
+            select_range(Some(&range("bytes=0-9")), 100),
+            RangeSelection::Partial { start: 0, end: 9 }
+        );
+        assert_eq!(
+            select_range(Some(&range("bytes=90-")), 100),
+            RangeSelection::Partial { start: 90, end: 99 }
+        );
+        assert_eq!(
+            select_range(Some(&range("bytes=-10")), 100),
+            RangeSelection::Partial { start: 90, end: 99 }
+        );
+        assert_eq!(
+            select_range(Some(&range("bytes=50-1000")), 100),
+            RangeSelection::Partial { start: 50, end: 99 }
+        );
+    }
+
+    #[test]
+    fn range_outside_file_is_unsatisfiable() {
+        assert_eq!(
+            select_range(Some(&range("bytes=100-")), 100),
+            RangeSelection::Unsatisfiable
+        );
+        assert_eq!(
+            select_range(Some(&range("bytes=0-9")), 0),
+            RangeSelection::Unsatisfiable
+        );
+    }
+
+    #[test]
+    fn multiple_ranges_select_full_file() {
+        assert_eq!(
+            select_range(Some(&range("bytes=0-9,20-29")), 100),
+            RangeSelection::Full
+        );
+        assert_eq!(select_range(None, 100), RangeSelection::Full);
+    }
+}
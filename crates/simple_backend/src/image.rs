@@ -1,11 +1,17 @@
 //! Image process
 
-use std::{env, os::unix::process::CommandExt, path::Path, str::from_utf8};
+use std::{
+    env,
+    os::unix::process::CommandExt,
+    path::{Path, PathBuf},
+    str::from_utf8,
+};
 
 use error_stack::{Result, ResultExt};
-use simple_backend_config::args::InputFileType;
-use simple_backend_image_process::ImageProcessingInfo;
+use simple_backend_config::args::{InputFileType, SmallImageCrop};
+use simple_backend_image_process::{ImageProcessingInfo, EXIT_CODE_UNSUPPORTED_INPUT_FORMAT};
 use simple_backend_utils::ContextExt;
+use tokio::io::AsyncReadExt;
 use tracing::{error, warn};
 
 #[derive(thiserror::Error, Debug)]
@@ -19,6 +25,10 @@ pub enum ImageProcessError {
 
     #[error("Image processing failed")]
     ImageProcessingFailure,
+    #[error("Unsupported input format")]
+    UnsupportedInputFormat,
+    #[error("Input reading failed")]
+    InputReadingFailed,
 }
 
 /// Start this binary again running in image processing mode.
@@ -27,14 +37,95 @@ pub enum ImageProcessError {
 /// Argument struct can be found from
 /// `simple_backend_config::args::ImageProcessModeArgs`.
 ///
+/// Supported input formats are listed in [InputFileType].
 /// Outputs JPEG images only.
 pub struct ImageProcess;
 
 impl ImageProcess {
+    /// Detect input file type from file header.
+    ///
+    /// Returns [ImageProcessError::UnsupportedInputFormat] if the
+    /// file format is not supported.
+    pub async fn detect_input_file_type(input: &Path) -> Result<InputFileType, ImageProcessError> {
+        let file = tokio::fs::File::open(input)
+            .await
+            .change_context(ImageProcessError::InputReadingFailed)?;
+        let mut header = Vec::with_capacity(InputFileType::FILE_HEADER_LEN);
+        file.take(InputFileType::FILE_HEADER_LEN as u64)
+            .read_to_end(&mut header)
+            .await
+            .change_context(ImageProcessError::InputReadingFailed)?;
+        InputFileType::from_file_header(&header)
+            .ok_or(ImageProcessError::UnsupportedInputFormat.report())
+    }
+
+    /// Process image and create also medium and small size
+    /// image variants.
     pub async fn start_image_process(
         input: &Path,
         input_file_type: InputFileType,
         output: &Path,
+        output_medium: &Path,
+        output_small: &Path,
+    ) -> Result<ImageProcessingInfo, ImageProcessError> {
+        let output_medium = Self::canonicalize_output(output_medium)?;
+        let output_small = Self::canonicalize_output(output_small)?;
+        Self::run_image_process(input, input_file_type, output, |command| {
+            command
+                .arg("--output-medium")
+                .arg(output_medium)
+                .arg("--output-small")
+                .arg(output_small);
+        })
+        .await
+    }
+
+    /// Create small square image variant from already processed image.
+    /// If crop is `None`, the largest centered square is used.
+    pub async fn start_small_image_process(
+        input: &Path,
+        crop: Option<SmallImageCrop>,
+        output: &Path,
+    ) -> Result<(), ImageProcessError> {
+        Self::run_image_process(input, InputFileType::JpegImage, output, |command| {
+            command.arg("--small-only");
+            if let Some(crop) = crop {
+                command
+                    .arg("--small-crop-size")
+                    .arg(crop.size.to_string())
+                    .arg("--small-crop-x")
+                    .arg(crop.x.to_string())
+                    .arg("--small-crop-y")
+                    .arg(crop.y.to_string());
+            }
+        })
+        .await
+        .map(|_| ())
+    }
+
+    fn canonicalize_output(output: &Path) -> Result<PathBuf, ImageProcessError> {
+        if output.exists() {
+            std::fs::canonicalize(output).change_context(ImageProcessError::LaunchCommand)
+        } else {
+            let output_file_name = output
+                .file_name()
+                .ok_or(ImageProcessError::LaunchCommand.report())?;
+            if let Some(parent) = output.parent() {
+                let path = std::fs::canonicalize(parent)
+                    .change_context(ImageProcessError::LaunchCommand)?;
+                Ok(path.join(output_file_name))
+            } else {
+                Err(ImageProcessError::LaunchCommand.report())
+                    .attach_printable(format!("Output path {:?} has no parent", output))
+            }
+        }
+    }
+
+    async fn run_image_process(
+        input: &Path,
+        input_file_type: InputFileType,
+        output: &Path,
+        extra_args: impl FnOnce(&mut std::process::Command),
     ) -> Result<ImageProcessingInfo, ImageProcessError> {
         let start_cmd = env::args()
             .next()
@@ -53,21 +144,7 @@ impl ImageProcess {
 
         let input =
             std::fs::canonicalize(input).change_context(ImageProcessError::LaunchCommand)?;
-        let output = if output.exists() {
-            std::fs::canonicalize(output).change_context(ImageProcessError::LaunchCommand)?
-        } else {
-            let output_file_name = output
-                .file_name()
-                .ok_or(ImageProcessError::LaunchCommand.report())?;
-            if let Some(parent) = output.parent() {
-                let path = std::fs::canonicalize(parent)
-                    .change_context(ImageProcessError::LaunchCommand)?;
-                path.join(output_file_name)
-            } else {
-                return Err(ImageProcessError::LaunchCommand.report())
-                    .attach_printable(format!("Output path {:?} has no parent", output));
-            }
-        };
+        let output = Self::canonicalize_output(output)?;
 
         let mut command = std::process::Command::new(start_cmd);
         command
@@ -79,6 +156,7 @@ impl ImageProcess {
             .arg("--output")
             .arg(output)
             .process_group(0);
+        extra_args(&mut command);
 
         let mut tokio_command: tokio::process::Command = command.into();
         let result = tokio_command
@@ -101,7 +179,12 @@ impl ImageProcess {
 
             Ok(info)
         } else {
-            let mut report = ImageProcessError::ImageProcessingFailure.report();
+            let error = if result.status.code() == Some(EXIT_CODE_UNSUPPORTED_INPUT_FORMAT.into()) {
+                ImageProcessError::UnsupportedInputFormat
+            } else {
+                ImageProcessError::ImageProcessingFailure
+            };
+            let mut report = error.report();
             let stdout_str = match from_utf8(&result.stdout) {
                 Ok(msg) => msg.trim(),
                 Err(_) => "stdout contains invalid utf-8",
@@ -13,6 +13,7 @@ pub mod app;
 pub mod email;
 pub mod event;
 pub mod file_package;
+pub mod file_response;
 pub mod image;
 pub mod manager_client;
 pub mod map;
@@ -28,7 +29,7 @@ use app::{
     GetManagerApi, GetSimpleBackendConfig, GetTileMap, PerfCounterDataProvider, SignInWith,
     SimpleBackendAppState,
 };
-use axum::Router;
+use axum::{routing::get, Router};
 use futures::future::poll_fn;
 use hyper::body::Incoming;
 use hyper_util::rt::{TokioExecutor, TokioIo};
@@ -57,7 +58,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilte
 use utoipa_swagger_ui::SwaggerUi;
 
 use self::web_socket::WebSocketManager;
-use crate::perf::{PerfMetricsManager, PerfMetricsManagerData};
+use crate::perf::{storage::StorageMetrics, PerfMetricsManager, PerfMetricsManagerData};
 
 pub const HTTPS_DEFAULT_PORT: u16 = 443;
 pub const SERVER_START_MESSAGE: &str = "Server start complete";
@@ -117,6 +118,21 @@ pub trait BusinessLogic: Sized + Send + Sync + 'static {
     ) -> Router {
         Router::new()
     }
+    /// Create router for Prometheus metrics API. The default router
+    /// contains only the metrics which [perf::metrics::metrics_response]
+    /// creates.
+    fn metrics_api_router(
+        &self,
+        state: &Self::AppState,
+    ) -> Router {
+        let state = state.clone();
+        Router::new().route(
+            perf::metrics::PATH_METRICS,
+            get(|| async move {
+                perf::metrics::metrics_response(state.perf_counter_data(), vec![]).await
+            }),
+        )
+    }
 
     /// Swagger UI which added to enabled internal API router
     /// only if debug mode is enabled.
@@ -211,7 +227,10 @@ impl<T: BusinessLogic> SimpleBackend<T> {
 
         let (server_quit_handle, server_quit_watcher) = broadcast::channel(1);
 
-        let perf_data = Arc::new(PerfMetricsManagerData::new(self.logic.all_counters()));
+        let perf_data = Arc::new(PerfMetricsManagerData::new(
+            self.logic.all_counters(),
+            StorageMetrics::new(&self.config),
+        ));
         let perf_manager_quit_handle =
             PerfMetricsManager::new_manager(perf_data.clone(), server_quit_watcher.resubscribe());
 
@@ -302,6 +321,22 @@ impl<T: BusinessLogic> SimpleBackend<T> {
                 None
             };
 
+        let metrics_api_server_task =
+            if let Some(addr) = self.config.socket().metrics_api {
+                info!("Metrics API is available on {}", addr);
+                Some(
+                    self.create_server_task_no_tls(
+                        self.logic.metrics_api_router(&state),
+                        addr,
+                        "Metrics API",
+                        server_quit_watcher.resubscribe(),
+                    )
+                    .await,
+                )
+            } else {
+                None
+            };
+
         if public_api_server_task.is_none() &&
             public_bot_api_server_task.is_none() &&
             bot_api_server_task.is_none() &&
@@ -324,6 +359,11 @@ impl<T: BusinessLogic> SimpleBackend<T> {
         drop(server_quit_handle);
 
         // Wait until all tasks quit
+        if let Some(task) = metrics_api_server_task {
+            task
+                .await
+                .expect("Metrics API server task panic detected");
+        }
         if let Some(task) = internal_api_server_task {
             task
                 .await
@@ -189,6 +189,7 @@ impl <T: ManagerEventHandler> ManagerConnectionManager<T> {
                     };
                     self.client.latest_scheduled_reboot.store(ut, Ordering::Relaxed);
                 }
+                ServerEventType::BackupVerificationStatus(_) => (),
             }
             self.event_handler.handle(event.event()).await;
         }
@@ -1,15 +1,15 @@
 //! Slippy map tile server logic.
 
 use error_stack::{Result, ResultExt};
+use headers::ContentType;
 use simple_backend_config::{file::TileMapConfig, SimpleBackendConfig};
-use tokio_util::io::ReaderStream;
+
+use crate::file_response::{FileCache, FileResponse};
 
 #[derive(thiserror::Error, Debug)]
 pub enum TileMapError {
-    #[error("File open failed")]
-    IoFileOpen,
-    #[error("Getting file metadata failed")]
-    IoFileMetadata,
+    #[error("File response creation failed")]
+    FileResponse,
 
     #[error("Missing tile map config")]
     MissingTileMapConfig,
@@ -26,12 +26,13 @@ impl TileMapManager {
         }
     }
 
-    pub async fn map_tile_byte_count_and_byte_stream(
+    /// Returns `None` if the tile does not exist.
+    pub async fn map_tile_response(
         &self,
         z: u32,
         x: u32,
         y: u32,
-    ) -> Result<Option<(u64, ReaderStream<tokio::fs::File>)>, TileMapError> {
+    ) -> Result<Option<FileResponse>, TileMapError> {
         let config = self
             .config
             .as_ref()
@@ -43,15 +44,15 @@ impl TileMapManager {
             return Ok(None);
         }
 
-        let file = tokio::fs::File::open(path)
-            .await
-            .change_context(TileMapError::IoFileOpen)?;
-        let metadata = file
-            .metadata()
-            .await
-            .change_context(TileMapError::IoFileMetadata)?;
-        let stream = ReaderStream::new(file);
+        let response = FileResponse::open(
+            &path,
+            &format!("{}-{}-{}", z, x, y),
+            ContentType::png(),
+            FileCache::Immutable,
+        )
+        .await
+        .change_context(TileMapError::FileResponse)?;
 
-        Ok(Some((metadata.len(), stream)))
+        Ok(Some(response))
     }
 }
@@ -5,15 +5,16 @@
 use std::{
     collections::{HashMap, VecDeque},
     sync::{
-        atomic::{AtomicU32, Ordering},
+        atomic::{AtomicU32, AtomicU64, Ordering},
         Arc,
     },
     time::Duration,
 };
 
 use simple_backend_model::{
-    MetricKey, PerfMetricQueryResult, PerfMetricValueArea, PerfMetricValues, TimeGranularity, UnixTime
+    MetricKey, PerfMetricQuery, PerfMetricQueryResult, PerfMetricValueArea, PerfMetricValues, TimeGranularity, UnixTime
 };
+use storage::StorageMetrics;
 use sysinfo::MemoryRefreshKind;
 use tokio::{sync::RwLock, task::JoinHandle};
 use tracing::{error, warn};
@@ -21,11 +22,15 @@ use websocket::WebSocketConnectionTracker;
 
 use crate::ServerQuitWatcher;
 
+pub mod metrics;
+pub mod storage;
 pub mod websocket;
 
 pub struct PerfCounter {
     name: &'static str,
     value: AtomicU32,
+    /// Value which is not reset
+    total: AtomicU64,
 }
 
 impl PerfCounter {
@@ -33,18 +38,26 @@ impl PerfCounter {
         Self {
             name,
             value: AtomicU32::new(0),
+            total: AtomicU64::new(0),
         }
     }
 
     /// Increment counter
     pub fn incr(&self) {
         self.value.fetch_add(1, Ordering::Relaxed);
+        self.total.fetch_add(1, Ordering::Relaxed);
     }
 
     pub fn value(&self) -> u32 {
         self.value.load(Ordering::Relaxed)
     }
 
+    /// Counter value since server start. [Self::load_and_reset] does not
+    /// reset this value.
+    pub fn total(&self) -> u64 {
+        self.total.load(Ordering::Relaxed)
+    }
+
     pub fn load_and_reset(&self) -> u32 {
         self.value.swap(0, Ordering::Relaxed)
     }
@@ -176,7 +189,7 @@ impl PerformanceMetricsHistory {
         }
     }
 
-    async fn append_and_reset_counters(&mut self) {
+    async fn append_and_reset_counters(&mut self, storage_values: Vec<(MetricKey, u32)>) {
         self.first_item_time = Some(UnixTime::current_time());
         let mut first_item = self.data.pop_back().expect("Buffer is empty");
 
@@ -205,6 +218,8 @@ impl PerformanceMetricsHistory {
             }
         }
 
+        first_item.extend(storage_values);
+
         first_item.insert(
             MetricKey::SERVER_WEBSOCKET_CONNECTIONS,
             WebSocketConnectionTracker::connection_count(),
@@ -213,6 +228,14 @@ impl PerformanceMetricsHistory {
         self.data.push_front(first_item);
     }
 
+    fn latest_values(&self) -> HashMap<MetricKey, u32> {
+        if self.first_item_time.is_some() {
+            self.data.front().cloned().unwrap_or_default()
+        } else {
+            HashMap::new()
+        }
+    }
+
     fn get_history(&self, only_latest_hour: bool) -> HashMap<MetricKey, PerfMetricValueArea> {
         let mut counter_data = HashMap::new();
 
@@ -254,6 +277,7 @@ impl PerformanceMetricsHistory {
 #[derive(Debug)]
 pub struct PerfMetricsManagerQuitHandle {
     task: JoinHandle<()>,
+    dir_size_task: JoinHandle<()>,
 }
 
 impl PerfMetricsManagerQuitHandle {
@@ -264,20 +288,39 @@ impl PerfMetricsManagerQuitHandle {
                 warn!("PefCounterManager quit failed. Error: {:?}", e);
             }
         }
+        match self.dir_size_task.await {
+            Ok(()) => (),
+            Err(e) => {
+                warn!("PefCounterManager dir size task quit failed. Error: {:?}", e);
+            }
+        }
     }
 }
 
 pub struct PerfMetricsManagerData {
     history: RwLock<PerformanceMetricsHistory>,
+    counters: AllCounters,
+    storage: Arc<StorageMetrics>,
 }
 
 impl PerfMetricsManagerData {
-    pub fn new(counters: AllCounters) -> Self {
+    pub fn new(counters: AllCounters, storage: StorageMetrics) -> Self {
         Self {
             history: RwLock::new(PerformanceMetricsHistory::new(counters)),
+            counters,
+            storage: Arc::new(storage),
         }
     }
 
+    pub fn counters(&self) -> AllCounters {
+        self.counters
+    }
+
+    /// Metric values from the latest minute.
+    pub async fn latest_values(&self) -> HashMap<MetricKey, u32> {
+        self.history.read().await.latest_values()
+    }
+
     pub async fn get_history(&self, only_latest_hour: bool) -> PerfMetricQueryResult {
         let counter_data = self.history.read().await.get_history(only_latest_hour);
         let mut counters = vec![];
@@ -292,11 +335,47 @@ impl PerfMetricsManagerData {
         PerfMetricQueryResult { metrics: counters }
     }
 
+    /// Get minute values which match the query.
+    pub async fn get_history_with_query(&self, query: &PerfMetricQuery) -> PerfMetricQueryResult {
+        let mut result = self.get_history(false).await;
+        result
+            .metrics
+            .retain(|metric| query.is_metric_selected(&metric.name));
+        for metric in &mut result.metrics {
+            metric.values = metric
+                .values
+                .iter()
+                .filter_map(|area| select_values_in_time_range(area, query))
+                .collect();
+        }
+        result
+    }
+
     pub async fn get_history_raw(&self, only_latest_hour: bool) -> HashMap<MetricKey, PerfMetricValueArea> {
         self.history.read().await.get_history(only_latest_hour)
     }
 }
 
+fn select_values_in_time_range(
+    area: &PerfMetricValueArea,
+    query: &PerfMetricQuery,
+) -> Option<PerfMetricValueArea> {
+    let step = area.time_granularity.seconds();
+    let time = |i: usize| UnixTime::new(area.start_time.ut - i as i64 * step);
+    let first = (0..area.values.len()).find(|&i| query.contains(time(i)))?;
+    let values = area.values[first..]
+        .iter()
+        .enumerate()
+        .take_while(|(i, _)| query.contains(time(first + i)))
+        .map(|(_, v)| *v)
+        .collect();
+    Some(PerfMetricValueArea {
+        start_time: time(first),
+        time_granularity: area.time_granularity,
+        values,
+    })
+}
+
 pub struct PerfMetricsManager {
     data: Arc<PerfMetricsManagerData>,
 }
@@ -308,9 +387,16 @@ impl PerfMetricsManager {
     ) -> PerfMetricsManagerQuitHandle {
         let manager = Self { data };
 
+        let dir_size_task = tokio::spawn(Self::run_dir_size_updates(
+            manager.data.storage.clone(),
+            quit_notification.resubscribe(),
+        ));
         let task = tokio::spawn(manager.run(quit_notification));
 
-        PerfMetricsManagerQuitHandle { task }
+        PerfMetricsManagerQuitHandle {
+            task,
+            dir_size_task,
+        }
     }
 
     pub async fn run(self, mut quit_notification: ServerQuitWatcher) {
@@ -323,7 +409,42 @@ impl PerfMetricsManager {
                 // as wrong information in data and original tick timing will recover
                 // eventually.
                 _ = timer.tick() => {
-                    self.data.history.write().await.append_and_reset_counters().await;
+                    // Read storage info before locking the history, so that
+                    // slow file system does not block history reading.
+                    let storage_values = self.storage_values().await;
+                    self.data.history.write().await.append_and_reset_counters(storage_values).await;
+                }
+                _ = quit_notification.recv() => {
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn storage_values(&self) -> Vec<(MetricKey, u32)> {
+        let storage = self.data.storage.clone();
+        match tokio::task::spawn_blocking(move || storage.values()).await {
+            Ok(values) => values,
+            Err(e) => {
+                error!("Getting storage info failed: {e}");
+                vec![]
+            }
+        }
+    }
+
+    async fn run_dir_size_updates(
+        storage: Arc<StorageMetrics>,
+        mut quit_notification: ServerQuitWatcher,
+    ) {
+        let mut timer = tokio::time::interval(storage::DIR_SIZE_UPDATE_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = timer.tick() => {
+                    let storage = storage.clone();
+                    if let Err(e) = tokio::task::spawn_blocking(move || storage.update_dir_sizes()).await {
+                        error!("Updating directory sizes failed: {e}");
+                    }
                 }
                 _ = quit_notification.recv() => {
                     return;
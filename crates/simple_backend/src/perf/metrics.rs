@@ -0,0 +1,114 @@
+//! Performance metrics in Prometheus text format
+
+use std::fmt::Write;
+
+use axum::{
+    http::header,
+    response::{IntoResponse, Response},
+};
+use simple_backend_model::MetricKey;
+
+use super::{websocket::WebSocketConnectionTracker, AllCounters, PerfMetricsManagerData};
+
+pub const PATH_METRICS: &str = "/metrics";
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Current value of some application specific metric.
+pub struct MetricGauge {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub value: u64,
+}
+
+impl MetricGauge {
+    pub fn new(name: &'static str, help: &'static str, value: impl Into<u64>) -> Self {
+        Self {
+            name,
+            help,
+            value: value.into(),
+        }
+    }
+}
+
+/// Create response containing all counters as monotonically
+/// increasing counters and gauges for WebSocket connections, CPU and
+/// RAM usage and the provided application specific gauges.
+pub async fn metrics_response(
+    perf_data: &PerfMetricsManagerData,
+    app_gauges: Vec<MetricGauge>,
+) -> Response {
+    let latest = perf_data.latest_values().await;
+    let mut gauges = vec![MetricGauge::new(
+        "websocket_connections",
+        "Current WebSocket connection count",
+        WebSocketConnectionTracker::connection_count(),
+    )];
+    if let Some(&value) = latest.get(&MetricKey::SYSTEM_CPU_USAGE) {
+        gauges.push(MetricGauge::new(
+            "system_cpu_usage_percent",
+            "CPU usage during the latest minute",
+            value,
+        ));
+    }
+    if let Some(&value) = latest.get(&MetricKey::SYSTEM_RAM_USAGE_MIB) {
+        gauges.push(MetricGauge::new(
+            "system_ram_usage_mib",
+            "RAM usage during the latest minute",
+            value,
+        ));
+    }
+    gauges.extend(app_gauges);
+
+    let text = metrics_text(perf_data.counters(), &gauges);
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], text).into_response()
+}
+
+fn metrics_text(counters: AllCounters, gauges: &[MetricGauge]) -> String {
+    let mut text = String::new();
+
+    text.push_str("# HELP perf_counter_total Counter value since server start\n");
+    text.push_str("# TYPE perf_counter_total counter\n");
+    for category in counters {
+        for counter in category.counter_list {
+            let _ = writeln!(
+                text,
+                "perf_counter_total{{category=\"{}\",name=\"{}\"}} {}",
+                category.name,
+                counter.name(),
+                counter.total(),
+            );
+        }
+    }
+
+    for gauge in gauges {
+        let _ = writeln!(text, "# HELP {} {}", gauge.name, gauge.help);
+        let _ = writeln!(text, "# TYPE {} gauge", gauge.name);
+        let _ = writeln!(text, "{} {}", gauge.name, gauge.value);
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_counters, perf::CounterCategory};
+
+    create_counters!(TestCounters, TEST, TEST_COUNTERS_LIST, test_counter,);
+
+    static TEST_CATEGORIES: AllCounters = &[&CounterCategory::new("test", TEST_COUNTERS_LIST)];
+
+    #[test]
+    fn counter_total_is_not_reset() {
+        TEST.test_counter.incr();
+        TEST.test_counter.load_and_reset();
+        TEST.test_counter.incr();
+
+        let gauges = [MetricGauge::new("test_gauge", "Test gauge", 3u32)];
+        let text = metrics_text(TEST_CATEGORIES, &gauges);
+
+        assert!(text.contains("perf_counter_total{category=\"test\",name=\"test_counter\"} 2\n"));
+        assert!(text.contains("# TYPE test_gauge gauge\ntest_gauge 3\n"));
+    }
+}
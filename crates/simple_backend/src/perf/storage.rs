@@ -0,0 +1,200 @@
+//! Disk space and file size metrics
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use nix::sys::statvfs::statvfs;
+use simple_backend_config::SimpleBackendConfig;
+use simple_backend_database::data::{sqlite_database_file_path, FILE_DIR_NAME};
+use simple_backend_model::MetricKey;
+use tracing::error;
+
+const KIB: u64 = 1024;
+const MIB: u64 = 1024 * 1024;
+
+/// Directory sizes are calculated less often than other values
+/// because every file metadata must be read.
+pub const DIR_SIZE_UPDATE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone)]
+struct SqliteFileMetrics {
+    db_file: PathBuf,
+    wal_file: PathBuf,
+    db_file_size_kib: MetricKey,
+    wal_file_size_kib: MetricKey,
+}
+
+/// Disk space and file sizes which are saved to perf history.
+#[derive(Debug, Clone, Default)]
+pub struct StorageMetrics {
+    data_dir: Option<PathBuf>,
+    secure_storage_dir: Option<PathBuf>,
+    media_content_dir: Option<PathBuf>,
+    /// Cached value which [StorageMetrics::update_dir_sizes] updates.
+    media_content_dir_size_mib: Arc<Mutex<Option<u32>>>,
+    sqlite_files: Vec<SqliteFileMetrics>,
+}
+
+impl StorageMetrics {
+    pub fn new(config: &SimpleBackendConfig) -> Self {
+        let sqlite_files = if config.sqlite_in_ram() {
+            vec![]
+        } else {
+            config
+                .databases()
+                .iter()
+                .map(|db| {
+                    let db = db.to_sqlite_database();
+                    let db_file = sqlite_database_file_path(config, &db);
+                    let mut wal_file = db_file.clone().into_os_string();
+                    wal_file.push("-wal");
+                    SqliteFileMetrics {
+                        db_file,
+                        wal_file: wal_file.into(),
+                        db_file_size_kib: MetricKey::sqlite(metric_name(format!(
+                            "{}_db_size_kib",
+                            db.name
+                        ))),
+                        wal_file_size_kib: MetricKey::sqlite(metric_name(format!(
+                            "{}_wal_size_kib",
+                            db.name
+                        ))),
+                    }
+                })
+                .collect()
+        };
+
+        Self {
+            data_dir: Some(config.data_dir().to_path_buf()),
+            secure_storage_dir: config.secure_storage_dir().map(|dir| dir.to_path_buf()),
+            media_content_dir: Some(config.data_dir().join(FILE_DIR_NAME)),
+            media_content_dir_size_mib: Arc::new(Mutex::new(None)),
+            sqlite_files,
+        }
+    }
+
+    /// Calculate directory sizes and cache the values. This blocks
+    /// the thread.
+    pub fn update_dir_sizes(&self) {
+        if let Some(dir) = &self.media_content_dir {
+            let size = to_u32(dir_size(dir) / MIB);
+            match self.media_content_dir_size_mib.lock() {
+                Ok(mut cached) => *cached = Some(size),
+                Err(e) => error!("Updating media content dir size failed: {e}"),
+            }
+        }
+    }
+
+    /// Read current metric values. Directory sizes are read from
+    /// cache. This blocks the thread.
+    pub fn values(&self) -> Vec<(MetricKey, u32)> {
+        let mut values = vec![];
+
+        if let Some(dir) = &self.data_dir {
+            disk_space_values(
+                &mut values,
+                dir,
+                MetricKey::SYSTEM_DATA_DIR_AVAILABLE_MIB,
+                MetricKey::SYSTEM_DATA_DIR_AVAILABLE_INODES,
+            );
+        }
+
+        if let Some(dir) = &self.secure_storage_dir {
+            disk_space_values(
+                &mut values,
+                dir,
+                MetricKey::SYSTEM_SECURE_STORAGE_AVAILABLE_MIB,
+                MetricKey::SYSTEM_SECURE_STORAGE_AVAILABLE_INODES,
+            );
+        }
+
+        let media_content_dir_size_mib = self
+            .media_content_dir_size_mib
+            .lock()
+            .map(|cached| *cached)
+            .unwrap_or_default();
+        if let Some(size) = media_content_dir_size_mib {
+            values.push((MetricKey::SERVER_MEDIA_CONTENT_DIR_SIZE_MIB, size));
+        }
+
+        for sqlite in &self.sqlite_files {
+            values.push((
+                sqlite.db_file_size_kib,
+                to_u32(file_size(&sqlite.db_file) / KIB),
+            ));
+            values.push((
+                sqlite.wal_file_size_kib,
+                to_u32(file_size(&sqlite.wal_file) / KIB),
+            ));
+        }
+
+        values
+    }
+}
+
+/// Database names are from the config file, so the metric names are
+/// created only once when the server starts.
+fn metric_name(name: String) -> &'static str {
+    Box::leak(name.into_boxed_str())
+}
+
+fn disk_space_values(
+    values: &mut Vec<(MetricKey, u32)>,
+    dir: &Path,
+    available_mib: MetricKey,
+    available_inodes: MetricKey,
+) {
+    match statvfs(dir) {
+        Ok(info) => {
+            let available_bytes =
+                (info.blocks_available() as u64).saturating_mul(info.fragment_size() as u64);
+            values.push((available_mib, to_u32(available_bytes / MIB)));
+            values.push((available_inodes, to_u32(info.files_available() as u64)));
+        }
+        Err(e) => {
+            error!(
+                "Getting disk space info failed, path: {}, error: {e}",
+                dir.display()
+            );
+        }
+    }
+}
+
+/// Returns 0 if the file does not exist.
+fn file_size(file: &Path) -> u64 {
+    std::fs::metadata(file)
+        .map(|metadata| metadata.len())
+        .unwrap_or_default()
+}
+
+/// Files can be removed while the size is calculated, so
+/// IO errors are ignored.
+fn dir_size(dir: &Path) -> u64 {
+    fn dir_size_recursive(dir: &Path) -> io::Result<u64> {
+        let mut size = 0;
+        for entry in std::fs::read_dir(dir)? {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                size += dir_size_recursive(&entry.path()).unwrap_or_default();
+            } else {
+                size += metadata.len();
+            }
+        }
+        Ok(size)
+    }
+
+    dir_size_recursive(dir).unwrap_or_default()
+}
+
+fn to_u32(value: u64) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
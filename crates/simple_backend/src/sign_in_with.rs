@@ -4,7 +4,7 @@ use error_stack::Result;
 use simple_backend_config::SimpleBackendConfig;
 
 use self::{
-    apple::{AppleAccountId, SignInWithAppleError, SignInWithAppleManager},
+    apple::{AppleAccountInfo, SignInWithAppleError, SignInWithAppleManager},
     google::{GoogleAccountInfo, SignInWithGoogleError, SignInWithGoogleManager},
 };
 
@@ -35,7 +35,8 @@ impl SignInWithManager {
     pub async fn validate_apple_token(
         &self,
         token: String,
-    ) -> Result<AppleAccountId, SignInWithAppleError> {
-        self.apple.validate_apple_token(token).await
+        nonce: String,
+    ) -> Result<AppleAccountInfo, SignInWithAppleError> {
+        self.apple.validate_apple_token(token, nonce).await
     }
 }
@@ -1,9 +1,31 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use error_stack::{Result, ResultExt};
-use simple_backend_config::SimpleBackendConfig;
+use headers::{CacheControl, HeaderMapExt};
+use jsonwebtoken::{
+    jwk::{Jwk, JwkSet},
+    DecodingKey, Validation,
+};
+use reqwest::Url;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use simple_backend_config::{file::SignInWithAppleConfig, SimpleBackendConfig};
 use simple_backend_utils::ContextExt;
-use tracing::{error, info};
+use tokio::sync::RwLock;
+
+/// Apple ID token (from client) iss field (issuer) value.
+const ISS_VALUE_APPLE: &str = "https://appleid.apple.com";
+
+/// Apple public keys download response does not always have
+/// cache control header, so use this when max age is not available.
+const DEFAULT_PUBLIC_KEYS_CACHE_TIME: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// Minimum time between public key downloads which are triggered
+/// because the wanted key was not found from the cached keys.
+const MIN_KEY_NOT_FOUND_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
 
 #[derive(thiserror::Error, Debug)]
 pub enum SignInWithAppleError {
@@ -12,25 +34,400 @@ pub enum SignInWithAppleError {
 
     #[error("Token was invalid")]
     InvalidToken,
+
+    #[error("Token nonce was invalid")]
+    InvalidNonce,
+
+    #[error("Couldn't download Apple public key")]
+    PublicKeyDownloadFailed,
+
+    #[error("Token kid property not found from token received from client")]
+    MissingJwtKid,
+
+    #[error("Parsing HTTP GET for Apple public key response cache control header failed.")]
+    ParsingCacheControlHeader,
+
+    #[error("Max age related time calculation failed")]
+    CacheCalculation,
+
+    #[error("HTTP GET for Apple public keys didn't contain valid JwkSet")]
+    JwkSetParsingFailed,
+
+    #[error("Requested Jwk was not found")]
+    JwkNotFound,
+
+    #[error("Decoding key generation failed")]
+    DecodingKeyGenerationFailed,
+
+    #[error("Sign in with Apple is not enabled from server settings file")]
+    NotEnabled,
+}
+
+/// Apple uses strings for boolean values in some of the ID tokens.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AppleBool {
+    Bool(bool),
+    String(String),
+}
+
+impl AppleBool {
+    fn value(&self) -> bool {
+        match self {
+            Self::Bool(v) => *v,
+            Self::String(v) => v == "true",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AppleTokenClaims {
+    /// Apple Account ID
+    sub: String,
+    /// SHA-256 hash of the nonce which client generated. The hash is in
+    /// lowercase hex format.
+    nonce: Option<String>,
+    /// Email linked to Apple Account. Might be a private relay address.
+    email: Option<String>,
+    /// Email verification status.
+    email_verified: Option<AppleBool>,
+}
+
+pub struct AppleAccountInfo {
+    pub id: String,
+    /// Verified email address if it is available.
+    pub email: Option<String>,
 }
 
-pub struct AppleAccountId;
+struct ApplePublicKeys {
+    keys: JwkSet,
+    valid_until_this: Instant,
+    downloaded_at: Instant,
+}
 
-pub struct SignInWithAppleManager;
+enum KeyStatus {
+    Found(Jwk),
+    KeyRefreshNeeded,
+}
+
+pub struct SignInWithAppleManager {
+    client: reqwest::Client,
+    apple_config: Option<SignInWithAppleConfig>,
+    public_keys_url: Url,
+    apple_public_keys: RwLock<Option<ApplePublicKeys>>,
+}
 
 impl SignInWithAppleManager {
-    pub fn new(_config: Arc<SimpleBackendConfig>, _client: reqwest::Client) -> Self {
-        // TODO(prod): Implement Sign in with Apple support
-        Self
+    pub fn new(config: Arc<SimpleBackendConfig>, client: reqwest::Client) -> Self {
+        Self::new_with_config(
+            config.sign_in_with_apple_config().cloned(),
+            config.sign_in_with_urls().apple_public_keys.clone(),
+            client,
+        )
     }
+
+    fn new_with_config(
+        apple_config: Option<SignInWithAppleConfig>,
+        public_keys_url: Url,
+        client: reqwest::Client,
+    ) -> Self {
+        Self {
+            client,
+            apple_config,
+            public_keys_url,
+            apple_public_keys: RwLock::new(None),
+        }
+    }
+
+    /// Validate Apple ID token.
+    ///
+    /// The `nonce` is the raw nonce which client generated. The token
+    /// must contain SHA-256 hash of it.
     pub async fn validate_apple_token(
         &self,
         token: String,
-    ) -> Result<AppleAccountId, SignInWithAppleError> {
+        nonce: String,
+    ) -> Result<AppleAccountInfo, SignInWithAppleError> {
+        let apple_config = self
+            .apple_config
+            .as_ref()
+            .ok_or(SignInWithAppleError::NotEnabled)?;
+
         let not_validated_header = jsonwebtoken::decode_header(&token)
             .change_context(SignInWithAppleError::InvalidTokenHeader)?;
-        info!("{:?}", &not_validated_header);
+        let wanted_kid = not_validated_header
+            .kid
+            .ok_or(SignInWithAppleError::MissingJwtKid)?;
+
+        let apple_public_key = self.get_apple_public_key(&wanted_kid).await?;
+
+        let key = DecodingKey::from_jwk(&apple_public_key)
+            .change_context(SignInWithAppleError::DecodingKeyGenerationFailed)?;
+
+        let mut valid_audience = vec![apple_config.app_bundle_id.as_str()];
+        if let Some(service_id) = &apple_config.service_id {
+            valid_audience.push(service_id.as_str());
+        }
+
+        let mut v = Validation::new(not_validated_header.alg);
+        v.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        v.set_issuer(&[ISS_VALUE_APPLE]);
+        v.set_audience(&valid_audience);
+
+        let data = jsonwebtoken::decode::<AppleTokenClaims>(&token, &key, &v)
+            .change_context(SignInWithAppleError::InvalidToken)?;
+
+        let nonce_hash = base16ct::lower::encode_string(&Sha256::digest(nonce.as_bytes()));
+        if data.claims.nonce.as_deref() != Some(nonce_hash.as_str()) {
+            return Err(SignInWithAppleError::InvalidNonce.report());
+        }
+
+        let email_verified = data
+            .claims
+            .email_verified
+            .as_ref()
+            .map(|v| v.value())
+            .unwrap_or_default();
+        let email = if email_verified {
+            data.claims.email
+        } else {
+            None
+        };
+
+        Ok(AppleAccountInfo {
+            id: data.claims.sub,
+            email,
+        })
+    }
+
+    async fn get_apple_public_key(&self, wanted_kid: &str) -> Result<Jwk, SignInWithAppleError> {
+        match self
+            .get_apple_public_key_from_local_keys(wanted_kid)
+            .await?
+        {
+            KeyStatus::Found(key) => Ok(key),
+            KeyStatus::KeyRefreshNeeded => {
+                self.download_apple_public_keys_and_get_key(wanted_kid)
+                    .await
+            }
+        }
+    }
+
+    async fn get_apple_public_key_from_local_keys(
+        &self,
+        wanted_kid: &str,
+    ) -> Result<KeyStatus, SignInWithAppleError> {
+        let keys = self.apple_public_keys.read().await;
+        match keys.as_ref() {
+            None => Ok(KeyStatus::KeyRefreshNeeded),
+            Some(keys) => {
+                let now = Instant::now();
+                if now >= keys.valid_until_this {
+                    return Ok(KeyStatus::KeyRefreshNeeded);
+                }
+
+                match keys.keys.find(wanted_kid) {
+                    Some(jwk) => Ok(KeyStatus::Found(jwk.clone())),
+                    None => {
+                        // Apple might have rotated the keys
+                        if now.duration_since(keys.downloaded_at)
+                            >= MIN_KEY_NOT_FOUND_REFRESH_INTERVAL
+                        {
+                            Ok(KeyStatus::KeyRefreshNeeded)
+                        } else {
+                            Err(SignInWithAppleError::JwkNotFound.report())
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn download_apple_public_keys_and_get_key(
+        &self,
+        wanted_kid: &str,
+    ) -> Result<Jwk, SignInWithAppleError> {
+        let download_request =
+            reqwest::Request::new(reqwest::Method::GET, self.public_keys_url.clone());
+
+        let r = self
+            .client
+            .execute(download_request)
+            .await
+            .change_context(SignInWithAppleError::PublicKeyDownloadFailed)?
+            .error_for_status()
+            .change_context(SignInWithAppleError::PublicKeyDownloadFailed)?;
+
+        let max_age = r
+            .headers()
+            .typed_try_get::<CacheControl>()
+            .change_context(SignInWithAppleError::ParsingCacheControlHeader)?
+            .and_then(|v| v.max_age())
+            .unwrap_or(DEFAULT_PUBLIC_KEYS_CACHE_TIME);
+        let downloaded_at = Instant::now();
+        let valid_until_this = downloaded_at
+            .checked_add(max_age)
+            .ok_or(SignInWithAppleError::CacheCalculation)?;
+
+        let jwk_set: JwkSet = r
+            .json()
+            .await
+            .change_context(SignInWithAppleError::JwkSetParsingFailed)?;
+        let mut key_store = self.apple_public_keys.write().await;
+        *key_store = Some(ApplePublicKeys {
+            keys: jwk_set.clone(),
+            valid_until_this,
+            downloaded_at,
+        });
+
+        let jwk = jwk_set
+            .find(wanted_kid)
+            .ok_or(SignInWithAppleError::JwkNotFound)?
+            .clone();
+        Ok(jwk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use axum::{routing::get, Router};
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde_json::{json, Value};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    const KID: &str = "test-kid";
+    const SECRET: &[u8] = b"sign-in-with-apple-test-key-00000";
+    /// Base64 encoded [SECRET]. The value is the same in standard and
+    /// URL safe encodings.
+    const SECRET_BASE64: &str = "c2lnbi1pbi13aXRoLWFwcGxlLXRlc3Qta2V5LTAwMDAw";
+    const APP_BUNDLE_ID: &str = "com.example.app";
+    const NONCE: &str = "test-nonce";
+
+    /// Local server which returns JwkSet containing one key.
+    async fn start_public_keys_server() -> Url {
+        let keys = json!({
+            "keys": [{ "kty": "oct", "kid": KID, "k": SECRET_BASE64 }]
+        })
+        .to_string();
+        let router = Router::new().route("/keys", get(move || async move { keys }));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        Url::parse(&format!("http://{}/keys", addr)).unwrap()
+    }
+
+    async fn manager() -> SignInWithAppleManager {
+        let public_keys_url = start_public_keys_server().await;
+        SignInWithAppleManager::new_with_config(
+            Some(SignInWithAppleConfig {
+                app_bundle_id: APP_BUNDLE_ID.to_string(),
+                service_id: None,
+                public_keys_url: Some(public_keys_url.clone()),
+            }),
+            public_keys_url,
+            reqwest::Client::new(),
+        )
+    }
+
+    fn current_time() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    fn valid_claims() -> Value {
+        json!({
+            "iss": ISS_VALUE_APPLE,
+            "aud": APP_BUNDLE_ID,
+            "sub": "apple-account-id",
+            "exp": current_time() + 600,
+            "nonce": base16ct::lower::encode_string(&Sha256::digest(NONCE.as_bytes())),
+            "email": "test@example.com",
+            "email_verified": "true",
+        })
+    }
+
+    fn token(kid: &str, claims: &Value) -> String {
+        let mut header = Header::new(jsonwebtoken::Algorithm::HS256);
+        header.kid = Some(kid.to_string());
+        jsonwebtoken::encode(&header, claims, &EncodingKey::from_secret(SECRET)).unwrap()
+    }
+
+    async fn validate(kid: &str, claims: &Value) -> Result<AppleAccountInfo, SignInWithAppleError> {
+        manager()
+            .await
+            .validate_apple_token(token(kid, claims), NONCE.to_string())
+            .await
+    }
+
+    fn assert_error(
+        result: Result<AppleAccountInfo, SignInWithAppleError>,
+        expected: SignInWithAppleError,
+    ) {
+        let error = result.err().unwrap();
+        assert_eq!(
+            std::mem::discriminant(error.current_context()),
+            std::mem::discriminant(&expected),
+        );
+    }
+
+    #[tokio::test]
+    async fn valid_token() {
+        let info = validate(KID, &valid_claims()).await.unwrap();
+        assert_eq!(info.id, "apple-account-id");
+        assert_eq!(info.email.as_deref(), Some("test@example.com"));
+    }
+
+    #[tokio::test]
+    async fn wrong_audience() {
+        let mut claims = valid_claims();
+        claims["aud"] = json!("com.example.other");
+        assert_error(
+            validate(KID, &claims).await,
+            SignInWithAppleError::InvalidToken,
+        );
+    }
+
+    #[tokio::test]
+    async fn wrong_issuer() {
+        let mut claims = valid_claims();
+        claims["iss"] = json!("https://example.com");
+        assert_error(
+            validate(KID, &claims).await,
+            SignInWithAppleError::InvalidToken,
+        );
+    }
+
+    #[tokio::test]
+    async fn expired_token() {
+        let mut claims = valid_claims();
+        claims["exp"] = json!(current_time() - 3600);
+        assert_error(
+            validate(KID, &claims).await,
+            SignInWithAppleError::InvalidToken,
+        );
+    }
+
+    #[tokio::test]
+    async fn nonce_mismatch() {
+        let mut claims = valid_claims();
+        claims["nonce"] = json!(base16ct::lower::encode_string(&Sha256::digest(b"other")));
+        assert_error(
+            validate(KID, &claims).await,
+            SignInWithAppleError::InvalidNonce,
+        );
+    }
 
-        Err(SignInWithAppleError::InvalidToken.report())
+    #[tokio::test]
+    async fn unknown_kid() {
+        assert_error(
+            validate("unknown-kid", &valid_claims()).await,
+            SignInWithAppleError::JwkNotFound,
+        );
     }
 }
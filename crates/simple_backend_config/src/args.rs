@@ -27,18 +27,128 @@ pub struct ImageProcessModeArgs {
     /// Output jpeg image file. Will be overwritten if exists.
     #[arg(long, value_name = "FILE")]
     pub output: PathBuf,
+
+    /// Output medium size jpeg image file. Will be overwritten if exists.
+    #[arg(long, value_name = "FILE")]
+    pub output_medium: Option<PathBuf>,
+
+    /// Output small square jpeg thumbnail file. Will be overwritten
+    /// if exists.
+    #[arg(long, value_name = "FILE")]
+    pub output_small: Option<PathBuf>,
+
+    /// Write only small square thumbnail to the output file.
+    /// Face detection is skipped.
+    #[arg(long)]
+    pub small_only: bool,
+
+    /// Thumbnail crop area size. See [SmallImageCrop] for details.
+    /// If crop is not set, the thumbnail is the largest centered square.
+    #[arg(long, requires_all = ["small_crop_x", "small_crop_y"])]
+    pub small_crop_size: Option<f64>,
+
+    /// Thumbnail crop area X coordinate.
+    #[arg(long, requires = "small_crop_size")]
+    pub small_crop_x: Option<f64>,
+
+    /// Thumbnail crop area Y coordinate.
+    #[arg(long, requires = "small_crop_size")]
+    pub small_crop_y: Option<f64>,
+}
+
+impl ImageProcessModeArgs {
+    pub fn small_crop(&self) -> Option<SmallImageCrop> {
+        Some(SmallImageCrop {
+            size: self.small_crop_size?,
+            x: self.small_crop_x?,
+            y: self.small_crop_y?,
+        })
+    }
+}
+
+/// Square crop area for small image variant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmallImageCrop {
+    /// Square side length relative to the image width or height
+    /// depending on which one is smaller.
+    pub size: f64,
+    /// Top left corner X coordinate relative to the image width.
+    pub x: f64,
+    /// Top left corner Y coordinate relative to the image height.
+    pub y: f64,
 }
 
 #[derive(Debug, Clone, PartialEq, ValueEnum)]
 pub enum InputFileType {
     JpegImage,
+    PngImage,
+    WebpImage,
+    /// HEIC image. Decoding requires that the image process binary is
+    /// built with `heic` feature.
+    HeicImage,
 }
 
 impl InputFileType {
+    /// Byte count which [Self::from_file_header] needs for detecting
+    /// all supported file types.
+    pub const FILE_HEADER_LEN: usize = 12;
+
     pub fn to_cmd_arg_value(&self) -> String {
         self.to_possible_value()
             .expect("Input file type variant hidden by mistake")
             .get_name()
             .to_string()
     }
+
+    /// Detect file type from the first [Self::FILE_HEADER_LEN] bytes
+    /// of the file.
+    ///
+    /// Returns `None` if the file type is not supported.
+    pub fn from_file_header(header: &[u8]) -> Option<Self> {
+        const HEIC_BRANDS: &[&[u8]] = &[
+            b"heic", b"heix", b"hevc", b"hevx", b"heim", b"heis", b"mif1", b"msf1",
+        ];
+
+        if header.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(Self::JpegImage)
+        } else if header.starts_with(b"\x89PNG\r\n\x1A\n") {
+            Some(Self::PngImage)
+        } else if header.len() >= 12 && &header[0..4] == b"RIFF" && &header[8..12] == b"WEBP" {
+            Some(Self::WebpImage)
+        } else if header.len() >= 12
+            && &header[4..8] == b"ftyp"
+            && HEIC_BRANDS.contains(&&header[8..12])
+        {
+            Some(Self::HeicImage)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_type_detection() {
+        assert_eq!(
+            InputFileType::from_file_header(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some(InputFileType::JpegImage)
+        );
+        assert_eq!(
+            InputFileType::from_file_header(b"\x89PNG\r\n\x1A\n\0\0\0\x0D"),
+            Some(InputFileType::PngImage)
+        );
+        assert_eq!(
+            InputFileType::from_file_header(b"RIFF\0\0\0\0WEBP"),
+            Some(InputFileType::WebpImage)
+        );
+        assert_eq!(
+            InputFileType::from_file_header(b"\0\0\0\x18ftypheic"),
+            Some(InputFileType::HeicImage)
+        );
+        assert_eq!(InputFileType::from_file_header(b"GIF89a"), None);
+        assert_eq!(InputFileType::from_file_header(&[]), None);
+    }
 }
@@ -26,9 +26,11 @@ pub const DEFAULT_CONFIG_FILE_TEXT: &str = r#"
 public_api = "127.0.0.1:3000"
 public_bot_api = "127.0.0.1:3001"
 local_bot_api_port = 3002
+# metrics_api = "127.0.0.1:3003"
 
 [data]
 dir = "data"
+# secure_storage_dir = "/afrodite-secure-storage"
 
 [[data.sqlite]]
 name = "current"
@@ -56,6 +58,10 @@ name = "history"
 # client_id_web = "id"
 # client_id_server = "id"
 
+# [sign_in_with_apple]
+# app_bundle_id = "com.example.app"
+# service_id = "com.example.service" # optional, enables web client support
+
 # [firebase_cloud_messaging]
 # service_account_key_path = "server_config/service_account_key.json"
 # token_cache_path = "firebase_token_cache.json"
@@ -132,6 +138,7 @@ pub struct SimpleBackendConfigFile {
     pub tile_map: Option<TileMapConfig>,
     pub manager: Option<ManagerConfig>,
     pub sign_in_with_google: Option<SignInWithGoogleConfig>,
+    pub sign_in_with_apple: Option<SignInWithAppleConfig>,
     pub firebase_cloud_messaging: Option<FirebaseCloudMessagingConfig>,
     pub email_sending: Option<EmailSendingConfig>,
 
@@ -158,17 +165,20 @@ impl SimpleBackendConfigFile {
             data: DataConfig {
                 dir: PathBuf::new(),
                 sqlite: vec![],
+                secure_storage_dir: None,
             },
             socket: SocketConfig {
                 public_api: None,
                 public_bot_api: None,
                 local_bot_api_port: None,
                 experimental_internal_api: None,
+                metrics_api: None,
             },
             email_sending: None,
             tile_map: None,
             manager: None,
             sign_in_with_google: None,
+            sign_in_with_apple: None,
             firebase_cloud_messaging: None,
             tls: None,
             lets_encrypt: None,
@@ -236,6 +246,9 @@ pub struct DataConfig {
     /// Data directory for SQLite databases and other files.
     pub dir: PathBuf,
     pub sqlite: Vec<SqliteDatabase>,
+    /// Optional. Secure storage mount point. If configured, free disk
+    /// space and i-node counts of the mount are saved to perf history.
+    pub secure_storage_dir: Option<PathBuf>,
 }
 
 impl DataConfig {
@@ -295,6 +308,9 @@ pub struct SocketConfig {
     /// when backend is running in microservice mode.
     /// The microservice mode is not currently working properly.
     pub experimental_internal_api: Option<SocketAddr>,
+    /// Prometheus metrics API (path `/metrics`). TLS is not used
+    /// for this socket.
+    pub metrics_api: Option<SocketAddr>,
 }
 
 impl SocketConfig {
@@ -340,6 +356,19 @@ pub struct SignInWithGoogleConfig {
     pub client_id_server: String,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SignInWithAppleConfig {
+    /// iOS app bundle ID. Apple ID tokens from the iOS client have
+    /// this as the audience.
+    pub app_bundle_id: String,
+    /// Services ID for Sign in with Apple on the web client. Apple ID tokens
+    /// from the web client have this as the audience.
+    pub service_id: Option<String>,
+    /// Override Apple public keys (JwkSet) URL. Tests can use this to
+    /// download the keys from a local server.
+    pub public_keys_url: Option<Url>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct FirebaseCloudMessagingConfig {
     /// Path to service account key JSON file.
@@ -363,6 +392,10 @@ pub struct EmailSendingConfig {
     pub email_from_header: EmailFromHeader,
     pub send_limit_per_minute: Option<NonZeroU32>,
     pub send_limit_per_day: Option<NonZeroU32>,
+    /// Send emails without TLS using this SMTP server port. Tests can use
+    /// this to receive emails using a local SMTP server. Only available
+    /// in debug mode.
+    pub debug_smtp_port: Option<u16>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -477,6 +510,10 @@ fn validate_path(input: &Path) -> std::result::Result<(), String> {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ScheduledTasksConfig {
     pub daily_start_time: UtcTimeValue,
+    /// Run scheduled tasks also when the server starts. Debug mode
+    /// must be enabled.
+    #[serde(default)]
+    pub debug_run_on_start: bool,
 }
 
 impl Default for ScheduledTasksConfig {
@@ -485,6 +522,7 @@ impl Default for ScheduledTasksConfig {
 
         Self {
             daily_start_time: UtcTimeValue(DEFAULT_SCHEDULED_TASKS_TIME),
+            debug_run_on_start: false,
         }
     }
 }
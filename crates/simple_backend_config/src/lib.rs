@@ -25,7 +25,7 @@ use rustls_pemfile::certs;
 use tokio_rustls::rustls::ServerConfig;
 
 use self::file::{
-    ManagerConfig, SignInWithGoogleConfig,
+    ManagerConfig, SignInWithAppleConfig, SignInWithGoogleConfig,
     SimpleBackendConfigFile, SocketConfig,
 };
 
@@ -111,6 +111,10 @@ impl SimpleBackendConfig {
         &self.data_dir
     }
 
+    pub fn secure_storage_dir(&self) -> Option<&Path> {
+        self.file.data.secure_storage_dir.as_deref()
+    }
+
     pub fn databases(&self) -> &Vec<DatabaseInfo> {
         &self.databases
     }
@@ -131,6 +135,7 @@ impl SimpleBackendConfig {
     /// * SQLite in RAM mode is allowed.
     /// * Atomic boolean `RUNNING_IN_DEBUG_MODE` is set to `true`.
     /// * Experimental internal API can be enabled.
+    /// * Sending emails without TLS is possible.
     pub fn debug_mode(&self) -> bool {
         self.file.general.debug.unwrap_or(false)
     }
@@ -143,6 +148,10 @@ impl SimpleBackendConfig {
         self.file.sign_in_with_google.as_ref()
     }
 
+    pub fn sign_in_with_apple_config(&self) -> Option<&SignInWithAppleConfig> {
+        self.file.sign_in_with_apple.as_ref()
+    }
+
     pub fn firebase_cloud_messaging_config(&self) -> Option<&FirebaseCloudMessagingConfig> {
         self.file.firebase_cloud_messaging.as_ref()
     }
@@ -317,13 +326,14 @@ pub fn get_config(
     };
 
     let databases = file_config.data.get_databases()?;
+    let sign_in_with_urls = SignInWithUrls::new(&file_config)?;
 
     let config = SimpleBackendConfig {
         file: file_config,
         data_dir,
         databases,
         sqlite_in_ram,
-        sign_in_with_urls: SignInWithUrls::new()?,
+        sign_in_with_urls,
         public_api_tls_config,
         internal_api_tls_config,
         internal_api_root_certificate,
@@ -356,18 +366,32 @@ impl InternalApiUrls {
 }
 
 const GOOGLE_PUBLIC_KEY_URL: &str = "https://www.googleapis.com/oauth2/v3/certs";
+const APPLE_PUBLIC_KEY_URL: &str = "https://appleid.apple.com/auth/keys";
 
 #[derive(Debug, Clone)]
 pub struct SignInWithUrls {
     /// Request to this should return JwkSet.
     pub google_public_keys: Url,
+    /// Request to this should return JwkSet.
+    pub apple_public_keys: Url,
 }
 
 impl SignInWithUrls {
-    pub fn new() -> Result<Self, GetConfigError> {
+    pub fn new(file: &SimpleBackendConfigFile) -> Result<Self, GetConfigError> {
+        let apple_public_keys = match file
+            .sign_in_with_apple
+            .as_ref()
+            .and_then(|v| v.public_keys_url.clone())
+        {
+            Some(url) => url,
+            None => Url::parse(APPLE_PUBLIC_KEY_URL)
+                .change_context(GetConfigError::ConstUrlParsingFailed)?,
+        };
+
         Ok(Self {
             google_public_keys: Url::parse(GOOGLE_PUBLIC_KEY_URL)
                 .change_context(GetConfigError::ConstUrlParsingFailed)?,
+            apple_public_keys,
         })
     }
 }
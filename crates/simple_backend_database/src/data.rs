@@ -28,9 +28,19 @@ pub fn create_dirs_and_get_sqlite_database_file_path(
         fs::create_dir(&db_dir).change_context(SimpleDatabaseError::FilePathCreationFailed)?;
     }
 
-    let db_file = db_dir.join(format!("{}.db", database_info.name));
+    Ok(sqlite_database_file_path(config, database_info))
+}
 
-    Ok(db_file)
+/// Path to SQLite database file. The directories might not exist.
+pub fn sqlite_database_file_path(
+    config: &SimpleBackendConfig,
+    database_info: &SqliteDatabase,
+) -> PathBuf {
+    config
+        .data_dir()
+        .join(SQLITE_DIR_NAME)
+        .join(&database_info.name)
+        .join(format!("{}.db", database_info.name))
 }
 
 pub fn create_dirs_and_get_files_dir_path(
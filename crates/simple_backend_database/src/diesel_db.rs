@@ -1,4 +1,7 @@
-use std::{fmt, path::PathBuf};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
 
 use diesel::{Connection, RunQueryDsl, SqliteConnection};
 use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
@@ -269,6 +272,31 @@ pub fn sqlite_setup_connection(
     Ok(())
 }
 
+#[derive(diesel::QueryableByName)]
+struct IntegrityCheckRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    integrity_check: String,
+}
+
+/// Run `PRAGMA integrity_check` for SQLite database file.
+///
+/// Returns the found errors. Empty list means that the database is valid.
+pub fn sqlite_integrity_check(db_file: &Path) -> Result<Vec<String>, DieselDatabaseError> {
+    let db_path = db_file
+        .to_str()
+        .ok_or(DieselDatabaseError::Connect.report())?;
+    let mut conn =
+        SqliteConnection::establish(db_path).change_context(DieselDatabaseError::Connect)?;
+    let rows: Vec<IntegrityCheckRow> = diesel::sql_query("PRAGMA integrity_check;")
+        .load(&mut conn)
+        .change_context(DieselDatabaseError::Execute)?;
+    Ok(rows
+        .into_iter()
+        .map(|row| row.integrity_check)
+        .filter(|result| result != "ok")
+        .collect())
+}
+
 #[derive(Clone)]
 pub struct DieselReadHandle {
     pool: DieselPool,
@@ -16,6 +16,10 @@ use error_stack::{Result, ResultExt};
 use simple_backend_config::SimpleBackendConfig;
 use simple_backend_utils::ContextExt;
 
+// Only the `bundled` feature is needed from this crate, so that
+// SQLite with FTS5 support is always available.
+use libsqlite3_sys as _;
+
 pub type PoolObject = diesel_db::PoolObject;
 
 #[derive(thiserror::Error, Debug)]
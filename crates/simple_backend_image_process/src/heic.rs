@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use error_stack::{report, Result, ResultExt};
+use image::{DynamicImage, RgbImage};
+use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+
+use crate::ImageProcessError;
+
+/// Decode primary image of HEIC file. Image transformations
+/// like rotation are applied during decoding.
+pub fn read_heic_image(input: &Path) -> Result<DynamicImage, ImageProcessError> {
+    let input = input
+        .to_str()
+        .ok_or_else(|| report!(ImageProcessError::InputReadingFailed))
+        .attach_printable("Input path is not valid UTF-8")?;
+    let lib_heif = LibHeif::new();
+    let context =
+        HeifContext::read_from_file(input).change_context(ImageProcessError::InputReadingFailed)?;
+    let handle = context
+        .primary_image_handle()
+        .change_context(ImageProcessError::InputReadingFailed)?;
+    let img = lib_heif
+        .decode(&handle, ColorSpace::Rgb(RgbChroma::Rgb), None)
+        .change_context(ImageProcessError::InputReadingFailed)?;
+
+    let plane = img
+        .planes()
+        .interleaved
+        .ok_or_else(|| report!(ImageProcessError::InputReadingFailed))
+        .attach_printable("Decoded image does not have interleaved plane")?;
+
+    let width = plane.width as usize;
+    let row_len = width * 3;
+    let mut data = Vec::with_capacity(row_len * plane.height as usize);
+    for row in plane.data.chunks(plane.stride).take(plane.height as usize) {
+        let row = row
+            .get(..row_len)
+            .ok_or_else(|| report!(ImageProcessError::InputReadingFailed))?;
+        data.extend_from_slice(row);
+    }
+
+    let img = RgbImage::from_raw(plane.width, plane.height, data)
+        .ok_or_else(|| report!(ImageProcessError::InputReadingFailed))?;
+    Ok(DynamicImage::ImageRgb8(img))
+}
@@ -1,20 +1,42 @@
-use std::io::Write;
+use std::io::{Read, Write};
 
 use error_stack::{report, Result, ResultExt};
-use image::{DynamicImage, EncodableLayout, GrayImage, ImageDecoder, ImageReader};
+use image::{
+    metadata::Orientation, DynamicImage, EncodableLayout, GrayImage, ImageDecoder, ImageReader,
+    Rgb, RgbImage,
+};
 use serde::{Deserialize, Serialize};
 use simple_backend_config::{
-    args::{ImageProcessModeArgs, InputFileType},
+    args::{ImageProcessModeArgs, InputFileType, SmallImageCrop},
     file::ImageProcessingConfig,
 };
 
+#[cfg(feature = "heic")]
+mod heic;
+
 const SOURCE_IMG_MIN_WIDTH_AND_HEIGHT: u32 = 512;
 
+/// Max width or height of medium size image variant.
+const MEDIUM_IMG_MAX_WIDTH_AND_HEIGHT: u32 = 960;
+
+/// Width and height of small square image variant.
+const SMALL_IMG_WIDTH_AND_HEIGHT: u32 = 384;
+
+/// Image process exit code when [ImageProcessError::UnsupportedInputFormat]
+/// error happens.
+pub const EXIT_CODE_UNSUPPORTED_INPUT_FORMAT: u8 = 3;
+
+/// Image process exit code when other [ImageProcessError] happens.
+pub const EXIT_CODE_FAILURE: u8 = 1;
+
 #[derive(thiserror::Error, Debug)]
 pub enum ImageProcessError {
     #[error("Input reading failed")]
     InputReadingFailed,
 
+    #[error("Unsupported input format")]
+    UnsupportedInputFormat,
+
     #[error("Mozjpeg panic detected")]
     MozjpegPanic,
 
@@ -43,31 +65,43 @@ pub enum ImageProcessError {
     Stdout,
 }
 
+impl ImageProcessError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::UnsupportedInputFormat => EXIT_CODE_UNSUPPORTED_INPUT_FORMAT,
+            _ => EXIT_CODE_FAILURE,
+        }
+    }
+}
+
 /// Image process returns this info as JSON to standard output.
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct ImageProcessingInfo {
     pub face_detected: bool,
+    /// Difference hash (dHash) of the processed image. Near-duplicate
+    /// images have small Hamming distance between the hashes.
+    #[serde(default)]
+    pub perceptual_hash: Option<u64>,
 }
 
 pub fn handle_image(
     args: ImageProcessModeArgs,
     config: ImageProcessingConfig,
 ) -> Result<(), ImageProcessError> {
-    let format = match args.input_file_type {
-        InputFileType::JpegImage => image::ImageFormat::Jpeg,
+    let (img, orientation) = match args.input_file_type {
+        InputFileType::JpegImage => read_image(&args, image::ImageFormat::Jpeg)?,
+        InputFileType::PngImage => read_image(&args, image::ImageFormat::Png)?,
+        InputFileType::WebpImage => read_image(&args, image::ImageFormat::WebP)?,
+        InputFileType::HeicImage => read_heic_image(&args)?,
     };
 
-    let mut img_reader =
-        ImageReader::open(&args.input).change_context(ImageProcessError::InputReadingFailed)?;
-    img_reader.set_format(format);
-    let mut img_decoder = img_reader
-        .into_decoder()
-        .change_context(ImageProcessError::InputReadingFailed)?;
-    let orientation = img_decoder
-        .orientation()
-        .change_context(ImageProcessError::ExifReadingFailed)?;
-    let img = DynamicImage::from_decoder(img_decoder)
-        .change_context(ImageProcessError::InputReadingFailed)?;
+    if args.small_only {
+        let mut img = img;
+        img.apply_orientation(orientation);
+        let small = small_image(&img, args.small_crop());
+        write_jpeg(&args.output, small, &config)?;
+        return write_info(&ImageProcessingInfo::default());
+    }
 
     if img.width() < SOURCE_IMG_MIN_WIDTH_AND_HEIGHT
         || img.height() < SOURCE_IMG_MIN_WIDTH_AND_HEIGHT
@@ -77,10 +111,78 @@ pub fn handle_image(
 
     let mut img = resize_image_if_needed(img);
     img.apply_orientation(orientation);
+
+    // Medium size variant is not created if the image is already
+    // small enough, so the original image is used instead of it.
+    if let Some(output_medium) = &args.output_medium {
+        if img.width().max(img.height()) > MEDIUM_IMG_MAX_WIDTH_AND_HEIGHT {
+            let medium = img.resize(
+                MEDIUM_IMG_MAX_WIDTH_AND_HEIGHT,
+                MEDIUM_IMG_MAX_WIDTH_AND_HEIGHT,
+                image::imageops::FilterType::Lanczos3,
+            );
+            write_jpeg(output_medium, medium, &config)?;
+        }
+    }
+
+    if let Some(output_small) = &args.output_small {
+        let small = small_image(&img, args.small_crop());
+        write_jpeg(output_small, small, &config)?;
+    }
+
+    let data_face_detection = img.to_luma8();
+    let perceptual_hash = difference_hash(&img);
+    write_jpeg(&args.output, img, &config)?;
+
+    let face_detected = match detect_face(config, data_face_detection) {
+        Ok(face_detected) => face_detected,
+        Err(e) => {
+            // Ignore
+            eprintln!("{:?}", e);
+            false
+        }
+    };
+
+    write_info(&ImageProcessingInfo {
+        face_detected,
+        perceptual_hash: Some(perceptual_hash),
+    })
+}
+
+/// Difference hash (dHash). Every bit tells is the pixel brighter than
+/// its left neighbour in 9x8 grayscale version of the image.
+fn difference_hash(img: &DynamicImage) -> u64 {
+    let small = img
+        .resize_exact(9, 8, image::imageops::FilterType::Triangle)
+        .to_luma8();
+    let mut hash = 0;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y).0[0] < small.get_pixel(x + 1, y).0[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+fn write_info(info: &ImageProcessingInfo) -> Result<(), ImageProcessError> {
+    let mut stdout = std::io::stdout();
+    serde_json::to_writer(&stdout, info).change_context(ImageProcessError::Stdout)?;
+    stdout.flush().change_context(ImageProcessError::Stdout)?;
+    Ok(())
+}
+
+/// Encode image as JPEG and write it to the output file.
+fn write_jpeg(
+    output: &std::path::Path,
+    img: DynamicImage,
+    config: &ImageProcessingConfig,
+) -> Result<(), ImageProcessError> {
     let width = img.width();
     let height = img.height();
-    let data_face_detection = img.to_luma8();
-    let data = img.into_rgb8();
+    let data = into_rgb8_with_white_background(img);
 
     let result = std::panic::catch_unwind(|| -> Result<Vec<u8>, ImageProcessError> {
         let mut compress = mozjpeg::Compress::new(mozjpeg::ColorSpace::JCS_RGB);
@@ -120,22 +222,107 @@ pub fn handle_image(
     }
     .change_context(ImageProcessError::EncodingError)?;
 
-    std::fs::write(&args.output, data).change_context(ImageProcessError::FileWriting)?;
+    std::fs::write(output, data).change_context(ImageProcessError::FileWriting)
+}
 
-    let info = match detect_face(config, data_face_detection) {
-        Ok(info) => info,
-        Err(e) => {
-            // Ignore
-            eprintln!("{:?}", e);
-            ImageProcessingInfo::default()
+/// Create small square image. If crop is not set, the largest centered
+/// square is used.
+fn small_image(img: &DynamicImage, crop: Option<SmallImageCrop>) -> DynamicImage {
+    let (side, x, y) = small_image_crop_area(img.width(), img.height(), crop);
+    img.crop_imm(x, y, side, side).resize_exact(
+        SMALL_IMG_WIDTH_AND_HEIGHT,
+        SMALL_IMG_WIDTH_AND_HEIGHT,
+        image::imageops::FilterType::Lanczos3,
+    )
+}
+
+/// Returns square side length and top left corner coordinates
+/// for the small image crop area.
+fn small_image_crop_area(width: u32, height: u32, crop: Option<SmallImageCrop>) -> (u32, u32, u32) {
+    let min_side = width.min(height);
+    match crop {
+        Some(crop) => {
+            // Float to integer casts saturate and NaN becomes zero.
+            let side = ((crop.size * min_side as f64).round() as u32).clamp(1, min_side);
+            let x = ((crop.x * width as f64).round() as u32).min(width - side);
+            let y = ((crop.y * height as f64).round() as u32).min(height - side);
+            (side, x, y)
         }
-    };
+        None => (min_side, (width - min_side) / 2, (height - min_side) / 2),
+    }
+}
 
-    let mut stdout = std::io::stdout();
-    serde_json::to_writer(&stdout, &info).change_context(ImageProcessError::Stdout)?;
-    stdout.flush().change_context(ImageProcessError::Stdout)?;
+/// Read image and its EXIF orientation.
+///
+/// Returns [ImageProcessError::UnsupportedInputFormat] if the file
+/// header does not match any supported file type.
+fn read_image(
+    args: &ImageProcessModeArgs,
+    format: image::ImageFormat,
+) -> Result<(DynamicImage, Orientation), ImageProcessError> {
+    let mut header = Vec::with_capacity(InputFileType::FILE_HEADER_LEN);
+    std::fs::File::open(&args.input)
+        .change_context(ImageProcessError::InputReadingFailed)?
+        .take(InputFileType::FILE_HEADER_LEN as u64)
+        .read_to_end(&mut header)
+        .change_context(ImageProcessError::InputReadingFailed)?;
+    if InputFileType::from_file_header(&header).is_none() {
+        return Err(report!(ImageProcessError::UnsupportedInputFormat))
+            .attach_printable("Unknown file header");
+    }
 
-    Ok(())
+    let mut img_reader =
+        ImageReader::open(&args.input).change_context(ImageProcessError::InputReadingFailed)?;
+    img_reader.set_format(format);
+    let mut img_decoder = img_reader.into_decoder().map_err(|e| match e {
+        image::ImageError::Unsupported(_) => {
+            report!(e).change_context(ImageProcessError::UnsupportedInputFormat)
+        }
+        e => report!(e).change_context(ImageProcessError::InputReadingFailed),
+    })?;
+    let orientation = img_decoder
+        .orientation()
+        .change_context(ImageProcessError::ExifReadingFailed)?;
+    let img = DynamicImage::from_decoder(img_decoder)
+        .change_context(ImageProcessError::InputReadingFailed)?;
+    Ok((img, orientation))
+}
+
+/// HEIC decoder applies the image transformations, so returned
+/// orientation is always [Orientation::NoTransforms].
+#[cfg(feature = "heic")]
+fn read_heic_image(
+    args: &ImageProcessModeArgs,
+) -> Result<(DynamicImage, Orientation), ImageProcessError> {
+    let img = heic::read_heic_image(&args.input)?;
+    Ok((img, Orientation::NoTransforms))
+}
+
+#[cfg(not(feature = "heic"))]
+fn read_heic_image(
+    _args: &ImageProcessModeArgs,
+) -> Result<(DynamicImage, Orientation), ImageProcessError> {
+    Err(report!(ImageProcessError::UnsupportedInputFormat))
+        .attach_printable("HEIC support is not enabled")
+}
+
+/// Convert image to RGB. Transparent pixels are blended with white
+/// background as JPEG does not support transparency.
+fn into_rgb8_with_white_background(img: DynamicImage) -> RgbImage {
+    if !img.color().has_alpha() {
+        return img.into_rgb8();
+    }
+
+    let img = img.into_rgba8();
+    RgbImage::from_fn(img.width(), img.height(), |x, y| {
+        let [r, g, b, a] = img.get_pixel(x, y).0;
+        let blend = |c: u8| -> u8 {
+            let c = c as u32;
+            let a = a as u32;
+            ((c * a + 255 * (255 - a)) / 255) as u8
+        };
+        Rgb([blend(r), blend(g), blend(b)])
+    })
 }
 
 fn resize_image_if_needed(img: DynamicImage) -> DynamicImage {
@@ -157,14 +344,9 @@ fn resize_image_if_needed(img: DynamicImage) -> DynamicImage {
     }
 }
 
-fn detect_face(
-    config: ImageProcessingConfig,
-    data: GrayImage,
-) -> Result<ImageProcessingInfo, ImageProcessError> {
+fn detect_face(config: ImageProcessingConfig, data: GrayImage) -> Result<bool, ImageProcessError> {
     let Some(config) = config.seetaface else {
-        return Ok(ImageProcessingInfo {
-            face_detected: true,
-        });
+        return Ok(true);
     };
 
     let data = rustface::ImageData::new(&data, data.width(), data.height());
@@ -194,7 +376,181 @@ fn detect_face(
     }
     .change_context(ImageProcessError::FaceDetection)?;
 
-    Ok(ImageProcessingInfo {
-        face_detected: !data.is_empty(),
-    })
+    Ok(!data.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use image::{GrayImage, ImageFormat, Rgba, RgbaImage};
+
+    use super::*;
+
+    fn gradient(width: u32, height: u32, reverse: bool) -> DynamicImage {
+        DynamicImage::ImageLuma8(GrayImage::from_fn(width, height, |x, _| {
+            let value = (x * 255 / width) as u8;
+            image::Luma([if reverse { 255 - value } else { value }])
+        }))
+    }
+
+    #[test]
+    fn resized_image_has_same_difference_hash() {
+        let hash = difference_hash(&gradient(512, 512, false));
+        let resized_hash = difference_hash(&gradient(300, 200, false));
+        assert_eq!((hash ^ resized_hash).count_ones(), 0);
+    }
+
+    #[test]
+    fn different_image_has_different_difference_hash() {
+        let hash = difference_hash(&gradient(512, 512, false));
+        let reversed_hash = difference_hash(&gradient(512, 512, true));
+        assert_eq!((hash ^ reversed_hash).count_ones(), 64);
+    }
+
+    fn crop(size: f64, x: f64, y: f64) -> Option<SmallImageCrop> {
+        Some(SmallImageCrop { size, x, y })
+    }
+
+    #[test]
+    fn small_image_crop_area_without_crop_is_centered_square() {
+        assert_eq!(small_image_crop_area(1000, 600, None), (600, 200, 0));
+        assert_eq!(small_image_crop_area(600, 1000, None), (600, 0, 200));
+        assert_eq!(small_image_crop_area(512, 512, None), (512, 0, 0));
+    }
+
+    #[test]
+    fn small_image_crop_area_uses_crop_info() {
+        assert_eq!(
+            small_image_crop_area(1000, 600, crop(0.5, 0.1, 0.25)),
+            (300, 100, 150)
+        );
+        assert_eq!(
+            small_image_crop_area(600, 1000, crop(1.0, 0.0, 0.4)),
+            (600, 0, 400)
+        );
+    }
+
+    #[test]
+    fn small_image_crop_area_stays_inside_image() {
+        assert_eq!(
+            small_image_crop_area(1000, 600, crop(0.5, 0.9, 0.9)),
+            (300, 700, 300)
+        );
+        assert_eq!(
+            small_image_crop_area(1000, 600, crop(2.0, 0.0, 0.0)),
+            (600, 0, 0)
+        );
+        assert_eq!(
+            small_image_crop_area(1000, 600, crop(0.0, -1.0, 0.0)),
+            (1, 0, 0)
+        );
+        assert_eq!(
+            small_image_crop_area(1000, 600, crop(f64::NAN, f64::NAN, f64::INFINITY)),
+            (1, 0, 599)
+        );
+    }
+
+    #[test]
+    fn small_image_has_small_image_size() {
+        let img = small_image(&gradient(1000, 600, false), crop(0.5, 0.1, 0.25));
+        assert_eq!(img.width(), SMALL_IMG_WIDTH_AND_HEIGHT);
+        assert_eq!(img.height(), SMALL_IMG_WIDTH_AND_HEIGHT);
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "image_process_test_{}_{}",
+            name,
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn args(
+        input: PathBuf,
+        input_file_type: InputFileType,
+        output: PathBuf,
+    ) -> ImageProcessModeArgs {
+        ImageProcessModeArgs {
+            input,
+            input_file_type,
+            output,
+            output_medium: None,
+            output_small: None,
+            small_only: false,
+            small_crop_size: None,
+            small_crop_x: None,
+            small_crop_y: None,
+        }
+    }
+
+    /// Left half is transparent and right half is opaque black.
+    fn half_transparent_image() -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::from_fn(16, 16, |x, _| {
+            if x < 8 {
+                Rgba([0, 0, 0, 0])
+            } else {
+                Rgba([0, 0, 0, 255])
+            }
+        }))
+    }
+
+    fn half_transparent_image_to_jpeg(
+        name: &str,
+        format: ImageFormat,
+        input_file_type: InputFileType,
+    ) -> RgbImage {
+        let dir = test_dir(name);
+        let input = dir.join("input");
+        half_transparent_image()
+            .save_with_format(&input, format)
+            .unwrap();
+        let args = args(input, input_file_type, dir.join("output.jpg"));
+        let (img, _) = read_image(&args, format).unwrap();
+        write_jpeg(&args.output, img, &ImageProcessingConfig::default()).unwrap();
+        let jpeg = image::open(&args.output).unwrap().into_rgb8();
+        std::fs::remove_dir_all(&dir).unwrap();
+        jpeg
+    }
+
+    fn assert_transparent_pixels_are_white(jpeg: RgbImage) {
+        assert_eq!(jpeg.dimensions(), (16, 16));
+        // JPEG is lossy, so allow small differences
+        assert!(jpeg.get_pixel(2, 8).0.iter().all(|&c| c >= 250));
+        assert!(jpeg.get_pixel(13, 8).0.iter().all(|&c| c <= 5));
+    }
+
+    #[test]
+    fn png_transparent_pixels_are_white_in_jpeg() {
+        assert_transparent_pixels_are_white(half_transparent_image_to_jpeg(
+            "png",
+            ImageFormat::Png,
+            InputFileType::PngImage,
+        ));
+    }
+
+    #[test]
+    fn webp_transparent_pixels_are_white_in_jpeg() {
+        assert_transparent_pixels_are_white(half_transparent_image_to_jpeg(
+            "webp",
+            ImageFormat::WebP,
+            InputFileType::WebpImage,
+        ));
+    }
+
+    #[test]
+    fn unknown_file_header_is_unsupported_input_format() {
+        let dir = test_dir("unknown_header");
+        let input = dir.join("input");
+        std::fs::write(&input, b"GIF89a\0\0\0\0\0\0").unwrap();
+        let args = args(input, InputFileType::PngImage, dir.join("output.jpg"));
+        let result = read_image(&args, ImageFormat::Png);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            result.unwrap_err().current_context().exit_code(),
+            EXIT_CODE_UNSUPPORTED_INPUT_FORMAT
+        );
+    }
 }
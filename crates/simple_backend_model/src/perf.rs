@@ -1,13 +1,26 @@
-use diesel::{deserialize::FromSqlRow, expression::AsExpression, sql_types::Text};
+use std::collections::{BTreeMap, HashMap};
+
+use diesel::{deserialize::FromSqlRow, expression::AsExpression, sql_types::Text, Queryable};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
 use crate::{diesel_string_wrapper, UnixTime};
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, ToSchema)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, ToSchema)]
 pub enum TimeGranularity {
     Minutes,
     Hours,
+    Days,
+}
+
+impl TimeGranularity {
+    pub fn seconds(&self) -> i64 {
+        match self {
+            Self::Minutes => 60,
+            Self::Hours => 60 * 60,
+            Self::Days => 24 * 60 * 60,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, ToSchema, IntoParams)]
@@ -16,8 +29,76 @@ pub struct PerfMetricQuery {
     pub start_time: Option<UnixTime>,
     /// End time for query results.
     pub end_time: Option<UnixTime>,
+    /// Comma separated list of metric names. If empty, all metrics
+    /// are returned.
+    pub metrics: Option<String>,
+    /// Time granularity for query results. If empty, minutes is used
+    /// when the query results are available from the last 24 hours and
+    /// otherwise hours is used.
+    ///
+    /// Values for hours and days are read from the history database,
+    /// which contains hourly averages of the metrics.
+    pub time_granularity: Option<TimeGranularity>,
+}
+
+impl PerfMetricQuery {
+    /// Metric values with minute granularity are available for
+    /// this time.
+    pub const MINUTE_VALUES_SECONDS: i64 = 24 * 60 * 60;
+
+    pub fn metric_names(&self) -> Option<Vec<MetricName>> {
+        let names: Vec<MetricName> = self
+            .metrics
+            .as_deref()?
+            .split(',')
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .map(|name| MetricName::new(name.to_string()))
+            .collect();
+        if names.is_empty() {
+            None
+        } else {
+            Some(names)
+        }
+    }
+
+    pub fn is_metric_selected(&self, name: &MetricName) -> bool {
+        self.metric_names()
+            .map(|names| names.contains(name))
+            .unwrap_or(true)
+    }
+
+    /// Returns granularity for values which are read from the history
+    /// database or `None` if the values are available from the
+    /// minute values.
+    pub fn history_time_granularity(&self, current_time: UnixTime) -> Option<TimeGranularity> {
+        match self.time_granularity {
+            Some(TimeGranularity::Minutes) | None => {
+                let minute_values_start = current_time.ut - Self::MINUTE_VALUES_SECONDS;
+                match self.start_time {
+                    Some(start_time) if start_time.ut < minute_values_start => {
+                        Some(TimeGranularity::Hours)
+                    }
+                    _ => None,
+                }
+            }
+            Some(granularity) => Some(granularity),
+        }
+    }
+
+    /// Returns true if time is inside the query time range.
+    pub fn contains(&self, time: UnixTime) -> bool {
+        self.start_time
+            .map(|start| start.ut <= time.ut)
+            .unwrap_or(true)
+            && self.end_time.map(|end| time.ut <= end.ut).unwrap_or(true)
+    }
 }
 
+/// Values which are in between start time and the last time point.
+///
+/// The first value is the latest value and the following values
+/// are older.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, ToSchema)]
 pub struct PerfMetricValueArea {
     /// Time for first data point in values.
@@ -48,6 +129,74 @@ pub struct PerfMetricQueryResult {
     pub metrics: Vec<PerfMetricValues>,
 }
 
+impl PerfMetricQueryResult {
+    /// Downsample values from the history database.
+    ///
+    /// Values are averages of history values in the time granularity
+    /// sized time slots. Zero values are not saved to the history
+    /// database, so missing value for some save time is handled as a
+    /// zero value. Every continuous range of time slots which have
+    /// save times creates a new [PerfMetricValueArea].
+    pub fn from_history(
+        save_times: &[UnixTime],
+        values: Vec<PerfHistoryValue>,
+        time_granularity: TimeGranularity,
+    ) -> Self {
+        let slot_seconds = time_granularity.seconds();
+        let slot = |time: UnixTime| time.ut.div_euclid(slot_seconds);
+
+        let mut save_time_counts: BTreeMap<i64, i64> = BTreeMap::new();
+        for time in save_times {
+            *save_time_counts.entry(slot(*time)).or_default() += 1;
+        }
+
+        let mut metric_sums: HashMap<MetricName, HashMap<i64, i64>> = HashMap::new();
+        for v in values {
+            *metric_sums
+                .entry(v.name)
+                .or_default()
+                .entry(slot(v.time))
+                .or_default() += v.value;
+        }
+
+        let mut metrics: Vec<PerfMetricValues> = metric_sums
+            .into_iter()
+            .map(|(name, sums)| {
+                let mut areas: Vec<PerfMetricValueArea> = vec![];
+                let mut previous_slot = None;
+                for (&slot, &count) in save_time_counts.iter().rev() {
+                    let average = sums.get(&slot).copied().unwrap_or_default() / count;
+                    let value = average.clamp(0, u32::MAX as i64) as u32;
+                    match areas.last_mut() {
+                        Some(area) if previous_slot == Some(slot + 1) => area.values.push(value),
+                        _ => areas.push(PerfMetricValueArea {
+                            start_time: UnixTime::new(slot * slot_seconds),
+                            time_granularity,
+                            values: vec![value],
+                        }),
+                    }
+                    previous_slot = Some(slot);
+                }
+                PerfMetricValues {
+                    name,
+                    values: areas,
+                }
+            })
+            .collect();
+        metrics.sort_by(|a, b| a.name.as_str().cmp(b.name.as_str()));
+
+        Self { metrics }
+    }
+}
+
+/// Hourly average of metric from the history database.
+#[derive(Debug, Clone, PartialEq, Queryable)]
+pub struct PerfHistoryValue {
+    pub time: UnixTime,
+    pub name: MetricName,
+    pub value: i64,
+}
+
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 pub struct MetricKey {
     category: &'static str,
@@ -57,6 +206,7 @@ pub struct MetricKey {
 impl MetricKey {
     const SYSTEM_CATEGORY: &str = "system";
     const SERVER_CATEGORY: &str = "server";
+    const SQLITE_CATEGORY: &str = "sqlite";
 
     pub const SYSTEM_CPU_USAGE: Self = Self {
         category: Self::SYSTEM_CATEGORY,
@@ -73,6 +223,33 @@ impl MetricKey {
         name: "websocket_connections",
     };
 
+    pub const SYSTEM_DATA_DIR_AVAILABLE_MIB: Self = Self {
+        category: Self::SYSTEM_CATEGORY,
+        name: "data_dir_available_mib",
+    };
+
+    pub const SYSTEM_DATA_DIR_AVAILABLE_INODES: Self = Self {
+        category: Self::SYSTEM_CATEGORY,
+        name: "data_dir_available_inodes",
+    };
+
+    pub const SYSTEM_SECURE_STORAGE_AVAILABLE_MIB: Self = Self {
+        category: Self::SYSTEM_CATEGORY,
+        name: "secure_storage_available_mib",
+    };
+
+    pub const SYSTEM_SECURE_STORAGE_AVAILABLE_INODES: Self = Self {
+        category: Self::SYSTEM_CATEGORY,
+        name: "secure_storage_available_inodes",
+    };
+
+    /// Size of all files in the data dir's files directory
+    /// which contains media content. Updated once per hour.
+    pub const SERVER_MEDIA_CONTENT_DIR_SIZE_MIB: Self = Self {
+        category: Self::SERVER_CATEGORY,
+        name: "media_content_dir_size_mib",
+    };
+
     pub fn new(category: &'static str, name: &'static str) -> MetricKey {
         Self {
             category,
@@ -80,6 +257,11 @@ impl MetricKey {
         }
     }
 
+    /// Metric for SQLite database file sizes.
+    pub fn sqlite(name: &'static str) -> MetricKey {
+        Self::new(Self::SQLITE_CATEGORY, name)
+    }
+
     pub fn to_name(&self) -> MetricName {
         let name = format!("{}_{}", self.category, self.name);
         MetricName::new(name)
@@ -101,3 +283,49 @@ impl MetricName {
 }
 
 diesel_string_wrapper!(MetricName);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(time: i64, value: i64) -> PerfHistoryValue {
+        PerfHistoryValue {
+            time: UnixTime::new(time),
+            name: MetricName::new("test".to_string()),
+            value,
+        }
+    }
+
+    #[test]
+    fn history_values_are_downsampled_to_days() {
+        const DAY: i64 = 24 * 60 * 60;
+        const HOUR: i64 = 60 * 60;
+        let save_times = [
+            UnixTime::new(HOUR),
+            UnixTime::new(2 * HOUR),
+            UnixTime::new(DAY + HOUR),
+            UnixTime::new(3 * DAY),
+        ];
+        // Zero value for the second save time is not saved.
+        let values = vec![value(HOUR, 10), value(DAY + HOUR, 4), value(3 * DAY, 1)];
+
+        let result =
+            PerfMetricQueryResult::from_history(&save_times, values, TimeGranularity::Days);
+
+        assert_eq!(
+            result.metrics[0].values,
+            vec![
+                PerfMetricValueArea {
+                    start_time: UnixTime::new(3 * DAY),
+                    time_granularity: TimeGranularity::Days,
+                    values: vec![1],
+                },
+                PerfMetricValueArea {
+                    start_time: UnixTime::new(DAY),
+                    time_granularity: TimeGranularity::Days,
+                    values: vec![4, 5],
+                },
+            ]
+        );
+    }
+}
@@ -120,17 +120,27 @@ impl TryFrom<String> for TimeValue {
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
-#[serde(try_from = "String")]
+#[serde(try_from = "String", into = "String")]
 pub struct DurationValue {
     pub seconds: u32,
 }
 
 impl DurationValue {
+    pub const fn from_minutes(minutes: u32) -> Self {
+        Self { seconds: minutes * 60 }
+    }
+
     pub const fn from_days(days: u32) -> Self {
         Self { seconds: days * 60 * 60 * 24 }
     }
 }
 
+impl From<DurationValue> for String {
+    fn from(value: DurationValue) -> Self {
+        format!("{}s", value.seconds)
+    }
+}
+
 impl TryFrom<String> for DurationValue {
     type Error = String;
     fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
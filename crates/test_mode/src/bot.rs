@@ -12,7 +12,7 @@ use std::{
     vec,
 };
 
-use actions::{admin::AdminBotState, profile::ProfileState};
+use actions::{admin::AdminBotState, chat::ChatState, profile::ProfileState};
 use api_client::models::{AccountId, EventToClient};
 use async_trait::async_trait;
 use config::{
@@ -213,6 +213,7 @@ pub struct BotState {
     pub benchmark: BenchmarkState,
     pub media: MediaState,
     pub profile: ProfileState,
+    pub chat: ChatState,
     pub admin: AdminBotState,
     pub connections: BotConnections,
     pub refresh_token: Option<Vec<u8>>,
@@ -246,6 +247,7 @@ impl BotState {
             action_history: vec![],
             media: MediaState::new(),
             profile: ProfileState::new(),
+            chat: ChatState::new(),
             admin: AdminBotState::default(),
             connections: BotConnections::default(),
             refresh_token: None,
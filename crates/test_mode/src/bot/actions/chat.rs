@@ -1 +1,17 @@
+use crate::bot::utils::encrypt::GeneratedKeys;
 
+#[derive(Debug, Default)]
+pub struct ChatState {
+    /// Keys are generated when bot starts. Previous keys are not
+    /// stored, so messages encrypted for previous keys can not be
+    /// decrypted.
+    pub keys: Option<GeneratedKeys>,
+    /// Server side ID for the public key in [Self::keys].
+    pub public_key_id: Option<i64>,
+}
+
+impl ChatState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
@@ -2,8 +2,11 @@
 
 use bstr::BStr;
 use pgp::{
-    crypto::hash::HashAlgorithm, ser::Serialize, Deserializable, Message, SignedPublicKey,
-    SignedSecretKey,
+    crypto::{ecc_curve::ECCCurve, hash::HashAlgorithm},
+    ser::Serialize,
+    types::SecretKeyTrait,
+    ArmorOptions, Deserializable, KeyType, Message, SecretKeyParamsBuilder, SignedPublicKey,
+    SignedSecretKey, SubkeyParamsBuilder,
 };
 use rand::rngs::OsRng;
 
@@ -37,6 +40,66 @@ pub enum MessageEncryptionError {
     DecryptDataDecryptedMessageCapacityTooLarge = 27,
 }
 
+/// Armored OpenPGP v4 keys. The primary key is EdDSALegacy (Ed25519)
+/// signing key and the subkey is ECDH Curve25519 encryption key.
+#[derive(Clone)]
+pub struct GeneratedKeys {
+    pub public: String,
+    pub private: String,
+}
+
+impl std::fmt::Debug for GeneratedKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GeneratedKeys")
+            .field("public", &self.public)
+            .finish_non_exhaustive()
+    }
+}
+
+pub fn generate_keys(account_id: String) -> Result<GeneratedKeys, MessageEncryptionError> {
+    let mut key_params = SecretKeyParamsBuilder::default();
+    key_params
+        .key_type(KeyType::EdDSALegacy)
+        .can_encrypt(false)
+        .can_certify(false)
+        .can_sign(true)
+        .primary_user_id(account_id)
+        .subkey(
+            SubkeyParamsBuilder::default()
+                .key_type(KeyType::ECDH(ECCCurve::Curve25519))
+                .can_authenticate(false)
+                .can_certify(false)
+                .can_encrypt(true)
+                .can_sign(false)
+                .build()
+                .map_err(|_| MessageEncryptionError::GenerateKeysPrivateKeySubKeyParams)?,
+        );
+
+    let private_key = key_params
+        .build()
+        .map_err(|_| MessageEncryptionError::GenerateKeysPrivateKeyParams)?
+        .generate(&mut OsRng)
+        .map_err(|_| MessageEncryptionError::GenerateKeysPrivateKeyGenerate)?
+        .sign(&mut OsRng, String::new)
+        .map_err(|_| MessageEncryptionError::GenerateKeysPrivateKeySign)?;
+
+    let public_key = private_key
+        .public_key()
+        .sign(&mut OsRng, &private_key, String::new)
+        .map_err(|_| MessageEncryptionError::GenerateKeysPublicKeySign)?;
+
+    let private = private_key
+        .to_armored_string(ArmorOptions::default())
+        .map_err(|_| MessageEncryptionError::GenerateKeysPrivateKeyArmor)?;
+    let public = public_key
+        .to_armored_string(ArmorOptions::default())
+        .map_err(|_| MessageEncryptionError::GenerateKeysPublicKeyArmor)?;
+
+    Ok(GeneratedKeys { public, private })
+}
+
+/// Sign data with sender private key and then encrypt the signed
+/// message to receiver public key.
 pub fn encrypt_data(
     // The sender private key can be used for signing the message
     data_sender_armored_private_key: &str,
@@ -56,7 +119,14 @@ pub fn encrypt_data(
         .first()
         .ok_or(MessageEncryptionError::EncryptDataPublicSubkeyMissing)?;
 
-    let armored_message = Message::new_literal_bytes(empty_file_name, data)
+    let message = Message::new_literal_bytes(empty_file_name, data)
+        .sign(
+            &mut OsRng,
+            &my_private_key,
+            String::new,
+            HashAlgorithm::SHA2_256,
+        )
+        .map_err(|_| MessageEncryptionError::EncryptDataSign)?
         // Compression is not done for now as this library does not
         // have possibility to limit decompressed data size.
         // If the data would be compressed, then denial of service attacks
@@ -67,10 +137,37 @@ pub fn encrypt_data(
             &[encryption_public_subkey],
         )
         .map_err(|_| MessageEncryptionError::EncryptDataEncrypt)?
-        .sign(&mut OsRng, &my_private_key, String::new, HashAlgorithm::SHA2_256)
-        .map_err(|_| MessageEncryptionError::EncryptDataSign)?
         .to_bytes()
         .map_err(|_| MessageEncryptionError::EncryptDataToBytes)?;
 
-    Ok(armored_message)
+    Ok(message)
+}
+
+/// Decrypt data with receiver private key and then verify the
+/// signature using sender public key.
+pub fn decrypt_data(
+    data_receiver_armored_private_key: &str,
+    data_sender_armored_public_key: &str,
+    data: &[u8],
+) -> Result<Vec<u8>, MessageEncryptionError> {
+    let (my_private_key, _) = SignedSecretKey::from_string(data_receiver_armored_private_key)
+        .map_err(|_| MessageEncryptionError::DecryptDataPrivateKeyParse)?;
+    let (other_person_public_key, _) = SignedPublicKey::from_string(data_sender_armored_public_key)
+        .map_err(|_| MessageEncryptionError::DecryptDataPublicKeyParse)?;
+
+    let message =
+        Message::from_bytes(data).map_err(|_| MessageEncryptionError::DecryptDataMessageParse)?;
+
+    let (decrypted_message, _) = message
+        .decrypt(String::new, &[&my_private_key])
+        .map_err(|_| MessageEncryptionError::DecryptDataDecrypt)?;
+
+    decrypted_message
+        .verify(&other_person_public_key)
+        .map_err(|_| MessageEncryptionError::DecryptDataVerify)?;
+
+    decrypted_message
+        .get_content()
+        .map_err(|_| MessageEncryptionError::DecryptDataDataNotFound)?
+        .ok_or(MessageEncryptionError::DecryptDataDataNotFound)
 }
@@ -14,6 +14,7 @@ use crate::{ServerTestError, TestFunction};
 
 pub mod assert;
 pub mod context;
+pub mod email;
 mod manager;
 
 pub struct QaTestRunner {
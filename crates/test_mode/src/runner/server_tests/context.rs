@@ -7,9 +7,10 @@ use api_client::{
         profile_api::{post_profile, post_search_age_range, post_search_groups},
     },
     models::{
-        AccountId, EventToClient, MediaContentType, ModerationQueueType, ProfileSearchAgeRange, ProfileUpdate, SearchGroups
+        AccountId, EventToClient, MediaContentType, ModerationQueueType, ProfileSearchAgeRange, ProfileUpdate, RefreshToken, SearchGroups
     },
 };
+use base64::Engine;
 use config::{args::TestMode, bot_config_file::BotConfigFile, Config};
 use error_stack::{Result, ResultExt};
 use tokio::sync::Mutex;
@@ -62,6 +63,28 @@ impl TestContext {
         }
     }
 
+    /// New context for a server which uses a different
+    /// account server API port.
+    pub fn new_for_server(&self, account_server_api_port: u16) -> Self {
+        Self::new(
+            self.config.clone(),
+            self.test_config.clone(),
+            Some(account_server_api_port),
+        )
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn test_config(&self) -> &TestMode {
+        &self.test_config
+    }
+
+    pub fn account_server_api_port(&self) -> Option<u16> {
+        self.account_server_api_port
+    }
+
     pub async fn close_websocket_connections(&mut self) {
         let mut state = self.state.lock().await;
         let mut connections = Vec::new();
@@ -278,6 +301,14 @@ impl Account {
         self.account_id().aid.to_string()
     }
 
+    /// Refresh token received from the current WebSocket connection.
+    pub fn refresh_token(&self) -> Option<RefreshToken> {
+        self.bot_state
+            .refresh_token
+            .as_ref()
+            .map(|token| RefreshToken::new(base64::engine::general_purpose::STANDARD.encode(token)))
+    }
+
     /// Only actions without TaskState usage are supported
     pub async fn run<T: BotAction>(&mut self, action: T) -> Result<(), TestError> {
         action.excecute_impl(&mut self.bot_state).await
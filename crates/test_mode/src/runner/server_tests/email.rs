@@ -0,0 +1,293 @@
+//! Server instance which sends emails to a local SMTP server
+
+use std::{path::PathBuf, time::Duration};
+
+use api_client::{
+    apis::account_api::{post_account_data, post_send_email_confirmation},
+    models::{AccountData, EmailConfirmationToken},
+};
+use config::file::{AccountLimitsConfig, Components, LimitsConfig};
+use error_stack::ResultExt;
+use simple_backend_config::file::{EmailFromHeader, EmailSendingConfig};
+use simple_backend_utils::UuidBase64Url;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+    task::JoinHandle,
+};
+
+use super::context::{Account, TestContext};
+use crate::{
+    client::TestError,
+    runner::bot::DataDirUtils,
+    server::{new_config, AdditionalSettings, ServerInstance},
+    ServerTestError,
+};
+
+const EMAIL_CONTENT: &str = r#"
+[[email]]
+message_type = "AccountRegistered"
+subject = "Account registered"
+body = "Account registered"
+
+[[email]]
+message_type = "EmailConfirmation"
+subject = "Email confirmation"
+body = "Token: {email_confirmation_token}"
+
+[[email]]
+message_type = "ChatNotificationDigest"
+subject = "Chat notifications"
+body = "Chat requests: {new_chat_requests_count}, messages: {new_messages_count}"
+"#;
+
+const EMAIL_CONFIRMATION_TOKEN_PREFIX: &str = "Token: ";
+
+const EMAIL_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Email which the local SMTP server received.
+#[derive(Debug, Default)]
+pub struct ReceivedEmail {
+    pub to: String,
+    pub body: String,
+}
+
+/// Server instance with email sending enabled. The server
+/// sends the emails to a local SMTP server.
+pub struct EmailTestServer {
+    /// Context for creating accounts to the server.
+    pub context: TestContext,
+    server: ServerInstance,
+    smtp: SmtpServer,
+    dir: PathBuf,
+    remove_dir: bool,
+}
+
+impl EmailTestServer {
+    pub async fn start(
+        context: &TestContext,
+        account_limits: AccountLimitsConfig,
+    ) -> Result<Self, ServerTestError> {
+        let api_port = context
+            .account_server_api_port()
+            .ok_or(TestError::MissingValue.report())?;
+        let server_port = api_port + 1000;
+
+        let dir = DataDirUtils::create_data_dir_if_needed(context.test_config())
+            .join(format!("email_test_{}", UuidBase64Url::new_random_id()));
+        std::fs::create_dir(&dir).unwrap();
+        let dir = std::fs::canonicalize(dir).unwrap();
+        let email_content_file = dir.join("email_content.toml");
+        std::fs::write(&email_content_file, EMAIL_CONTENT).unwrap();
+
+        let smtp = SmtpServer::new().await;
+
+        let (mut server_config, mut simple_backend_config) = new_config(
+            context.test_config(),
+            server_port,
+            Components::all_enabled(),
+            None,
+        );
+        server_config.config_files.email_content = Some(email_content_file);
+        server_config.limits = Some(LimitsConfig {
+            account: Some(account_limits),
+            ..LimitsConfig::default()
+        });
+        simple_backend_config.email_sending = Some(EmailSendingConfig {
+            smtp_server_address: "127.0.0.1".to_string(),
+            use_starttls_instead_of_smtps: false,
+            username: String::new(),
+            password: String::new(),
+            email_from_header: EmailFromHeader::try_from("Test <no-reply@example.com>".to_string())
+                .unwrap(),
+            send_limit_per_minute: None,
+            send_limit_per_day: None,
+            debug_smtp_port: Some(smtp.port),
+        });
+
+        let server = ServerInstance::new(
+            dir.clone(),
+            context.config(),
+            (server_config, simple_backend_config),
+            context.test_config(),
+            AdditionalSettings {
+                log_to_memory: true,
+                account_server_api_port: Some(server_port),
+                ..AdditionalSettings::default()
+            },
+        )
+        .await;
+
+        Ok(Self {
+            context: context.new_for_server(server_port),
+            server,
+            smtp,
+            dir,
+            remove_dir: !context.test_config().no_clean,
+        })
+    }
+
+    /// Wait next email which the server sends.
+    pub async fn wait_email(&mut self) -> Result<ReceivedEmail, ServerTestError> {
+        let email = tokio::time::timeout(EMAIL_WAIT_TIMEOUT, self.smtp.emails.recv())
+            .await
+            .change_context(TestError::ServerTestFailed)
+            .attach_printable("Email not received")?
+            .ok_or(TestError::ServerTestFailed.report())?;
+        Ok(parse_email(&email))
+    }
+
+    /// Returns true if the server sends an email during `duration`.
+    pub async fn email_received_during(&mut self, duration: Duration) -> bool {
+        tokio::time::timeout(duration, self.smtp.emails.recv())
+            .await
+            .is_ok()
+    }
+
+    /// Set email address for the account and request email confirmation
+    /// email. Returns the token from the received email.
+    pub async fn request_email_confirmation_token(
+        &mut self,
+        account: &Account,
+    ) -> Result<EmailConfirmationToken, ServerTestError> {
+        let email_address = format!("{}@example.test", account.account_id_string());
+        let data = AccountData {
+            email: Some(email_address.clone()),
+        };
+        post_account_data(account.account_api(), data).await?;
+        post_send_email_confirmation(account.account_api()).await?;
+
+        let email = self.wait_email().await?;
+        if email.to != email_address {
+            return Err(TestError::ServerTestFailed.report())
+                .attach_printable(format!("Unexpected email receiver: {}", email.to))
+                .map_err(Into::into);
+        }
+        let token = email
+            .body
+            .trim()
+            .strip_prefix(EMAIL_CONFIRMATION_TOKEN_PREFIX)
+            .ok_or(TestError::MissingValue.report())?;
+        Ok(EmailConfirmationToken::new(token.to_string()))
+    }
+
+    pub async fn close(mut self) {
+        self.context.close_websocket_connections().await;
+        self.server.close_and_maeby_remove_data(false).await;
+        self.smtp.task.abort();
+        if self.remove_dir {
+            std::fs::remove_dir_all(self.dir).unwrap();
+        }
+    }
+}
+
+/// Minimal SMTP server which accepts all emails.
+struct SmtpServer {
+    port: u16,
+    emails: mpsc::UnboundedReceiver<String>,
+    task: JoinHandle<()>,
+}
+
+impl SmtpServer {
+    async fn new() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (sender, emails) = mpsc::unbounded_channel();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle_smtp_connection(stream, sender.clone()));
+            }
+        });
+        Self { port, emails, task }
+    }
+}
+
+async fn handle_smtp_connection(
+    stream: TcpStream,
+    sender: mpsc::UnboundedSender<String>,
+) -> std::io::Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+    write.write_all(b"220 localhost ESMTP\r\n").await?;
+
+    let mut data: Option<String> = None;
+    while let Some(line) = lines.next_line().await? {
+        if let Some(email) = &mut data {
+            if line == "." {
+                let _ = sender.send(std::mem::take(email));
+                data = None;
+                write.write_all(b"250 OK\r\n").await?;
+            } else {
+                // Remove SMTP dot-stuffing
+                email.push_str(line.strip_prefix('.').unwrap_or(&line));
+                email.push('\n');
+            }
+            continue;
+        }
+
+        let command = line.to_ascii_uppercase();
+        let response: &[u8] = if command.starts_with("EHLO") {
+            b"250-localhost\r\n250 AUTH PLAIN\r\n"
+        } else if command.starts_with("HELO") {
+            b"250 localhost\r\n"
+        } else if command.starts_with("AUTH") {
+            b"235 Authentication successful\r\n"
+        } else if command.starts_with("DATA") {
+            data = Some(String::new());
+            b"354 Start mail input\r\n"
+        } else if command.starts_with("QUIT") {
+            write.write_all(b"221 Bye\r\n").await?;
+            return Ok(());
+        } else {
+            b"250 OK\r\n"
+        };
+        write.write_all(response).await?;
+    }
+
+    Ok(())
+}
+
+fn parse_email(data: &str) -> ReceivedEmail {
+    let (headers, body) = data.split_once("\n\n").unwrap_or((data, ""));
+    let mut email = ReceivedEmail::default();
+    let mut quoted_printable = false;
+    for header in headers.lines() {
+        if let Some(value) = header.strip_prefix("To: ") {
+            email.to = value.to_string();
+        } else if header == "Content-Transfer-Encoding: quoted-printable" {
+            quoted_printable = true;
+        }
+    }
+    email.body = if quoted_printable {
+        decode_quoted_printable(body)
+    } else {
+        body.to_string()
+    };
+    email
+}
+
+/// Email body with long lines is quoted-printable encoded.
+fn decode_quoted_printable(text: &str) -> String {
+    let text = text.replace("=\n", "");
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'=', Some(value)) => {
+                decoded.push(value);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
@@ -136,6 +136,7 @@ impl TestTask {
             Some(AdditionalSettings {
                 log_to_memory: true,
                 account_server_api_port: Some(self.api_port),
+                ..AdditionalSettings::default()
             }),
         )
         .await;
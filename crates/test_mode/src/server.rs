@@ -1,6 +1,6 @@
 use std::{
     env, num::NonZeroU8, os::unix::process::CommandExt, path::PathBuf,
-    process::Stdio, sync::Arc,
+    process::{Output, Stdio}, sync::Arc, time::Duration,
 };
 
 use config::{
@@ -29,6 +29,7 @@ use crate::runner::bot::DataDirUtils;
 pub const TEST_ADMIN_ACCESS_EMAIL: &str = "admin@example.com";
 
 pub const SERVER_INSTANCE_DIR_START: &str = "server_instance_";
+pub const MANAGER_INSTANCE_DIR_START: &str = "manager_instance_";
 
 pub const DEFAULT_LOCATION_CONFIG: LocationConfig = LocationConfig {
     latitude_top_left: 70.1,
@@ -51,6 +52,9 @@ pub struct AdditionalSettings {
     /// Store logs in RAM instead of using standard output or error.
     pub log_to_memory: bool,
     pub account_server_api_port: Option<u16>,
+    /// Store SQLite databases to files even if SQLite in RAM mode
+    /// is enabled.
+    pub disable_sqlite_in_ram: bool,
 }
 
 pub struct ServerManager {
@@ -132,7 +136,7 @@ impl ServerManager {
     }
 }
 
-fn new_config(
+pub fn new_config(
     config: &TestMode,
     bot_api_port: u16,
     components: Components,
@@ -186,6 +190,7 @@ fn new_config(
                     name: "history".into(),
                 },
             ],
+            secure_storage_dir: None,
         },
         socket: SocketConfig {
             public_api: None,
@@ -193,8 +198,10 @@ fn new_config(
             local_bot_api_port: Some(bot_api_port),
             // TODO(microservice): Configure internal API properly
             experimental_internal_api: None,
+            metrics_api: None,
         },
         sign_in_with_google: None,
+        sign_in_with_apple: None,
         manager: None,
         tls: None,
         lets_encrypt: None,
@@ -244,12 +251,7 @@ impl ServerInstance {
         )
         .unwrap();
 
-        let start_cmd = env::args().next().unwrap();
-        let start_cmd = std::fs::canonicalize(&start_cmd).unwrap();
-
-        if !start_cmd.is_file() {
-            panic!("First argument does not point to a file {:?}", &start_cmd);
-        }
+        let start_cmd = start_cmd();
 
         let log_value = if args_config.server.log_debug {
             "debug"
@@ -263,7 +265,7 @@ impl ServerInstance {
             .env("RUST_LOG", log_value)
             .process_group(0);
 
-        if all_config.simple_backend().sqlite_in_ram() {
+        if all_config.simple_backend().sqlite_in_ram() && !settings.disable_sqlite_in_ram {
             command.arg("--sqlite-in-ram");
         }
 
@@ -278,50 +280,12 @@ impl ServerInstance {
         let stderr = server.stderr.take().expect("Stderr handle is missing");
         let (start_sender, start_receiver) = tokio::sync::oneshot::channel::<()>();
 
-        fn create_read_lines_task(
-            stream: impl AsyncRead + Unpin + Send + 'static,
-            stream_name: &'static str,
-            logs: Arc<Mutex<Vec<String>>>,
-            log_to_memory: bool,
-            start_sender: Option<tokio::sync::oneshot::Sender<()>>,
-        ) -> JoinHandle<()> {
-            tokio::spawn(async move {
-                let mut start_sender = start_sender;
-                let mut line_stream = tokio::io::BufReader::new(stream).lines();
-                loop {
-                    let (line, stream_ended) = match line_stream.next_line().await {
-                        Ok(Some(line)) => (line, false),
-                        Ok(None) => (format!("Server {stream_name} closed"), true),
-                        Err(e) => (format!("Server {stream_name} error: {e:?}"), true),
-                    };
-
-                    if let Some(sender) = start_sender.take() {
-                        if line.contains(simple_backend::SERVER_START_MESSAGE) {
-                            sender.send(()).unwrap();
-                        } else {
-                            start_sender = Some(sender);
-                        }
-                    }
-
-                    if log_to_memory {
-                        logs.lock().await.push(line);
-                    } else {
-                        println!("{line}");
-                    }
-
-                    if stream_ended {
-                        break;
-                    }
-                }
-            })
-        }
-
         let stdout_task = create_read_lines_task(
             stdout,
             "stdout",
             logs.clone(),
             settings.log_to_memory,
-            Some(start_sender),
+            Some((start_sender, simple_backend::SERVER_START_MESSAGE)),
         );
         let stderr_task =
             create_read_lines_task(stderr, "stderr", logs.clone(), settings.log_to_memory, None);
@@ -342,7 +306,7 @@ impl ServerInstance {
         }
     }
 
-    async fn close_and_maeby_remove_data(mut self, remove: bool) {
+    pub async fn close_and_maeby_remove_data(mut self, remove: bool) {
         let id = self.server.id().unwrap();
         nix::sys::signal::kill(Pid::from_raw(id.try_into().unwrap()), Signal::SIGINT).unwrap(); // CTRL-C
         self.server.wait().await.unwrap();
@@ -364,3 +328,159 @@ impl ServerInstance {
         self.logs.lock().await.clone()
     }
 }
+
+/// Manager instance which runs in a new directory.
+pub struct ManagerInstance {
+    manager: Child,
+    dir: PathBuf,
+    stdout_task: JoinHandle<()>,
+    stderr_task: JoinHandle<()>,
+    logs: Arc<Mutex<Vec<String>>>,
+}
+
+impl ManagerInstance {
+    /// Start manager using `config` as manager config file content.
+    /// Logs are stored in RAM.
+    pub async fn new(dir: PathBuf, config: String) -> Self {
+        let id = simple_backend_utils::UuidBase64Url::new_random_id();
+        let dir = dir.join(format!(
+            "{}{}_{}",
+            MANAGER_INSTANCE_DIR_START,
+            chrono::Utc::now(),
+            id,
+        ));
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(dir.join(manager_config::file::CONFIG_FILE_NAME), config).unwrap();
+
+        let mut command = std::process::Command::new(start_cmd());
+        command
+            .current_dir(&dir)
+            .arg("manager")
+            .env("RUST_LOG", "info")
+            .process_group(0);
+
+        let mut tokio_command: tokio::process::Command = command.into();
+
+        tokio_command.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+        let mut manager = tokio_command.kill_on_drop(true).spawn().unwrap();
+
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let stdout = manager.stdout.take().expect("Stdout handle is missing");
+        let stderr = manager.stderr.take().expect("Stderr handle is missing");
+        let stdout_task = create_read_lines_task(stdout, "stdout", logs.clone(), true, None);
+        let stderr_task = create_read_lines_task(stderr, "stderr", logs.clone(), true, None);
+
+        Self {
+            manager,
+            dir,
+            stdout_task,
+            stderr_task,
+            logs,
+        }
+    }
+
+    /// Run manager API client command in the manager directory. The client
+    /// reads the API connection settings from the manager config file.
+    pub async fn run_api_command(&self, args: &[&std::ffi::OsStr]) -> Output {
+        tokio::process::Command::new(start_cmd())
+            .current_dir(&self.dir)
+            .arg("manager-api")
+            .args(args)
+            .output()
+            .await
+            .unwrap()
+    }
+
+    /// Returns false if the message is not logged before the timeout.
+    pub async fn wait_log_message(&self, message: &str, timeout: Duration) -> bool {
+        let wait = async {
+            loop {
+                if self.logs_contain(message).await {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        };
+        tokio::time::timeout(timeout, wait).await.is_ok()
+    }
+
+    pub async fn logs_contain(&self, message: &str) -> bool {
+        self.logs
+            .lock()
+            .await
+            .iter()
+            .any(|line| line.contains(message))
+    }
+
+    pub async fn close_and_maeby_remove_data(mut self, remove: bool) {
+        let id = self.manager.id().unwrap();
+        nix::sys::signal::kill(Pid::from_raw(id.try_into().unwrap()), Signal::SIGINT).unwrap(); // CTRL-C
+        self.manager.wait().await.unwrap();
+
+        if remove {
+            let dir = self.dir.file_name().unwrap().to_string_lossy();
+            if dir.starts_with(MANAGER_INSTANCE_DIR_START) {
+                std::fs::remove_dir_all(self.dir).unwrap();
+            } else {
+                panic!("Not manager instance dir {}", dir);
+            }
+        }
+
+        self.stdout_task.await.unwrap();
+        self.stderr_task.await.unwrap();
+    }
+
+    pub async fn logs_string(&self) -> String {
+        self.logs.lock().await.join("\n")
+    }
+}
+
+fn start_cmd() -> PathBuf {
+    let start_cmd = env::args().next().unwrap();
+    let start_cmd = std::fs::canonicalize(&start_cmd).unwrap();
+
+    if !start_cmd.is_file() {
+        panic!("First argument does not point to a file {:?}", &start_cmd);
+    }
+
+    start_cmd
+}
+
+fn create_read_lines_task(
+    stream: impl AsyncRead + Unpin + Send + 'static,
+    stream_name: &'static str,
+    logs: Arc<Mutex<Vec<String>>>,
+    log_to_memory: bool,
+    start_sender: Option<(tokio::sync::oneshot::Sender<()>, &'static str)>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut start_sender = start_sender;
+        let mut line_stream = tokio::io::BufReader::new(stream).lines();
+        loop {
+            let (line, stream_ended) = match line_stream.next_line().await {
+                Ok(Some(line)) => (line, false),
+                Ok(None) => (format!("Server {stream_name} closed"), true),
+                Err(e) => (format!("Server {stream_name} error: {e:?}"), true),
+            };
+
+            if let Some((sender, start_message)) = start_sender.take() {
+                if line.contains(start_message) {
+                    sender.send(()).unwrap();
+                } else {
+                    start_sender = Some((sender, start_message));
+                }
+            }
+
+            if log_to_memory {
+                logs.lock().await.push(line);
+            } else {
+                println!("{line}");
+            }
+
+            if stream_ended {
+                break;
+            }
+        }
+    })
+}
@@ -1,3 +1,5 @@
 mod account;
+mod backup;
+mod chat;
 mod media;
 mod profile;
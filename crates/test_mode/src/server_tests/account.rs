@@ -1,4 +1,6 @@
 //! Account API tests
 
 mod admin;
+mod email;
 mod initial_setup;
+mod login_session;
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use api_client::{
+    apis::account_api::{get_email_confirmation_state, post_confirm_email},
+    models::EmailConfirmationToken,
+};
+use config::file::AccountLimitsConfig;
+use simple_backend_utils::time::DurationValue;
+use test_mode_macro::server_test;
+
+use crate::{
+    runner::server_tests::{
+        assert::{assert, assert_failure},
+        context::Account,
+        email::EmailTestServer,
+    },
+    ServerTestError, TestContext, TestResult,
+};
+
+async fn email_verified(account: &Account) -> Result<bool, ServerTestError> {
+    Ok(get_email_confirmation_state(account.account_api())
+        .await?
+        .email_verified)
+}
+
+#[server_test]
+async fn email_confirmation_email_token_confirms_email_address(context: TestContext) -> TestResult {
+    let mut server = EmailTestServer::start(&context, AccountLimitsConfig::default()).await?;
+    let account = server.context.new_account().await?;
+
+    let token = server.request_email_confirmation_token(&account).await?;
+    assert(!email_verified(&account).await?)?;
+    post_confirm_email(account.account_api(), token).await?;
+    assert(email_verified(&account).await?)?;
+
+    server.close().await;
+    Ok(())
+}
+
+#[server_test]
+async fn tampered_email_confirmation_token_is_rejected(context: TestContext) -> TestResult {
+    let mut server = EmailTestServer::start(&context, AccountLimitsConfig::default()).await?;
+    let account = server.context.new_account().await?;
+
+    let token = server.request_email_confirmation_token(&account).await?;
+    let signature_start = token.token.rfind('.').unwrap_or_default() + 1;
+    let mut tampered = token.token.clone();
+    let replacement = if tampered[signature_start..].starts_with('A') {
+        "B"
+    } else {
+        "A"
+    };
+    tampered.replace_range(signature_start..signature_start + 1, replacement);
+    assert_failure(
+        post_confirm_email(account.account_api(), EmailConfirmationToken::new(tampered)).await,
+    )?;
+    assert(!email_verified(&account).await?)?;
+
+    server.close().await;
+    Ok(())
+}
+
+#[server_test]
+async fn expired_email_confirmation_token_is_rejected(context: TestContext) -> TestResult {
+    let limits = AccountLimitsConfig {
+        email_confirmation_token_max_age: DurationValue { seconds: 1 },
+        ..AccountLimitsConfig::default()
+    };
+    let mut server = EmailTestServer::start(&context, limits).await?;
+    let account = server.context.new_account().await?;
+
+    let token = server.request_email_confirmation_token(&account).await?;
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    assert_failure(post_confirm_email(account.account_api(), token).await)?;
+    assert(!email_verified(&account).await?)?;
+
+    server.close().await;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use api_client::apis::account_api::post_refresh_login_session_tokens;
+use test_mode_macro::server_test;
+
+use crate::{
+    runner::server_tests::assert::{assert_failure, assert_ne},
+    TestContext, TestError, TestResult,
+};
+
+#[server_test]
+async fn reusing_rotated_refresh_token_revokes_login_session(
+    mut context: TestContext,
+) -> TestResult {
+    let account = context.new_account().await?;
+    let refresh_token = account
+        .refresh_token()
+        .ok_or(TestError::MissingValue.report())?;
+
+    let new_tokens =
+        post_refresh_login_session_tokens(account.account_api(), refresh_token.clone()).await?;
+    assert_ne(refresh_token.clone(), *new_tokens.refresh.clone())?;
+
+    assert_failure(post_refresh_login_session_tokens(account.account_api(), refresh_token).await)?;
+    // Login session is revoked, so the latest refresh token is not valid
+    assert_failure(
+        post_refresh_login_session_tokens(account.account_api(), *new_tokens.refresh).await,
+    )
+}
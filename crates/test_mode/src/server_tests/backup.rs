@@ -0,0 +1,243 @@
+//! Backup link and backup restore tests
+
+use std::{ffi::OsStr, path::Path, time::Duration};
+
+use api_client::apis::{account_bot_api::post_bot_login, media_api::get_all_account_media_content};
+use config::file::Components;
+use error_stack::ResultExt;
+use manager_model::ManagerInstanceName;
+use reqwest::Url;
+use server_data::{db_manager::DB_FILE_DIR_NAME, file::utils::CONTENT_DIR_NAME};
+use simple_backend_config::file::{ManagerConfig, ScheduledTasksConfig};
+use simple_backend_utils::UuidBase64Url;
+use test_mode_macro::server_test;
+
+use crate::{
+    client::ApiClient,
+    runner::{bot::DataDirUtils, server_tests::assert::assert},
+    server::{new_config, AdditionalSettings, ManagerInstance, ServerInstance},
+    TestContext, TestError, TestResult,
+};
+
+const MANAGER_NAME: &str = "default";
+const MANAGER_API_KEY: &str = "password";
+const BACKUP_LINK_PASSWORD_TARGET: &str = "target";
+const BACKUP_LINK_PASSWORD_SOURCE: &str = "source";
+const BACKUP_ENCRYPTION_KEY: &str = "backup-encryption-key";
+
+const MANAGER_LOG_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[server_test]
+async fn backup_can_be_restored_and_used_as_server_data(context: TestContext) -> TestResult {
+    let api_port = context
+        .account_server_api_port()
+        .ok_or(TestError::MissingValue.report())?;
+    let server_port = api_port + 1000;
+    let manager_port = api_port + 2000;
+
+    let test_dir = DataDirUtils::create_data_dir_if_needed(context.test_config())
+        .join(format!("backup_test_{}", UuidBase64Url::new_random_id()));
+    std::fs::create_dir(&test_dir).unwrap();
+    let test_dir = std::fs::canonicalize(test_dir).unwrap();
+    let data_dir = test_dir.join("backend_data");
+    let restored_data_dir = test_dir.join("restored_backend_data");
+    let restored_without_key_data_dir = test_dir.join("restored_without_key_backend_data");
+
+    let manager =
+        ManagerInstance::new(test_dir.clone(), manager_config(&test_dir, manager_port)).await;
+    wait_manager_log(&manager, "Backup target link connected").await?;
+
+    let server = start_server(
+        &context,
+        &test_dir,
+        &data_dir,
+        server_port,
+        Some(manager_port),
+        false,
+    )
+    .await;
+    let mut server_context = context.new_for_server(server_port);
+    let account = server_context.new_account().await?;
+    let account_id = account.account_id();
+    server_context.close_websocket_connections().await;
+    server.close_and_maeby_remove_data(false).await;
+
+    // Backup is created when scheduled tasks run at server start
+    let server = start_server(
+        &context,
+        &test_dir,
+        &data_dir,
+        server_port,
+        Some(manager_port),
+        true,
+    )
+    .await;
+    wait_manager_log(&manager, "Backup session completed").await?;
+    server.close_and_maeby_remove_data(false).await;
+    assert(!manager.logs_contain("Backup session error").await)?;
+
+    // Backups are encrypted, so restoring requires the key
+    let output = manager
+        .run_api_command(&[
+            OsStr::new("restore-backup"),
+            restored_without_key_data_dir.as_os_str(),
+        ])
+        .await;
+    assert(!output.status.success())?;
+
+    let output = manager
+        .run_api_command(&[
+            OsStr::new("restore-backup"),
+            restored_data_dir.as_os_str(),
+            OsStr::new("--encryption-key-name"),
+            OsStr::new(MANAGER_NAME),
+        ])
+        .await;
+    if !output.status.success() {
+        return Err(TestError::ServerTestFailed.report())
+            .attach_printable(String::from_utf8_lossy(&output.stderr).to_string())
+            .map_err(Into::into);
+    }
+    manager.close_and_maeby_remove_data(false).await;
+
+    let server = start_server(
+        &context,
+        &test_dir,
+        &restored_data_dir,
+        server_port,
+        None,
+        false,
+    )
+    .await;
+
+    let urls = context
+        .test_config()
+        .api_urls
+        .clone()
+        .change_ports(Some(server_port))
+        .map_err(|_| TestError::ApiUrlPortConfigFailed.report())?;
+    let mut api = ApiClient::new(urls);
+    let login = post_bot_login(api.account(), account_id.clone()).await?;
+    let Some(Some(auth_pair)) = login.account else {
+        return Err(TestError::MissingValue.report().into());
+    };
+    api.set_access_token(auth_pair.access.access_token.clone());
+
+    let aid = account_id.aid.to_string();
+    let content = get_all_account_media_content(api.media(), &aid).await?;
+    assert(!content.data.is_empty())?;
+    for c in content.data {
+        let content_file = restored_data_dir
+            .join(DB_FILE_DIR_NAME)
+            .join(&aid)
+            .join(CONTENT_DIR_NAME)
+            .join(c.cid.cid.to_string());
+        assert(content_file.exists())?;
+    }
+
+    server.close_and_maeby_remove_data(false).await;
+
+    if !context.test_config().no_clean {
+        std::fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    Ok(())
+}
+
+async fn wait_manager_log(manager: &ManagerInstance, message: &str) -> TestResult {
+    if manager.wait_log_message(message, MANAGER_LOG_TIMEOUT).await {
+        Ok(())
+    } else {
+        Err(TestError::ServerTestFailed.report())
+            .attach_printable(format!("Manager log message missing: {message}"))
+            .attach_printable(manager.logs_string().await)
+            .map_err(Into::into)
+    }
+}
+
+async fn start_server(
+    context: &TestContext,
+    dir: &Path,
+    data_dir: &Path,
+    port: u16,
+    manager_port: Option<u16>,
+    run_scheduled_tasks_on_start: bool,
+) -> ServerInstance {
+    let (server_config, mut simple_backend_config) =
+        new_config(context.test_config(), port, Components::all_enabled(), None);
+    simple_backend_config.data.dir = data_dir.to_path_buf();
+    simple_backend_config.manager = manager_port.map(|manager_port| ManagerConfig {
+        manager_name: ManagerInstanceName::new(MANAGER_NAME.to_string()),
+        address: manager_url(manager_port),
+        api_key: MANAGER_API_KEY.to_string(),
+        tls: None,
+        backup_link_password: Some(BACKUP_LINK_PASSWORD_SOURCE.to_string()),
+    });
+    simple_backend_config.scheduled_tasks = Some(ScheduledTasksConfig {
+        debug_run_on_start: run_scheduled_tasks_on_start,
+        ..ScheduledTasksConfig::default()
+    });
+
+    ServerInstance::new(
+        dir.to_path_buf(),
+        context.config(),
+        (server_config, simple_backend_config),
+        context.test_config(),
+        AdditionalSettings {
+            log_to_memory: true,
+            account_server_api_port: Some(port),
+            disable_sqlite_in_ram: true,
+        },
+    )
+    .await
+}
+
+fn manager_url(port: u16) -> Url {
+    Url::parse(&format!("tcp://127.0.0.1:{port}")).unwrap()
+}
+
+fn manager_config(test_dir: &Path, port: u16) -> String {
+    let storage_dir = test_dir.join("manager_storage");
+    let scripts_dir = test_dir.join("manager_scripts");
+    let encryption_key_path = test_dir.join("backup_encryption.key");
+    std::fs::write(&encryption_key_path, BACKUP_ENCRYPTION_KEY).unwrap();
+    format!(
+        r#"
+[manager]
+name = "{MANAGER_NAME}"
+api_key = "{MANAGER_API_KEY}"
+
+[dir]
+storage = "{}"
+scripts = "{}"
+
+[general]
+debug = true
+
+[socket]
+public_api = "127.0.0.1:{port}"
+
+[backup_link.target]
+password = "{BACKUP_LINK_PASSWORD_TARGET}"
+url = "{}"
+
+[backup_link.server]
+password_target = "{BACKUP_LINK_PASSWORD_TARGET}"
+password_source = "{BACKUP_LINK_PASSWORD_SOURCE}"
+encryption_key_name = "{MANAGER_NAME}"
+
+[[server_encryption_key]]
+manager_name = "{MANAGER_NAME}"
+key_path = "{}"
+
+[manual_tasks]
+allow_backend_restart = false
+allow_system_reboot = false
+allow_backup_restore = true
+"#,
+        storage_dir.display(),
+        scripts_dir.display(),
+        manager_url(port),
+        encryption_key_path.display(),
+    )
+}
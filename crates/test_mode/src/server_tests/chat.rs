@@ -0,0 +1,6 @@
+//! Chat API tests
+
+mod email_notification;
+mod like;
+mod message;
+mod public_key;
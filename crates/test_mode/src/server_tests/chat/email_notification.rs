@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use api_client::{
+    apis::{
+        account_api::{post_confirm_email, put_setting_email_notifications},
+        chat_api::post_send_like,
+    },
+    models::BooleanSetting,
+};
+use config::file::AccountLimitsConfig;
+use simple_backend_utils::time::DurationValue;
+use test_mode_macro::server_test;
+
+use crate::{
+    runner::server_tests::{
+        assert::{assert, assert_eq},
+        context::Account,
+        email::EmailTestServer,
+    },
+    ServerTestError, TestContext, TestResult,
+};
+
+/// Long enough time for the server to send the digest email if
+/// it would be sent.
+const NO_EMAIL_WAIT_DURATION: Duration = Duration::from_secs(8);
+
+async fn start_server(context: &TestContext) -> Result<EmailTestServer, ServerTestError> {
+    let limits = AccountLimitsConfig {
+        chat_email_notification_delay: DurationValue { seconds: 2 },
+        ..AccountLimitsConfig::default()
+    };
+    EmailTestServer::start(context, limits).await
+}
+
+/// Create account which has confirmed email address. Returns the
+/// account and its email address.
+async fn account_with_confirmed_email(
+    server: &mut EmailTestServer,
+) -> Result<(Account, String), ServerTestError> {
+    let account = server.context.new_account().await?;
+    let token = server.request_email_confirmation_token(&account).await?;
+    post_confirm_email(account.account_api(), token).await?;
+    let email = format!("{}@example.test", account.account_id_string());
+    Ok((account, email))
+}
+
+#[server_test]
+async fn chat_notification_digest_is_sent_once_for_unread_likes(
+    context: TestContext,
+) -> TestResult {
+    let mut server = start_server(&context).await?;
+    let (receiver, receiver_email) = account_with_confirmed_email(&mut server).await?;
+    // Notifications for disconnected clients are sent using emails
+    server.context.close_websocket_connections().await;
+    let sender1 = server.context.new_account().await?;
+    let sender2 = server.context.new_account().await?;
+
+    post_send_like(sender1.chat_api(), receiver.account_id()).await?;
+    let email = server.wait_email().await?;
+    assert_eq(receiver_email, email.to)?;
+    assert_eq("Chat requests: 1, messages: 0", email.body.trim())?;
+
+    // Receiver is already notified about new likes
+    post_send_like(sender2.chat_api(), receiver.account_id()).await?;
+    assert(!server.email_received_during(NO_EMAIL_WAIT_DURATION).await)?;
+
+    server.close().await;
+    Ok(())
+}
+
+#[server_test]
+async fn chat_notification_digest_is_not_sent_when_client_is_connected(
+    context: TestContext,
+) -> TestResult {
+    let mut server = start_server(&context).await?;
+    let (receiver, _) = account_with_confirmed_email(&mut server).await?;
+    let sender = server.context.new_account().await?;
+
+    // WebSocket connection receives the notification
+    post_send_like(sender.chat_api(), receiver.account_id()).await?;
+    assert(!server.email_received_during(NO_EMAIL_WAIT_DURATION).await)?;
+
+    server.close().await;
+    Ok(())
+}
+
+#[server_test]
+async fn chat_notification_digest_is_not_sent_when_email_notifications_are_disabled(
+    context: TestContext,
+) -> TestResult {
+    let mut server = start_server(&context).await?;
+    let (receiver, _) = account_with_confirmed_email(&mut server).await?;
+    put_setting_email_notifications(receiver.account_api(), BooleanSetting::new(false)).await?;
+    server.context.close_websocket_connections().await;
+    let sender = server.context.new_account().await?;
+
+    post_send_like(sender.chat_api(), receiver.account_id()).await?;
+    assert(!server.email_received_during(NO_EMAIL_WAIT_DURATION).await)?;
+
+    server.close().await;
+    Ok(())
+}
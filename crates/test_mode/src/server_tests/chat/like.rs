@@ -0,0 +1,126 @@
+use std::path::Path;
+
+use api_client::{
+    apis::{
+        chat_api::post_send_like,
+        profile_api::{post_profile, post_profile_filtering_settings},
+    },
+    models::{
+        LimitedActionStatus, ProfileAttributeFilterValueUpdate, ProfileAttributeValueUpdate,
+        ProfileFilteringSettingsUpdate, ProfileUpdate,
+    },
+};
+use config::file::Components;
+use simple_backend_utils::UuidBase64Url;
+use test_mode_macro::server_test;
+
+use crate::{
+    runner::{
+        bot::DataDirUtils,
+        server_tests::{assert::assert_eq, context::Account},
+    },
+    server::{new_config, AdditionalSettings, ServerInstance},
+    TestContext, TestError, TestResult,
+};
+
+const PROFILE_ATTRIBUTES: &str = r#"
+attribute_order = "OrderNumber"
+
+[[attribute]]
+key = "city"
+name = "City"
+mode = "SelectSingleFilterSingle"
+id = 0
+order_number = 0
+value_order = "AlphabethicalValue"
+values = ["Helsinki", "Espoo"]
+"#;
+
+const CITY: i32 = 0;
+const HELSINKI: i32 = 0;
+const ESPOO: i32 = 1;
+
+#[server_test]
+async fn like_is_sent_only_when_mutual_attribute_filters_match(context: TestContext) -> TestResult {
+    let api_port = context
+        .account_server_api_port()
+        .ok_or(TestError::MissingValue.report())?;
+    let server_port = api_port + 1000;
+
+    let test_dir = DataDirUtils::create_data_dir_if_needed(context.test_config())
+        .join(format!("like_test_{}", UuidBase64Url::new_random_id()));
+    std::fs::create_dir(&test_dir).unwrap();
+    let test_dir = std::fs::canonicalize(test_dir).unwrap();
+
+    let server = start_server_with_profile_attributes(&context, &test_dir, server_port).await;
+    let mut server_context = context.new_for_server(server_port);
+
+    let receiver = server_context.new_account().await?;
+    let mismatching_sender = server_context.new_account().await?;
+    let matching_sender = server_context.new_account().await?;
+    set_city(&mismatching_sender, ESPOO).await?;
+    set_city(&matching_sender, HELSINKI).await?;
+
+    let settings = ProfileFilteringSettingsUpdate {
+        mutual_attribute_filters: Some(true),
+        ..ProfileFilteringSettingsUpdate::new(vec![ProfileAttributeFilterValueUpdate::new(
+            vec![HELSINKI],
+            CITY,
+        )])
+    };
+    post_profile_filtering_settings(receiver.profile_api(), settings).await?;
+
+    let result = post_send_like(mismatching_sender.chat_api(), receiver.account_id()).await?;
+    assert_eq(Some(true), result.error_attribute_filters_mismatch)?;
+    assert_eq(None, result.status)?;
+
+    let result = post_send_like(matching_sender.chat_api(), receiver.account_id()).await?;
+    assert_eq(None, result.error_attribute_filters_mismatch)?;
+    assert_eq(Some(Some(LimitedActionStatus::Success)), result.status)?;
+
+    server_context.close_websocket_connections().await;
+    server.close_and_maeby_remove_data(false).await;
+
+    if !context.test_config().no_clean {
+        std::fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    Ok(())
+}
+
+async fn set_city(account: &Account, city: i32) -> TestResult {
+    let update = ProfileUpdate {
+        attributes: vec![ProfileAttributeValueUpdate::new(CITY, vec![city])],
+        age: 30,
+        name: "Test".to_string(),
+        ptext: String::new(),
+    };
+    post_profile(account.profile_api(), update).await?;
+    Ok(())
+}
+
+async fn start_server_with_profile_attributes(
+    context: &TestContext,
+    dir: &Path,
+    port: u16,
+) -> ServerInstance {
+    let attributes_file = dir.join("profile_attributes.toml");
+    std::fs::write(&attributes_file, PROFILE_ATTRIBUTES).unwrap();
+
+    let (mut server_config, simple_backend_config) =
+        new_config(context.test_config(), port, Components::all_enabled(), None);
+    server_config.config_files.profile_attributes = Some(attributes_file);
+
+    ServerInstance::new(
+        dir.to_path_buf(),
+        context.config(),
+        (server_config, simple_backend_config),
+        context.test_config(),
+        AdditionalSettings {
+            log_to_memory: true,
+            account_server_api_port: Some(port),
+            ..AdditionalSettings::default()
+        },
+    )
+    .await
+}
@@ -0,0 +1,63 @@
+use api_client::{
+    apis::chat_api::{post_public_key, post_send_like},
+    manual_additions::post_send_message_fixed,
+    models::{PublicKeyData, PublicKeyId, PublicKeyVersion, SetPublicKey},
+};
+use test_mode_macro::server_test;
+
+use crate::{
+    runner::server_tests::{assert::assert_eq, context::Account},
+    ServerTestError, TestContext, TestResult,
+};
+
+/// Default value from server config
+const MAX_PENDING_MESSAGES_PER_CONVERSATION: i64 = 50;
+
+async fn create_match(account0: &Account, account1: &Account) -> TestResult {
+    post_send_like(account0.chat_api(), account1.account_id()).await?;
+    post_send_like(account1.chat_api(), account0.account_id()).await?;
+    Ok(())
+}
+
+async fn set_public_key(account: &Account) -> Result<PublicKeyId, ServerTestError> {
+    let key = SetPublicKey::new(
+        PublicKeyData::new("test".to_string()),
+        PublicKeyVersion::new(1),
+    );
+    Ok(post_public_key(account.chat_api(), key).await?)
+}
+
+#[server_test]
+async fn pending_messages_per_conversation_limit(mut context: TestContext) -> TestResult {
+    let sender = context.new_account().await?;
+    let receiver = context.new_account().await?;
+    create_match(&sender, &receiver).await?;
+    let key_id = set_public_key(&receiver).await?;
+
+    let send = || {
+        post_send_message_fixed(
+            sender.chat_api(),
+            &receiver.account_id_string(),
+            key_id.id,
+            1,
+            0,
+            0,
+            vec![0, 1, 2],
+        )
+    };
+
+    for _ in 0..MAX_PENDING_MESSAGES_PER_CONVERSATION {
+        let result = send().await?;
+        assert_eq(
+            None,
+            result.error_too_many_receiver_acknowledgements_missing,
+        )?;
+    }
+
+    let result = send().await?;
+    assert_eq(
+        Some(true),
+        result.error_too_many_receiver_acknowledgements_missing,
+    )?;
+    assert_eq(None, result.mn)
+}
@@ -0,0 +1,90 @@
+use api_client::{
+    apis::{
+        chat_api::{get_public_key_history, get_public_key_history_entry, post_public_key},
+        Error,
+    },
+    models::{PublicKeyData, PublicKeyId, PublicKeyVersion, SetPublicKey},
+};
+use test_mode_macro::server_test;
+
+use crate::{
+    runner::server_tests::{
+        assert::{assert, assert_eq},
+        context::Account,
+    },
+    ServerTestError, TestContext, TestResult,
+};
+
+/// Same as `PublicKeyHistory::MAX_UPLOAD_COUNT` on server side
+const MAX_PUBLIC_KEY_UPLOAD_COUNT: usize = 1024;
+
+async fn upload_key(account: &Account, data: &str) -> Result<PublicKeyId, ServerTestError> {
+    let key = SetPublicKey::new(
+        PublicKeyData::new(data.to_string()),
+        PublicKeyVersion::new(1),
+    );
+    Ok(post_public_key(account.chat_api(), key).await?)
+}
+
+#[server_test]
+async fn public_key_history_contains_uploaded_keys(mut context: TestContext) -> TestResult {
+    let owner = context.new_account().await?;
+    let reader = context.new_account().await?;
+    let id0 = upload_key(&owner, "key0").await?;
+    let id1 = upload_key(&owner, "key1").await?;
+
+    let history = get_public_key_history(reader.chat_api(), &owner.account_id_string()).await?;
+    assert_eq(2, history.keys.len())?;
+    assert_eq(id0.id, history.keys[0].id.id)?;
+    assert_eq("key0", history.keys[0].data.data.as_str())?;
+    assert_eq(id1.id, history.keys[1].id.id)?;
+    assert_eq("key1", history.keys[1].data.data.as_str())?;
+    assert(history.keys.iter().all(|k| !k.signature.s.is_empty()))
+}
+
+#[server_test]
+async fn public_key_history_entry_is_returned(mut context: TestContext) -> TestResult {
+    let owner = context.new_account().await?;
+    let reader = context.new_account().await?;
+    let id0 = upload_key(&owner, "key0").await?;
+    upload_key(&owner, "key1").await?;
+
+    let entry =
+        get_public_key_history_entry(reader.chat_api(), &owner.account_id_string(), 1, id0.id)
+            .await?
+            .key
+            .flatten();
+    assert_eq(Some("key0"), entry.as_ref().map(|k| k.data.data.as_str()))?;
+
+    let unknown = get_public_key_history_entry(
+        reader.chat_api(),
+        &owner.account_id_string(),
+        1,
+        id0.id + 100,
+    )
+    .await?
+    .key
+    .flatten();
+    assert(unknown.is_none())
+}
+
+#[server_test]
+async fn public_key_upload_count_is_limited(mut context: TestContext) -> TestResult {
+    let owner = context.new_account().await?;
+    for i in 0..MAX_PUBLIC_KEY_UPLOAD_COUNT {
+        upload_key(&owner, &format!("key{i}")).await?;
+    }
+
+    let key = SetPublicKey::new(
+        PublicKeyData::new("too many".to_string()),
+        PublicKeyVersion::new(1),
+    );
+    let status = match post_public_key(owner.chat_api(), key).await {
+        Err(Error::ResponseError(e)) => Some(e.status.as_u16()),
+        _ => None,
+    };
+    assert_eq(Some(406), status)?;
+
+    let history = get_public_key_history(owner.chat_api(), &owner.account_id_string()).await?;
+    assert_eq(MAX_PUBLIC_KEY_UPLOAD_COUNT, history.keys.len())
+}
@@ -1,5 +1,7 @@
 mod data;
+mod hidden_profiles;
 mod location;
+mod moderation_history;
 mod visibility;
 
 // TOOD(test): ProfileVersion tests which test API route and
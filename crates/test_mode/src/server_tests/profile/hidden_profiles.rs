@@ -0,0 +1,107 @@
+use api_client::{
+    apis::{
+        chat_api::{delete_like, post_block_profile, post_send_like, post_unblock_profile},
+        profile_api::{
+            post_get_next_profile_page, post_profile_filtering_settings, post_reset_profile_paging,
+        },
+    },
+    models::{ProfileFilteringSettingsUpdate, ProfileIteratorSessionId},
+};
+use test_mode_macro::server_test;
+
+use crate::{
+    bot::actions::account::SetProfileVisibility,
+    runner::server_tests::{assert::assert_eq, context::Account},
+    ServerTestError, TestContext, TestResult,
+};
+
+async fn set_hide_interacted_profiles(account: &Account, value: bool) -> TestResult {
+    let settings = ProfileFilteringSettingsUpdate {
+        hide_interacted_profiles: Some(value),
+        ..ProfileFilteringSettingsUpdate::new(vec![])
+    };
+    post_profile_filtering_settings(account.profile_api(), settings).await?;
+    Ok(())
+}
+
+async fn next_page_len(
+    account: &Account,
+    iterator_id: ProfileIteratorSessionId,
+) -> Result<usize, ServerTestError> {
+    Ok(
+        post_get_next_profile_page(account.profile_api(), iterator_id)
+            .await?
+            .profiles
+            .len(),
+    )
+}
+
+async fn viewer_and_public_profile(
+    context: &mut TestContext,
+) -> Result<(Account, Account), ServerTestError> {
+    let mut viewer = context.new_man_18_years().await?;
+    let mut profile = context.new_woman_18_years().await?;
+    context.new_admin_and_moderate_initial_content().await?;
+    viewer.run(SetProfileVisibility(true)).await?;
+    profile.run(SetProfileVisibility(true)).await?;
+    Ok((viewer, profile))
+}
+
+#[server_test]
+async fn liked_profile_is_hidden_from_current_iterator_session(
+    mut context: TestContext,
+) -> TestResult {
+    let (viewer, profile) = viewer_and_public_profile(&mut context).await?;
+    set_hide_interacted_profiles(&viewer, true).await?;
+    let iterator_id = post_reset_profile_paging(viewer.profile_api()).await?;
+    post_send_like(viewer.chat_api(), profile.account_id()).await?;
+    assert_eq(0, next_page_len(&viewer, iterator_id).await?)?;
+    let iterator_id = post_reset_profile_paging(viewer.profile_api()).await?;
+    assert_eq(0, next_page_len(&viewer, iterator_id).await?)
+}
+
+#[server_test]
+async fn deleted_like_shows_profile_in_current_iterator_session(
+    mut context: TestContext,
+) -> TestResult {
+    let (viewer, profile) = viewer_and_public_profile(&mut context).await?;
+    set_hide_interacted_profiles(&viewer, true).await?;
+    post_send_like(viewer.chat_api(), profile.account_id()).await?;
+    let iterator_id = post_reset_profile_paging(viewer.profile_api()).await?;
+    delete_like(viewer.chat_api(), profile.account_id()).await?;
+    assert_eq(1, next_page_len(&viewer, iterator_id).await?)
+}
+
+#[server_test]
+async fn matched_profile_is_hidden_from_current_iterator_session(
+    mut context: TestContext,
+) -> TestResult {
+    let (viewer, profile) = viewer_and_public_profile(&mut context).await?;
+    set_hide_interacted_profiles(&profile, true).await?;
+    post_send_like(viewer.chat_api(), profile.account_id()).await?;
+    let iterator_id = post_reset_profile_paging(profile.profile_api()).await?;
+    post_send_like(profile.chat_api(), viewer.account_id()).await?;
+    assert_eq(0, next_page_len(&profile, iterator_id).await?)
+}
+
+#[server_test]
+async fn blocked_profile_is_hidden_until_unblocked(mut context: TestContext) -> TestResult {
+    let (viewer, profile) = viewer_and_public_profile(&mut context).await?;
+    set_hide_interacted_profiles(&viewer, true).await?;
+    let iterator_id = post_reset_profile_paging(viewer.profile_api()).await?;
+    post_block_profile(viewer.chat_api(), profile.account_id()).await?;
+    assert_eq(0, next_page_len(&viewer, iterator_id).await?)?;
+    let iterator_id = post_reset_profile_paging(viewer.profile_api()).await?;
+    post_unblock_profile(viewer.chat_api(), profile.account_id()).await?;
+    assert_eq(1, next_page_len(&viewer, iterator_id).await?)
+}
+
+#[server_test]
+async fn interacted_profiles_are_not_hidden_when_setting_is_disabled(
+    mut context: TestContext,
+) -> TestResult {
+    let (viewer, profile) = viewer_and_public_profile(&mut context).await?;
+    let iterator_id = post_reset_profile_paging(viewer.profile_api()).await?;
+    post_send_like(viewer.chat_api(), profile.account_id()).await?;
+    assert_eq(1, next_page_len(&viewer, iterator_id).await?)
+}
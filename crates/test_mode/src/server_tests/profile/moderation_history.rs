@@ -0,0 +1,110 @@
+use api_client::{
+    apis::{
+        profile_admin_api::{
+            get_profile_moderation_history, post_moderate_profile_name, post_moderate_profile_text,
+        },
+        profile_api::post_profile,
+    },
+    models::{
+        PostModerateProfileName, PostModerateProfileText, ProfileNameModerationState,
+        ProfileTextModerationRejectedReasonCategory, ProfileTextModerationState, ProfileUpdate,
+    },
+};
+use test_mode_macro::server_test;
+
+use crate::{
+    runner::server_tests::assert::{assert_eq, assert_failure},
+    TestContext, TestResult,
+};
+
+#[server_test]
+async fn profile_moderation_history_contains_submitted_name_and_text(
+    mut context: TestContext,
+) -> TestResult {
+    let admin = context.new_admin().await?;
+    let account = context.new_account().await?;
+    let profile = ProfileUpdate {
+        attributes: vec![],
+        age: 30,
+        name: "Test".to_string(),
+        ptext: "Hello".to_string(),
+    };
+    post_profile(account.profile_api(), profile).await?;
+
+    let history =
+        get_profile_moderation_history(admin.account().profile_api(), &account.account_id_string())
+            .await?;
+    assert_eq(1, history.names.len())?;
+    assert_eq("Test", history.names[0].name.as_str())?;
+    assert_eq(None, history.names[0].moderator_id.clone().flatten())?;
+    assert_eq(1, history.texts.len())?;
+    assert_eq("Hello", history.texts[0].text.as_str())?;
+    assert_eq(
+        ProfileTextModerationState::WaitingBotOrHumanModeration,
+        history.texts[0].state,
+    )
+}
+
+#[server_test]
+async fn profile_moderation_history_contains_moderation_results(
+    mut context: TestContext,
+) -> TestResult {
+    let admin = context.new_admin().await?;
+    let account = context.new_account().await?;
+    let profile = ProfileUpdate {
+        attributes: vec![],
+        age: 30,
+        name: "Test".to_string(),
+        ptext: "Hello".to_string(),
+    };
+    post_profile(account.profile_api(), profile).await?;
+
+    post_moderate_profile_name(
+        admin.account().profile_api(),
+        PostModerateProfileName::new(true, account.account_id(), "Test".to_string()),
+    )
+    .await?;
+    let mut moderate_text =
+        PostModerateProfileText::new(false, account.account_id(), "Hello".to_string());
+    moderate_text.rejected_category = Some(Some(Box::new(
+        ProfileTextModerationRejectedReasonCategory::new(1),
+    )));
+    post_moderate_profile_text(admin.account().profile_api(), moderate_text).await?;
+
+    let history =
+        get_profile_moderation_history(admin.account().profile_api(), &account.account_id_string())
+            .await?;
+    let admin_id = Some(Box::new(admin.account().account_id()));
+    assert_eq(1, history.names.len())?;
+    assert_eq(
+        ProfileNameModerationState::AcceptedByHuman,
+        history.names[0].state,
+    )?;
+    assert_eq(
+        admin_id.clone(),
+        history.names[0].moderator_id.clone().flatten(),
+    )?;
+    assert_eq(1, history.texts.len())?;
+    assert_eq(
+        ProfileTextModerationState::RejectedByHuman,
+        history.texts[0].state,
+    )?;
+    assert_eq(admin_id, history.texts[0].moderator_id.clone().flatten())?;
+    assert_eq(
+        Some(1),
+        history.texts[0]
+            .rejected_reason_category
+            .clone()
+            .flatten()
+            .map(|c| c.value),
+    )
+}
+
+#[server_test]
+async fn profile_moderation_history_requires_permission(mut context: TestContext) -> TestResult {
+    let account = context.new_account().await?;
+    let other = context.new_account().await?;
+    assert_failure(
+        get_profile_moderation_history(other.profile_api(), &account.account_id_string()).await,
+    )
+}
@@ -22,22 +22,41 @@ CREATE TABLE IF NOT EXISTS used_account_ids(
     uuid       BLOB                              NOT NULL UNIQUE
 );
 
--- API access token for account
-CREATE TABLE IF NOT EXISTS access_token(
-    account_id   INTEGER PRIMARY KEY NOT NULL,
-    token        TEXT                          UNIQUE,
+-- Login sessions for account. One account can have multiple
+-- login sessions, for example one session per device.
+CREATE TABLE IF NOT EXISTS login_session(
+    -- UUID for the session
+    session_id                   BLOB PRIMARY KEY NOT NULL,
+    account_id                   INTEGER          NOT NULL,
+    -- API access token
+    access_token                 TEXT             NOT NULL UNIQUE,
+    -- Access token creation time
+    access_token_unix_time       INTEGER          NOT NULL,
+    -- API refresh token
+    refresh_token                BLOB             NOT NULL UNIQUE,
+    fcm_device_token             TEXT                      UNIQUE,
+    fcm_notification_sent        BOOLEAN          NOT NULL DEFAULT 0,
+    -- Access token for getting pending notifications from server.
+    pending_notification_token   TEXT                      UNIQUE,
+    created_unix_time            INTEGER          NOT NULL,
+    -- Login or WebSocket connection time
+    last_used_unix_time          INTEGER          NOT NULL,
     FOREIGN KEY (account_id)
         REFERENCES account_id (id)
             ON DELETE CASCADE
             ON UPDATE CASCADE
 );
 
--- API refresh token for account
-CREATE TABLE IF NOT EXISTS refresh_token(
-    account_id    INTEGER PRIMARY KEY NOT NULL,
-    token         BLOB                          UNIQUE,
-    FOREIGN KEY (account_id)
-        REFERENCES account_id (id)
+-- Refresh tokens which are already rotated. If one of these is used
+-- again, the token has been most likely stolen and the related login session
+-- is revoked.
+-- Only latest used refresh tokens of the login session are stored.
+CREATE TABLE IF NOT EXISTS used_refresh_token(
+    id                           INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    refresh_token                BLOB    UNIQUE      NOT NULL,
+    session_id                   BLOB                NOT NULL,
+    FOREIGN KEY (session_id)
+        REFERENCES login_session (session_id)
             ON DELETE CASCADE
             ON UPDATE CASCADE
 );
@@ -73,6 +92,8 @@ CREATE TABLE IF NOT EXISTS account_permissions(
     admin_news_create                            BOOLEAN NOT NULL DEFAULT 0,
     admin_news_edit_all                          BOOLEAN NOT NULL DEFAULT 0,
     admin_profile_statistics                     BOOLEAN NOT NULL DEFAULT 0,
+    admin_export_data                            BOOLEAN NOT NULL DEFAULT 0,
+    admin_search_profiles                        BOOLEAN NOT NULL DEFAULT 0,
     FOREIGN KEY (account_id)
         REFERENCES account_id (id)
             ON DELETE CASCADE
@@ -187,6 +208,7 @@ CREATE TABLE IF NOT EXISTS common_state(
 CREATE TABLE IF NOT EXISTS sign_in_with_info(
     account_id         INTEGER PRIMARY KEY NOT NULL,
     google_account_id  TEXT                          UNIQUE,
+    apple_account_id   TEXT                          UNIQUE,
     FOREIGN KEY (account_id)
         REFERENCES account_id (id)
             ON DELETE CASCADE
@@ -197,6 +219,13 @@ CREATE TABLE IF NOT EXISTS sign_in_with_info(
 CREATE TABLE IF NOT EXISTS account(
     account_id   INTEGER PRIMARY KEY NOT NULL,
     email        TEXT                                UNIQUE,
+    -- Email address confirmation state. Changing email address
+    -- resets this.
+    email_verified                    BOOLEAN NOT NULL DEFAULT 0,
+    -- Time when latest email confirmation email was requested.
+    email_confirmation_sent_unix_time INTEGER,
+    -- Chat notification digest emails are sent when this is enabled.
+    email_notifications_enabled       BOOLEAN NOT NULL DEFAULT 1,
     FOREIGN KEY (account_id)
         REFERENCES account_id (id)
             ON DELETE CASCADE
@@ -225,6 +254,8 @@ CREATE TABLE IF NOT EXISTS account_setup(
 CREATE TABLE IF NOT EXISTS account_email_sending_state(
     account_id                      INTEGER PRIMARY KEY NOT NULL,
     account_registered_state_number INTEGER             NOT NULL DEFAULT 0,
+    email_confirmation_state_number INTEGER             NOT NULL DEFAULT 0,
+    chat_notification_digest_state_number INTEGER       NOT NULL DEFAULT 0,
     FOREIGN KEY (account_id)
         REFERENCES account_id (id)
             ON DELETE CASCADE
@@ -324,7 +355,10 @@ CREATE TABLE IF NOT EXISTS account_global_state(
     row_type                   INTEGER PRIMARY KEY NOT NULL,
     admin_access_granted_count INTEGER             NOT NULL DEFAULT 0,
     -- Publication ID for news which always increments.
-    next_news_publication_id   INTEGER             NOT NULL DEFAULT 0
+    next_news_publication_id   INTEGER             NOT NULL DEFAULT 0,
+    -- Key for signing email confirmation tokens. Created when
+    -- the first token is created.
+    email_confirmation_token_key BLOB
 );
 
 -- Store custom reports file hash, so that changes to it can be detected
@@ -361,6 +395,12 @@ CREATE TABLE IF NOT EXISTS profile_state(
     profile_edited_time_filter INTEGER,
     -- Profile iterator setting for random profile order.
     random_profile_order       BOOLEAN              NOT NULL    DEFAULT 0,
+    -- Profile iterator setting for hiding liked, matched and
+    -- blocked profiles.
+    hide_interacted_profiles   BOOLEAN              NOT NULL    DEFAULT 0,
+    -- Show the profile only to profiles which match the profile's
+    -- attribute filters.
+    mutual_attribute_filters   BOOLEAN              NOT NULL    DEFAULT 0,
     latitude                   DOUBLE               NOT NULL    DEFAULT 0.0,
     longitude                  DOUBLE               NOT NULL    DEFAULT 0.0,
     -- Sync version for profile data for this account.
@@ -420,6 +460,23 @@ CREATE TABLE IF NOT EXISTS profile(
             ON UPDATE CASCADE
 );
 
+-- Full-text search index for admin profile search. The rowid is
+-- the profile account_id. Profile write commands update the index
+-- when profile name or profile text changes.
+CREATE VIRTUAL TABLE IF NOT EXISTS profile_search_index USING fts5(
+    name,
+    profile_text,
+    tokenize = 'trigram'
+);
+
+-- Accounts are deleted from account component, so remove the
+-- index entry using a trigger.
+CREATE TRIGGER IF NOT EXISTS profile_search_index_delete
+AFTER DELETE ON profile
+BEGIN
+    DELETE FROM profile_search_index WHERE rowid = old.account_id;
+END;
+
 -- Store profile attributes which config file defines.
 CREATE TABLE IF NOT EXISTS profile_attributes(
     account_id      INTEGER             NOT NULL,
@@ -638,12 +695,70 @@ CREATE TABLE IF NOT EXISTS media_content(
     moderation_moderator_account_id     INTEGER,
     usage_start_unix_time  INTEGER,
     usage_end_unix_time    INTEGER,
+    -- Difference hash (dHash) of the image. Null if the hash is not
+    -- calculated.
+    perceptual_hash        INTEGER,
+    -- 16-bit chunks of the perceptual hash starting from the least
+    -- significant bits. Used for similar hash search.
+    perceptual_hash_chunk_0 INTEGER,
+    perceptual_hash_chunk_1 INTEGER,
+    perceptual_hash_chunk_2 INTEGER,
+    perceptual_hash_chunk_3 INTEGER,
+    -- Perceptual hash is similar to blocklisted hash or content of
+    -- some other account. Content moderation is done by humans.
+    perceptual_hash_match  BOOLEAN          NOT NULL    DEFAULT 0,
     FOREIGN KEY (account_id)
         REFERENCES account_id (id)
             ON DELETE CASCADE
             ON UPDATE CASCADE
 );
 
+-- Perceptual hashes of images which human moderators rejected or
+-- which were uploaded by banned accounts.
+CREATE TABLE IF NOT EXISTS media_content_perceptual_hash_blocklist(
+    perceptual_hash     INTEGER PRIMARY KEY NOT NULL,
+    -- 16-bit chunks of the perceptual hash starting from the least
+    -- significant bits. Used for similar hash search.
+    perceptual_hash_chunk_0 INTEGER         NOT NULL,
+    perceptual_hash_chunk_1 INTEGER         NOT NULL,
+    perceptual_hash_chunk_2 INTEGER         NOT NULL,
+    perceptual_hash_chunk_3 INTEGER         NOT NULL,
+    -- Account which uploaded the image
+    account_id          INTEGER,
+    -- RejectedContent = 0
+    -- BannedAccount = 1
+    reason              INTEGER             NOT NULL,
+    added_unix_time     INTEGER             NOT NULL,
+    FOREIGN KEY (account_id)
+        REFERENCES account_id (id)
+            ON DELETE SET NULL
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS media_content_perceptual_hash_chunk_0_index
+    ON media_content (perceptual_hash_chunk_0);
+
+CREATE INDEX IF NOT EXISTS media_content_perceptual_hash_chunk_1_index
+    ON media_content (perceptual_hash_chunk_1);
+
+CREATE INDEX IF NOT EXISTS media_content_perceptual_hash_chunk_2_index
+    ON media_content (perceptual_hash_chunk_2);
+
+CREATE INDEX IF NOT EXISTS media_content_perceptual_hash_chunk_3_index
+    ON media_content (perceptual_hash_chunk_3);
+
+CREATE INDEX IF NOT EXISTS media_content_perceptual_hash_blocklist_perceptual_hash_chunk_0_index
+    ON media_content_perceptual_hash_blocklist (perceptual_hash_chunk_0);
+
+CREATE INDEX IF NOT EXISTS media_content_perceptual_hash_blocklist_perceptual_hash_chunk_1_index
+    ON media_content_perceptual_hash_blocklist (perceptual_hash_chunk_1);
+
+CREATE INDEX IF NOT EXISTS media_content_perceptual_hash_blocklist_perceptual_hash_chunk_2_index
+    ON media_content_perceptual_hash_blocklist (perceptual_hash_chunk_2);
+
+CREATE INDEX IF NOT EXISTS media_content_perceptual_hash_blocklist_perceptual_hash_chunk_3_index
+    ON media_content_perceptual_hash_blocklist (perceptual_hash_chunk_3);
+
 CREATE TABLE IF NOT EXISTS used_content_ids(
     account_id            INTEGER                           NOT NULL,
     uuid                  BLOB                              NOT NULL,
@@ -677,10 +792,6 @@ CREATE TABLE IF NOT EXISTS chat_state(
     matches_sync_version         INTEGER        NOT NULL DEFAULT 0,
     -- Bitflag value for pending notification
     pending_notification         INTEGER        NOT NULL DEFAULT 0,
-    -- Access token for getting pending notifications from server.
-    pending_notification_token   TEXT           UNIQUE,
-    fcm_notification_sent        BOOLEAN        NOT NULL DEFAULT 0,
-    fcm_device_token             TEXT           UNIQUE,
     new_received_likes_count     INTEGER        NOT NULL DEFAULT 0,
     next_received_like_id        INTEGER        NOT NULL DEFAULT 0,
     received_like_id_at_received_likes_iterator_reset           INTEGER,
@@ -702,6 +813,22 @@ CREATE TABLE IF NOT EXISTS public_key(
             ON UPDATE CASCADE
 );
 
+-- All public keys which account has uploaded. Server signs the
+-- public keys, so clients can check that keys are not modified.
+CREATE TABLE IF NOT EXISTS public_key_history(
+    account_id                   INTEGER NOT NULL,
+    public_key_version           INTEGER NOT NULL,
+    public_key_id                INTEGER NOT NULL,
+    public_key_data              TEXT    NOT NULL,
+    upload_unix_time             INTEGER NOT NULL,
+    server_signature             BLOB    NOT NULL,
+    PRIMARY KEY (account_id, public_key_version, public_key_id),
+    FOREIGN KEY (account_id)
+        REFERENCES account_id (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
 -- Lookup table for finding interaction ID for a pair of accounts.
 -- One account pair has two rows in this table, so accessing
 -- with (a1, a2) and (a2, a1) is possible.
@@ -803,6 +930,8 @@ CREATE TABLE IF NOT EXISTS pending_messages(
     sender_client_local_id                 INTEGER NOT NULL,
     -- Message bytes.
     message_bytes                   BLOB    NOT NULL,
+    -- Server Ed25519 signature for message metadata.
+    server_signature                BLOB,
     FOREIGN KEY (account_id_sender)
         REFERENCES account_id (id)
             ON DELETE CASCADE
@@ -813,9 +942,18 @@ CREATE TABLE IF NOT EXISTS pending_messages(
             ON UPDATE CASCADE
 );
 
+-- Reported message and its server signature. The signature is
+-- verified before the report is saved.
 CREATE TABLE IF NOT EXISTS chat_report_chat_message(
     report_id                INTEGER PRIMARY KEY NOT NULL,
-    chat_message             TEXT,
+    -- Message text from the report creator. Not verified by the server.
+    chat_message             TEXT    NOT NULL,
+    message_sender_account_id_uuid   BLOB    NOT NULL,
+    message_receiver_account_id_uuid BLOB    NOT NULL,
+    message_number           INTEGER NOT NULL,
+    message_unix_time        INTEGER NOT NULL,
+    message_bytes            BLOB    NOT NULL,
+    message_signature        BLOB    NOT NULL,
     FOREIGN KEY (report_id)
         REFERENCES common_report (id)
             ON DELETE CASCADE
@@ -953,4 +1091,40 @@ CREATE TABLE IF NOT EXISTS history_profile_statistics_count_changes_all_genders(
             ON UPDATE CASCADE
 );
 
+-- Every submitted profile name with the latest moderation
+-- result for it. History database does not contain account_id
+-- table data, so account IDs are stored as UUIDs.
+CREATE TABLE IF NOT EXISTS history_profile_name_moderation(
+    id                      INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    account_uuid            BLOB                              NOT NULL,
+    profile_name            TEXT                              NOT NULL,
+    submitted_unix_time     INTEGER                           NOT NULL,
+    -- Same values as profile_state.profile_name_moderation_state
+    moderation_state        INTEGER                           NOT NULL,
+    moderator_account_uuid  BLOB,
+    moderation_unix_time    INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS history_profile_name_moderation_account_uuid_index
+    ON history_profile_name_moderation (account_uuid);
+
+-- Every submitted profile text with the latest moderation
+-- result for it. History database does not contain account_id
+-- table data, so account IDs are stored as UUIDs.
+CREATE TABLE IF NOT EXISTS history_profile_text_moderation(
+    id                      INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    account_uuid            BLOB                              NOT NULL,
+    profile_text            TEXT                              NOT NULL,
+    submitted_unix_time     INTEGER                           NOT NULL,
+    -- Same values as profile_state.profile_text_moderation_state
+    moderation_state        INTEGER                           NOT NULL,
+    moderator_account_uuid  BLOB,
+    moderation_unix_time    INTEGER,
+    rejected_reason_category INTEGER,
+    rejected_reason_details  TEXT
+);
+
+CREATE INDEX IF NOT EXISTS history_profile_text_moderation_account_uuid_index
+    ON history_profile_text_moderation (account_uuid);
+
 ---------- History tables for server component media ----------